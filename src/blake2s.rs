@@ -0,0 +1,224 @@
+use crate::{
+    builder::CircuitBuilder,
+    errors::ZKError,
+    field::Field,
+    gadgets::Gadget,
+    uint::{UInt, UInt32},
+};
+
+/// A byte inside a circuit.
+pub type UInt8 = UInt<8>;
+
+/// Size of a Blake2s block in bytes.
+pub const BLOCK_BYTES: usize = 64;
+
+/// Size of the digest in bytes.
+pub const DIGEST_BYTES: usize = 32;
+
+// The initialization vector, the same as that of SHA-256.
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// The message word permutation of each round.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Computes the 32-byte, unkeyed Blake2s digest of `input` inside a circuit (RFC 7693).
+///
+/// The input length is part of the circuit, as it fixes the block count and counters.
+/// Words are little-endian, so a word is just the bits of four consecutive bytes. Blake2s
+/// needs only additions, XORs and rotations; rotations are free, and a block costs about
+/// 22 000 constraints over a 64-bit field.
+pub fn blake2s<F: Field>(
+    builder: &mut CircuitBuilder<F>,
+    input: &[UInt8],
+) -> Result<Vec<UInt8>, ZKError> {
+    // Parameter block: digest length 32, no key, fanout and depth 1.
+    let mut state: Vec<u32> = IV.to_vec();
+    state[0] ^= 0x0101_0000 ^ DIGEST_BYTES as u32;
+    let mut h: Vec<UInt32> = state
+        .iter()
+        .map(|&word| UInt32::constant(builder, word as u64))
+        .collect();
+
+    // The empty message still compresses one block of zeros.
+    let num_blocks = input.len().div_ceil(BLOCK_BYTES).max(1);
+    let zero = UInt8::constant(builder, 0);
+    for block in 0..num_blocks {
+        let start = block * BLOCK_BYTES;
+        let end = (start + BLOCK_BYTES).min(input.len());
+        let mut bytes: Vec<&UInt8> = input[start..end].iter().collect();
+        bytes.resize(BLOCK_BYTES, &zero);
+        let words: Vec<UInt32> = bytes
+            .chunks(4)
+            .map(|word| {
+                UInt32::from_bits(word.iter().flat_map(|byte| byte.bits()).copied().collect())
+            })
+            .collect();
+
+        let last = block + 1 == num_blocks;
+        h = compress(builder, &h, &words, end as u64, last)?;
+    }
+
+    Ok(h.iter()
+        .flat_map(|word| word.bits().chunks(8))
+        .map(|bits| UInt8::from_bits(bits.to_vec()))
+        .collect())
+}
+
+/// The `blake2s` function as a `Gadget`, whose cost is measured by running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blake2s;
+
+impl<F: Field> Gadget<F> for Blake2s {
+    type Input = Vec<UInt8>;
+    type Output = Vec<UInt8>;
+
+    fn synthesize(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        input: Vec<UInt8>,
+    ) -> Result<Vec<UInt8>, ZKError> {
+        blake2s(builder, &input)
+    }
+}
+
+// The compression function F, for `counter` bytes hashed so far.
+fn compress<F: Field>(
+    builder: &mut CircuitBuilder<F>,
+    h: &[UInt32],
+    m: &[UInt32],
+    counter: u64,
+    last: bool,
+) -> Result<Vec<UInt32>, ZKError> {
+    // The counter and finalization flag are known when the circuit is built, so they
+    // are folded into the constant half of the working vector.
+    let mut tail = IV;
+    tail[4] ^= counter as u32;
+    tail[5] ^= (counter >> 32) as u32;
+    if last {
+        tail[6] ^= u32::MAX;
+    }
+    let mut v: Vec<UInt32> = h.to_vec();
+    v.extend(
+        tail.iter()
+            .map(|&word| UInt32::constant(builder, word as u64)),
+    );
+
+    for s in &SIGMA {
+        mix(builder, &mut v, [0, 4, 8, 12], &m[s[0]], &m[s[1]])?;
+        mix(builder, &mut v, [1, 5, 9, 13], &m[s[2]], &m[s[3]])?;
+        mix(builder, &mut v, [2, 6, 10, 14], &m[s[4]], &m[s[5]])?;
+        mix(builder, &mut v, [3, 7, 11, 15], &m[s[6]], &m[s[7]])?;
+        mix(builder, &mut v, [0, 5, 10, 15], &m[s[8]], &m[s[9]])?;
+        mix(builder, &mut v, [1, 6, 11, 12], &m[s[10]], &m[s[11]])?;
+        mix(builder, &mut v, [2, 7, 8, 13], &m[s[12]], &m[s[13]])?;
+        mix(builder, &mut v, [3, 4, 9, 14], &m[s[14]], &m[s[15]])?;
+    }
+
+    Ok((0..8)
+        .map(|i| {
+            let mixed = v[i].xor(builder, &v[i + 8]);
+            h[i].xor(builder, &mixed)
+        })
+        .collect())
+}
+
+// The mixing function G on the words of `v` at `indices`.
+fn mix<F: Field>(
+    builder: &mut CircuitBuilder<F>,
+    v: &mut [UInt32],
+    [a, b, c, d]: [usize; 4],
+    x: &UInt32,
+    y: &UInt32,
+) -> Result<(), ZKError> {
+    v[a] = v[a].wrapping_sum(builder, &[&v[b], x])?;
+    v[d] = v[d].xor(builder, &v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_sum(builder, &[&v[d]])?;
+    v[b] = v[b].xor(builder, &v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_sum(builder, &[&v[b], y])?;
+    v[d] = v[d].xor(builder, &v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_sum(builder, &[&v[d]])?;
+    v[b] = v[b].xor(builder, &v[c]).rotate_right(7);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goldilocks::Goldilocks;
+
+    fn hex(digest: &str) -> Vec<u64> {
+        (0..digest.len())
+            .step_by(2)
+            .map(|i| u64::from_str_radix(&digest[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // Hashes `message` in a circuit and returns the digest read from the witness.
+    fn hash(message: &[u8]) -> Vec<u64> {
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let input: Vec<UInt8> = message
+            .iter()
+            .map(|_| {
+                let byte = builder.private_witness();
+                UInt8::from_var(&mut builder, byte).unwrap()
+            })
+            .collect();
+        let digest = blake2s(&mut builder, &input).unwrap();
+        assert_eq!(digest.len(), DIGEST_BYTES);
+
+        let inputs: Vec<Goldilocks> = message.iter().map(|&b| Goldilocks::new(b as u64)).collect();
+        let witness = builder.generate_witness(&[], &inputs).unwrap();
+        digest
+            .iter()
+            .map(|byte| byte.value(&witness).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_blake2s() {
+        // Test vectors from Python's hashlib.blake2s.
+        assert_eq!(
+            hash(b""),
+            hex("69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9")
+        );
+        assert_eq!(
+            hash(b"abc"),
+            hex("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+        );
+        // Exactly one block, and one byte into a second block.
+        let bytes: Vec<u8> = (0..65).collect();
+        assert_eq!(
+            hash(&bytes[..64]),
+            hex("56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e")
+        );
+        assert_eq!(
+            hash(&bytes),
+            hex("1b53ee94aaf34e4b159d48de352c7f0661d0a40edff95a0b1639b4090e974472")
+        );
+    }
+
+    #[test]
+    fn test_gadget() {
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let cost = Blake2s.cost(&builder, &vec![]).unwrap();
+        assert_eq!(builder.constraint_system().constraints.len(), 0);
+        let digest = Blake2s.synthesize(&mut builder, vec![]).unwrap();
+        assert_eq!(digest.len(), DIGEST_BYTES);
+        let cs = builder.constraint_system();
+        assert_eq!(cost.constraints, cs.constraints.len());
+        assert_eq!(cost.variables, cs.num_variables - 1);
+    }
+}
@@ -0,0 +1,199 @@
+use std::marker::PhantomData;
+
+use rand::RngCore;
+
+use crate::{
+    curve::{Curve, EllipticCurvePoint},
+    errors::ZKError,
+    field::FieldElement,
+    fp2::Fp2,
+    hash::hash_to_curve,
+    pairing::Pairing,
+};
+
+// Domain separation tag for hashing messages to G1.
+const SIGNATURE_DST: &[u8] = b"ZKSFS-BLS-SIG-V01-G1";
+
+/// A BLS secret key, a nonzero scalar sk modulo r.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretKey {
+    pub scalar: u64,
+}
+
+/// A BLS public key sk * G2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub point: EllipticCurvePoint<Fp2>,
+}
+
+/// A BLS signature sk * H(m), where H hashes messages to G1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub point: EllipticCurvePoint,
+}
+
+/// BLS signatures over the pairing-friendly curve `C`, with public keys in G2 and
+/// signatures in G1.
+///
+/// A signature verifies when e(σ, G2) = e(H(m), pk). Signatures on distinct messages
+/// aggregate into a single G1 point by addition.
+pub struct BLS<C: Curve> {
+    _curve: PhantomData<C>,
+}
+
+impl<C: Curve> BLS<C> {
+    /// Generates a random key pair.
+    pub fn generate_keypair<R: RngCore + ?Sized>(
+        rng: &mut R,
+    ) -> Result<(SecretKey, PublicKey), ZKError> {
+        loop {
+            let scalar = FieldElement::random(rng, C::SCALAR_MODULUS)?.value;
+            if scalar != 0 {
+                let secret_key = SecretKey { scalar };
+                let public_key = Self::public_key(&secret_key)?;
+                return Ok((secret_key, public_key));
+            }
+        }
+    }
+
+    /// Derives the public key sk * G2 of a secret key.
+    pub fn public_key(secret_key: &SecretKey) -> Result<PublicKey, ZKError> {
+        let point = C::g2_curve()?.mul_scalar(&C::g2_generator()?, secret_key.scalar)?;
+        Ok(PublicKey { point })
+    }
+
+    /// Signs a message.
+    pub fn sign(secret_key: &SecretKey, message: &[u8]) -> Result<Signature, ZKError> {
+        let point = C::g1_curve()?.mul_scalar(&Self::hash(message)?, secret_key.scalar)?;
+        Ok(Signature { point })
+    }
+
+    /// Verifies a signature on a message.
+    pub fn verify(
+        public_key: &PublicKey,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<bool, ZKError> {
+        Self::verify_aggregate(std::slice::from_ref(public_key), &[message], signature)
+    }
+
+    /// Aggregates signatures into one by adding their points.
+    pub fn aggregate(signatures: &[Signature]) -> Result<Signature, ZKError> {
+        let curve = C::g1_curve()?;
+        let mut point = EllipticCurvePoint::Infinity;
+        for signature in signatures {
+            point = curve.add_points(&point, &signature.point)?;
+        }
+        Ok(Signature { point })
+    }
+
+    /// Verifies an aggregate signature, checking e(σ, G2) = ∏ e(H(m_i), pk_i).
+    ///
+    /// The messages must be distinct: otherwise a signer could cancel out another's
+    /// signature on the same message with a rogue public key.
+    pub fn verify_aggregate(
+        public_keys: &[PublicKey],
+        messages: &[&[u8]],
+        signature: &Signature,
+    ) -> Result<bool, ZKError> {
+        if public_keys.len() != messages.len() || messages.is_empty() {
+            return Err(ZKError::CurveError(format!(
+                "Aggregate verification needs one public key per message, got {} public keys and {} messages.",
+                public_keys.len(),
+                messages.len()
+            )));
+        }
+        let mut sorted = messages.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != messages.len() {
+            return Err(ZKError::CurveError(
+                "Aggregated messages must be distinct.".into(),
+            ));
+        }
+
+        // Reject points outside the prime-order subgroups before pairing them.
+        let g1 = C::g1_curve()?;
+        let g2 = C::g2_curve()?;
+        if !g1.is_in_prime_subgroup(&signature.point, C::SCALAR_MODULUS)? {
+            return Ok(false);
+        }
+        for public_key in public_keys {
+            if public_key.point == EllipticCurvePoint::Infinity
+                || !g2.is_in_prime_subgroup(&public_key.point, C::SCALAR_MODULUS)?
+            {
+                return Ok(false);
+            }
+        }
+
+        let lhs = [(signature.point.clone(), C::g2_generator()?)];
+        let rhs = messages
+            .iter()
+            .zip(public_keys)
+            .map(|(message, public_key)| Ok((Self::hash(message)?, public_key.point.clone())))
+            .collect::<Result<Vec<_>, ZKError>>()?;
+        Pairing::check_equal::<C>(&lhs, &rhs)
+    }
+
+    // Hashes a message to G1.
+    fn hash(message: &[u8]) -> Result<EllipticCurvePoint, ZKError> {
+        hash_to_curve(message, SIGNATURE_DST, &C::g1_curve()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::curves::{ToyBls12, ToyBn};
+
+    fn check_signatures<C: Curve>() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (secret_key, public_key) = BLS::<C>::generate_keypair(&mut rng).unwrap();
+        let signature = BLS::<C>::sign(&secret_key, b"hello").unwrap();
+        assert!(BLS::<C>::verify(&public_key, b"hello", &signature).unwrap());
+
+        // A different key or an unrelated signature is rejected.
+        let other = SecretKey {
+            scalar: secret_key.scalar % (C::SCALAR_MODULUS - 1) + 1,
+        };
+        let other_public_key = BLS::<C>::public_key(&other).unwrap();
+        assert!(!BLS::<C>::verify(&other_public_key, b"hello", &signature).unwrap());
+        let forged = BLS::<C>::sign(&other, b"hello").unwrap();
+        assert!(!BLS::<C>::verify(&public_key, b"hello", &forged).unwrap());
+        let infinity = Signature {
+            point: EllipticCurvePoint::Infinity,
+        };
+        assert!(!BLS::<C>::verify(&public_key, b"hello", &infinity).unwrap());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        check_signatures::<ToyBn>();
+        check_signatures::<ToyBls12>();
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let messages: Vec<&[u8]> = vec![b"first", b"second", b"third"];
+        let mut public_keys = Vec::new();
+        let mut signatures = Vec::new();
+        for message in &messages {
+            let (secret_key, public_key) = BLS::<ToyBn>::generate_keypair(&mut rng).unwrap();
+            signatures.push(BLS::<ToyBn>::sign(&secret_key, message).unwrap());
+            public_keys.push(public_key);
+        }
+        let aggregate = BLS::<ToyBn>::aggregate(&signatures).unwrap();
+        assert!(BLS::<ToyBn>::verify_aggregate(&public_keys, &messages, &aggregate).unwrap());
+
+        // Swapping two public keys breaks the aggregate.
+        public_keys.swap(0, 1);
+        assert!(!BLS::<ToyBn>::verify_aggregate(&public_keys, &messages, &aggregate).unwrap());
+
+        let repeated: Vec<&[u8]> = vec![b"first", b"first", b"third"];
+        assert!(BLS::<ToyBn>::verify_aggregate(&public_keys, &repeated, &aggregate).is_err());
+        assert!(BLS::<ToyBn>::verify_aggregate(&public_keys[..2], &messages, &aggregate).is_err());
+    }
+}
@@ -0,0 +1,467 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    circuit::{ConstraintSystem, LinearCombination, R1CSConstraint, Term, Variable},
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// A variable of a circuit under construction, identified by its witness index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var(usize);
+
+impl Var {
+    /// Returns the index of the variable in the witness vector.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// Returns the value of the variable in `witness`, for use in a `Hint::Computed`
+    /// closure. Fails if the variable is not computed yet.
+    pub fn value<'a, F: Field>(&self, witness: &'a [F]) -> Result<&'a F, ZKError> {
+        value_of(witness, *self)
+    }
+}
+
+/// Describes how the witness generator computes the value of a variable from the values
+/// of the variables allocated before it.
+#[derive(Debug, Clone)]
+pub enum Hint<F: Field = FieldElement> {
+    /// The value is supplied by the caller, as a public input or private witness.
+    Input,
+    /// The value is a fixed constant.
+    Constant(F),
+    /// The value of a linear combination of earlier variables.
+    Linear(LinearCombination<F>),
+    /// The product of two earlier variables.
+    Product(Var, Var),
+    /// Bit `i` of the canonical integer value of an earlier variable.
+    Bit(Var, usize),
+    /// The inverse of an earlier variable, or zero if it is zero.
+    Inverse(Var),
+    /// One if an earlier variable is zero, and zero otherwise.
+    IsZero(Var),
+    /// The quotient a / b of linear combinations of earlier variables; fails if b is
+    /// zero.
+    Quotient(LinearCombination<F>, LinearCombination<F>),
+    /// a · b + c for linear combinations of earlier variables.
+    Quadratic(
+        LinearCombination<F>,
+        LinearCombination<F>,
+        LinearCombination<F>,
+    ),
+    /// a + b − 2ab for earlier variables, i.e. a XOR b for bits.
+    Xor(Var, Var),
+    /// The result of a closure over the values of the earlier variables, for witnesses
+    /// the other hints cannot describe; see `Hint::computed`.
+    Computed(ComputeFn<F>),
+}
+
+impl<F: Field> Hint<F> {
+    /// Returns a hint that computes the value with `compute`, which is given the values
+    /// of the variables allocated before, indexed by `Var::index`, e.g. via `Var::value`.
+    pub fn computed<C>(compute: C) -> Self
+    where
+        C: Fn(&[F]) -> Result<F, ZKError> + Send + Sync + 'static,
+    {
+        Hint::Computed(ComputeFn(Arc::new(compute)))
+    }
+}
+
+/// A closure computing the value of a variable, held by `Hint::Computed`.
+#[derive(Clone)]
+pub struct ComputeFn<F: Field = FieldElement>(Arc<Compute<F>>);
+
+type Compute<F> = dyn Fn(&[F]) -> Result<F, ZKError> + Send + Sync;
+
+impl<F: Field> fmt::Debug for ComputeFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ComputeFn")
+    }
+}
+
+/// Builds a constraint system from high-level operations.
+///
+/// Each operation allocates a variable for its result and emits the R1CS constraint that
+/// ties it to the operands, so a circuit reads like the computation it checks. Variable 0
+/// is the constant one, which the witness must set to 1; it is the first public input,
+/// followed by the ones allocated with `public_input`.
+///
+/// The builder also records a `Hint` per variable, so that `generate_witness` can compute
+/// every intermediate value from the inputs alone.
+#[derive(Debug, Clone)]
+pub struct CircuitBuilder<F: Field = FieldElement> {
+    cs: ConstraintSystem<F>,
+    pub(crate) params: F::Params,
+    one: Var,
+    hints: Vec<Hint<F>>,
+}
+
+impl<F: Field> CircuitBuilder<F> {
+    /// Creates a builder over the field described by `params`, rejecting invalid
+    /// parameters such as a composite modulus.
+    pub fn new(params: &F::Params) -> Result<Self, ZKError> {
+        let mut cs = ConstraintSystem::new_checked(params)?;
+        let one = Var(cs.allocate_public_input()?);
+        Ok(CircuitBuilder {
+            cs,
+            params: params.clone(),
+            one,
+            hints: vec![Hint::Constant(F::one(params))],
+        })
+    }
+
+    /// Returns the variable holding the constant one.
+    pub fn one(&self) -> Var {
+        self.one
+    }
+
+    /// Allocates a public input. Public inputs must come before every other variable.
+    pub fn public_input(&mut self) -> Result<Var, ZKError> {
+        let var = Var(self.cs.allocate_public_input()?);
+        self.hints.push(Hint::Input);
+        Ok(var)
+    }
+
+    /// Allocates a private witness variable with no constraints attached.
+    pub fn private_witness(&mut self) -> Var {
+        self.allocate(Hint::Input)
+    }
+
+    /// Allocates a private variable whose value the witness generator computes with
+    /// `hint`. The caller is responsible for constraining it.
+    pub fn allocate(&mut self, hint: Hint<F>) -> Var {
+        self.hints.push(hint);
+        Var(self.cs.allocate_private_witness())
+    }
+
+    /// Allocates a private variable whose value the witness generator computes by calling
+    /// `compute` on the values of the variables allocated before it, as with
+    /// `Hint::computed`. The caller is responsible for constraining it.
+    pub fn allocate_with<C>(&mut self, compute: C) -> Var
+    where
+        C: Fn(&[F]) -> Result<F, ZKError> + Send + Sync + 'static,
+    {
+        self.allocate(Hint::computed(compute))
+    }
+
+    /// Returns a variable constrained to equal `value`: value · 1 = c.
+    pub fn constant(&mut self, value: F) -> Var {
+        let c = self.allocate(Hint::Constant(value.clone()));
+        let a = self.lc(&[(self.one, value)]);
+        self.emit(a, self.single(self.one), self.single(c));
+        c
+    }
+
+    /// Returns a variable constrained to equal a + b: (a + b) · 1 = c.
+    pub fn add(&mut self, a: Var, b: Var) -> Var {
+        let sum = self.lc(&[(a, F::one(&self.params)), (b, F::one(&self.params))]);
+        let c = self.allocate(Hint::Linear(sum.clone()));
+        self.emit(sum, self.single(self.one), self.single(c));
+        c
+    }
+
+    /// Returns a variable constrained to equal a − b: (a − b) · 1 = c.
+    pub fn sub(&mut self, a: Var, b: Var) -> Var {
+        let difference = self.lc(&[(a, F::one(&self.params)), (b, F::one(&self.params).neg())]);
+        let c = self.allocate(Hint::Linear(difference.clone()));
+        self.emit(difference, self.single(self.one), self.single(c));
+        c
+    }
+
+    /// Returns a variable constrained to equal a · b.
+    pub fn mul(&mut self, a: Var, b: Var) -> Var {
+        let c = self.allocate(Hint::Product(a, b));
+        self.emit(self.single(a), self.single(b), self.single(c));
+        c
+    }
+
+    /// Constrains a and b to be equal: (a − b) · 1 = 0.
+    pub fn enforce_equal(&mut self, a: Var, b: Var) {
+        let difference = self.lc(&[(a, F::one(&self.params)), (b, F::one(&self.params).neg())]);
+        self.emit(difference, self.single(self.one), LinearCombination::new());
+    }
+
+    /// Returns `var` as a symbolic `Variable`, for writing constraints with `enforce`.
+    pub fn variable(&self, var: Var) -> Variable<F> {
+        Variable::new(var.0, &self.params)
+    }
+
+    /// Adds the constraint a x b = c, for linear combinations written symbolically.
+    /// Unlike the other operations, it allocates no variable.
+    pub fn enforce(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+        c: impl Into<LinearCombination<F>>,
+    ) {
+        self.cs.enforce(a, b, c);
+    }
+
+    /// Computes the full witness vector from the values of the inputs: `public_inputs`
+    /// for the variables allocated with `public_input` and `private_inputs` for those
+    /// allocated with `private_witness`, each in allocation order. Fails if the inputs do
+    /// not satisfy the constraints.
+    pub fn generate_witness(
+        &self,
+        public_inputs: &[F],
+        private_inputs: &[F],
+    ) -> Result<Vec<F>, ZKError> {
+        // The constant one is a public input the caller does not supply.
+        let num_public = self.cs.num_public - 1;
+        if public_inputs.len() != num_public {
+            return Err(ZKError::CircuitError(format!(
+                "Expected {} public inputs, got {}.",
+                num_public,
+                public_inputs.len()
+            )));
+        }
+        let mut inputs = public_inputs.iter().chain(private_inputs);
+
+        let mut witness = Vec::with_capacity(self.hints.len());
+        for hint in &self.hints {
+            let value = match hint {
+                Hint::Input => inputs.next().cloned().ok_or_else(|| {
+                    ZKError::CircuitError("Too few private inputs for the circuit.".into())
+                })?,
+                Hint::Constant(value) => value.clone(),
+                Hint::Linear(lc) => lc.evaluate(&witness)?,
+                Hint::Product(a, b) => value_of(&witness, *a)?.mul(value_of(&witness, *b)?)?,
+                Hint::Bit(var, i) => {
+                    let bytes = value_of(&witness, *var)?.to_bytes();
+                    let bit = bytes.get(i / 8).map_or(0, |byte| (byte >> (i % 8)) & 1);
+                    F::from_u64(bit as u64, &self.params)
+                }
+                Hint::Inverse(var) => {
+                    let value = value_of(&witness, *var)?;
+                    if value.is_zero() {
+                        F::zero(&self.params)
+                    } else {
+                        value.inv()?
+                    }
+                }
+                Hint::IsZero(var) => {
+                    F::from_u64(value_of(&witness, *var)?.is_zero() as u64, &self.params)
+                }
+                Hint::Quotient(a, b) => a.evaluate(&witness)?.div(&b.evaluate(&witness)?)?,
+                Hint::Quadratic(a, b, c) => a
+                    .evaluate(&witness)?
+                    .mul(&b.evaluate(&witness)?)?
+                    .add(&c.evaluate(&witness)?)?,
+                Hint::Xor(a, b) => {
+                    let (a, b) = (value_of(&witness, *a)?, value_of(&witness, *b)?);
+                    let product = a.mul(b)?;
+                    a.add(b)?.sub(&product.add(&product)?)?
+                }
+                Hint::Computed(compute) => (compute.0)(&witness)?,
+            };
+            witness.push(value);
+        }
+        if inputs.next().is_some() {
+            return Err(ZKError::CircuitError(
+                "Too many private inputs for the circuit.".into(),
+            ));
+        }
+
+        self.cs.evaluate(&witness)?;
+        Ok(witness)
+    }
+
+    /// Returns the largest n such that every integer below 2^n is smaller than the
+    /// modulus, so n-bit values never wrap around.
+    pub fn capacity(&self) -> usize {
+        capacity::<F>(&self.params)
+    }
+
+    /// Returns the constraint system built so far.
+    pub fn constraint_system(&self) -> &ConstraintSystem<F> {
+        &self.cs
+    }
+
+    /// Consumes the builder and returns its constraint system.
+    pub fn build(self) -> ConstraintSystem<F> {
+        self.cs
+    }
+
+    pub(crate) fn lc(&self, terms: &[(Var, F)]) -> LinearCombination<F> {
+        let mut lc = LinearCombination::new();
+        for (var, coefficient) in terms {
+            lc.add_term(Term {
+                index: var.0,
+                coefficient: coefficient.clone(),
+            });
+        }
+        lc
+    }
+
+    pub(crate) fn single(&self, var: Var) -> LinearCombination<F> {
+        self.lc(&[(var, F::one(&self.params))])
+    }
+
+    pub(crate) fn emit(
+        &mut self,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+    ) {
+        self.cs.add_constraint(R1CSConstraint::new(a, b, c));
+    }
+}
+
+// Returns the capacity of the field described by `params`; see
+// `CircuitBuilder::capacity`.
+pub(crate) fn capacity<F: Field>(params: &F::Params) -> usize {
+    // The bit length of p - 1 is that of p, as an odd prime is no power of two.
+    let bytes = F::one(params).neg().to_bytes();
+    let bits = bytes
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |i| 8 * i + 8 - bytes[i].leading_zeros() as usize);
+    bits.saturating_sub(1)
+}
+
+// Hints may only refer to variables allocated before the one they compute.
+fn value_of<F: Field>(witness: &[F], var: Var) -> Result<&F, ZKError> {
+    witness.get(var.0).ok_or_else(|| {
+        ZKError::CircuitError(format!(
+            "Hint refers to variable {}, which is not computed yet.",
+            var.0
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fp::Fp, qap::QAP, r1cs};
+
+    fn witness(values: &[u64]) -> Vec<FieldElement> {
+        values
+            .iter()
+            .map(|&v| FieldElement::new(v, 97).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_cubic() {
+        // x^3 + x + 5 = out, with out public.
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let out = builder.public_input().unwrap();
+        let x = builder.private_witness();
+        let x2 = builder.mul(x, x);
+        let x3 = builder.mul(x2, x);
+        let sum = builder.add(x3, x);
+        let five = builder.constant(FieldElement::new(5, 97).unwrap());
+        let result = builder.add(sum, five);
+        builder.enforce_equal(result, out);
+
+        // Public inputs cannot follow the intermediate variables.
+        assert!(builder.public_input().is_err());
+
+        // Only the inputs are supplied; the intermediate values are computed.
+        let generated = builder
+            .generate_witness(&witness(&[35]), &witness(&[3]))
+            .unwrap();
+        // Unsatisfiable inputs and wrong input counts are rejected.
+        assert!(builder
+            .generate_witness(&witness(&[36]), &witness(&[3]))
+            .is_err());
+        assert!(builder.generate_witness(&[], &witness(&[3])).is_err());
+        assert!(builder.generate_witness(&witness(&[35]), &[]).is_err());
+        assert!(builder
+            .generate_witness(&witness(&[35]), &witness(&[3, 4]))
+            .is_err());
+
+        let cs = builder.build();
+        assert_eq!((cs.num_public, cs.num_variables), (2, 8));
+        assert_eq!(cs.constraints.len(), 6);
+
+        // [one, out, x, x^2, x^3, x^3 + x, 5, x^3 + x + 5] for x = 3.
+        let valid = witness(&[1, 35, 3, 9, 27, 30, 5, 35]);
+        assert_eq!(generated, valid);
+        assert!(cs.evaluate(&valid).unwrap());
+        assert!(QAP::create(&cs).is_ok());
+
+        // Claiming a different output breaks the equality constraint.
+        let wrong = witness(&[1, 36, 3, 9, 27, 30, 5, 35]);
+        assert!(cs.evaluate(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_sub_and_generic_field() {
+        // (a - b) * a = c over a typed field.
+        type F = Fp<97>;
+        let mut builder = CircuitBuilder::<F>::new(&()).unwrap();
+        let a = builder.private_witness();
+        let b = builder.private_witness();
+        let difference = builder.sub(a, b);
+        let c = builder.mul(difference, a);
+        assert_eq!(c.index(), 4);
+
+        // c = a^2 - ab, written symbolically: (a - b) * a = c.
+        let (a, b, c) = (
+            builder.variable(a),
+            builder.variable(b),
+            builder.variable(c),
+        );
+        r1cs!(builder; (a - b) * a == c);
+
+        let cs = builder.constraint_system();
+        // a = 10, b = 20: (10 - 20) * 10 = -100 = 94 mod 97.
+        let valid: Vec<F> = [1, 10, 20, 87, 94].into_iter().map(F::new).collect();
+        assert!(cs.evaluate(&valid).unwrap());
+        let generated = builder.generate_witness(&[], &[F::new(10), F::new(20)]);
+        assert_eq!(generated.unwrap(), valid);
+        let wrong: Vec<F> = [1, 10, 20, 87, 3].into_iter().map(F::new).collect();
+        assert!(cs.evaluate(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_computed_hints() {
+        // y is a cube root of x, found by search when the witness is generated:
+        // y * y = y2 and y2 * y = x.
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let x = builder.public_input().unwrap();
+        let y = builder.allocate_with(move |witness: &[FieldElement]| {
+            let x = x.value(witness)?;
+            for v in 0..97 {
+                let y = FieldElement::new(v, 97)?;
+                if &y.mul(&y)?.mul(&y)? == x {
+                    return Ok(y);
+                }
+            }
+            Err(ZKError::CircuitError("No cube root.".into()))
+        });
+        let y2 = builder.mul(y, y);
+        let (x, y, y2) = (
+            builder.variable(x),
+            builder.variable(y),
+            builder.variable(y2),
+        );
+        r1cs!(builder; y2 * y == x);
+
+        // 3^3 = 27; the closure supplies y, so there are no private inputs.
+        let generated = builder.generate_witness(&witness(&[27]), &[]).unwrap();
+        assert_eq!(generated[2], FieldElement::new(3, 97).unwrap());
+        assert!(builder.constraint_system().evaluate(&generated).unwrap());
+
+        // Errors from the closure are returned, and so are reads of later variables.
+        let mut failing = builder.clone();
+        let later = Var(failing.constraint_system().num_variables);
+        failing.allocate_with(move |witness: &[FieldElement]| later.value(witness).cloned());
+        assert!(failing.generate_witness(&witness(&[27]), &[]).is_err());
+        let mut failing = builder.clone();
+        failing.allocate(Hint::computed(|_: &[FieldElement]| {
+            Err(ZKError::CircuitError("No value.".into()))
+        }));
+        assert!(failing.generate_witness(&witness(&[27]), &[]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_composite_modulus() {
+        assert!(matches!(
+            CircuitBuilder::<FieldElement>::new(&91),
+            Err(ZKError::CompositeModulus(91))
+        ));
+    }
+}
@@ -0,0 +1,483 @@
+//! Readers and writers for the binary files produced by circom and snarkjs.
+//!
+//! Values are read into `FieldElement`, whose modulus is a u64, so only circuits and
+//! witnesses over a prime below 2^64 are supported, e.g. those compiled with
+//! `--prime goldilocks`. circom's default prime, BN254, is 254 bits wide; files over it
+//! and other wide primes are rejected with `ZKError::ModulusTooWide`.
+
+use crate::{
+    circuit::{ConstraintSystem, LinearCombination, R1CSConstraint, Term},
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+// Section types of the .r1cs format.
+const R1CS_HEADER: u32 = 1;
+const R1CS_CONSTRAINTS: u32 = 2;
+// Section types of the .wtns format.
+const WTNS_HEADER: u32 = 1;
+const WTNS_VALUES: u32 = 2;
+
+/// A circuit read from a circom .r1cs file.
+///
+/// Circom numbers its wires as the constant one, then the public outputs, the public
+/// inputs, the private inputs and finally the internal wires. The constraint system
+/// keeps this numbering, with the first three groups as its public inputs.
+#[derive(Clone, Debug)]
+pub struct CircomR1cs {
+    pub cs: ConstraintSystem,
+    pub modulus: u64,
+    pub num_outputs: usize,
+    pub num_public_inputs: usize,
+    pub num_private_inputs: usize,
+}
+
+/// Reads a circuit from the binary .r1cs format written by circom.
+///
+/// Field elements are `FieldElement`s modulo the prime in the header, so only circuits
+/// compiled for a prime below 2^64 can be read, e.g. with `--prime goldilocks`. Wider
+/// primes, including circom's default BN254, fail with `ZKError::ModulusTooWide`.
+pub fn read_r1cs(bytes: &[u8]) -> Result<CircomR1cs, ZKError> {
+    let sections = read_sections(bytes, b"r1cs")?;
+
+    let mut header = Reader::new(find_section(&sections, R1CS_HEADER)?);
+    let n8 = header.u32()? as usize;
+    let modulus = read_prime(&mut header, n8)?;
+    let num_wires = header.u32()? as usize;
+    let num_outputs = header.u32()? as usize;
+    let num_public_inputs = header.u32()? as usize;
+    let num_private_inputs = header.u32()? as usize;
+    let _num_labels = header.u64()?;
+    let num_constraints = header.u32()? as usize;
+    header.finish()?;
+
+    let num_public = 1 + num_outputs + num_public_inputs;
+    if num_public + num_private_inputs > num_wires {
+        return Err(ZKError::InvalidFormat(format!(
+            "Header declares {} inputs and outputs but only {} wires.",
+            num_public + num_private_inputs - 1,
+            num_wires
+        )));
+    }
+
+    let mut cs = ConstraintSystem::new();
+    for _ in 0..num_public {
+        cs.allocate_public_input()?;
+    }
+    for _ in num_public..num_wires {
+        cs.allocate_private_witness();
+    }
+
+    let mut constraints = Reader::new(find_section(&sections, R1CS_CONSTRAINTS)?);
+    let read_lc = |reader: &mut Reader| -> Result<LinearCombination, ZKError> {
+        let mut lc = LinearCombination::new();
+        for _ in 0..reader.u32()? {
+            let index = reader.u32()? as usize;
+            if index >= num_wires {
+                return Err(ZKError::InvalidFormat(format!(
+                    "Wire {} is out of range for {} wires.",
+                    index, num_wires
+                )));
+            }
+            lc.add_term(Term {
+                index,
+                coefficient: read_element(reader, n8, modulus)?,
+            });
+        }
+        Ok(lc)
+    };
+    for _ in 0..num_constraints {
+        let a = read_lc(&mut constraints)?;
+        let b = read_lc(&mut constraints)?;
+        let c = read_lc(&mut constraints)?;
+        cs.add_constraint(R1CSConstraint::new(a, b, c));
+    }
+    constraints.finish()?;
+
+    Ok(CircomR1cs {
+        cs,
+        modulus,
+        num_outputs,
+        num_public_inputs,
+        num_private_inputs,
+    })
+}
+
+/// Reads a witness from the binary .wtns format written by circom and snarkjs witness
+/// generators. The values are in wire order, so they can be passed to
+/// `QAP::calculate_witness_quotient` for a circuit read with `read_r1cs`. As there, the
+/// prime must fit in 64 bits, or reading fails with `ZKError::ModulusTooWide`.
+pub fn read_wtns(bytes: &[u8]) -> Result<Vec<FieldElement>, ZKError> {
+    let sections = read_sections(bytes, b"wtns")?;
+
+    let mut header = Reader::new(find_section(&sections, WTNS_HEADER)?);
+    let n8 = header.u32()? as usize;
+    let modulus = read_prime(&mut header, n8)?;
+    let num_values = header.u32()? as usize;
+    header.finish()?;
+
+    let mut values = Reader::new(find_section(&sections, WTNS_VALUES)?);
+    let witness = (0..num_values)
+        .map(|_| read_element(&mut values, n8, modulus))
+        .collect::<Result<Vec<_>, _>>()?;
+    values.finish()?;
+    Ok(witness)
+}
+
+/// Writes a witness in the binary .wtns format, with 8-byte field elements. All values
+/// must share a modulus.
+pub fn write_wtns(witness: &[FieldElement]) -> Result<Vec<u8>, ZKError> {
+    let modulus = witness
+        .first()
+        .ok_or_else(|| ZKError::CircuitError("Cannot write an empty witness.".into()))?
+        .modulus;
+    if let Some(element) = witness.iter().find(|w| w.modulus != modulus) {
+        return Err(ZKError::InvalidFieldElement(format!(
+            "Witness mixes the moduli {} and {}.",
+            modulus, element.modulus
+        )));
+    }
+    let num_values = u32::try_from(witness.len())
+        .map_err(|_| ZKError::CircuitError("Witness is too long.".into()))?;
+
+    let mut header = 8u32.to_le_bytes().to_vec();
+    header.extend(modulus.to_le_bytes());
+    header.extend(num_values.to_le_bytes());
+    let values = witness.iter().flat_map(|w| w.to_bytes()).collect();
+    Ok(write_sections(
+        b"wtns",
+        2,
+        &[(WTNS_HEADER, header), (WTNS_VALUES, values)],
+    ))
+}
+
+// Splits a file in the common circom container format into its sections: a magic
+// value, a version and a count, then the sections as type, byte length and contents.
+fn read_sections<'a>(bytes: &'a [u8], magic: &[u8; 4]) -> Result<Vec<(u32, &'a [u8])>, ZKError> {
+    let mut reader = Reader::new(bytes);
+    if reader.take(4)? != magic {
+        return Err(ZKError::InvalidFormat(format!(
+            "Expected a {} file.",
+            String::from_utf8_lossy(magic)
+        )));
+    }
+    let version = reader.u32()?;
+    if version != 1 && version != 2 {
+        return Err(ZKError::InvalidFormat(format!(
+            "Unsupported version {}.",
+            version
+        )));
+    }
+    let count = reader.u32()?;
+    let mut sections = Vec::new();
+    for _ in 0..count {
+        let section_type = reader.u32()?;
+        let length = usize::try_from(reader.u64()?)
+            .map_err(|_| ZKError::InvalidFormat("Section is too long.".into()))?;
+        sections.push((section_type, reader.take(length)?));
+    }
+    reader.finish()?;
+    Ok(sections)
+}
+
+// Writes sections in the container format read by `read_sections`.
+fn write_sections(magic: &[u8; 4], version: u32, sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = magic.to_vec();
+    bytes.extend(version.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (section_type, contents) in sections {
+        bytes.extend(section_type.to_le_bytes());
+        bytes.extend((contents.len() as u64).to_le_bytes());
+        bytes.extend(contents);
+    }
+    bytes
+}
+
+// Returns the only section of the given type.
+fn find_section<'a>(sections: &[(u32, &'a [u8])], section_type: u32) -> Result<&'a [u8], ZKError> {
+    let mut matching = sections.iter().filter(|(t, _)| *t == section_type);
+    match (matching.next(), matching.next()) {
+        (Some((_, contents)), None) => Ok(contents),
+        (None, _) => Err(ZKError::InvalidFormat(format!(
+            "Missing section of type {}.",
+            section_type
+        ))),
+        (Some(_), Some(_)) => Err(ZKError::InvalidFormat(format!(
+            "Repeated section of type {}.",
+            section_type
+        ))),
+    }
+}
+
+// Reads the prime of an `n8`-byte field and checks that it fits `FieldElement`. Wider
+// primes, such as circom's default BN254, are reported with their bit length.
+fn read_prime(reader: &mut Reader, n8: usize) -> Result<u64, ZKError> {
+    check_field_size(n8)?;
+    let bytes = reader.take(n8)?;
+    if let Some(top) = bytes[8..].iter().rposition(|&byte| byte != 0) {
+        let bits = (top + 9) * 8 - bytes[top + 8].leading_zeros() as usize;
+        return Err(ZKError::ModulusTooWide(bits));
+    }
+    let modulus = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    FieldElement::validate_params(&modulus)?;
+    Ok(modulus)
+}
+
+// Reads an `n8`-byte little-endian field element, rejecting unreduced values.
+fn read_element(reader: &mut Reader, n8: usize, modulus: u64) -> Result<FieldElement, ZKError> {
+    let value = read_u64(reader, n8)?;
+    FieldElement::from_bytes(&value.to_le_bytes(), &modulus)
+}
+
+// Reads an `n8`-byte little-endian integer that must fit in 64 bits.
+fn read_u64(reader: &mut Reader, n8: usize) -> Result<u64, ZKError> {
+    check_field_size(n8)?;
+    let bytes = reader.take(n8)?;
+    if bytes[8..].iter().any(|&byte| byte != 0) {
+        return Err(ZKError::FieldElementOutOfRange(
+            "Value does not fit in 64 bits.".into(),
+        ));
+    }
+    Ok(u64::from_le_bytes(bytes[..8].try_into().unwrap()))
+}
+
+fn check_field_size(n8: usize) -> Result<(), ZKError> {
+    if n8 == 0 || !n8.is_multiple_of(8) {
+        return Err(ZKError::InvalidFormat(format!(
+            "Field size of {} bytes is not a multiple of 8.",
+            n8
+        )));
+    }
+    Ok(())
+}
+
+// A cursor over little-endian binary data.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ZKError> {
+        if self.bytes.len() < n {
+            return Err(ZKError::InvalidFormat("Unexpected end of data.".into()));
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, ZKError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, ZKError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    // Fails if any data is left over.
+    fn finish(self) -> Result<(), ZKError> {
+        if !self.bytes.is_empty() {
+            return Err(ZKError::InvalidFormat(format!(
+                "{} unexpected trailing bytes.",
+                self.bytes.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qap::QAP;
+
+    const MODULUS: u64 = 97;
+    const N8: usize = 32;
+
+    fn element(value: u64) -> Vec<u8> {
+        let mut bytes = value.to_le_bytes().to_vec();
+        bytes.resize(N8, 0);
+        bytes
+    }
+
+    fn container(magic: &[u8; 4], sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        write_sections(magic, 1, sections)
+    }
+
+    fn header(prime: Vec<u8>, wires: [u32; 4], constraints: u32) -> Vec<u8> {
+        let mut bytes = (N8 as u32).to_le_bytes().to_vec();
+        bytes.extend(prime);
+        for count in wires {
+            bytes.extend(count.to_le_bytes());
+        }
+        bytes.extend(0u64.to_le_bytes());
+        bytes.extend(constraints.to_le_bytes());
+        bytes
+    }
+
+    fn lc(terms: &[(u32, u64)]) -> Vec<u8> {
+        let mut bytes = (terms.len() as u32).to_le_bytes().to_vec();
+        for &(wire, value) in terms {
+            bytes.extend(wire.to_le_bytes());
+            bytes.extend(element(value));
+        }
+        bytes
+    }
+
+    // out = a * b + 5, compiled as the wires [one, out, a, b, ab] and the constraints
+    // a * b = ab and 0 * 0 = out - ab - 5.
+    fn multiplier() -> Vec<Vec<u8>> {
+        vec![
+            lc(&[(2, 1)]),
+            lc(&[(3, 1)]),
+            lc(&[(4, 1)]),
+            lc(&[]),
+            lc(&[]),
+            lc(&[(1, 1), (4, MODULUS - 1), (0, MODULUS - 5)]),
+        ]
+    }
+
+    fn fe(values: &[u64]) -> Vec<FieldElement> {
+        values
+            .iter()
+            .map(|&v| FieldElement::new(v, MODULUS).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_read_r1cs() {
+        let bytes = container(
+            b"r1cs",
+            &[
+                (R1CS_CONSTRAINTS, multiplier().concat()),
+                (R1CS_HEADER, header(element(MODULUS), [5, 1, 0, 2], 2)),
+            ],
+        );
+        let circuit = read_r1cs(&bytes).unwrap();
+        assert_eq!(circuit.modulus, MODULUS);
+        assert_eq!(
+            (
+                circuit.num_outputs,
+                circuit.num_public_inputs,
+                circuit.num_private_inputs
+            ),
+            (1, 0, 2)
+        );
+        let cs = &circuit.cs;
+        assert_eq!(
+            (cs.num_variables, cs.num_public, cs.constraints.len()),
+            (5, 2, 2)
+        );
+
+        // a = 4, b = 6: out = 29.
+        assert!(cs.evaluate(&fe(&[1, 29, 4, 6, 24])).unwrap());
+        assert!(cs.evaluate(&fe(&[1, 30, 4, 6, 24])).is_err());
+    }
+
+    #[test]
+    fn test_read_r1cs_rejects_malformed_files() {
+        let read = |header_bytes: Vec<u8>, constraints: Vec<Vec<u8>>| {
+            read_r1cs(&container(
+                b"r1cs",
+                &[
+                    (R1CS_HEADER, header_bytes),
+                    (R1CS_CONSTRAINTS, constraints.concat()),
+                ],
+            ))
+        };
+        let valid_header = || header(element(MODULUS), [5, 1, 0, 2], 2);
+        assert!(read(valid_header(), multiplier()).is_ok());
+
+        // Wrong magic, and truncated data.
+        let mut bytes = container(b"r1cs", &[(R1CS_HEADER, valid_header())]);
+        assert!(read_r1cs(&bytes[..bytes.len() - 1]).is_err());
+        bytes[0] = b'x';
+        assert!(read_r1cs(&bytes).is_err());
+
+        // The BN254 prime does not fit, and a composite modulus is not a field.
+        let mut bn254 = element(0);
+        bn254[31] = 0x30;
+        assert!(matches!(
+            read(header(bn254, [5, 1, 0, 2], 2), multiplier()),
+            Err(ZKError::ModulusTooWide(254))
+        ));
+        assert!(matches!(
+            read(header(element(91), [5, 1, 0, 2], 2), multiplier()),
+            Err(ZKError::CompositeModulus(91))
+        ));
+
+        // Wires out of range, unreduced coefficients and missing constraints.
+        assert!(read(header(element(MODULUS), [4, 1, 0, 2], 2), multiplier()).is_err());
+        assert!(read(header(element(MODULUS), [3, 1, 0, 2], 0), vec![]).is_err());
+        let mut unreduced = multiplier();
+        unreduced[0] = lc(&[(2, MODULUS)]);
+        assert!(read(valid_header(), unreduced).is_err());
+        assert!(read(valid_header(), multiplier()[..5].to_vec()).is_err());
+        assert!(read(header(element(MODULUS), [5, 1, 0, 2], 1), multiplier()).is_err());
+    }
+
+    #[test]
+    fn test_wtns() {
+        let circuit = read_r1cs(&container(
+            b"r1cs",
+            &[
+                (R1CS_HEADER, header(element(MODULUS), [5, 1, 0, 2], 2)),
+                (R1CS_CONSTRAINTS, multiplier().concat()),
+            ],
+        ))
+        .unwrap();
+
+        // A witness file with 32-byte elements, as for BN254-sized fields.
+        let mut values = Vec::new();
+        for value in [1, 29, 4, 6, 24] {
+            values.extend(element(value));
+        }
+        let mut wtns_header = (N8 as u32).to_le_bytes().to_vec();
+        wtns_header.extend(element(MODULUS));
+        wtns_header.extend(5u32.to_le_bytes());
+        let bytes = write_sections(
+            b"wtns",
+            2,
+            &[(WTNS_HEADER, wtns_header), (WTNS_VALUES, values.clone())],
+        );
+        let witness = read_wtns(&bytes).unwrap();
+        assert_eq!(witness, fe(&[1, 29, 4, 6, 24]));
+        assert!(circuit.cs.evaluate(&witness).unwrap());
+        let qap = QAP::create(&circuit.cs).unwrap();
+        assert!(qap.calculate_witness_quotient(&witness).is_ok());
+
+        // Writing and reading back gives the same witness.
+        let written = write_wtns(&witness).unwrap();
+        assert_eq!(read_wtns(&written).unwrap(), witness);
+        assert!(read_r1cs(&written).is_err());
+
+        // Too few values, mixed moduli and empty witnesses are rejected.
+        let mut short_header = (N8 as u32).to_le_bytes().to_vec();
+        short_header.extend(element(MODULUS));
+        short_header.extend(6u32.to_le_bytes());
+        let short = write_sections(
+            b"wtns",
+            2,
+            &[(WTNS_HEADER, short_header), (WTNS_VALUES, values)],
+        );
+        assert!(read_wtns(&short).is_err());
+        let mut bn254 = element(0);
+        bn254[31] = 0x30;
+        let mut wide_header = (N8 as u32).to_le_bytes().to_vec();
+        wide_header.extend(bn254);
+        wide_header.extend(5u32.to_le_bytes());
+        let wide = write_sections(b"wtns", 2, &[(WTNS_HEADER, wide_header)]);
+        assert!(matches!(
+            read_wtns(&wide),
+            Err(ZKError::ModulusTooWide(254))
+        ));
+        let mut mixed = witness.clone();
+        mixed.push(FieldElement::new(1, 101).unwrap());
+        assert!(write_wtns(&mixed).is_err());
+        assert!(write_wtns(&[]).is_err());
+    }
+}
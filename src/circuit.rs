@@ -1,43 +1,181 @@
-use crate::{errors::ZKError, field::FieldElement};
+use std::{
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    errors::ZKError,
+    field::{DisplayFormat, Field, FieldElement},
+    store::ConstraintStore,
+};
+
+// Domain separation for `ConstraintSystem::digest`.
+const DIGEST_DOMAIN: &[u8] = b"zksfs-r1cs-digest-v1";
+
+/// Represents a variable of a constraint system, for writing linear combinations
+/// symbolically, e.g. `x + y` or `x * c`.
+///
+/// The variable carries the parameters of its field, so that it can be turned into a
+/// linear combination with coefficient one.
+#[derive(Debug, PartialEq)]
+pub struct Variable<F: Field = FieldElement> {
+    pub index: usize,
+    params: F::Params,
+}
+
+impl<F: Field> Variable<F> {
+    /// Creates the variable at `index` over the field described by `params`.
+    pub fn new(index: usize, params: &F::Params) -> Self {
+        Variable {
+            index,
+            params: params.clone(),
+        }
+    }
+}
+
+impl<F: Field> Clone for Variable<F> {
+    fn clone(&self) -> Self {
+        Variable::new(self.index, &self.params)
+    }
+}
+
+// Field parameters are plain values for the prime fields, so variables can be copied
+// like indices.
+impl<F: Field> Copy for Variable<F> where F::Params: Copy {}
 
 /// Represents a term i.e. a variable with a coefficient at an index.
-#[derive(Clone, Debug)]
-pub struct Term {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "F: serde::Serialize",
+        deserialize = "F: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Term<F: Field = FieldElement> {
     pub index: usize,
-    pub coefficient: FieldElement,
+    pub coefficient: F,
 }
 
 /// Represents a linear combination of terms.
-#[derive(Clone, Debug)]
-pub struct LinearCombination {
-    pub terms: Vec<Term>,
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "F: serde::Serialize",
+        deserialize = "F: serde::Deserialize<'de>"
+    ))
+)]
+pub struct LinearCombination<F: Field = FieldElement> {
+    pub terms: Vec<Term<F>>,
 }
 
-impl LinearCombination {
+impl<F: Field> LinearCombination<F> {
     /// Creates a new, empty linear combinaton.
     pub fn new() -> Self {
         LinearCombination { terms: Vec::new() }
     }
 
-    /// Add a term.
-    pub fn add_term(&mut self, term: Term) {
+    /// Add a term. Terms on a variable that already appears are kept separate until
+    /// `normalize` merges them.
+    pub fn add_term(&mut self, term: Term<F>) {
         self.terms.push(term);
     }
 
+    /// Merges the terms on each variable into one by summing their coefficients, and
+    /// drops terms with coefficient zero. The first occurrence of a variable keeps its
+    /// place.
+    pub fn normalize(&mut self) -> Result<(), ZKError> {
+        let terms = std::mem::take(&mut self.terms);
+        for term in terms {
+            self.try_fold_term(term)?;
+        }
+        Ok(())
+    }
+
+    /// Serializes the linear combination compactly: the number of terms as a
+    /// little-endian u64, followed by each term's variable index as a u64 and its
+    /// coefficient's `Field::to_bytes` encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Deserializes a linear combination over the field described by `params` from the
+    /// output of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8], params: &F::Params) -> Result<Self, ZKError> {
+        let mut reader = ByteReader::new(bytes, params);
+        let lc = reader.linear_combination()?;
+        reader.finish()?;
+        Ok(lc)
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend((self.terms.len() as u64).to_le_bytes());
+        for term in &self.terms {
+            bytes.extend((term.index as u64).to_le_bytes());
+            bytes.extend(term.coefficient.to_bytes());
+        }
+    }
+
+    /// Returns the combined coefficient of the variable at `index`, summing over all
+    /// of its terms, or `None` if no term refers to it.
+    pub fn coefficient(&self, index: usize) -> Result<Option<F>, ZKError> {
+        let mut terms = self.terms.iter().filter(|term| term.index == index);
+        let Some(first) = terms.next() else {
+            return Ok(None);
+        };
+        terms
+            .try_fold(first.coefficient.clone(), |sum, term| {
+                sum.add(&term.coefficient)
+            })
+            .map(Some)
+    }
+
+    // Adds a term, folding it into an existing term on the same variable and dropping
+    // terms whose coefficients cancel.
+    fn try_fold_term(&mut self, term: Term<F>) -> Result<(), ZKError> {
+        match self.terms.iter().position(|t| t.index == term.index) {
+            Some(position) => {
+                let sum = self.terms[position].coefficient.add(&term.coefficient)?;
+                if sum.is_zero() {
+                    self.terms.remove(position);
+                } else {
+                    self.terms[position].coefficient = sum;
+                }
+            }
+            None if !term.coefficient.is_zero() => self.terms.push(term),
+            None => {}
+        }
+        Ok(())
+    }
+
+    // Like `try_fold_term`, but panics on a field mismatch, like the operators on
+    // `FieldElement`.
+    fn fold_term(&mut self, term: Term<F>) {
+        self.try_fold_term(term).unwrap_or_else(|e| panic!("{}", e));
+    }
+
     /// Evaluates the linear combination given a witness victor.
     /// Each variable's value is taken from the witness by its index.
-    pub fn evaluate(&self, witness: &[FieldElement]) -> Result<FieldElement, ZKError> {
+    pub fn evaluate(&self, witness: &[F]) -> Result<F, ZKError> {
         if witness.is_empty() {
             return Err(ZKError::CircuitError("Witness vector is empty.".into()));
         }
 
-        let modulus = witness[0].modulus;
-        let mut result = FieldElement::new(0, modulus)?;
+        let mut result = F::zero(&witness[0].params());
         for term in &self.terms {
             if term.index >= witness.len() {
-                return Err(ZKError::CircuitError(
-                    "Witness index {} is out of bounds.".into(),
-                ));
+                return Err(ZKError::CircuitError(format!(
+                    "Witness index {} is out of bounds.",
+                    term.index
+                )));
             }
             let term_value = term.coefficient.mul(&witness[term.index])?;
             result = result.add(&term_value)?;
@@ -47,70 +185,846 @@ impl LinearCombination {
     }
 }
 
+impl<F: Field> Default for LinearCombination<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> From<Variable<F>> for LinearCombination<F> {
+    fn from(variable: Variable<F>) -> Self {
+        let one = F::one(&variable.params);
+        variable * one
+    }
+}
+
+impl<F: Field> Add for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(mut self, rhs: LinearCombination<F>) -> LinearCombination<F> {
+        for term in rhs.terms {
+            self.fold_term(term);
+        }
+        self
+    }
+}
+
+impl<F: Field> Sub for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn sub(self, rhs: LinearCombination<F>) -> LinearCombination<F> {
+        self + -rhs
+    }
+}
+
+impl<F: Field> Neg for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn neg(mut self) -> LinearCombination<F> {
+        for term in &mut self.terms {
+            term.coefficient = term.coefficient.neg();
+        }
+        self
+    }
+}
+
+impl<F: Field> Mul<F> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn mul(self, rhs: F) -> LinearCombination<F> {
+        let mut result = LinearCombination::new();
+        for term in self.terms {
+            let coefficient = term
+                .coefficient
+                .mul(&rhs)
+                .unwrap_or_else(|e| panic!("{}", e));
+            result.fold_term(Term {
+                index: term.index,
+                coefficient,
+            });
+        }
+        result
+    }
+}
+
+impl<F: Field> Add<Variable<F>> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(self, rhs: Variable<F>) -> LinearCombination<F> {
+        self + LinearCombination::from(rhs)
+    }
+}
+
+impl<F: Field> Sub<Variable<F>> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn sub(self, rhs: Variable<F>) -> LinearCombination<F> {
+        self - LinearCombination::from(rhs)
+    }
+}
+
+impl<F: Field> Add<LinearCombination<F>> for Variable<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(self, rhs: LinearCombination<F>) -> LinearCombination<F> {
+        LinearCombination::from(self) + rhs
+    }
+}
+
+impl<F: Field> Sub<LinearCombination<F>> for Variable<F> {
+    type Output = LinearCombination<F>;
+
+    fn sub(self, rhs: LinearCombination<F>) -> LinearCombination<F> {
+        LinearCombination::from(self) - rhs
+    }
+}
+
+impl<F: Field> Add for Variable<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(self, rhs: Variable<F>) -> LinearCombination<F> {
+        LinearCombination::from(self) + rhs
+    }
+}
+
+impl<F: Field> Sub for Variable<F> {
+    type Output = LinearCombination<F>;
+
+    fn sub(self, rhs: Variable<F>) -> LinearCombination<F> {
+        LinearCombination::from(self) - rhs
+    }
+}
+
+impl<F: Field> Neg for Variable<F> {
+    type Output = LinearCombination<F>;
+
+    fn neg(self) -> LinearCombination<F> {
+        -LinearCombination::from(self)
+    }
+}
+
+impl<F: Field> Mul<F> for Variable<F> {
+    type Output = LinearCombination<F>;
+
+    fn mul(self, rhs: F) -> LinearCombination<F> {
+        let mut lc = LinearCombination::new();
+        lc.fold_term(Term {
+            index: self.index,
+            coefficient: rhs,
+        });
+        lc
+    }
+}
+
 /// Represents a R1CS constraint which is defined as:
 /// (LinearCombination a) x (LinearCombination b) = (LinearCombination c)
-#[derive(Clone, Debug)]
-pub struct R1CSConstraint {
-    pub a: LinearCombination,
-    pub b: LinearCombination,
-    pub c: LinearCombination,
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "F: serde::Serialize",
+        deserialize = "F: serde::Deserialize<'de>"
+    ))
+)]
+pub struct R1CSConstraint<F: Field = FieldElement> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
 }
 
-impl R1CSConstraint {
+impl<F: Field> R1CSConstraint<F> {
     /// Creates a new R1CSConstraint.
-    pub fn new(a: LinearCombination, b: LinearCombination, c: LinearCombination) -> Self {
+    pub fn new(a: LinearCombination<F>, b: LinearCombination<F>, c: LinearCombination<F>) -> Self {
         R1CSConstraint { a, b, c }
     }
+
+    /// Serializes the constraint as the `LinearCombination::to_bytes` encodings of a, b
+    /// and c.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Deserializes a constraint over the field described by `params` from the output of
+    /// `to_bytes`.
+    pub fn from_bytes(bytes: &[u8], params: &F::Params) -> Result<Self, ZKError> {
+        let mut reader = ByteReader::new(bytes, params);
+        let constraint = reader.constraint()?;
+        reader.finish()?;
+        Ok(constraint)
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        for lc in [&self.a, &self.b, &self.c] {
+            lc.write_bytes(bytes);
+        }
+    }
+}
+
+// Reads the encodings written by the `to_bytes` methods of this module.
+struct ByteReader<'a, F: Field> {
+    bytes: &'a [u8],
+    params: &'a F::Params,
+    width: usize,
+}
+
+impl<'a, F: Field> ByteReader<'a, F> {
+    fn new(bytes: &'a [u8], params: &'a F::Params) -> Self {
+        ByteReader {
+            bytes,
+            params,
+            width: F::zero(params).to_bytes().len(),
+        }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ZKError> {
+        if self.bytes.len() < n {
+            return Err(ZKError::InvalidFormat("Unexpected end of data.".into()));
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn usize(&mut self) -> Result<usize, ZKError> {
+        let value = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        usize::try_from(value)
+            .map_err(|_| ZKError::InvalidFormat(format!("Value {} is too large.", value)))
+    }
+
+    fn linear_combination(&mut self) -> Result<LinearCombination<F>, ZKError> {
+        let mut lc = LinearCombination::new();
+        for _ in 0..self.usize()? {
+            let index = self.usize()?;
+            let coefficient = F::from_bytes(self.take(self.width)?, self.params)?;
+            lc.add_term(Term { index, coefficient });
+        }
+        Ok(lc)
+    }
+
+    fn constraint(&mut self) -> Result<R1CSConstraint<F>, ZKError> {
+        Ok(R1CSConstraint::new(
+            self.linear_combination()?,
+            self.linear_combination()?,
+            self.linear_combination()?,
+        ))
+    }
+
+    fn finish(self) -> Result<(), ZKError> {
+        if !self.bytes.is_empty() {
+            return Err(ZKError::InvalidFormat(format!(
+                "{} unexpected trailing bytes.",
+                self.bytes.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The assigned value of one term of a constraint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermValue<F: Field = FieldElement> {
+    pub index: usize,
+    pub coefficient: F,
+    pub value: F,
+}
+
+/// One side of a constraint: the value of each term and of the whole combination.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SideValue<F: Field = FieldElement> {
+    pub terms: Vec<TermValue<F>>,
+    pub value: F,
+}
+
+impl<F: Field> SideValue<F> {
+    fn new(lc: &LinearCombination<F>, witness: &[F]) -> Result<Self, ZKError> {
+        let terms = lc
+            .terms
+            .iter()
+            .map(|term| {
+                let value = witness.get(term.index).cloned().ok_or_else(|| {
+                    ZKError::CircuitError(format!("Witness index {} is out of bounds.", term.index))
+                })?;
+                Ok(TermValue {
+                    index: term.index,
+                    coefficient: term.coefficient.clone(),
+                    value,
+                })
+            })
+            .collect::<Result<_, ZKError>>()?;
+        Ok(SideValue {
+            terms,
+            value: lc.evaluate(witness)?,
+        })
+    }
+}
+
+/// Reports a constraint that a witness does not satisfy, with the assigned value of
+/// every term, for debugging circuits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnsatisfiedConstraint<F: Field = FieldElement> {
+    /// The position of the constraint in the system.
+    pub index: usize,
+    pub a: SideValue<F>,
+    pub b: SideValue<F>,
+    pub c: SideValue<F>,
+}
+
+/// Displays the sides of the constraint one per line, each term as
+/// `coefficient * v<index> (= value)`.
+impl<F: Field> std::fmt::Display for UnsatisfiedConstraint<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Constraint {} not satisfied: {:?} x {:?} != {:?}",
+            self.index, self.a.value, self.b.value, self.c.value
+        )?;
+        for (name, side) in [("a", &self.a), ("b", &self.b), ("c", &self.c)] {
+            write!(f, "\n  {} =", name)?;
+            if side.terms.is_empty() {
+                write!(f, " 0")?;
+            }
+            for (i, term) in side.terms.iter().enumerate() {
+                let separator = if i == 0 { "" } else { " +" };
+                write!(
+                    f,
+                    "{} {:?} * v{} (= {:?})",
+                    separator, term.coefficient, term.index, term.value
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Stores a set of R1CS constraints and the number of variables.
-#[derive(Clone, Debug)]
-pub struct ConstraintSystem {
-    pub constraints: Vec<R1CSConstraint>,
+///
+/// The first `num_public` variables are public inputs, i.e. the statement known to the
+/// verifier; the rest are the prover's private witness. The constraints are kept in a
+/// `ConstraintStore`, by default a `Vec`; see `with_store` for keeping them elsewhere.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "RawConstraintSystem<F, S>",
+        bound(
+            serialize = "F: serde::Serialize, S: serde::Serialize",
+            deserialize = "F: serde::Deserialize<'de>, S: serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct ConstraintSystem<F: Field = FieldElement, S: ConstraintStore<F> = Vec<R1CSConstraint<F>>>
+{
+    pub constraints: S,
     pub num_variables: usize,
+    pub num_public: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    field: PhantomData<F>,
 }
 
-impl ConstraintSystem {
+// Unvalidated form of a constraint system, checked when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawConstraintSystem<F: Field, S> {
+    constraints: S,
+    num_variables: usize,
+    num_public: usize,
+    #[serde(skip)]
+    field: PhantomData<F>,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Field, S: ConstraintStore<F>> TryFrom<RawConstraintSystem<F, S>>
+    for ConstraintSystem<F, S>
+{
+    type Error = ZKError;
+
+    fn try_from(raw: RawConstraintSystem<F, S>) -> Result<Self, Self::Error> {
+        let cs = ConstraintSystem {
+            constraints: raw.constraints,
+            num_variables: raw.num_variables,
+            num_public: raw.num_public,
+            field: raw.field,
+        };
+        cs.check_indices()?;
+        Ok(cs)
+    }
+}
+
+impl<F: Field> ConstraintSystem<F> {
     /// Creates a new, empty constraint system.
     pub fn new() -> Self {
-        ConstraintSystem {
-            constraints: Vec::new(),
-            num_variables: 0,
+        Self::with_store(Vec::new())
+    }
+
+    /// Creates a constraint system holding the constraints of `circuit`.
+    pub fn from_circuit<C: Circuit<F> + ?Sized>(circuit: &C) -> Result<Self, ZKError> {
+        let mut cs = Self::new();
+        circuit.synthesize(&mut cs)?;
+        Ok(cs)
+    }
+
+    /// Creates a new, empty constraint system after checking that `params` describe a
+    /// field, e.g. rejecting a composite modulus.
+    pub fn new_checked(params: &F::Params) -> Result<Self, ZKError> {
+        F::validate_params(params)?;
+        Ok(Self::new())
+    }
+
+    /// Serializes the constraint system compactly: the number of variables, the number
+    /// of public inputs and the number of constraints as little-endian u64s, followed by
+    /// the `R1CSConstraint::to_bytes` encoding of each constraint.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for count in [self.num_variables, self.num_public, self.constraints.len()] {
+            bytes.extend((count as u64).to_le_bytes());
         }
+        for constraint in &self.constraints {
+            constraint.write_bytes(&mut bytes);
+        }
+        bytes
     }
 
-    /// Adds a new R1CS constraint.
-    pub fn add_constraint(&mut self, constraint: R1CSConstraint) {
-        self.constraints.push(constraint);
+    /// Deserializes a constraint system over the field described by `params` from the
+    /// output of `to_bytes`. Variable indices and the number of public inputs are
+    /// checked against the number of variables.
+    pub fn from_bytes(bytes: &[u8], params: &F::Params) -> Result<Self, ZKError> {
+        let mut reader = ByteReader::new(bytes, params);
+        let num_variables = reader.usize()?;
+        let num_public = reader.usize()?;
+        let num_constraints = reader.usize()?;
+        let mut constraints = Vec::new();
+        for _ in 0..num_constraints {
+            constraints.push(reader.constraint()?);
+        }
+        reader.finish()?;
+
+        let cs = ConstraintSystem {
+            constraints,
+            num_variables,
+            num_public,
+            field: PhantomData,
+        };
+        cs.check_indices()?;
+        Ok(cs)
     }
 
-    /// Allocates a new variable and returns its index.
-    pub fn allocate_variable(&mut self) -> usize {
-        let var_index = self.num_variables;
-        self.num_variables += 1;
-        var_index
+    /// Returns a SHA-256 fingerprint of the constraint system, over its `to_bytes`
+    /// encoding and the field of its coefficients. Equal systems have equal digests, so
+    /// a verifier can tell whether a proof was made for the circuit it expects. Terms are
+    /// hashed in order, so reordering them changes the digest.
+    pub fn digest(&self) -> [u8; 32] {
+        // -1 = p - 1 tells apart fields whose elements encode the same way.
+        let field_tag = self
+            .terms()
+            .next()
+            .map(|term| F::one(&term.coefficient.params()).neg().to_bytes())
+            .unwrap_or_default();
+        Sha256::new()
+            .chain_update(DIGEST_DOMAIN)
+            .chain_update((field_tag.len() as u64).to_le_bytes())
+            .chain_update(field_tag)
+            .chain_update(self.to_bytes())
+            .finalize()
+            .into()
+    }
+
+    // Iterates over the terms of all constraints.
+    fn terms(&self) -> impl Iterator<Item = &Term<F>> {
+        self.constraints
+            .iter()
+            .flat_map(|constraint| [&constraint.a, &constraint.b, &constraint.c])
+            .flat_map(|lc| &lc.terms)
     }
 
     /// Evaluates the provided witness against all constraints.
     /// For each constraint, it checks that LC a (witness) x LC b (witness) = LC c (witness).
-    pub fn evaluate(&self, witness: &[FieldElement]) -> Result<bool, ZKError> {
+    /// The error for the first failing constraint lists the value of every term, see
+    /// `UnsatisfiedConstraint`.
+    pub fn evaluate(&self, witness: &[F]) -> Result<bool, ZKError> {
+        if let Some(failure) = self.find_unsatisfied(witness, true)?.pop() {
+            return Err(ZKError::CircuitError(failure.to_string()));
+        }
+
+        Ok(true)
+    }
+
+    /// Returns every constraint the witness does not satisfy, in order. Fails only if
+    /// the witness is too short for the constraints.
+    pub fn unsatisfied_constraints(
+        &self,
+        witness: &[F],
+    ) -> Result<Vec<UnsatisfiedConstraint<F>>, ZKError> {
+        self.find_unsatisfied(witness, false)
+    }
+
+    fn find_unsatisfied(
+        &self,
+        witness: &[F],
+        first_only: bool,
+    ) -> Result<Vec<UnsatisfiedConstraint<F>>, ZKError> {
+        let mut failures = Vec::new();
         for (i, constraint) in self.constraints.iter().enumerate() {
             let a_val = constraint.a.evaluate(witness)?;
             let b_val = constraint.b.evaluate(witness)?;
             let c_val = constraint.c.evaluate(witness)?;
             let product = a_val.mul(&b_val)?;
             if product != c_val {
+                failures.push(UnsatisfiedConstraint {
+                    index: i,
+                    a: SideValue::new(&constraint.a, witness)?,
+                    b: SideValue::new(&constraint.b, witness)?,
+                    c: SideValue::new(&constraint.c, witness)?,
+                });
+                if first_only {
+                    break;
+                }
+            }
+        }
+        Ok(failures)
+    }
+}
+
+impl<F: Field, S: ConstraintStore<F>> ConstraintSystem<F, S> {
+    /// Creates a new constraint system that adds its constraints to `store`, e.g. a
+    /// `FileConstraintStore` for circuits too large to keep in memory.
+    pub fn with_store(store: S) -> Self {
+        ConstraintSystem {
+            constraints: store,
+            num_variables: 0,
+            num_public: 0,
+            field: PhantomData,
+        }
+    }
+
+    /// Checks that the constraint system is well-formed, before it is handed to
+    /// `QAP::create` or a prover:
+    /// - every term refers to one of the `num_variables` variables;
+    /// - all coefficients belong to the same field;
+    /// - every constraint has a term, so none is trivially satisfied;
+    /// - variable 0, which holds the constant one, is a public input.
+    ///
+    /// Systems whose variables were all allocated with `allocate_variable` have no public
+    /// inputs, so they fail the last check.
+    pub fn validate(&self) -> Result<(), ZKError> {
+        self.field_params()?;
+        if self.num_public == 0 {
+            return Err(ZKError::CircuitError(
+                "Variable 0 must be the public constant one, but there are no public inputs."
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Returns the parameters of the field all coefficients belong to, after checking
+    // the indices and that no constraint is empty.
+    pub(crate) fn field_params(&self) -> Result<F::Params, ZKError> {
+        self.check_num_public()?;
+        let mut params: Option<F::Params> = None;
+        let mut i = 0;
+        self.constraints.for_each(|constraint| {
+            self.check_constraint_indices(i, constraint)?;
+            let sides = [&constraint.a, &constraint.b, &constraint.c];
+            if sides.iter().all(|lc| lc.terms.is_empty()) {
                 return Err(ZKError::CircuitError(format!(
-                    "Constraint {} not satisfied: {:?} x {:?} != {:?}",
-                    i, a_val, b_val, c_val
+                    "Constraint {} has no terms.",
+                    i
                 )));
             }
+            for term in sides.iter().flat_map(|lc| &lc.terms) {
+                let term_params = term.coefficient.params();
+                match &params {
+                    Some(expected) if *expected != term_params => {
+                        return Err(ZKError::CircuitError(format!(
+                            "Constraint {} mixes fields: expected coefficients with {:?}, got {:?}.",
+                            i, expected, term_params
+                        )));
+                    }
+                    Some(_) => {}
+                    None => params = Some(term_params),
+                }
+            }
+            i += 1;
+            Ok(())
+        })?;
+        params.ok_or_else(|| ZKError::CircuitError("No constraints available.".into()))
+    }
+
+    // Checks that the public inputs and every term refer to allocated variables.
+    fn check_indices(&self) -> Result<(), ZKError> {
+        self.check_num_public()?;
+        let mut i = 0;
+        self.constraints.for_each(|constraint| {
+            self.check_constraint_indices(i, constraint)?;
+            i += 1;
+            Ok(())
+        })
+    }
+
+    fn check_num_public(&self) -> Result<(), ZKError> {
+        if self.num_public > self.num_variables {
+            return Err(ZKError::CircuitError(format!(
+                "{} public inputs exceed {} variables.",
+                self.num_public, self.num_variables
+            )));
         }
+        Ok(())
+    }
 
-        Ok(true)
+    fn check_constraint_indices(
+        &self,
+        i: usize,
+        constraint: &R1CSConstraint<F>,
+    ) -> Result<(), ZKError> {
+        for lc in [&constraint.a, &constraint.b, &constraint.c] {
+            if let Some(term) = lc.terms.iter().find(|t| t.index >= self.num_variables) {
+                return Err(ZKError::CircuitError(format!(
+                    "Constraint {} refers to variable {}, but there are only {}.",
+                    i, term.index, self.num_variables
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the witness against all constraints at once, in one pass over the store:
+    /// for random r_i it tests Σ r_i · (a_i · b_i − c_i) = 0 instead of comparing every
+    /// product. A satisfying witness always passes; one that violates some constraint
+    /// passes with probability at most 1 / |F|. This makes a cheap pre-flight check
+    /// before building the QAP or proving; use `unsatisfied_constraints` to find the
+    /// failing constraints.
+    pub fn check_batched<R: RngCore + ?Sized>(
+        &self,
+        witness: &[F],
+        rng: &mut R,
+    ) -> Result<bool, ZKError> {
+        let mut sum: Option<F> = None;
+        self.constraints.for_each(|constraint| {
+            let a = constraint.a.evaluate(witness)?;
+            let b = constraint.b.evaluate(witness)?;
+            let c = constraint.c.evaluate(witness)?;
+            let r = F::random(rng, &a.params())?;
+            let term = r.mul(&a.mul(&b)?.sub(&c)?)?;
+            sum = Some(match sum.take() {
+                Some(sum) => sum.add(&term)?,
+                None => term,
+            });
+            Ok(())
+        })?;
+        Ok(sum.is_none_or(|sum| sum.is_zero()))
+    }
+
+    /// Adds a new R1CS constraint.
+    pub fn add_constraint(&mut self, constraint: R1CSConstraint<F>) {
+        self.constraints.push(constraint);
+    }
+
+    /// Adds the constraint a x b = c, for linear combinations written symbolically.
+    pub fn enforce(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+        c: impl Into<LinearCombination<F>>,
+    ) {
+        self.add_constraint(R1CSConstraint::new(a.into(), b.into(), c.into()));
+    }
+
+    // The assertion helpers below use variable 0 as the constant one, as `validate`
+    // requires.
+    fn one(var: &Variable<F>) -> Variable<F> {
+        Variable::new(0, &var.params)
+    }
+
+    /// Constrains a and b to be equal: (a − b) x 1 = 0.
+    pub fn enforce_equal(&mut self, a: Variable<F>, b: Variable<F>) {
+        let one = Self::one(&a);
+        self.enforce(a - b, one, LinearCombination::new());
+    }
+
+    /// Constrains `var` to equal the constant `value`: var x 1 = value · 1.
+    pub fn enforce_constant(&mut self, var: Variable<F>, value: F) {
+        let one = Self::one(&var);
+        self.enforce(var, one.clone(), one * value);
+    }
+
+    /// Constrains `var` to be 0 or 1: var x (var − 1) = 0.
+    pub fn enforce_boolean(&mut self, var: Variable<F>) {
+        let one = Self::one(&var);
+        self.enforce(var.clone(), var - one, LinearCombination::new());
+    }
+
+    /// Constrains `var` to be nonzero by allocating a private witness for its inverse:
+    /// var x inverse = 1. Returns the index of the inverse, which the witness must set.
+    pub fn enforce_nonzero(&mut self, var: Variable<F>) -> usize {
+        let one = Self::one(&var);
+        let inverse = Variable::new(self.allocate_private_witness(), &var.params);
+        let index = inverse.index;
+        self.enforce(var, inverse, one);
+        index
+    }
+
+    /// Allocates a new variable and returns its index.
+    /// The variable is part of the private witness.
+    pub fn allocate_variable(&mut self) -> usize {
+        let var_index = self.num_variables;
+        self.num_variables += 1;
+        var_index
+    }
+
+    /// Allocates a new public input and returns its index.
+    /// Public inputs occupy the first indices of the witness, so they must all be
+    /// allocated before any private witness variable.
+    pub fn allocate_public_input(&mut self) -> Result<usize, ZKError> {
+        if self.num_public != self.num_variables {
+            return Err(ZKError::CircuitError(
+                "Public inputs must be allocated before private witness variables.".into(),
+            ));
+        }
+        self.num_public += 1;
+        Ok(self.allocate_variable())
+    }
+
+    /// Allocates a new private witness variable and returns its index.
+    pub fn allocate_private_witness(&mut self) -> usize {
+        self.allocate_variable()
+    }
+
+    /// Returns the number of private witness variables.
+    pub fn num_private(&self) -> usize {
+        self.num_variables - self.num_public
+    }
+
+    /// Splits a full witness vector into the public inputs (the statement) and the
+    /// private witness.
+    pub fn split_witness<'a>(&self, witness: &'a [F]) -> Result<(&'a [F], &'a [F]), ZKError> {
+        if witness.len() != self.num_variables {
+            return Err(ZKError::CircuitError(format!(
+                "Expected a witness of {} variables, got {}.",
+                self.num_variables,
+                witness.len()
+            )));
+        }
+        Ok(witness.split_at(self.num_public))
+    }
+}
+
+impl ConstraintSystem<FieldElement> {
+    /// Renders the constraint system as a Graphviz DOT graph. Variables are nodes,
+    /// with public inputs drawn as double circles, and each constraint is a box labelled
+    /// with its equation, e.g. `c0: 2 v1 * (v1 + v2) = v3`. Edges run from the
+    /// variables of a and b into the constraint and from the constraint to those of c.
+    pub fn to_dot(&self) -> String {
+        let value_only = DisplayFormat {
+            hex: false,
+            with_modulus: false,
+        };
+        let render = |lc: &LinearCombination| {
+            if lc.terms.is_empty() {
+                return "0".to_string();
+            }
+            let terms: Vec<String> = lc
+                .terms
+                .iter()
+                .map(|term| {
+                    if term.coefficient.is_one() {
+                        format!("v{}", term.index)
+                    } else {
+                        format!("{} v{}", term.coefficient.display(value_only), term.index)
+                    }
+                })
+                .collect();
+            terms.join(" + ")
+        };
+        let parenthesize = |lc: &LinearCombination| match lc.terms.len() {
+            0 | 1 => render(lc),
+            _ => format!("({})", render(lc)),
+        };
+
+        let mut dot = String::from("digraph circuit {\n");
+        if let Some(term) = self.terms().next() {
+            dot.push_str(&format!(
+                "  label=\"R1CS over F_{}\";\n",
+                term.coefficient.modulus
+            ));
+        }
+        dot.push_str("  node [shape=circle];\n");
+        for i in 0..self.num_variables {
+            let shape = if i < self.num_public {
+                " shape=doublecircle"
+            } else {
+                ""
+            };
+            dot.push_str(&format!("  v{} [label=\"v{}\"{}];\n", i, i, shape));
+        }
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            dot.push_str(&format!(
+                "  c{} [shape=box label=\"c{}: {} * {} = {}\"];\n",
+                i,
+                i,
+                parenthesize(&constraint.a),
+                parenthesize(&constraint.b),
+                render(&constraint.c)
+            ));
+            for (side, lc) in [("a", &constraint.a), ("b", &constraint.b)] {
+                for term in &lc.terms {
+                    dot.push_str(&format!(
+                        "  v{} -> c{} [label=\"{}\"];\n",
+                        term.index, i, side
+                    ));
+                }
+            }
+            for term in &constraint.c.terms {
+                dot.push_str(&format!("  c{} -> v{} [label=\"c\"];\n", i, term.index));
+            }
+        }
+        dot.push_str("}\n");
+        dot
     }
 }
 
+impl<F: Field> Default for ConstraintSystem<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Adds constraints written as `a * b == c` to a `ConstraintSystem` or `CircuitBuilder`,
+/// separated by semicolons, e.g. `r1cs!(cs; x * x == x2; (x2 + x) * one == out)`.
+///
+/// Each side is anything that converts into a `LinearCombination`, e.g. a `Variable`
+/// or a symbolic sum of them; the factors on the left must be single tokens, so sums
+/// need parentheses. Expands to one `enforce` call per constraint.
+#[macro_export]
+macro_rules! r1cs {
+    ($cs:expr; $($a:tt * $b:tt == $c:expr);+ $(;)?) => {
+        // Parenthesized factors are passed on as they are.
+        $(
+            #[allow(unused_parens)]
+            $cs.enforce($a, $b, $c);
+        )+
+    };
+}
+
+/// A circuit that can be laid out in a constraint system, so one description serves
+/// both the setup, which needs only the shape of the constraints, and the prover, which
+/// also needs the assignment.
+pub trait Circuit<F: Field = FieldElement> {
+    /// Allocates the variables of the circuit in `cs` and adds its constraints. The
+    /// result must not depend on the assignment, nor on where `cs` stores constraints.
+    fn synthesize<S: ConstraintStore<F>>(
+        &self,
+        cs: &mut ConstraintSystem<F, S>,
+    ) -> Result<(), ZKError>;
+
+    /// Returns the value of every variable allocated by `synthesize`, in allocation
+    /// order. Fails if the circuit was created without an assignment, e.g. for setup.
+    fn witness(&self) -> Result<Vec<F>, ZKError>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,6 +1054,63 @@ mod tests {
         assert_eq!(result, FieldElement::new(26, modulus).unwrap());
     }
 
+    #[test]
+    fn test_normalize() {
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let term = |index, v| Term {
+            index,
+            coefficient: fe(v),
+        };
+        // 3v1 + 5v0 + 4v1 + 0v2 + 92v0 - the v0 terms cancel.
+        let mut lc = LinearCombination::new();
+        for (index, v) in [(1, 3), (0, 5), (1, 4), (2, 0), (0, 92)] {
+            lc.add_term(term(index, v));
+        }
+        assert_eq!(lc.coefficient(1).unwrap(), Some(fe(7)));
+        assert_eq!(lc.coefficient(0).unwrap(), Some(fe(0)));
+        assert_eq!(lc.coefficient(3).unwrap(), None);
+
+        let witness = vec![fe(2), fe(10), fe(50)];
+        let before = lc.evaluate(&witness).unwrap();
+        lc.normalize().unwrap();
+        assert_eq!(lc.terms.len(), 1);
+        assert_eq!((lc.terms[0].index, &lc.terms[0].coefficient), (1, &fe(7)));
+        assert_eq!(lc.evaluate(&witness).unwrap(), before);
+
+        lc.add_term(Term {
+            index: 1,
+            coefficient: FieldElement::new(1, 101).unwrap(),
+        });
+        assert!(lc.normalize().is_err());
+    }
+
+    #[test]
+    fn test_r1cs_macro() {
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::new();
+        let [one, x, y, z] = [(); 4].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+        r1cs!(cs;
+            x * y == z;
+            (x + y) * one == z - x * fe(2);
+        );
+        assert_eq!(cs.constraints.len(), 2);
+        assert_eq!(
+            cs.constraints[1].c.coefficient(x.index).unwrap(),
+            Some(fe(95))
+        );
+
+        // x = 2, y = 6, z = 12: 2 * 6 = 12 and 2 + 6 = 12 - 4.
+        assert!(cs.evaluate(&[fe(1), fe(2), fe(6), fe(12)]).unwrap());
+        // x = 3, y = 1, z = 3 satisfies only the first.
+        let failures = cs
+            .unsatisfied_constraints(&[fe(1), fe(3), fe(1), fe(3)])
+            .unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].index, 1);
+    }
+
     #[test]
     fn test_constraint_system() {
         let modulus = 97;
@@ -182,6 +1153,352 @@ mod tests {
             FieldElement::new(12, modulus).unwrap(),
         ];
         let result = cs.evaluate(&witness).unwrap();
-        assert_eq!(result, true);
+        assert!(result);
+    }
+
+    // x * y = z with x public, over F_97.
+    fn product_circuit() -> ConstraintSystem {
+        let modulus = 97;
+        let mut cs = ConstraintSystem::new();
+        let x = Variable::new(cs.allocate_public_input().unwrap(), &modulus);
+        let [y, z] = [(); 2].map(|_| Variable::new(cs.allocate_private_witness(), &modulus));
+        r1cs!(cs; (x * FieldElement::new(2, modulus).unwrap()) * (y + x) == z);
+        cs
+    }
+
+    #[test]
+    fn test_assertion_helpers() {
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::<FieldElement>::new();
+        cs.allocate_public_input().unwrap();
+        let x = Variable::new(cs.allocate_public_input().unwrap(), &modulus);
+        let y = Variable::new(cs.allocate_private_witness(), &modulus);
+        let b = Variable::new(cs.allocate_private_witness(), &modulus);
+        cs.enforce_equal(x, y);
+        cs.enforce_constant(x, fe(5));
+        cs.enforce_boolean(b);
+        assert_eq!(cs.enforce_nonzero(x), 4);
+        assert_eq!((cs.constraints.len(), cs.num_variables), (4, 5));
+        assert!(cs.validate().is_ok());
+
+        // x = y = 5, b = 1 and 1 / 5 = 39, as 5 · 39 = 195 = 2 · 97 + 1.
+        let witness = |values: [u64; 5]| values.map(fe);
+        assert!(cs.evaluate(&witness([1, 5, 5, 1, 39])).unwrap());
+        let failing = |values| {
+            cs.unsatisfied_constraints(&witness(values))
+                .unwrap()
+                .iter()
+                .map(|failure| failure.index)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(failing([1, 5, 6, 1, 39]), [0]);
+        assert_eq!(failing([1, 6, 6, 1, 39]), [1, 3]);
+        assert_eq!(failing([1, 5, 5, 2, 39]), [2]);
+        assert_eq!(failing([1, 5, 5, 0, 0]), [3]);
+    }
+
+    #[test]
+    fn test_check_batched() {
+        let cs = product_circuit();
+        let mut rng = rand::rng();
+        let witness = |values: [u64; 3]| values.map(|v| FieldElement::new(v, 97).unwrap());
+        // (2x) * (y + x) = z for x = 3, y = 4: 6 * 7 = 42.
+        for _ in 0..10 {
+            assert!(cs.check_batched(&witness([3, 4, 42]), &mut rng).unwrap());
+        }
+        // A wrong z passes only if r = 0, with probability 1 / 97 per try.
+        let rejected = (0..10)
+            .filter(|_| !cs.check_batched(&witness([3, 4, 43]), &mut rng).unwrap())
+            .count();
+        assert!(rejected > 0);
+        assert!(cs
+            .check_batched(&witness([3, 4, 42])[..2], &mut rng)
+            .is_err());
+        assert!(ConstraintSystem::<FieldElement>::new()
+            .check_batched(&[], &mut rng)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let cs = product_circuit();
+        let bytes = cs.to_bytes();
+        // Three counts, then per side a count and 16 bytes per term: 1 + 2 + 1 terms.
+        assert_eq!(bytes.len(), 3 * 8 + 3 * 8 + 4 * 16);
+        assert_eq!(ConstraintSystem::from_bytes(&bytes, &97).unwrap(), cs);
+
+        let constraint = &cs.constraints[0];
+        let decoded = R1CSConstraint::from_bytes(&constraint.to_bytes(), &97).unwrap();
+        assert_eq!(&decoded, constraint);
+        let decoded = LinearCombination::from_bytes(&constraint.b.to_bytes(), &97).unwrap();
+        assert_eq!(decoded, constraint.b);
+
+        // Truncated and trailing data, non-canonical coefficients and out-of-range
+        // variables are rejected.
+        let from_bytes = |bytes: &[u8]| ConstraintSystem::<FieldElement>::from_bytes(bytes, &97);
+        assert!(from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(ConstraintSystem::<FieldElement>::from_bytes(&bytes, &2).is_err());
+        let mut too_few = bytes.clone();
+        too_few[0] = 2;
+        assert!(from_bytes(&too_few).is_err());
+        let mut too_public = bytes.clone();
+        too_public[8] = 4;
+        assert!(from_bytes(&too_public).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        assert!(product_circuit().validate().is_ok());
+
+        // No public inputs, so variable 0 is not the constant one.
+        let mut cs = ConstraintSystem::<FieldElement>::new();
+        let [x, y] = [(); 2].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+        r1cs!(cs; x * x == y);
+        assert!(cs.validate().is_err());
+        assert!(cs.field_params().is_ok());
+
+        let valid = product_circuit();
+        let mut out_of_range = valid.clone();
+        out_of_range.constraints[0].c.terms[0].index = 3;
+        let mut mixed = valid.clone();
+        mixed.constraints[0].c.terms[0].coefficient = FieldElement::new(1, 101).unwrap();
+        let mut empty = valid.clone();
+        empty.add_constraint(R1CSConstraint::new(
+            LinearCombination::new(),
+            LinearCombination::new(),
+            LinearCombination::new(),
+        ));
+        let mut no_constraints = valid.clone();
+        no_constraints.constraints.clear();
+        for cs in [out_of_range, mixed, empty, no_constraints] {
+            assert!(matches!(cs.validate(), Err(ZKError::CircuitError(_))));
+        }
+
+        // Linear constraints with empty a and b, as circom writes them, are fine.
+        let mut linear = valid;
+        let (one, z) = (Variable::new(0, &modulus), Variable::new(2, &modulus));
+        linear.enforce(
+            LinearCombination::new(),
+            LinearCombination::new(),
+            z - one * fe(24),
+        );
+        assert!(linear.validate().is_ok());
+    }
+
+    #[test]
+    fn test_digest() {
+        let cs = product_circuit();
+        assert_eq!(cs.digest(), product_circuit().digest());
+        assert_eq!(
+            cs.digest(),
+            ConstraintSystem::<FieldElement>::from_bytes(&cs.to_bytes(), &97)
+                .unwrap()
+                .digest()
+        );
+
+        let mut coefficient = cs.clone();
+        coefficient.constraints[0].a.terms[0].coefficient = FieldElement::new(3, 97).unwrap();
+        let mut public = cs.clone();
+        public.num_public = 2;
+        let mut variables = cs.clone();
+        variables.allocate_variable();
+        // The same values modulo 101 encode the same way.
+        let mut field = cs.clone();
+        for constraint in &mut field.constraints {
+            for lc in [&mut constraint.a, &mut constraint.b, &mut constraint.c] {
+                for term in &mut lc.terms {
+                    term.coefficient.modulus = 101;
+                }
+            }
+        }
+        assert_eq!(field.to_bytes(), cs.to_bytes());
+        for other in [coefficient, public, variables, field] {
+            assert_ne!(other.digest(), cs.digest());
+        }
+        assert_ne!(
+            ConstraintSystem::<FieldElement>::new().digest(),
+            cs.digest()
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = product_circuit().to_dot();
+        let expected = [
+            "digraph circuit {",
+            "  label=\"R1CS over F_97\";",
+            "  node [shape=circle];",
+            "  v0 [label=\"v0\" shape=doublecircle];",
+            "  v1 [label=\"v1\"];",
+            "  v2 [label=\"v2\"];",
+            "  c0 [shape=box label=\"c0: 2 v0 * (v1 + v0) = v2\"];",
+            "  v0 -> c0 [label=\"a\"];",
+            "  v1 -> c0 [label=\"b\"];",
+            "  v0 -> c0 [label=\"b\"];",
+            "  c0 -> v2 [label=\"c\"];",
+            "}",
+            "",
+        ];
+        assert_eq!(dot, expected.join("\n"));
+
+        // Empty linear combinations render as zero.
+        let mut cs = ConstraintSystem::new();
+        cs.allocate_variable();
+        cs.add_constraint(R1CSConstraint::new(
+            LinearCombination::new(),
+            LinearCombination::new(),
+            LinearCombination::new(),
+        ));
+        assert!(cs.to_dot().contains("c0: 0 * 0 = 0"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let cs = product_circuit();
+        let json = serde_json::to_string(&cs).unwrap();
+        assert_eq!(serde_json::from_str::<ConstraintSystem>(&json).unwrap(), cs);
+        let lc = &cs.constraints[0].b;
+        let json = serde_json::to_string(lc).unwrap();
+        assert_eq!(
+            &serde_json::from_str::<LinearCombination>(&json).unwrap(),
+            lc
+        );
+
+        // Deserialization checks the variable counts.
+        let tampered = serde_json::to_string(&cs)
+            .unwrap()
+            .replace("\"num_variables\":3", "\"num_variables\":2");
+        assert!(serde_json::from_str::<ConstraintSystem>(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_public_inputs() {
+        let modulus = 97;
+        let mut cs = ConstraintSystem::new();
+
+        // Prove knowledge of a factorisation: v0 = v1 * v2, with v0 public.
+        let v0 = cs.allocate_public_input().unwrap();
+        let v1 = cs.allocate_private_witness();
+        let v2 = cs.allocate_private_witness();
+        assert_eq!((v0, v1, v2), (0, 1, 2));
+        assert_eq!((cs.num_public, cs.num_private()), (1, 2));
+
+        // Public inputs cannot follow private witness variables.
+        assert!(cs.allocate_public_input().is_err());
+        assert_eq!(cs.num_variables, 3);
+
+        let witness: Vec<FieldElement> = [91, 7, 13]
+            .iter()
+            .map(|&v| FieldElement::new(v, modulus).unwrap())
+            .collect();
+        let (public, private) = cs.split_witness(&witness).unwrap();
+        assert_eq!(public, &witness[..1]);
+        assert_eq!(private, &witness[1..]);
+        assert!(cs.split_witness(&witness[..2]).is_err());
+    }
+
+    #[test]
+    fn test_symbolic_linear_combinations() {
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::new();
+        let variable = |index| Variable::<FieldElement>::new(index, &modulus);
+        let one = variable(cs.allocate_public_input().unwrap());
+        let x = variable(cs.allocate_private_witness());
+        let y = variable(cs.allocate_private_witness());
+        let z = variable(cs.allocate_private_witness());
+
+        // Coefficients on the same variable are folded, and cancelling terms vanish.
+        let lc = x + y * fe(2) + x * fe(3) - y - y;
+        assert_eq!(lc.terms.len(), 1);
+        assert_eq!(lc.terms[0].index, x.index);
+        assert_eq!(lc.terms[0].coefficient, fe(4));
+        assert!((x - x).terms.is_empty());
+        assert!(((x + y) * fe(0)).terms.is_empty());
+
+        // x = 3, y = 4, z = 7: (x + y) * 1 = z and (2x - y + 5) * y = 4z.
+        cs.enforce(x + y, one, z);
+        cs.enforce(x * fe(2) - y + one * fe(5), y, z * fe(4));
+        assert!(cs.evaluate(&[fe(1), fe(3), fe(4), fe(7)]).unwrap());
+        assert!(cs.evaluate(&[fe(1), fe(3), fe(5), fe(8)]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_symbolic_field_mismatch() {
+        let x = Variable::<FieldElement>::new(0, &97);
+        let y = Variable::<FieldElement>::new(0, &101);
+        let _ = x + y;
+    }
+
+    #[test]
+    fn test_unsatisfied_constraints() {
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::new();
+        let variable = |index| Variable::<FieldElement>::new(index, &modulus);
+        let one = variable(cs.allocate_public_input().unwrap());
+        let x = variable(cs.allocate_private_witness());
+        let y = variable(cs.allocate_private_witness());
+
+        // x * x = y, (x + 2) * 1 = y, and y * 0 = 0.
+        cs.enforce(x, x, y);
+        cs.enforce(x + one * fe(2), one, y);
+        cs.enforce(y, LinearCombination::new(), LinearCombination::new());
+
+        // x = 3 and y = 6 break the first two constraints.
+        let witness = [fe(1), fe(3), fe(6)];
+        let failures = cs.unsatisfied_constraints(&witness).unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].index, 0);
+        assert_eq!(failures[1].index, 1);
+
+        let second = &failures[1];
+        assert_eq!(
+            second.a.terms,
+            vec![
+                TermValue {
+                    index: 1,
+                    coefficient: fe(1),
+                    value: fe(3),
+                },
+                TermValue {
+                    index: 0,
+                    coefficient: fe(2),
+                    value: fe(1),
+                },
+            ]
+        );
+        assert_eq!(
+            (&second.a.value, &second.b.value, &second.c.value),
+            (&fe(5), &fe(1), &fe(6))
+        );
+
+        // evaluate reports the first failure with its terms.
+        let message = cs.evaluate(&witness).unwrap_err().to_string();
+        assert!(message.contains("Constraint 0 not satisfied"));
+        assert!(message.contains(&format!("{:?} * v1 (= {:?})", fe(1), fe(3))));
+        assert!(cs.unsatisfied_constraints(&witness[..2]).is_err());
+
+        let failures = cs.unsatisfied_constraints(&[fe(1), fe(3), fe(9)]).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert!(cs
+            .unsatisfied_constraints(&[fe(1), fe(2), fe(4)])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_new_checked() {
+        assert!(ConstraintSystem::<FieldElement>::new_checked(&97).is_ok());
+        assert!(matches!(
+            ConstraintSystem::<FieldElement>::new_checked(&91),
+            Err(ZKError::CompositeModulus(91))
+        ));
     }
 }
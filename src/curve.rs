@@ -1,23 +1,232 @@
-use crate::{errors::ZKError, field::FieldElement};
+use std::collections::HashMap;
+
+use crate::{
+    errors::ZKError,
+    field::{is_prime, Field, FieldElement},
+    fp::PrimeField,
+    fp2::Fp2,
+    parallel,
+};
+
+/// Largest scalar for which `AdditionChain::new` searches for a shortest chain.
+pub const SHORTEST_CHAIN_LIMIT: u64 = 1 << 10;
+
+// Largest modulus for which `EllipticCurve::points` enumerates the curve.
+const POINT_ENUMERATION_LIMIT: u64 = 1 << 16;
 
 /// Represents an elliptic curve defined by the equation:
 /// y^2 = x^3 + ax + b (mod p)
+///
+/// The coefficients may live in any field, e.g. `Fp2` for the twist carrying G2.
+/// Curves used for cryptography also describe their prime-order subgroup.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "RawEllipticCurve<F>",
+        bound(
+            serialize = "F: serde::Serialize",
+            deserialize = "F: serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct EllipticCurve<F: Field = FieldElement> {
+    pub a: F,
+    pub b: F,
+    pub subgroup: Option<PrimeSubgroup<F>>,
+}
+
+// Unvalidated form of a curve, checked by `EllipticCurve::with_subgroup` when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawEllipticCurve<F: Field> {
+    a: F,
+    b: F,
+    subgroup: Option<PrimeSubgroup<F>>,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Field> TryFrom<RawEllipticCurve<F>> for EllipticCurve<F> {
+    type Error = ZKError;
+
+    fn try_from(raw: RawEllipticCurve<F>) -> Result<Self, Self::Error> {
+        match raw.subgroup {
+            Some(subgroup) => EllipticCurve::with_subgroup(
+                raw.a,
+                raw.b,
+                subgroup.generator,
+                subgroup.order,
+                subgroup.cofactor,
+            ),
+            None => EllipticCurve::new(raw.a, raw.b),
+        }
+    }
+}
+
+/// The prime-order subgroup of a curve that protocols work in.
 #[derive(Debug, Clone)]
-pub struct EllipticCurve {
-    pub a: FieldElement,
-    pub b: FieldElement,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "F: serde::Serialize",
+        deserialize = "F: serde::Deserialize<'de>"
+    ))
+)]
+pub struct PrimeSubgroup<F: Field = FieldElement> {
+    /// The canonical generator of the subgroup.
+    pub generator: EllipticCurvePoint<F>,
+    /// The prime order r of the subgroup.
+    pub order: u64,
+    /// The cofactor h, so that the curve has h * r points.
+    pub cofactor: u64,
 }
 
 /// Represents a point on the elliptic curve.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum EllipticCurvePoint {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "F: serde::Serialize",
+        deserialize = "F: serde::Deserialize<'de>"
+    ))
+)]
+pub enum EllipticCurvePoint<F: Field = FieldElement> {
     Infinity,
-    Point { x: FieldElement, y: FieldElement },
+    Point { x: F, y: F },
 }
 
-impl EllipticCurve {
+impl<F: Field> EllipticCurvePoint<F> {
+    /// Creates the affine point (x, y), checking that it lies on the curve.
+    pub fn new(x: F, y: F, curve: &EllipticCurve<F>) -> Result<Self, ZKError> {
+        let point = EllipticCurvePoint::Point { x, y };
+        curve.check_field(&point)?;
+        if !curve.is_on_curve(&point)? {
+            return Err(ZKError::CurveError("Point is not on the curve.".into()));
+        }
+        Ok(point)
+    }
+
+    /// Returns the inverse -P = (x, -y) of the point.
+    pub fn neg(&self) -> Self {
+        match self {
+            EllipticCurvePoint::Infinity => EllipticCurvePoint::Infinity,
+            EllipticCurvePoint::Point { x, y } => EllipticCurvePoint::Point {
+                x: x.clone(),
+                y: y.neg(),
+            },
+        }
+    }
+}
+
+/// Represents a point in Jacobian coordinates (X : Y : Z), standing for the affine point
+/// (X / Z^2, Y / Z^3). Points with Z = 0 are the point at infinity.
+///
+/// Additions and doublings in these coordinates need no field inversion; a single one is
+/// paid when converting back to affine coordinates.
+#[derive(Debug, Clone)]
+pub struct JacobianPoint<F: Field = FieldElement> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+}
+
+impl<F: Field> JacobianPoint<F> {
+    /// Returns the point at infinity (1 : 1 : 0).
+    pub fn infinity(params: &F::Params) -> Self {
+        JacobianPoint {
+            x: F::one(params),
+            y: F::one(params),
+            z: F::zero(params),
+        }
+    }
+
+    /// Returns true if this is the point at infinity.
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+}
+
+impl<F: Field> EllipticCurve<F> {
+    /// Creates a curve, rejecting singular ones whose discriminant 4a^3 + 27b^2 is zero.
+    pub fn new(a: F, b: F) -> Result<Self, ZKError> {
+        if a.params() != b.params() {
+            return Err(ZKError::InvalidFieldElement(
+                "Curve coefficients must belong to the same field.".into(),
+            ));
+        }
+        let params = a.params();
+        let four_a3 = F::from_u64(4, &params).mul(&a.mul(&a)?.mul(&a)?)?;
+        let twenty_seven_b2 = F::from_u64(27, &params).mul(&b.mul(&b)?)?;
+        if four_a3.add(&twenty_seven_b2)?.is_zero() {
+            return Err(ZKError::CurveError(
+                "Curve is singular: 4a^3 + 27b^2 = 0.".into(),
+            ));
+        }
+        Ok(EllipticCurve {
+            a,
+            b,
+            subgroup: None,
+        })
+    }
+
+    /// Creates a curve together with its prime-order subgroup, checking that the
+    /// generator lies on the curve and has the given prime order.
+    pub fn with_subgroup(
+        a: F,
+        b: F,
+        generator: EllipticCurvePoint<F>,
+        order: u64,
+        cofactor: u64,
+    ) -> Result<Self, ZKError> {
+        if !is_prime(order) {
+            return Err(ZKError::CurveError(format!(
+                "Subgroup order {} is not prime.",
+                order
+            )));
+        }
+        if cofactor == 0 || order.checked_mul(cofactor).is_none() {
+            return Err(ZKError::CurveError(format!(
+                "Invalid cofactor {} for subgroup order {}.",
+                cofactor, order
+            )));
+        }
+
+        let curve = EllipticCurve::new(a, b)?;
+        curve.check_field(&generator)?;
+        if generator == EllipticCurvePoint::Infinity || !curve.is_on_curve(&generator)? {
+            return Err(ZKError::CurveError(
+                "Generator must be a finite point on the curve.".into(),
+            ));
+        }
+        if curve.mul_scalar(&generator, order)? != EllipticCurvePoint::Infinity {
+            return Err(ZKError::CurveError(format!(
+                "Generator does not have order {}.",
+                order
+            )));
+        }
+
+        Ok(EllipticCurve {
+            subgroup: Some(PrimeSubgroup {
+                generator,
+                order,
+                cofactor,
+            }),
+            ..curve
+        })
+    }
+
+    /// Returns the prime-order subgroup, or an error if the curve does not describe one.
+    pub fn subgroup(&self) -> Result<&PrimeSubgroup<F>, ZKError> {
+        self.subgroup.as_ref().ok_or_else(|| {
+            ZKError::CurveError("Curve does not describe a prime-order subgroup.".into())
+        })
+    }
+
     /// Check if the given point lies on the elliptic curve.
-    pub fn is_on_curve(&self, point: &EllipticCurvePoint) -> Result<bool, ZKError> {
+    pub fn is_on_curve(&self, point: &EllipticCurvePoint<F>) -> Result<bool, ZKError> {
         match point {
             EllipticCurvePoint::Infinity => Ok(true),
             EllipticCurvePoint::Point { x, y } => {
@@ -34,36 +243,43 @@ impl EllipticCurve {
     /// Add two points on the elliptic curve.
     pub fn add_points(
         &self,
-        p: &EllipticCurvePoint,
-        q: &EllipticCurvePoint,
-    ) -> Result<EllipticCurvePoint, ZKError> {
+        p: &EllipticCurvePoint<F>,
+        q: &EllipticCurvePoint<F>,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        self.check_field(p)?;
+        self.check_field(q)?;
+        self.add_points_unchecked(p, q)
+    }
+
+    // Checks that the point's coordinates belong to the curve's field.
+    fn check_field(&self, point: &EllipticCurvePoint<F>) -> Result<(), ZKError> {
+        if let EllipticCurvePoint::Point { x, y } = point {
+            let params = self.a.params();
+            if x.params() != params || y.params() != params {
+                return Err(ZKError::InvalidFieldElement(
+                    "Point coordinates must belong to the curve's field.".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Adds two points whose coordinates are known to belong to the curve's field.
+    fn add_points_unchecked(
+        &self,
+        p: &EllipticCurvePoint<F>,
+        q: &EllipticCurvePoint<F>,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
         match (p, q) {
-            (EllipticCurvePoint::Infinity, _) => return Ok(q.clone()),
-            (_, EllipticCurvePoint::Infinity) => return Ok(p.clone()),
+            (EllipticCurvePoint::Infinity, _) => Ok(q.clone()),
+            (_, EllipticCurvePoint::Infinity) => Ok(p.clone()),
             (
                 EllipticCurvePoint::Point { x: x1, y: y1 },
                 EllipticCurvePoint::Point { x: x2, y: y2 },
             ) => {
                 if x1 == x2 {
-                    if y1 == y2 && y1.value != 0 {
-                        // Point doubling.
-
-                        // slope(s) = (3x1^2 + a) / 2y1
-                        let numerator = FieldElement::new(3, x1.modulus)?
-                            .mul(&(x1.mul(x1)?))?
-                            .add(&self.a)?;
-                        let denominator = FieldElement::new(2, y1.modulus)?.mul(y1)?;
-                        let slope = numerator.mul(&denominator.inv()?)?;
-
-                        // x3 = s^2 - 2x1
-                        let x3 = slope
-                            .mul(&slope)?
-                            .sub(&(&FieldElement::new(2, x1.modulus)?.mul(x1)?))?;
-
-                        // y3 = s x (x1 - x3) - y1
-                        let y3 = slope.mul(&(x1.sub(&x3))?)?.sub(y1)?;
-
-                        Ok(EllipticCurvePoint::Point { x: x3, y: y3 })
+                    if y1 == y2 {
+                        self.double_unchecked(p)
                     } else {
                         // If the points are vertical reflections, their sum is Infinity (identity).
                         Ok(EllipticCurvePoint::Infinity)
@@ -80,7 +296,7 @@ impl EllipticCurve {
                     let x3 = slope.mul(&slope)?.sub(x1)?.sub(x2)?;
 
                     // y3 = s x (x1 - x3) - y1
-                    let y3 = slope.mul(&(x1.sub(&x3))?)?.sub(y1)?;
+                    let y3 = slope.mul(&x1.sub(&x3)?)?.sub(y1)?;
 
                     Ok(EllipticCurvePoint::Point { x: x3, y: y3 })
                 }
@@ -88,25 +304,723 @@ impl EllipticCurve {
         }
     }
 
+    /// Double a point on the elliptic curve.
+    pub fn double(&self, point: &EllipticCurvePoint<F>) -> Result<EllipticCurvePoint<F>, ZKError> {
+        self.check_field(point)?;
+        self.double_unchecked(point)
+    }
+
+    // Doubles a point whose coordinates are known to belong to the curve's field.
+    fn double_unchecked(
+        &self,
+        point: &EllipticCurvePoint<F>,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        let (x1, y1) = match point {
+            // Points with y = 0 have order 2.
+            EllipticCurvePoint::Point { y, .. } if y.is_zero() => {
+                return Ok(EllipticCurvePoint::Infinity)
+            }
+            EllipticCurvePoint::Infinity => return Ok(EllipticCurvePoint::Infinity),
+            EllipticCurvePoint::Point { x, y } => (x, y),
+        };
+
+        // slope(s) = (3x1^2 + a) / 2y1
+        let x1_squared = x1.mul(x1)?;
+        let numerator = x1_squared
+            .add(&x1_squared)?
+            .add(&x1_squared)?
+            .add(&self.a)?;
+        let denominator = y1.add(y1)?;
+        let slope = numerator.mul(&denominator.inv()?)?;
+
+        // x3 = s^2 - 2x1
+        let x3 = slope.mul(&slope)?.sub(&x1.add(x1)?)?;
+
+        // y3 = s x (x1 - x3) - y1
+        let y3 = slope.mul(&x1.sub(&x3)?)?.sub(y1)?;
+
+        Ok(EllipticCurvePoint::Point { x: x3, y: y3 })
+    }
+
+    /// Multiply a point with a fixed scalar by replaying a precomputed addition chain.
+    pub fn mul_chain(
+        &self,
+        point: &EllipticCurvePoint<F>,
+        chain: &AdditionChain,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        self.check_field(point)?;
+        let mut values = Vec::with_capacity(chain.steps.len() + 1);
+        values.push(self.to_jacobian(point));
+        for &(i, j) in &chain.steps {
+            let next = if i == j {
+                self.double_jacobian(&values[i])?
+            } else {
+                self.add_jacobian(&values[i], &values[j])?
+            };
+            values.push(next);
+        }
+        self.to_affine(&values[values.len() - 1])
+    }
+
     /// Multiply a point with a scalar using the double-and-add algorithm.
+    ///
+    /// The intermediate points are kept in Jacobian coordinates, so only the final
+    /// conversion to affine coordinates performs an inversion.
     pub fn mul_scalar(
         &self,
-        point: &EllipticCurvePoint,
+        point: &EllipticCurvePoint<F>,
         scalar: u64,
-    ) -> Result<EllipticCurvePoint, ZKError> {
-        let mut result = EllipticCurvePoint::Infinity;
-        let mut addend = point.clone();
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        self.check_field(point)?;
+        let mut result = JacobianPoint::infinity(&self.a.params());
+        let mut addend = self.to_jacobian(point);
         let mut k = scalar;
 
         while k > 0 {
             if k & 1 == 1 {
-                result = self.add_points(&result, &addend)?;
+                result = self.add_jacobian(&result, &addend)?;
             }
-            addend = self.add_points(&addend, &addend)?;
             k >>= 1;
+            if k > 0 {
+                addend = self.double_jacobian(&addend)?;
+            }
+        }
+
+        self.to_affine(&result)
+    }
+
+    /// Scalar multiplication by a scalar of any width, given as little-endian bytes, for
+    /// curves whose group order does not fit a u64, e.g. secp256k1.
+    pub fn mul_scalar_bytes(
+        &self,
+        point: &EllipticCurvePoint<F>,
+        scalar: &[u8],
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        self.check_field(point)?;
+        let mut result = JacobianPoint::infinity(&self.a.params());
+        let mut addend = self.to_jacobian(point);
+        let bits = scalar.len() * 8;
+
+        for i in 0..bits {
+            if (scalar[i / 8] >> (i % 8)) & 1 == 1 {
+                result = self.add_jacobian(&result, &addend)?;
+            }
+            if i + 1 < bits {
+                addend = self.double_jacobian(&addend)?;
+            }
+        }
+
+        self.to_affine(&result)
+    }
+
+    /// Check that the point lies on the curve and in the subgroup of the given prime
+    /// order r, i.e. that r * P is the point at infinity.
+    ///
+    /// Points from untrusted sources must pass this check: on a curve with a cofactor,
+    /// points of small order would otherwise leak information through small-subgroup
+    /// attacks.
+    pub fn is_in_prime_subgroup(
+        &self,
+        point: &EllipticCurvePoint<F>,
+        order: u64,
+    ) -> Result<bool, ZKError> {
+        self.check_field(point)?;
+        if !self.is_on_curve(point)? {
+            return Ok(false);
+        }
+        Ok(self.mul_scalar(point, order)? == EllipticCurvePoint::Infinity)
+    }
+
+    /// Map a point on the curve into the prime-order subgroup by multiplying it with the
+    /// cofactor h = #E / r.
+    pub fn clear_cofactor(
+        &self,
+        point: &EllipticCurvePoint<F>,
+        cofactor: u64,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        if !self.is_on_curve(point)? {
+            return Err(ZKError::CurveError("Point is not on the curve.".into()));
+        }
+        self.mul_scalar(point, cofactor)
+    }
+
+    /// Returns the order of the point, the smallest k > 0 with k * P = Infinity.
+    ///
+    /// When the curve describes its subgroup, the order divides the group order h * r
+    /// and is found among its divisors. Otherwise a baby-step giant-step search finds a
+    /// multiple of the order in O(sqrt(k)) group operations.
+    pub fn order_of(&self, point: &EllipticCurvePoint<F>) -> Result<u64, ZKError> {
+        self.check_field(point)?;
+        if !self.is_on_curve(point)? {
+            return Err(ZKError::CurveError("Point is not on the curve.".into()));
+        }
+        let multiple = match &self.subgroup {
+            Some(subgroup) => subgroup.order * subgroup.cofactor,
+            None => self.order_multiple(point)?,
+        };
+
+        // Strip every prime factor q for which (k / q) * P is still the identity.
+        let mut order = multiple;
+        for q in FieldElement::prime_factors(multiple) {
+            while order.is_multiple_of(q)
+                && self.mul_scalar(point, order / q)? == EllipticCurvePoint::Infinity
+            {
+                order /= q;
+            }
+        }
+        Ok(order)
+    }
+
+    // Finds some m > 0 with m * P = Infinity by baby-step giant-step: with baby steps
+    // jP for j < s, a giant step isP = jP gives m = is - j. The step size grows until
+    // the search covers the point's order.
+    fn order_multiple(&self, point: &EllipticCurvePoint<F>) -> Result<u64, ZKError> {
+        let key = |p: &EllipticCurvePoint<F>| match p {
+            EllipticCurvePoint::Infinity => Vec::new(),
+            EllipticCurvePoint::Point { x, y } => [x.to_bytes(), y.to_bytes()].concat(),
+        };
+
+        let mut step = 16u64;
+        loop {
+            let mut baby_steps = HashMap::new();
+            let mut current = EllipticCurvePoint::Infinity;
+            for j in 0..step {
+                baby_steps.entry(key(&current)).or_insert(j);
+                current = self.add_points_unchecked(&current, point)?;
+            }
+
+            let giant = current;
+            let mut current = giant.clone();
+            for i in 1..=step {
+                if let Some(&j) = baby_steps.get(&key(&current)) {
+                    return Ok(i * step - j);
+                }
+                current = self.add_points_unchecked(&current, &giant)?;
+            }
+            step = step.checked_mul(4).ok_or_else(|| {
+                ZKError::CurveError("Point order is too large to compute.".into())
+            })?;
+        }
+    }
+
+    /// Check whether the point is n-torsion, i.e. whether n * P is the point at infinity.
+    pub fn is_torsion(&self, point: &EllipticCurvePoint<F>, n: u64) -> Result<bool, ZKError> {
+        Ok(self.mul_scalar(point, n)? == EllipticCurvePoint::Infinity)
+    }
+
+    /// Compute the multi-scalar multiplication sum(scalars[i] * points[i]) with
+    /// Pippenger's bucket method.
+    ///
+    /// Scalars are split into windows of c bits. Within a window each point is added to
+    /// the bucket of its c-bit digit, and the buckets are combined with a running sum,
+    /// so a window costs about n + 2^(c+1) additions instead of n scalar multiplications.
+    pub fn msm(
+        &self,
+        points: &[EllipticCurvePoint<F>],
+        scalars: &[u64],
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        if points.len() != scalars.len() {
+            return Err(ZKError::CurveError(format!(
+                "MSM needs one scalar per point, got {} points and {} scalars.",
+                points.len(),
+                scalars.len()
+            )));
+        }
+        for point in points {
+            self.check_field(point)?;
+        }
+
+        let (points, scalars): (Vec<_>, Vec<_>) = points
+            .iter()
+            .zip(scalars)
+            .filter(|(point, &scalar)| scalar != 0 && *point != &EllipticCurvePoint::Infinity)
+            .map(|(point, &scalar)| (point.clone(), scalar))
+            .unzip();
+        let max_bits = scalars
+            .iter()
+            .map(|scalar| u64::BITS - scalar.leading_zeros())
+            .max()
+            .unwrap_or(0);
+        if max_bits == 0 {
+            return Ok(EllipticCurvePoint::Infinity);
+        }
+
+        let window = msm_window_size(points.len());
+        let offsets: Vec<u32> = (0..max_bits).step_by(window as usize).collect();
+        let params = self.a.params();
+        let window_sums = parallel::try_map(&offsets, |&offset| {
+            let mask = (1u64 << window) - 1;
+            let mut buckets = vec![JacobianPoint::infinity(&params); mask as usize];
+            for (point, scalar) in points.iter().zip(&scalars) {
+                let digit = (scalar >> offset) & mask;
+                if digit != 0 {
+                    let bucket = &mut buckets[digit as usize - 1];
+                    *bucket = self.add_mixed(bucket, point)?;
+                }
+            }
+
+            // sum(d * bucket[d]) as the sum of the running suffix sums.
+            let mut running = JacobianPoint::infinity(&params);
+            let mut sum = JacobianPoint::infinity(&params);
+            for bucket in buckets.iter().rev() {
+                running = self.add_jacobian(&running, bucket)?;
+                sum = self.add_jacobian(&sum, &running)?;
+            }
+            Ok::<_, ZKError>(sum)
+        })?;
+
+        let mut total = JacobianPoint::infinity(&params);
+        for (i, sum) in window_sums.iter().enumerate().rev() {
+            if i + 1 < window_sums.len() {
+                for _ in 0..window {
+                    total = self.double_jacobian(&total)?;
+                }
+            }
+            total = self.add_jacobian(&total, sum)?;
+        }
+        self.to_affine(&total)
+    }
+
+    /// Convert an affine point to Jacobian coordinates, with Z = 1.
+    pub fn to_jacobian(&self, point: &EllipticCurvePoint<F>) -> JacobianPoint<F> {
+        match point {
+            EllipticCurvePoint::Infinity => JacobianPoint::infinity(&self.a.params()),
+            EllipticCurvePoint::Point { x, y } => JacobianPoint {
+                x: x.clone(),
+                y: y.clone(),
+                z: F::one(&x.params()),
+            },
         }
+    }
 
-        Ok(result)
+    /// Convert a Jacobian point back to affine coordinates.
+    pub fn to_affine(&self, point: &JacobianPoint<F>) -> Result<EllipticCurvePoint<F>, ZKError> {
+        if point.is_infinity() {
+            return Ok(EllipticCurvePoint::Infinity);
+        }
+        let z_inv = point.z.inv()?;
+        let z_inv2 = z_inv.mul(&z_inv)?;
+        let z_inv3 = z_inv2.mul(&z_inv)?;
+        Ok(EllipticCurvePoint::Point {
+            x: point.x.mul(&z_inv2)?,
+            y: point.y.mul(&z_inv3)?,
+        })
+    }
+
+    /// Double a point in Jacobian coordinates.
+    pub fn double_jacobian(&self, p: &JacobianPoint<F>) -> Result<JacobianPoint<F>, ZKError> {
+        if p.is_infinity() || p.y.is_zero() {
+            return Ok(JacobianPoint::infinity(&self.a.params()));
+        }
+
+        // S = 4XY^2, M = 3X^2 + aZ^4
+        let yy = p.y.mul(&p.y)?;
+        let xyy = p.x.mul(&yy)?;
+        let s = xyy.add(&xyy)?;
+        let s = s.add(&s)?;
+        let xx = p.x.mul(&p.x)?;
+        let zz = p.z.mul(&p.z)?;
+        let m = xx.add(&xx)?.add(&xx)?.add(&self.a.mul(&zz.mul(&zz)?)?)?;
+
+        // X3 = M^2 - 2S
+        let x3 = m.mul(&m)?.sub(&s.add(&s)?)?;
+
+        // Y3 = M(S - X3) - 8Y^4
+        let yyyy = yy.mul(&yy)?;
+        let yyyy2 = yyyy.add(&yyyy)?;
+        let yyyy4 = yyyy2.add(&yyyy2)?;
+        let y3 = m.mul(&s.sub(&x3)?)?.sub(&yyyy4.add(&yyyy4)?)?;
+
+        // Z3 = 2YZ
+        let yz = p.y.mul(&p.z)?;
+        let z3 = yz.add(&yz)?;
+
+        Ok(JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        })
+    }
+
+    /// Add an affine point to a point in Jacobian coordinates.
+    ///
+    /// With the addend's Z fixed to 1 this saves five multiplications over
+    /// `add_jacobian`, which is why accumulation loops keep their addends affine.
+    pub fn add_mixed(
+        &self,
+        p: &JacobianPoint<F>,
+        q: &EllipticCurvePoint<F>,
+    ) -> Result<JacobianPoint<F>, ZKError> {
+        let (x2, y2) = match q {
+            EllipticCurvePoint::Infinity => return Ok(p.clone()),
+            EllipticCurvePoint::Point { x, y } => (x, y),
+        };
+        if p.is_infinity() {
+            return Ok(self.to_jacobian(q));
+        }
+
+        // U2 = x2 Z1^2, S2 = y2 Z1^3
+        let z1z1 = p.z.mul(&p.z)?;
+        let u2 = x2.mul(&z1z1)?;
+        let s2 = y2.mul(&p.z)?.mul(&z1z1)?;
+
+        if p.x == u2 {
+            return if p.y == s2 {
+                self.double_jacobian(p)
+            } else {
+                // The points are vertical reflections of each other.
+                Ok(JacobianPoint::infinity(&self.a.params()))
+            };
+        }
+
+        // H = U2 - X1, R = S2 - Y1
+        let h = u2.sub(&p.x)?;
+        let r = s2.sub(&p.y)?;
+        let hh = h.mul(&h)?;
+        let hhh = hh.mul(&h)?;
+        let x1hh = p.x.mul(&hh)?;
+
+        // X3 = R^2 - H^3 - 2 X1 H^2
+        let x3 = r.mul(&r)?.sub(&hhh)?.sub(&x1hh.add(&x1hh)?)?;
+
+        // Y3 = R(X1 H^2 - X3) - Y1 H^3
+        let y3 = r.mul(&x1hh.sub(&x3)?)?.sub(&p.y.mul(&hhh)?)?;
+
+        // Z3 = Z1 H
+        let z3 = p.z.mul(&h)?;
+
+        Ok(JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        })
+    }
+
+    /// Add two points in Jacobian coordinates.
+    pub fn add_jacobian(
+        &self,
+        p: &JacobianPoint<F>,
+        q: &JacobianPoint<F>,
+    ) -> Result<JacobianPoint<F>, ZKError> {
+        if p.is_infinity() {
+            return Ok(q.clone());
+        }
+        if q.is_infinity() {
+            return Ok(p.clone());
+        }
+
+        // U1 = X1 Z2^2, U2 = X2 Z1^2, S1 = Y1 Z2^3, S2 = Y2 Z1^3
+        let z1z1 = p.z.mul(&p.z)?;
+        let z2z2 = q.z.mul(&q.z)?;
+        let u1 = p.x.mul(&z2z2)?;
+        let u2 = q.x.mul(&z1z1)?;
+        let s1 = p.y.mul(&q.z)?.mul(&z2z2)?;
+        let s2 = q.y.mul(&p.z)?.mul(&z1z1)?;
+
+        if u1 == u2 {
+            return if s1 == s2 {
+                self.double_jacobian(p)
+            } else {
+                // The points are vertical reflections of each other.
+                Ok(JacobianPoint::infinity(&self.a.params()))
+            };
+        }
+
+        // H = U2 - U1, R = S2 - S1
+        let h = u2.sub(&u1)?;
+        let r = s2.sub(&s1)?;
+        let hh = h.mul(&h)?;
+        let hhh = hh.mul(&h)?;
+        let u1hh = u1.mul(&hh)?;
+
+        // X3 = R^2 - H^3 - 2 U1 H^2
+        let x3 = r.mul(&r)?.sub(&hhh)?.sub(&u1hh.add(&u1hh)?)?;
+
+        // Y3 = R(U1 H^2 - X3) - S1 H^3
+        let y3 = r.mul(&u1hh.sub(&x3)?)?.sub(&s1.mul(&hhh)?)?;
+
+        // Z3 = H Z1 Z2
+        let z3 = h.mul(&p.z)?.mul(&q.z)?;
+
+        Ok(JacobianPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        })
+    }
+}
+
+impl EllipticCurve<FieldElement> {
+    /// Enumerates every point of a curve over a small prime field, starting with the
+    /// point at infinity and sorted by coordinates.
+    pub fn points(&self) -> Result<Vec<EllipticCurvePoint>, ZKError> {
+        let modulus = self.a.modulus;
+        if modulus > POINT_ENUMERATION_LIMIT {
+            return Err(ZKError::CurveError(format!(
+                "Modulus {} is too large to enumerate the curve.",
+                modulus
+            )));
+        }
+
+        let mut points = vec![EllipticCurvePoint::Infinity];
+        for x in 0..modulus {
+            let x = FieldElement::new(x, modulus)?;
+            let rhs = x.mul(&x)?.mul(&x)?.add(&self.a.mul(&x)?)?.add(&self.b)?;
+            if rhs.is_zero() {
+                points.push(EllipticCurvePoint::Point {
+                    x,
+                    y: FieldElement::zero(modulus)?,
+                });
+            } else if let Some(y) = rhs.sqrt() {
+                let mut ys = [y.clone(), y.neg()];
+                ys.sort_by_key(|y| y.value);
+                for y in ys {
+                    points.push(EllipticCurvePoint::Point { x: x.clone(), y });
+                }
+            }
+        }
+        Ok(points)
+    }
+
+    /// Returns the embedding degree k, the smallest k > 0 with r | p^k - 1, where r is the
+    /// subgroup order. The pairing on the subgroup takes values in the degree-k extension.
+    pub fn embedding_degree(&self) -> Result<u64, ZKError> {
+        let order = self.subgroup()?.order;
+        let p = FieldElement::new(self.a.modulus % order, order)?;
+        if p.is_zero() {
+            return Err(ZKError::CurveError(
+                "Subgroup order equals the characteristic; the curve is anomalous.".into(),
+            ));
+        }
+
+        // The multiplicative order of p modulo r divides r - 1.
+        let mut degree = order - 1;
+        for q in FieldElement::prime_factors(order - 1) {
+            while degree.is_multiple_of(q) && p.exp(degree / q)?.is_one() {
+                degree /= q;
+            }
+        }
+        Ok(degree)
+    }
+
+    /// Checks that the embedding degree is `expected`, the degree of the extension a
+    /// pairing implementation works in. Subgroups of any other degree, such as those of
+    /// random curves, whose degree is huge, are refused with an error.
+    pub fn check_embedding_degree(&self, expected: u64) -> Result<(), ZKError> {
+        let degree = self.embedding_degree()?;
+        if degree != expected {
+            return Err(ZKError::CurveError(format!(
+                "The subgroup has embedding degree {}, but the pairing needs {}.",
+                degree, expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the points P with n * P = Infinity, the n-torsion subgroup E[n] over Fp.
+    pub fn torsion_points(&self, n: u64) -> Result<Vec<EllipticCurvePoint>, ZKError> {
+        let mut torsion = Vec::new();
+        for point in self.points()? {
+            if self.is_torsion(&point, n)? {
+                torsion.push(point);
+            }
+        }
+        Ok(torsion)
+    }
+}
+
+/// An addition chain 1 = c0, c1, ..., cn = scalar in which every element is the sum of
+/// two earlier ones. Replaying it computes scalar * P with n additions and doublings,
+/// which for fixed scalars such as cofactors or pairing loop parameters can be
+/// noticeably fewer than double-and-add needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdditionChain {
+    scalar: u64,
+    // Step k computes c(k + 1) = c(i) + c(j); i == j is a doubling.
+    steps: Vec<(usize, usize)>,
+}
+
+impl AdditionChain {
+    /// Finds an addition chain for the scalar. Chains for scalars up to
+    /// `SHORTEST_CHAIN_LIMIT` are as short as possible; larger scalars use the binary chain.
+    pub fn new(scalar: u64) -> Result<Self, ZKError> {
+        if scalar == 0 {
+            return Err(ZKError::CurveError(
+                "Addition chains start at 1 and cannot reach 0.".into(),
+            ));
+        }
+        if scalar > SHORTEST_CHAIN_LIMIT {
+            return Ok(Self::binary(scalar));
+        }
+
+        // Iterative deepening over star chains, in which each element adds the previous
+        // one to an earlier element. They are optimal for every scalar below 12509.
+        let mut chain = vec![1];
+        let mut steps = Vec::new();
+        let mut length = 0;
+        while !Self::search(scalar, length, &mut chain, &mut steps) {
+            length += 1;
+        }
+        Ok(AdditionChain { scalar, steps })
+    }
+
+    // Builds the chain of the double-and-add method, from the most significant bit down.
+    fn binary(scalar: u64) -> Self {
+        let mut steps = Vec::new();
+        let mut last = 0;
+        for i in (0..u64::BITS - 1 - scalar.leading_zeros()).rev() {
+            steps.push((last, last));
+            last += 1;
+            if (scalar >> i) & 1 == 1 {
+                steps.push((last, 0));
+                last += 1;
+            }
+        }
+        AdditionChain { scalar, steps }
+    }
+
+    // Extends the chain with star steps until it ends in the scalar within `length` steps.
+    fn search(
+        scalar: u64,
+        length: usize,
+        chain: &mut Vec<u64>,
+        steps: &mut Vec<(usize, usize)>,
+    ) -> bool {
+        let last = chain[chain.len() - 1];
+        if last == scalar {
+            return true;
+        }
+        let remaining = length - steps.len();
+        // Doubling every remaining step is the fastest possible growth.
+        if remaining == 0
+            || last
+                .checked_shl(remaining as u32)
+                .is_some_and(|max| max < scalar)
+        {
+            return false;
+        }
+        for j in (0..chain.len()).rev() {
+            let next = last + chain[j];
+            if next > scalar {
+                continue;
+            }
+            chain.push(next);
+            steps.push((chain.len() - 2, j));
+            if Self::search(scalar, length, chain, steps) {
+                return true;
+            }
+            chain.pop();
+            steps.pop();
+        }
+        false
+    }
+
+    /// Returns the scalar the chain computes.
+    pub fn scalar(&self) -> u64 {
+        self.scalar
+    }
+
+    /// Returns the number of additions and doublings in the chain.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns true if the chain has no steps, i.e. computes 1 * P.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+// Picks the Pippenger window width for n points, roughly ln(n) + 2 bits.
+fn msm_window_size(n: usize) -> u32 {
+    if n < 32 {
+        3
+    } else {
+        (usize::BITS - n.leading_zeros()) * 69 / 100 + 2
+    }
+}
+
+/// Which sextic twist of E: y^2 = x^3 + b carries G2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwistType {
+    /// E': y^2 = x^3 + b / ξ, as for BN254.
+    D,
+    /// E': y^2 = x^3 + b·ξ, as for BLS12-381.
+    M,
+}
+
+/// A pairing-friendly curve: G1 is the order-r subgroup of E(Fp), and G2 the order-r
+/// subgroup of a sextic twist E'(Fp2). The tower Fp2 = Fp[u] / (u^2 - β),
+/// Fp6 = Fp2[v] / (v^3 - ξ) and Fp12 = Fp6[w] / (w^2 - v) hosts the pairing.
+///
+/// Implementations are parameter sets, selected as a type parameter by the layers above.
+pub trait Curve {
+    /// A short identifier for the curve.
+    const NAME: &'static str;
+    /// The base field modulus p.
+    const BASE_MODULUS: u64;
+    /// The prime order r of G1 and G2, i.e. the scalar field modulus.
+    const SCALAR_MODULUS: u64;
+    /// #E(Fp) / r.
+    const G1_COFACTOR: u64;
+    /// #E'(Fp2) / r.
+    const G2_COFACTOR: u64;
+    /// The twist carrying G2.
+    const TWIST_TYPE: TwistType;
+
+    /// The base field Fp, in which coordinates live. Must have modulus `BASE_MODULUS`.
+    type BaseField: PrimeField;
+    /// The scalar field Fr, in which witnesses and exponents live. Must have modulus
+    /// `SCALAR_MODULUS`. Keeping the two apart lets the compiler reject mixing them.
+    type ScalarField: PrimeField;
+
+    /// Returns the non-residue β defining Fp2.
+    fn fp2_non_residue() -> Result<FieldElement, ZKError>;
+
+    /// Returns the non-residue ξ defining Fp6 and the twist.
+    fn fp6_non_residue() -> Result<Fp2, ZKError>;
+
+    /// Returns the curve E over Fp containing G1.
+    fn g1_curve() -> Result<EllipticCurve, ZKError>;
+
+    /// Returns the twist E' over Fp2 containing G2.
+    fn g2_curve() -> Result<EllipticCurve<Fp2>, ZKError>;
+
+    /// Returns the standard generator of G1.
+    fn g1_generator() -> Result<EllipticCurvePoint, ZKError>;
+
+    /// Returns the standard generator of G2.
+    fn g2_generator() -> Result<EllipticCurvePoint<Fp2>, ZKError>;
+
+    /// Checks that G1 has order `SCALAR_MODULUS` and embedding degree 12, so that the
+    /// pairing lands in the Fp12 tower. Pairings refuse curves failing this rather than
+    /// return meaningless values.
+    fn check_embedding_degree() -> Result<(), ZKError> {
+        let curve = Self::g1_curve()?;
+        if curve.subgroup()?.order != Self::SCALAR_MODULUS {
+            return Err(ZKError::CurveError(format!(
+                "The G1 subgroup of {} does not have order {}.",
+                Self::NAME,
+                Self::SCALAR_MODULUS
+            )));
+        }
+        curve.check_embedding_degree(12)
+    }
+
+    /// Multiplies a point of G1 by an element of the scalar field.
+    fn g1_mul(
+        point: &EllipticCurvePoint,
+        scalar: &Self::ScalarField,
+    ) -> Result<EllipticCurvePoint, ZKError> {
+        Self::g1_curve()?.mul_scalar(point, scalar.value())
+    }
+
+    /// Multiplies a point of G2 by an element of the scalar field.
+    fn g2_mul(
+        point: &EllipticCurvePoint<Fp2>,
+        scalar: &Self::ScalarField,
+    ) -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+        Self::g2_curve()?.mul_scalar(point, scalar.value())
     }
 }
 
@@ -118,24 +1032,25 @@ mod tests {
         let modulus = 97;
 
         // Test curve with the equation y^2 = x^3 + 2x + 3.
-        let curve = EllipticCurve {
-            a: FieldElement::new(2, modulus).unwrap(),
-            b: FieldElement::new(3, modulus).unwrap(),
-        };
+        let curve = EllipticCurve::new(
+            FieldElement::new(2, modulus).unwrap(),
+            FieldElement::new(3, modulus).unwrap(),
+        )
+        .unwrap();
 
         // Test point that lies on the curve.
         let x = FieldElement::new(3, modulus).unwrap();
         let y = FieldElement::new(6, modulus).unwrap();
-        let point = EllipticCurvePoint::Point { x, y };
+        let point = EllipticCurvePoint::new(x, y, &curve).unwrap();
 
-        return (curve, point);
+        (curve, point)
     }
 
     #[test]
     fn test_is_on_curve() {
         let (curve, point) = get_test_values();
         let on_curve = curve.is_on_curve(&point).unwrap();
-        assert_eq!(on_curve, true);
+        assert!(on_curve);
     }
 
     #[test]
@@ -163,4 +1078,449 @@ mod tests {
         // P + P = 2P
         assert_eq!(double, mul_scalar_result,);
     }
+
+    #[test]
+    fn test_mul_scalar_matches_repeated_addition() {
+        let (curve, point) = get_test_values();
+        let mut expected = EllipticCurvePoint::Infinity;
+        for k in 0..20 {
+            assert_eq!(curve.mul_scalar(&point, k).unwrap(), expected);
+            assert!(curve.is_on_curve(&expected).unwrap());
+            expected = curve.add_points(&expected, &point).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_jacobian_round_trip() {
+        let (curve, point) = get_test_values();
+        let jacobian = curve.to_jacobian(&point);
+        assert_eq!(curve.to_affine(&jacobian).unwrap(), point);
+
+        // Any representative (λ^2 X : λ^3 Y : λ Z) maps back to the same point.
+        let lambda = FieldElement::new(5, 97).unwrap();
+        let lambda2 = lambda.mul(&lambda).unwrap();
+        let scaled = JacobianPoint {
+            x: jacobian.x.mul(&lambda2).unwrap(),
+            y: jacobian.y.mul(&lambda2.mul(&lambda).unwrap()).unwrap(),
+            z: lambda,
+        };
+        assert_eq!(curve.to_affine(&scaled).unwrap(), point);
+
+        let infinity = curve.to_jacobian(&EllipticCurvePoint::Infinity);
+        assert!(infinity.is_infinity());
+        assert_eq!(
+            curve.to_affine(&infinity).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+    }
+
+    #[test]
+    fn test_jacobian_matches_affine() {
+        let (curve, point) = get_test_values();
+        let mut affine = EllipticCurvePoint::Infinity;
+        let mut jacobian = curve.to_jacobian(&affine);
+        for _ in 0..120 {
+            assert_eq!(curve.to_affine(&jacobian).unwrap(), affine);
+            assert_eq!(
+                curve
+                    .to_affine(&curve.double_jacobian(&jacobian).unwrap())
+                    .unwrap(),
+                curve.add_points(&affine, &affine).unwrap()
+            );
+            affine = curve.add_points(&affine, &point).unwrap();
+            jacobian = curve
+                .add_jacobian(&jacobian, &curve.to_jacobian(&point))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_double_two_torsion_point() {
+        // y^2 = x^3 + x has the 2-torsion point (0, 0).
+        let curve = EllipticCurve {
+            a: FieldElement::new(1, 97).unwrap(),
+            b: FieldElement::new(0, 97).unwrap(),
+            subgroup: None,
+        };
+        let point = EllipticCurvePoint::Point {
+            x: FieldElement::new(0, 97).unwrap(),
+            y: FieldElement::new(0, 97).unwrap(),
+        };
+        let doubled = curve.double_jacobian(&curve.to_jacobian(&point)).unwrap();
+        assert!(doubled.is_infinity());
+        assert_eq!(
+            curve.mul_scalar(&point, 2).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+        assert_eq!(curve.mul_scalar(&point, 3).unwrap(), point);
+    }
+
+    #[test]
+    fn test_msm() {
+        let (curve, point) = get_test_values();
+        for n in [0, 1, 5, 40, 100] {
+            let points: Vec<_> = (1..=n)
+                .map(|i| curve.mul_scalar(&point, i).unwrap())
+                .collect();
+            let scalars: Vec<u64> = (0..n).map(|i| i * 7919 + (i << 40)).collect();
+
+            let mut expected = EllipticCurvePoint::Infinity;
+            for (p, &k) in points.iter().zip(&scalars) {
+                expected = curve
+                    .add_points(&expected, &curve.mul_scalar(p, k).unwrap())
+                    .unwrap();
+            }
+            assert_eq!(curve.msm(&points, &scalars).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_msm_edge_cases() {
+        let (curve, point) = get_test_values();
+        let points = vec![point.clone(), EllipticCurvePoint::Infinity, point.clone()];
+        assert_eq!(
+            curve.msm(&points, &[0, 5, 0]).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+        let expected = curve
+            .add_points(&curve.mul_scalar(&point, u64::MAX).unwrap(), &point)
+            .unwrap();
+        assert_eq!(curve.msm(&points, &[u64::MAX, 1, 1]).unwrap(), expected);
+        assert!(matches!(
+            curve.msm(&points, &[1, 2]),
+            Err(ZKError::CurveError(_))
+        ));
+    }
+
+    #[test]
+    fn test_prime_subgroup() {
+        // y^2 = x^3 + 3 over F_37 has 39 = 3 * 13 points.
+        let modulus = 37;
+        let curve = EllipticCurve {
+            a: FieldElement::new(0, modulus).unwrap(),
+            b: FieldElement::new(3, modulus).unwrap(),
+            subgroup: None,
+        };
+        let point = |x, y| EllipticCurvePoint::Point {
+            x: FieldElement::new(x, modulus).unwrap(),
+            y: FieldElement::new(y, modulus).unwrap(),
+        };
+
+        // (15, 23) generates the subgroup of order 13, while (0, 15) has order 3.
+        let generator = point(15, 23);
+        let small_order = point(0, 15);
+        assert!(curve.is_in_prime_subgroup(&generator, 13).unwrap());
+        assert!(!curve.is_in_prime_subgroup(&small_order, 13).unwrap());
+        assert!(curve
+            .is_in_prime_subgroup(&EllipticCurvePoint::Infinity, 13)
+            .unwrap());
+        assert!(!curve.is_in_prime_subgroup(&point(1, 1), 13).unwrap());
+
+        let mixed = curve.add_points(&generator, &small_order).unwrap();
+        assert!(!curve.is_in_prime_subgroup(&mixed, 13).unwrap());
+        let cleared = curve.clear_cofactor(&mixed, 3).unwrap();
+        assert!(curve.is_in_prime_subgroup(&cleared, 13).unwrap());
+        assert_eq!(cleared, curve.mul_scalar(&generator, 3).unwrap());
+        assert_eq!(
+            curve.clear_cofactor(&small_order, 3).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+        assert!(matches!(
+            curve.clear_cofactor(&point(1, 1), 3),
+            Err(ZKError::CurveError(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_subgroup() {
+        let modulus = 37;
+        let a = FieldElement::new(0, modulus).unwrap();
+        let b = FieldElement::new(3, modulus).unwrap();
+        let point = |x, y| EllipticCurvePoint::Point {
+            x: FieldElement::new(x, modulus).unwrap(),
+            y: FieldElement::new(y, modulus).unwrap(),
+        };
+
+        let curve =
+            EllipticCurve::with_subgroup(a.clone(), b.clone(), point(15, 23), 13, 3).unwrap();
+        let subgroup = curve.subgroup().unwrap();
+        assert_eq!(subgroup.generator, point(15, 23));
+        assert_eq!((subgroup.order, subgroup.cofactor), (13, 3));
+
+        // The generator has order 3, is off the curve, or the order is not prime.
+        for (generator, order, cofactor) in [
+            (point(0, 15), 13, 3),
+            (point(1, 1), 13, 3),
+            (EllipticCurvePoint::Infinity, 13, 3),
+            (point(15, 23), 39, 1),
+            (point(15, 23), 13, 0),
+        ] {
+            assert!(matches!(
+                EllipticCurve::with_subgroup(a.clone(), b.clone(), generator, order, cofactor),
+                Err(ZKError::CurveError(_))
+            ));
+        }
+
+        let (curve, _) = get_test_values();
+        assert!(matches!(curve.subgroup(), Err(ZKError::CurveError(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let (curve, point) = get_test_values();
+        for p in [point.clone(), EllipticCurvePoint::Infinity] {
+            let json = serde_json::to_string(&p).unwrap();
+            assert_eq!(
+                serde_json::from_str::<EllipticCurvePoint>(&json).unwrap(),
+                p
+            );
+        }
+
+        let json = serde_json::to_string(&curve).unwrap();
+        let decoded = serde_json::from_str::<EllipticCurve>(&json).unwrap();
+        assert_eq!((decoded.a, decoded.b), (curve.a, curve.b));
+        assert!(decoded.subgroup.is_none());
+
+        let modulus = 37;
+        let generator = EllipticCurvePoint::Point {
+            x: FieldElement::new(15, modulus).unwrap(),
+            y: FieldElement::new(23, modulus).unwrap(),
+        };
+        let curve = EllipticCurve::with_subgroup(
+            FieldElement::new(0, modulus).unwrap(),
+            FieldElement::new(3, modulus).unwrap(),
+            generator.clone(),
+            13,
+            3,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&curve).unwrap();
+        let decoded = serde_json::from_str::<EllipticCurve>(&json).unwrap();
+        let subgroup = decoded.subgroup().unwrap();
+        assert_eq!(subgroup.generator, generator);
+        assert_eq!((subgroup.order, subgroup.cofactor), (13, 3));
+
+        // Deserialization validates the subgroup description.
+        let tampered = json.replace(r#""order":13"#, r#""order":11"#);
+        assert!(serde_json::from_str::<EllipticCurve>(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_new_validates() {
+        let fe = |v| FieldElement::new(v, 97).unwrap();
+
+        // 4 * (-3)^3 + 27 * 2^2 = 0, so y^2 = x^3 - 3x + 2 is singular.
+        assert!(matches!(
+            EllipticCurve::new(fe(94), fe(2)),
+            Err(ZKError::CurveError(_))
+        ));
+        assert!(matches!(
+            EllipticCurve::new(fe(0), fe(0)),
+            Err(ZKError::CurveError(_))
+        ));
+        assert!(matches!(
+            EllipticCurve::new(fe(2), FieldElement::new(3, 101).unwrap()),
+            Err(ZKError::InvalidFieldElement(_))
+        ));
+
+        let (curve, _) = get_test_values();
+        assert!(matches!(
+            EllipticCurvePoint::new(fe(3), fe(7), &curve),
+            Err(ZKError::CurveError(_))
+        ));
+        assert!(matches!(
+            EllipticCurvePoint::new(fe(3), FieldElement::new(6, 101).unwrap(), &curve),
+            Err(ZKError::InvalidFieldElement(_))
+        ));
+    }
+
+    #[test]
+    fn test_order_of() {
+        let (curve, point) = get_test_values();
+        // y^2 = x^3 + 2x + 3 over F_97 has 100 points.
+        let points = curve.points().unwrap();
+        assert_eq!(points.len(), 100);
+        for p in &points {
+            let order = curve.order_of(p).unwrap();
+            assert!(100_u64.is_multiple_of(order));
+            assert_eq!(
+                curve.mul_scalar(p, order).unwrap(),
+                EllipticCurvePoint::Infinity
+            );
+            for q in [2, 5] {
+                if order.is_multiple_of(q) {
+                    assert!(!curve.is_torsion(p, order / q).unwrap());
+                }
+            }
+        }
+        assert_eq!(curve.order_of(&EllipticCurvePoint::Infinity).unwrap(), 1);
+        assert!(curve
+            .is_torsion(&point, curve.order_of(&point).unwrap())
+            .unwrap());
+
+        // With a subgroup description the order is found among divisors of h * r.
+        let modulus = 37;
+        let generator = EllipticCurvePoint::Point {
+            x: FieldElement::new(15, modulus).unwrap(),
+            y: FieldElement::new(23, modulus).unwrap(),
+        };
+        let curve = EllipticCurve::with_subgroup(
+            FieldElement::new(0, modulus).unwrap(),
+            FieldElement::new(3, modulus).unwrap(),
+            generator.clone(),
+            13,
+            3,
+        )
+        .unwrap();
+        assert_eq!(curve.order_of(&generator).unwrap(), 13);
+        let small_order = EllipticCurvePoint::Point {
+            x: FieldElement::new(0, modulus).unwrap(),
+            y: FieldElement::new(15, modulus).unwrap(),
+        };
+        assert_eq!(curve.order_of(&small_order).unwrap(), 3);
+        let off_curve = EllipticCurvePoint::Point {
+            x: FieldElement::new(1, modulus).unwrap(),
+            y: FieldElement::new(1, modulus).unwrap(),
+        };
+        assert!(matches!(
+            curve.order_of(&off_curve),
+            Err(ZKError::CurveError(_))
+        ));
+    }
+
+    #[test]
+    fn test_torsion_points() {
+        let (curve, _) = get_test_values();
+        // E(F_97) has order 100 and three points with y = 0, so it is Z/2 x Z/50.
+        let two_torsion = curve.torsion_points(2).unwrap();
+        assert_eq!(two_torsion.len(), 4);
+        assert_eq!(two_torsion[0], EllipticCurvePoint::Infinity);
+        assert_eq!(curve.torsion_points(5).unwrap().len(), 5);
+        assert_eq!(curve.torsion_points(25).unwrap().len(), 25);
+        assert_eq!(curve.torsion_points(10).unwrap().len(), 20);
+        assert_eq!(curve.torsion_points(50).unwrap().len(), 100);
+
+        let too_large = EllipticCurve::new(
+            FieldElement::new(2, 65539).unwrap(),
+            FieldElement::new(3, 65539).unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            too_large.torsion_points(2),
+            Err(ZKError::CurveError(_))
+        ));
+    }
+
+    #[test]
+    fn test_embedding_degree() {
+        let modulus = 37;
+        let curve = EllipticCurve::with_subgroup(
+            FieldElement::new(0, modulus).unwrap(),
+            FieldElement::new(3, modulus).unwrap(),
+            EllipticCurvePoint::Point {
+                x: FieldElement::new(15, modulus).unwrap(),
+                y: FieldElement::new(23, modulus).unwrap(),
+            },
+            13,
+            3,
+        )
+        .unwrap();
+        assert_eq!(curve.embedding_degree().unwrap(), 12);
+        assert!(curve.check_embedding_degree(12).is_ok());
+        assert!(matches!(
+            curve.check_embedding_degree(6),
+            Err(ZKError::CurveError(_))
+        ));
+
+        let (curve, _) = get_test_values();
+        assert!(matches!(
+            curve.embedding_degree(),
+            Err(ZKError::CurveError(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_mixed() {
+        let (curve, point) = get_test_values();
+        let mut affine = EllipticCurvePoint::Infinity;
+        let mut jacobian = curve.to_jacobian(&affine);
+        for _ in 0..60 {
+            assert_eq!(curve.to_affine(&jacobian).unwrap(), affine);
+            // Adding a point to itself and to its negation goes through the special cases.
+            for q in [affine.clone(), curve.mul_scalar(&affine, 99).unwrap()] {
+                assert_eq!(
+                    curve
+                        .to_affine(&curve.add_mixed(&jacobian, &q).unwrap())
+                        .unwrap(),
+                    curve.add_points(&affine, &q).unwrap()
+                );
+            }
+            // Keep Z away from 1 so the mixed formula sees a general accumulator.
+            jacobian = curve.add_mixed(&jacobian, &point).unwrap();
+            jacobian = curve.double_jacobian(&jacobian).unwrap();
+            jacobian = curve
+                .add_jacobian(&jacobian, &curve.to_jacobian(&affine))
+                .unwrap();
+            let next = curve.add_points(&affine, &point).unwrap();
+            affine = curve
+                .add_points(&curve.add_points(&next, &next).unwrap(), &affine)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_double() {
+        let (curve, point) = get_test_values();
+        assert_eq!(
+            curve.double(&point).unwrap(),
+            curve.mul_scalar(&point, 2).unwrap()
+        );
+        assert_eq!(
+            curve.double(&EllipticCurvePoint::Infinity).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+        for p in curve.torsion_points(2).unwrap() {
+            assert_eq!(curve.double(&p).unwrap(), EllipticCurvePoint::Infinity);
+        }
+    }
+
+    #[test]
+    fn test_addition_chain() {
+        // Known shortest chain lengths l(n).
+        for (scalar, length) in [
+            (1, 0),
+            (2, 1),
+            (3, 2),
+            (15, 5),
+            (31, 7),
+            (127, 10),
+            (191, 11),
+        ] {
+            assert_eq!(AdditionChain::new(scalar).unwrap().len(), length);
+        }
+        assert!(AdditionChain::new(0).is_err());
+        assert!(AdditionChain::new(1).unwrap().is_empty());
+
+        let (curve, point) = get_test_values();
+        for scalar in [
+            1,
+            2,
+            3,
+            7,
+            15,
+            100,
+            1000,
+            SHORTEST_CHAIN_LIMIT + 1,
+            u64::MAX,
+        ] {
+            let chain = AdditionChain::new(scalar).unwrap();
+            assert_eq!(chain.scalar(), scalar);
+            assert!(chain.len() <= AdditionChain::binary(scalar).len());
+            assert_eq!(
+                curve.mul_chain(&point, &chain).unwrap(),
+                curve.mul_scalar(&point, scalar).unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,602 @@
+use crate::{
+    curve::{Curve, EllipticCurve, EllipticCurvePoint, TwistType},
+    edwards::{EdwardsPoint, TwistedEdwardsCurve},
+    errors::ZKError,
+    field::FieldElement,
+    fp::Fp,
+    fp2::Fp2,
+};
+
+/// The BN curve with seed u = 1, where p = 36u^4 + 36u^3 + 24u^2 + 6u + 1 = 103 and
+/// r = 36u^4 + 36u^3 + 18u^2 + 6u + 1 = 97.
+///
+/// G1 is all of E: y^2 = x^3 + 5 over Fp, and G2 lies on the D-type twist
+/// E': y^2 = x^3 + 5 / ξ over Fp2, with β = -1 and ξ = 2 + u. It is built with the same
+/// formulas as BN254 (see [`bn254`]) but small enough to enumerate every group in tests.
+pub struct ToyBn;
+
+impl Curve for ToyBn {
+    const NAME: &'static str = "toy-bn103";
+    const BASE_MODULUS: u64 = 103;
+    const SCALAR_MODULUS: u64 = 97;
+    const G1_COFACTOR: u64 = 1;
+    const G2_COFACTOR: u64 = 109;
+    const TWIST_TYPE: TwistType = TwistType::D;
+
+    type BaseField = Fp<103>;
+    type ScalarField = Fp<97>;
+
+    fn fp2_non_residue() -> Result<FieldElement, ZKError> {
+        FieldElement::from_i64(-1, Self::BASE_MODULUS)
+    }
+
+    fn fp6_non_residue() -> Result<Fp2, ZKError> {
+        fp2::<Self>(2, 1)
+    }
+
+    fn g1_curve() -> Result<EllipticCurve, ZKError> {
+        EllipticCurve::with_subgroup(
+            FieldElement::new(0, Self::BASE_MODULUS)?,
+            FieldElement::new(5, Self::BASE_MODULUS)?,
+            Self::g1_generator()?,
+            Self::SCALAR_MODULUS,
+            Self::G1_COFACTOR,
+        )
+    }
+
+    fn g2_curve() -> Result<EllipticCurve<Fp2>, ZKError> {
+        // 5 / (2 + u) = 2 - u.
+        EllipticCurve::with_subgroup(
+            fp2::<Self>(0, 0)?,
+            fp2::<Self>(2, 102)?,
+            Self::g2_generator()?,
+            Self::SCALAR_MODULUS,
+            Self::G2_COFACTOR,
+        )
+    }
+
+    fn g1_generator() -> Result<EllipticCurvePoint, ZKError> {
+        Ok(EllipticCurvePoint::Point {
+            x: FieldElement::new(2, Self::BASE_MODULUS)?,
+            y: FieldElement::new(42, Self::BASE_MODULUS)?,
+        })
+    }
+
+    fn g2_generator() -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+        Ok(EllipticCurvePoint::Point {
+            x: fp2::<Self>(86, 29)?,
+            y: fp2::<Self>(88, 47)?,
+        })
+    }
+}
+
+/// The BLS12 curve with seed x = -2, where p = (x - 1)^2 (x^4 - x^2 + 1) / 3 + x = 37 and
+/// r = x^4 - x^2 + 1 = 13.
+///
+/// G1 is the order-13 subgroup of E: y^2 = x^3 + 3 over Fp, and G2 lies on the M-type
+/// twist E': y^2 = x^3 + 3ξ over Fp2, with β = 2 and ξ = 2 + u. It is built with the
+/// same formulas as BLS12-381 (see [`bls12_381`]).
+pub struct ToyBls12;
+
+impl Curve for ToyBls12 {
+    const NAME: &'static str = "toy-bls12-37";
+    const BASE_MODULUS: u64 = 37;
+    const SCALAR_MODULUS: u64 = 13;
+    const G1_COFACTOR: u64 = 3;
+    const G2_COFACTOR: u64 = 109;
+    const TWIST_TYPE: TwistType = TwistType::M;
+
+    type BaseField = Fp<37>;
+    type ScalarField = Fp<13>;
+
+    fn fp2_non_residue() -> Result<FieldElement, ZKError> {
+        FieldElement::new(2, Self::BASE_MODULUS)
+    }
+
+    fn fp6_non_residue() -> Result<Fp2, ZKError> {
+        fp2::<Self>(2, 1)
+    }
+
+    fn g1_curve() -> Result<EllipticCurve, ZKError> {
+        EllipticCurve::with_subgroup(
+            FieldElement::new(0, Self::BASE_MODULUS)?,
+            FieldElement::new(3, Self::BASE_MODULUS)?,
+            Self::g1_generator()?,
+            Self::SCALAR_MODULUS,
+            Self::G1_COFACTOR,
+        )
+    }
+
+    fn g2_curve() -> Result<EllipticCurve<Fp2>, ZKError> {
+        // 3 · (2 + u) = 6 + 3u.
+        EllipticCurve::with_subgroup(
+            fp2::<Self>(0, 0)?,
+            fp2::<Self>(6, 3)?,
+            Self::g2_generator()?,
+            Self::SCALAR_MODULUS,
+            Self::G2_COFACTOR,
+        )
+    }
+
+    fn g1_generator() -> Result<EllipticCurvePoint, ZKError> {
+        Ok(EllipticCurvePoint::Point {
+            x: FieldElement::new(15, Self::BASE_MODULUS)?,
+            y: FieldElement::new(23, Self::BASE_MODULUS)?,
+        })
+    }
+
+    fn g2_generator() -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+        Ok(EllipticCurvePoint::Point {
+            x: fp2::<Self>(2, 28)?,
+            y: fp2::<Self>(27, 6)?,
+        })
+    }
+}
+
+/// y^2 = x^3 + 2x + 32 over F_97, whose 107 points form a group of prime order.
+/// It is a small curve for examples and tests, not a pairing-friendly one.
+pub struct Toy97;
+
+impl Toy97 {
+    /// A short identifier for the curve.
+    pub const NAME: &'static str = "toy97";
+
+    /// Returns the curve with its generator (7, 1).
+    pub fn curve() -> Result<EllipticCurve, ZKError> {
+        let modulus = 97;
+        EllipticCurve::with_subgroup(
+            FieldElement::new(2, modulus)?,
+            FieldElement::new(32, modulus)?,
+            EllipticCurvePoint::Point {
+                x: FieldElement::new(7, modulus)?,
+                y: FieldElement::new(1, modulus)?,
+            },
+            107,
+            1,
+        )
+    }
+}
+
+/// The twisted Edwards curve -x^2 + y^2 = 1 + 29 x^2 y^2 over F_97, the scalar field of
+/// `ToyBn`, like Baby Jubjub is over the scalar field of BN254. It has 116 = 4 * 29 points,
+/// so statements about its points are native arithmetic in ToyBn circuits.
+pub struct ToyBnJubjub;
+
+impl ToyBnJubjub {
+    /// A short identifier for the curve.
+    pub const NAME: &'static str = "toy-bn103-jubjub";
+    /// The prime order of the subgroup generated by `generator`.
+    pub const ORDER: u64 = 29;
+    /// The number of points divided by `ORDER`.
+    pub const COFACTOR: u64 = 4;
+
+    /// Returns the curve.
+    pub fn curve() -> Result<TwistedEdwardsCurve, ZKError> {
+        TwistedEdwardsCurve::new(
+            FieldElement::from_i64(-1, ToyBn::SCALAR_MODULUS)?,
+            FieldElement::new(29, ToyBn::SCALAR_MODULUS)?,
+        )
+    }
+
+    /// Returns the generator (8, 61) of the prime-order subgroup.
+    pub fn generator() -> Result<EdwardsPoint, ZKError> {
+        Ok(EdwardsPoint {
+            x: FieldElement::new(8, ToyBn::SCALAR_MODULUS)?,
+            y: FieldElement::new(61, ToyBn::SCALAR_MODULUS)?,
+        })
+    }
+}
+
+impl EllipticCurve {
+    /// Looks up a named curve: "toy97", "toy-bn103" or "toy-bls12-37". For the
+    /// pairing-friendly curves this is the curve carrying G1; `Curve` gives the twist.
+    ///
+    /// "bn254" and "bls12-381" are recognized but return an error: `Curve` and its
+    /// extension tower take u64 moduli over `FieldElement`, so there is no `Curve` over
+    /// `Fp256` for BN254, and BLS12-381's 381-bit base field fits no backend. "secp256k1"
+    /// also returns an error, since its curve is over a different field type; use
+    /// [`secp256k1::curve`] instead.
+    pub fn from_name(name: &str) -> Result<Self, ZKError> {
+        match name {
+            Toy97::NAME => Toy97::curve(),
+            ToyBn::NAME => ToyBn::g1_curve(),
+            ToyBls12::NAME => ToyBls12::g1_curve(),
+            "bn254" | "bls12-381" => Err(ZKError::CurveError(format!(
+                "Curve {} has no Curve implementation; its moduli do not fit a u64.",
+                name
+            ))),
+            "secp256k1" => Err(ZKError::CurveError(
+                "Curve secp256k1 is over a 256-bit field; use curves::secp256k1::curve.".into(),
+            )),
+            _ => Err(ZKError::CurveError(format!("Unknown curve: {}.", name))),
+        }
+    }
+}
+
+// Builds c0 + c1·u in the Fp2 of the given curve.
+fn fp2<C: Curve>(c0: u64, c1: u64) -> Result<Fp2, ZKError> {
+    Fp2::new(
+        FieldElement::new(c0, C::BASE_MODULUS)?,
+        FieldElement::new(c1, C::BASE_MODULUS)?,
+        C::fp2_non_residue()?,
+    )
+}
+
+/// Reference parameters of BN254 (alt_bn128), as hexadecimal strings.
+///
+/// Its 254-bit moduli fit `Fp256`, but `Curve` takes u64 moduli and builds the Fp2, Fp6
+/// and Fp12 tower over `FieldElement`, so there is no `Curve` or `PairingEngine` for
+/// BN254; the values are kept here for a tower over `Fp256`.
+/// E: y^2 = x^3 + 3, and G2 lies on the D-type twist y^2 = x^3 + 3 / (9 + u) with β = -1.
+pub mod bn254 {
+    /// The seed u from which p and r are derived.
+    pub const SEED: u64 = 0x44e9_92b4_4a69_09f1;
+    /// The base field modulus p.
+    pub const BASE_MODULUS: &str =
+        "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+    /// The prime subgroup order r.
+    pub const SCALAR_MODULUS: &str =
+        "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    /// E(Fp) has prime order, so G1 needs no cofactor clearing.
+    pub const G1_COFACTOR: u64 = 1;
+    /// The G1 generator (x, y).
+    pub const G1_GENERATOR: (&str, &str) = ("0x1", "0x2");
+    /// The G2 generator (x0 + x1·u, y0 + y1·u) as ((x0, x1), (y0, y1)).
+    pub const G2_GENERATOR: ((&str, &str), (&str, &str)) = (
+        (
+            "0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+        ),
+        (
+            "0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+            "0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+        ),
+    );
+}
+
+/// Reference parameters of BLS12-381, as hexadecimal strings.
+///
+/// These are constants only. Its 381-bit base field does not fit the 64-bit
+/// `FieldElement` and the extension tower is built on `FieldElement`, so nothing
+/// implements `Curve` or `PairingEngine` for BLS12-381: `TatePairing`, BLS signatures
+/// and the SNARK cannot run on it. `ToyBls12` exercises the same formulas at a size the
+/// crate supports.
+/// E: y^2 = x^3 + 4, and G2 lies on the M-type twist y^2 = x^3 + 4(1 + u) with β = -1.
+pub mod bls12_381 {
+    /// The seed x from which p and r are derived.
+    pub const SEED: i128 = -0xd201_0000_0001_0000;
+    /// The base field modulus p.
+    pub const BASE_MODULUS: &str = "0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+    /// The prime subgroup order r.
+    pub const SCALAR_MODULUS: &str =
+        "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+    /// #E(Fp) / r = (x - 1)^2 / 3.
+    pub const G1_COFACTOR: &str = "0x396c8c005555e1568c00aaab0000aaab";
+    /// #E'(Fp2) / r.
+    pub const G2_COFACTOR: &str = "0x5d543a95414e7f1091d50792876a202cd91de4547085abaa68a205b2e5a7ddfa628f1cb4d9e82ef21537e293a6691ae1616ec6e786f0c70cf1c38e31c7238e5";
+    /// The G1 generator (x, y).
+    pub const G1_GENERATOR: (&str, &str) = (
+        "0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+        "0x08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1",
+    );
+    /// The G2 generator (x0 + x1·u, y0 + y1·u) as ((x0, x1), (y0, y1)).
+    pub const G2_GENERATOR: ((&str, &str), (&str, &str)) = (
+        (
+            "0x024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+            "0x13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        ),
+        (
+            "0x0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+            "0x0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+        ),
+    );
+}
+
+/// Parameters of secp256k1, the curve used by Bitcoin and Ethereum for ECDSA.
+///
+/// E: y^2 = x^3 + 7 over Fp with p = 2^256 - 2^32 - 977. The group has prime order n,
+/// which is the scalar field ECDSA signatures live in. Both fields use the 256-bit
+/// [`Fp256`](crate::fp256::Fp256) backend, so [`curve`](secp256k1::curve) is an ordinary
+/// `EllipticCurve`; scalars wider than 64 bits go through
+/// [`EllipticCurve::mul_scalar_bytes`]. There is no `Curve` implementation, whose
+/// moduli are u64.
+pub mod secp256k1 {
+    use crate::{
+        curve::{EllipticCurve, EllipticCurvePoint},
+        errors::ZKError,
+        field::Field,
+        fp256::{limbs_from_hex, Fp256, Modulus256},
+    };
+
+    /// The curve coefficient a.
+    pub const A: u64 = 0;
+    /// The curve coefficient b.
+    pub const B: u64 = 7;
+    /// The base field modulus p.
+    pub const BASE_MODULUS: &str =
+        "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+    /// The group order n, i.e. the scalar field modulus.
+    pub const SCALAR_MODULUS: &str =
+        "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+    /// E(Fp) has prime order.
+    pub const COFACTOR: u64 = 1;
+    /// The generator (x, y).
+    pub const GENERATOR: (&str, &str) = (
+        "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    );
+
+    const fn limbs(hex: &str) -> [u64; 4] {
+        match limbs_from_hex(hex) {
+            Some(limbs) => limbs,
+            None => panic!("Invalid modulus."),
+        }
+    }
+
+    /// The modulus p of the base field.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BaseModulus;
+
+    impl Modulus256 for BaseModulus {
+        const MODULUS: [u64; 4] = limbs(BASE_MODULUS);
+    }
+
+    /// The modulus n of the scalar field.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ScalarModulus;
+
+    impl Modulus256 for ScalarModulus {
+        const MODULUS: [u64; 4] = limbs(SCALAR_MODULUS);
+    }
+
+    /// The base field Fp, in which point coordinates live.
+    pub type BaseField = Fp256<BaseModulus>;
+    /// The scalar field Fn of the group.
+    pub type ScalarField = Fp256<ScalarModulus>;
+
+    /// Returns the curve y^2 = x^3 + 7 over the base field.
+    pub fn curve() -> Result<EllipticCurve<BaseField>, ZKError> {
+        EllipticCurve::new(BaseField::from_u64(A, &()), BaseField::from_u64(B, &()))
+    }
+
+    /// Returns the standard generator G.
+    pub fn generator() -> Result<EllipticCurvePoint<BaseField>, ZKError> {
+        EllipticCurvePoint::new(
+            BaseField::from_hex(GENERATOR.0)?,
+            BaseField::from_hex(GENERATOR.1)?,
+            &curve()?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field::Field, fp::PrimeField};
+
+    // Number of points on y^2 = x^3 + ax + b over Fp, including infinity.
+    fn count_points(curve: &EllipticCurve) -> u64 {
+        let p = curve.a.modulus;
+        let mut count = 1;
+        for x in 0..p {
+            let x = FieldElement::new(x, p).unwrap();
+            let rhs = x
+                .mul(&x)
+                .unwrap()
+                .mul(&x)
+                .unwrap()
+                .add(&curve.a.mul(&x).unwrap())
+                .unwrap()
+                .add(&curve.b)
+                .unwrap();
+            count += (1 + rhs.legendre().unwrap()) as u64;
+        }
+        count
+    }
+
+    // Number of points on y^2 = x^3 + b over Fp2. A nonzero element of Fp2 is a square
+    // exactly when its norm is a square in Fp.
+    fn count_twist_points(curve: &EllipticCurve<Fp2>) -> u64 {
+        let p = curve.b.c0.modulus;
+        let mut count = 1;
+        for c0 in 0..p {
+            for c1 in 0..p {
+                let x = Fp2::new(
+                    FieldElement::new(c0, p).unwrap(),
+                    FieldElement::new(c1, p).unwrap(),
+                    curve.b.non_residue.clone(),
+                )
+                .unwrap();
+                let rhs = x.square().mul(&x).unwrap().add(&curve.b).unwrap();
+                count += (1 + rhs.norm().legendre().unwrap()) as u64;
+            }
+        }
+        count
+    }
+
+    fn check_curve<C: Curve>() {
+        let (p, r) = (C::BASE_MODULUS, C::SCALAR_MODULUS);
+        <Fp2 as Field>::validate_params(&C::fp2_non_residue().unwrap()).unwrap();
+
+        // ξ is neither a square nor a cube in Fp2.
+        let xi = C::fp6_non_residue().unwrap();
+        let order = p * p - 1;
+        assert!(!Field::is_one(&xi.exp(order / 2).unwrap()));
+        assert!(!Field::is_one(&xi.exp(order / 3).unwrap()));
+
+        // The embedding degree is 12: r divides p^12 - 1 but no smaller p^k - 1.
+        let p_mod_r = FieldElement::new(p, r).unwrap();
+        for k in 1..12 {
+            assert!(
+                !p_mod_r.exp(k).unwrap().is_one(),
+                "{} has degree {}",
+                C::NAME,
+                k
+            );
+        }
+        assert!(p_mod_r.exp(12).unwrap().is_one());
+
+        let g1 = C::g1_curve().unwrap();
+        let g1_generator = C::g1_generator().unwrap();
+        assert_eq!(count_points(&g1), r * C::G1_COFACTOR);
+        assert!(g1.is_on_curve(&g1_generator).unwrap());
+        assert_ne!(g1_generator, EllipticCurvePoint::Infinity);
+        assert_eq!(
+            g1.mul_scalar(&g1_generator, r).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+
+        let g2 = C::g2_curve().unwrap();
+        let g2_generator = C::g2_generator().unwrap();
+        assert_eq!(count_twist_points(&g2), r * C::G2_COFACTOR);
+        assert!(g2.is_on_curve(&g2_generator).unwrap());
+        assert_ne!(g2_generator, EllipticCurvePoint::Infinity);
+        assert_eq!(
+            g2.mul_scalar(&g2_generator, r).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+
+        // The typed fields agree with the moduli, and typed scalars act like integers.
+        assert_eq!(<C::BaseField as PrimeField>::MODULUS, p);
+        assert_eq!(<C::ScalarField as PrimeField>::MODULUS, r);
+        let scalar = C::ScalarField::from_u64(r + 5, &());
+        assert_eq!(
+            C::g1_mul(&g1_generator, &scalar).unwrap(),
+            g1.mul_scalar(&g1_generator, 5).unwrap()
+        );
+        assert_eq!(
+            C::g2_mul(&g2_generator, &scalar).unwrap(),
+            g2.mul_scalar(&g2_generator, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_toy_bn() {
+        let u = 1u64;
+        assert_eq!(
+            36 * u.pow(4) + 36 * u.pow(3) + 24 * u.pow(2) + 6 * u + 1,
+            103
+        );
+        assert_eq!(
+            36 * u.pow(4) + 36 * u.pow(3) + 18 * u.pow(2) + 6 * u + 1,
+            97
+        );
+        check_curve::<ToyBn>();
+    }
+
+    #[test]
+    fn test_toy_bls12() {
+        let x = -2i64;
+        let r = x.pow(4) - x.pow(2) + 1;
+        assert_eq!(r, 13);
+        assert_eq!((x - 1).pow(2) * r / 3 + x, 37);
+        assert_eq!((x - 1).pow(2) / 3, 3);
+        check_curve::<ToyBls12>();
+    }
+
+    #[test]
+    fn test_from_name() {
+        for (name, modulus, order, cofactor, embedding_degree) in [
+            ("toy97", 97, 107, 1, 106),
+            ("toy-bn103", 103, 97, 1, 12),
+            ("toy-bls12-37", 37, 13, 3, 12),
+        ] {
+            let curve = EllipticCurve::from_name(name).unwrap();
+            let subgroup = curve.subgroup().unwrap();
+            assert_eq!(curve.a.modulus, modulus);
+            assert_eq!((subgroup.order, subgroup.cofactor), (order, cofactor));
+            assert!(curve
+                .is_in_prime_subgroup(&subgroup.generator, order)
+                .unwrap());
+            assert_eq!(curve.embedding_degree().unwrap(), embedding_degree);
+            assert_eq!(curve.points().unwrap().len() as u64, order * cofactor);
+        }
+
+        for name in ["bn254", "bls12-381", "secp256k1", "p256"] {
+            assert!(matches!(
+                EllipticCurve::from_name(name),
+                Err(ZKError::CurveError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        use secp256k1::{BaseField, ScalarField};
+
+        let curve = secp256k1::curve().unwrap();
+        let generator = secp256k1::generator().unwrap();
+        let point = |x: &str, y: &str| {
+            EllipticCurvePoint::new(
+                BaseField::from_hex(x).unwrap(),
+                BaseField::from_hex(y).unwrap(),
+                &curve,
+            )
+            .unwrap()
+        };
+        let scalar = |hex: &str| ScalarField::from_hex(hex).unwrap().to_bytes();
+
+        // Known multiples of the generator.
+        assert_eq!(
+            curve.double(&generator).unwrap(),
+            point(
+                "0xc6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+                "0x1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+            )
+        );
+        assert_eq!(
+            curve.mul_scalar(&generator, 3).unwrap(),
+            point(
+                "0xf9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0x388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+            )
+        );
+        assert_eq!(
+            curve
+                .mul_scalar_bytes(
+                    &generator,
+                    &scalar("0xaa5e28d6a97a2479a65527f7290311a3624d4cc0fa1578598ee3c2613bf99522"),
+                )
+                .unwrap(),
+            point(
+                "0x34f9460f0e4f08393d192b3c5133a6ba099aa0ad9fd54ebccfacdfa239ff49c6",
+                "0x0b71ea9bd730fd8923f6d25a7a91e7dd7728a960686cb5a901bb419e0f2ca232",
+            )
+        );
+
+        // G has order n: (n - 1)G = -G and nG = O.
+        let minus_one = ScalarField::from_i64(-1, &()).to_bytes();
+        assert_eq!(
+            curve.mul_scalar_bytes(&generator, &minus_one).unwrap(),
+            generator.neg()
+        );
+        let order = crate::fp256::limbs_from_hex(secp256k1::SCALAR_MODULUS).unwrap();
+        let order: Vec<u8> = order.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        assert_eq!(
+            curve.mul_scalar_bytes(&generator, &order).unwrap(),
+            EllipticCurvePoint::Infinity
+        );
+    }
+
+    #[test]
+    fn test_toy_bn_jubjub() {
+        let curve = ToyBnJubjub::curve().unwrap();
+        let generator = ToyBnJubjub::generator().unwrap();
+        assert_eq!(curve.a.modulus, ToyBn::SCALAR_MODULUS);
+        assert!(curve.is_on_curve(&generator).unwrap());
+        assert_ne!(generator, curve.identity());
+        assert_eq!(
+            curve.mul_scalar(&generator, ToyBnJubjub::ORDER).unwrap(),
+            curve.identity()
+        );
+        let count: usize = (0..ToyBn::SCALAR_MODULUS)
+            .map(|y| {
+                curve
+                    .points_with_y(&FieldElement::new(y, ToyBn::SCALAR_MODULUS).unwrap())
+                    .unwrap()
+                    .len()
+            })
+            .sum();
+        assert_eq!(count as u64, ToyBnJubjub::ORDER * ToyBnJubjub::COFACTOR);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::{
+    builder::{CircuitBuilder, Hint, Var},
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// Represents a twisted Edwards curve defined by the equation:
+/// a x^2 + y^2 = 1 + d x^2 y^2
+///
+/// When a is a square and d is not, the addition law is complete: the same formula adds
+/// any two points, doublings and the identity (0, 1) included, which is what makes these
+/// curves cheap to use inside circuits. Curves of this shape defined over the scalar field
+/// of a pairing-friendly curve (like Baby Jubjub over BN254) are called embedded curves.
+#[derive(Debug, Clone)]
+pub struct TwistedEdwardsCurve<F: Field = FieldElement> {
+    pub a: F,
+    pub d: F,
+}
+
+/// Represents a point (x, y) on a twisted Edwards curve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdwardsPoint<F: Field = FieldElement> {
+    pub x: F,
+    pub y: F,
+}
+
+/// Represents a point on a twisted Edwards curve inside a circuit over the base field of
+/// the curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdwardsPointVar {
+    pub x: Var,
+    pub y: Var,
+}
+
+impl EdwardsPointVar {
+    /// Reads the value of the point from a witness.
+    pub fn value<F: Field>(&self, witness: &[F]) -> Result<EdwardsPoint<F>, ZKError> {
+        let coordinate = |var: Var| {
+            witness.get(var.index()).cloned().ok_or_else(|| {
+                ZKError::CircuitError(format!("Witness has no variable {}.", var.index()))
+            })
+        };
+        Ok(EdwardsPoint {
+            x: coordinate(self.x)?,
+            y: coordinate(self.y)?,
+        })
+    }
+}
+
+impl<F: Field> TwistedEdwardsCurve<F> {
+    /// Creates a twisted Edwards curve, rejecting the degenerate cases a = 0, d = 0 and a = d.
+    pub fn new(a: F, d: F) -> Result<Self, ZKError> {
+        if a.params() != d.params() {
+            return Err(ZKError::InvalidFieldElement(
+                "Curve coefficients must belong to the same field.".into(),
+            ));
+        }
+        if a.is_zero() || d.is_zero() || a == d {
+            return Err(ZKError::CurveError(
+                "Twisted Edwards curve requires a, d nonzero and a != d.".into(),
+            ));
+        }
+        Ok(TwistedEdwardsCurve { a, d })
+    }
+
+    /// Returns the identity (0, 1).
+    pub fn identity(&self) -> EdwardsPoint<F> {
+        let params = self.a.params();
+        EdwardsPoint {
+            x: F::zero(&params),
+            y: F::one(&params),
+        }
+    }
+
+    /// Check if the given point lies on the curve.
+    pub fn is_on_curve(&self, point: &EdwardsPoint<F>) -> Result<bool, ZKError> {
+        let xx = point.x.mul(&point.x)?;
+        let yy = point.y.mul(&point.y)?;
+        let lhs = self.a.mul(&xx)?.add(&yy)?;
+        let rhs = F::one(&self.a.params()).add(&self.d.mul(&xx)?.mul(&yy)?)?;
+        Ok(lhs == rhs)
+    }
+
+    /// Add two points with the unified addition law:
+    /// x3 = (x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2), y3 = (y1 y2 - a x1 x2) / (1 - d x1 x2 y1 y2)
+    pub fn add(
+        &self,
+        p: &EdwardsPoint<F>,
+        q: &EdwardsPoint<F>,
+    ) -> Result<EdwardsPoint<F>, ZKError> {
+        let one = F::one(&self.a.params());
+        let x1x2 = p.x.mul(&q.x)?;
+        let y1y2 = p.y.mul(&q.y)?;
+        let dxy = self.d.mul(&x1x2)?.mul(&y1y2)?;
+        let x3 = p.x.mul(&q.y)?.add(&p.y.mul(&q.x)?)?.div(&one.add(&dxy)?)?;
+        let y3 = y1y2.sub(&self.a.mul(&x1x2)?)?.div(&one.sub(&dxy)?)?;
+        Ok(EdwardsPoint { x: x3, y: y3 })
+    }
+
+    /// Negate a point: -(x, y) = (-x, y).
+    pub fn neg(&self, point: &EdwardsPoint<F>) -> EdwardsPoint<F> {
+        EdwardsPoint {
+            x: point.x.neg(),
+            y: point.y.clone(),
+        }
+    }
+
+    /// Multiply a point with a scalar using the double-and-add algorithm.
+    pub fn mul_scalar(
+        &self,
+        point: &EdwardsPoint<F>,
+        scalar: u64,
+    ) -> Result<EdwardsPoint<F>, ZKError> {
+        let mut result = self.identity();
+        let mut addend = point.clone();
+        let mut k = scalar;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = self.add(&result, &addend)?;
+            }
+            addend = self.add(&addend, &addend)?;
+            k >>= 1;
+        }
+        Ok(result)
+    }
+}
+
+impl<F: Field> TwistedEdwardsCurve<F> {
+    /// Adds two points inside a circuit with the unified addition law, in 7 constraints.
+    /// Because the law is complete, the denominators never vanish for points on the
+    /// curve, and no case distinction is needed.
+    pub fn add_gadget(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        p: &EdwardsPointVar,
+        q: &EdwardsPointVar,
+    ) -> Result<EdwardsPointVar, ZKError> {
+        if builder.params != self.a.params() {
+            return Err(ZKError::CircuitError(
+                "The curve must be defined over the field of the circuit.".into(),
+            ));
+        }
+        let one = F::one(&builder.params);
+        let x1y2 = builder.mul(p.x, q.y);
+        let y1x2 = builder.mul(p.y, q.x);
+        let x1x2 = builder.mul(p.x, q.x);
+        let y1y2 = builder.mul(p.y, q.y);
+        let product = builder.mul(x1x2, y1y2);
+
+        // x3 · (1 + d x1 x2 y1 y2) = x1 y2 + y1 x2.
+        let numerator = builder.lc(&[(x1y2, one.clone()), (y1x2, one.clone())]);
+        let denominator = builder.lc(&[(builder.one(), one.clone()), (product, self.d.clone())]);
+        let x = builder.allocate(Hint::Quotient(numerator.clone(), denominator.clone()));
+        builder.emit(builder.single(x), denominator, numerator);
+
+        // y3 · (1 - d x1 x2 y1 y2) = y1 y2 - a x1 x2.
+        let numerator = builder.lc(&[(y1y2, one.clone()), (x1x2, self.a.neg())]);
+        let denominator = builder.lc(&[(builder.one(), one), (product, self.d.neg())]);
+        let y = builder.allocate(Hint::Quotient(numerator.clone(), denominator.clone()));
+        builder.emit(builder.single(y), denominator, numerator);
+
+        Ok(EdwardsPointVar { x, y })
+    }
+}
+
+impl TwistedEdwardsCurve<FieldElement> {
+    /// Returns the points with the given y-coordinate, solving x^2 = (1 - y^2) / (a - d y^2).
+    pub fn points_with_y(&self, y: &FieldElement) -> Result<Vec<EdwardsPoint>, ZKError> {
+        let yy = y.mul(y)?;
+        let one = FieldElement::one(self.a.modulus)?;
+        let denominator = self.a.sub(&self.d.mul(&yy)?)?;
+        if denominator.is_zero() {
+            return Ok(Vec::new());
+        }
+        let xx = one.sub(&yy)?.mul(&denominator.inv()?)?;
+        let mut points = Vec::new();
+        if xx.is_zero() {
+            points.push(EdwardsPoint {
+                x: xx,
+                y: y.clone(),
+            });
+        } else if let Some(x) = xx.sqrt() {
+            points.push(EdwardsPoint {
+                x: x.neg(),
+                y: y.clone(),
+            });
+            points.push(EdwardsPoint { x, y: y.clone() });
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // -x^2 + y^2 = 1 + 29 x^2 y^2 over F_97 has 116 = 4 * 29 points.
+    fn get_test_curve() -> TwistedEdwardsCurve {
+        TwistedEdwardsCurve::new(
+            FieldElement::new(96, 97).unwrap(),
+            FieldElement::new(29, 97).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn all_points(curve: &TwistedEdwardsCurve) -> Vec<EdwardsPoint> {
+        (0..97)
+            .flat_map(|y| {
+                curve
+                    .points_with_y(&FieldElement::new(y, 97).unwrap())
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new_rejects_degenerate_curves() {
+        let fe = |v| FieldElement::new(v, 97).unwrap();
+        assert!(TwistedEdwardsCurve::new(fe(0), fe(29)).is_err());
+        assert!(TwistedEdwardsCurve::new(fe(96), fe(0)).is_err());
+        assert!(TwistedEdwardsCurve::new(fe(5), fe(5)).is_err());
+    }
+
+    #[test]
+    fn test_group_law() {
+        let curve = get_test_curve();
+        let points = all_points(&curve);
+        assert_eq!(points.len(), 116);
+
+        let identity = curve.identity();
+        for p in points.iter().step_by(5) {
+            assert!(curve.is_on_curve(p).unwrap());
+            assert_eq!(&curve.add(p, &identity).unwrap(), p);
+            assert_eq!(curve.add(p, &curve.neg(p)).unwrap(), identity);
+            assert_eq!(curve.mul_scalar(p, 116).unwrap(), identity);
+            for q in points.iter().step_by(11) {
+                // The addition law is complete, doublings included.
+                let sum = curve.add(p, q).unwrap();
+                assert!(curve.is_on_curve(&sum).unwrap());
+                assert_eq!(sum, curve.add(q, p).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_scalar_matches_repeated_addition() {
+        let curve = get_test_curve();
+        let point = all_points(&curve)[7].clone();
+        let mut expected = curve.identity();
+        for k in 0..40 {
+            assert_eq!(curve.mul_scalar(&point, k).unwrap(), expected);
+            expected = curve.add(&expected, &point).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_add_gadget() {
+        let curve = get_test_curve();
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let p = EdwardsPointVar {
+            x: builder.private_witness(),
+            y: builder.private_witness(),
+        };
+        let q = EdwardsPointVar {
+            x: builder.private_witness(),
+            y: builder.private_witness(),
+        };
+        let sum = curve.add_gadget(&mut builder, &p, &q).unwrap();
+        assert_eq!(builder.constraint_system().constraints.len(), 7);
+
+        let points = all_points(&curve);
+        for (a, b) in points.iter().step_by(13).zip(points.iter().step_by(7)) {
+            let inputs = [a.x.clone(), a.y.clone(), b.x.clone(), b.y.clone()];
+            let witness = builder.generate_witness(&[], &inputs).unwrap();
+            assert_eq!(sum.value(&witness).unwrap(), curve.add(a, b).unwrap());
+        }
+
+        // The curve must live in the field of the circuit.
+        let mut other = CircuitBuilder::new(&101).unwrap();
+        assert!(curve.add_gadget(&mut other, &p, &q).is_err());
+    }
+}
@@ -8,4 +8,20 @@ pub enum ZKError {
     CircuitError(String),
     #[error("Polynomial error: {0}")]
     PolynomialError(String),
+    #[error("Composite modulus: {0} is not prime")]
+    CompositeModulus(u64),
+    #[error("Unsupported modulus: a {0}-bit prime does not fit the 64-bit field backend")]
+    ModulusTooWide(usize),
+    #[error("Malformed field element: {0}")]
+    MalformedFieldElement(String),
+    #[error("Field element out of range: {0}")]
+    FieldElementOutOfRange(String),
+    #[error("Degree bound exceeded: degree {0} is above the bound {1}")]
+    DegreeBoundExceeded(usize, usize),
+    #[error("Curve error: {0}")]
+    CurveError(String),
+    #[error("Invalid format: {0}")]
+    InvalidFormat(String),
+    #[error("I/O error: {0}")]
+    IoError(String),
 }
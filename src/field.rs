@@ -1,100 +1,1740 @@
-use crate::errors::ZKError;
+use std::fmt::Debug;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use rand::RngCore;
+
+use crate::{errors::ZKError, parallel};
+
+/// Arithmetic interface shared by all finite field backends.
+///
+/// Polynomials, constraint systems, QAPs and the SNARK are generic over this trait,
+/// so the toy `FieldElement` can be swapped for another field implementation
+/// without touching the upper layers.
+pub trait Field: Clone + Debug + PartialEq + Eq + Send + Sync + Sized {
+    /// Runtime description of the field, e.g. the modulus of a `FieldElement`.
+    /// Backends whose field is fixed at compile time use `()`.
+    type Params: Clone + Debug + PartialEq + Send + Sync;
+
+    /// Returns the description of the field this element belongs to.
+    fn params(&self) -> Self::Params;
+
+    /// Returns the additive identity.
+    fn zero(params: &Self::Params) -> Self;
+
+    /// Returns the multiplicative identity.
+    fn one(params: &Self::Params) -> Self;
+
+    /// Maps an integer into the field.
+    fn from_u64(value: u64, params: &Self::Params) -> Self;
+
+    /// Maps a signed integer into the field, so that -v becomes the negation of v.
+    fn from_i64(value: i64, params: &Self::Params) -> Self {
+        let magnitude = Self::from_u64(value.unsigned_abs(), params);
+        if value < 0 {
+            magnitude.neg()
+        } else {
+            magnitude
+        }
+    }
+
+    /// Returns true if the element is zero.
+    fn is_zero(&self) -> bool;
+
+    /// Returns true if the element is one.
+    fn is_one(&self) -> bool {
+        *self == Self::one(&self.params())
+    }
+
+    /// Add two field elements.
+    fn add(&self, other: &Self) -> Result<Self, ZKError>;
+
+    /// Subtract two field elements.
+    fn sub(&self, other: &Self) -> Result<Self, ZKError>;
+
+    /// Multiply two field elements.
+    fn mul(&self, other: &Self) -> Result<Self, ZKError>;
+
+    /// Negate the field element.
+    fn neg(&self) -> Self;
+
+    /// Find the multiplicative inverse of the field element.
+    fn inv(&self) -> Result<Self, ZKError>;
+
+    /// Divide two field elements, i.e. multiply by the inverse of `other`.
+    fn div(&self, other: &Self) -> Result<Self, ZKError> {
+        self.mul(&other.inv()?)
+    }
+
+    /// Add two field elements, returning `None` instead of an error.
+    /// The `checked_*` variants suit hot loops whose inputs were validated up front.
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.add(other).ok()
+    }
+
+    /// Subtract two field elements, returning `None` instead of an error.
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.sub(other).ok()
+    }
+
+    /// Multiply two field elements, returning `None` instead of an error.
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        self.mul(other).ok()
+    }
+
+    /// Find the multiplicative inverse, returning `None` if it does not exist.
+    fn checked_inv(&self) -> Option<Self> {
+        self.inv().ok()
+    }
+
+    /// Divide two field elements, returning `None` instead of an error.
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(&other.checked_inv()?)
+    }
+
+    /// Samples a field element uniformly at random.
+    fn random<R: RngCore + ?Sized>(rng: &mut R, params: &Self::Params) -> Result<Self, ZKError>;
+
+    /// Serializes the canonical value of the element as little-endian bytes.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Deserializes an element from the output of `to_bytes`, rejecting non-canonical input.
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self, ZKError>;
+
+    /// Checks that the parameters really describe a field, e.g. that a modulus is prime.
+    /// Operations do not call this themselves; it is meant for validating input once,
+    /// up front. Backends whose field is fixed at compile time can keep the default.
+    fn validate_params(_params: &Self::Params) -> Result<(), ZKError> {
+        Ok(())
+    }
+
+    /// Returns an element of multiplicative order exactly `order`, which must be a power
+    /// of two, or `None` if the field has no such element. Radix-2 FFT domains are built
+    /// from these. The default returns `None`, for backends that do not know the
+    /// structure of their multiplicative group.
+    fn root_of_unity(_order: u64, _params: &Self::Params) -> Option<Self> {
+        None
+    }
+
+    /// Exponentiate the field element by the provided exponent.
+    fn exp(&self, exponent: u64) -> Result<Self, ZKError> {
+        let mut result = Self::one(&self.params());
+        let mut base = self.clone();
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base)?;
+            }
+            base = base.mul(&base)?;
+            exp >>= 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Inverts a batch of field elements using Montgomery's trick: a single inversion
+    /// of the product of all elements, plus three multiplications per element.
+    /// Fails if the moduli differ or any element has no inverse.
+    fn batch_inv(elements: &[Self]) -> Result<Vec<Self>, ZKError> {
+        let Some(first) = elements.first() else {
+            return Ok(Vec::new());
+        };
+
+        // prefix[i] = a_0 · a_1 · ... · a_(i-1)
+        let mut prefix = Vec::with_capacity(elements.len());
+        let mut acc = Self::one(&first.params());
+        for element in elements {
+            prefix.push(acc.clone());
+            acc = acc.mul(element)?;
+        }
+
+        // Walk backwards, peeling one element off the inverted product at a time.
+        let mut acc_inv = acc.inv()?;
+        let mut inverses = vec![acc_inv.clone(); elements.len()];
+        for (i, element) in elements.iter().enumerate().rev() {
+            inverses[i] = acc_inv.mul(&prefix[i])?;
+            acc_inv = acc_inv.mul(element)?;
+        }
+
+        Ok(inverses)
+    }
+
+    /// Adds `rhs` to `lhs` element-wise. The slices must have the same length.
+    /// Backends may override the slice operations with tighter, vectorizable loops.
+    fn add_assign_slices(lhs: &mut [Self], rhs: &[Self]) -> Result<(), ZKError> {
+        check_slice_lengths(lhs, rhs)?;
+        for (a, b) in lhs.iter_mut().zip(rhs) {
+            *a = a.add(b)?;
+        }
+        Ok(())
+    }
+
+    /// Subtracts `rhs` from `lhs` element-wise. The slices must have the same length.
+    fn sub_assign_slices(lhs: &mut [Self], rhs: &[Self]) -> Result<(), ZKError> {
+        check_slice_lengths(lhs, rhs)?;
+        for (a, b) in lhs.iter_mut().zip(rhs) {
+            *a = a.sub(b)?;
+        }
+        Ok(())
+    }
+
+    /// Multiplies `lhs` by `rhs` element-wise. The slices must have the same length.
+    fn mul_assign_slices(lhs: &mut [Self], rhs: &[Self]) -> Result<(), ZKError> {
+        check_slice_lengths(lhs, rhs)?;
+        for (a, b) in lhs.iter_mut().zip(rhs) {
+            *a = a.mul(b)?;
+        }
+        Ok(())
+    }
+
+    /// Multiplies every element of `values` by `scalar`.
+    fn scale_slice(values: &mut [Self], scalar: &Self) -> Result<(), ZKError> {
+        for value in values.iter_mut() {
+            *value = value.mul(scalar)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the consecutive powers 1, x, x^2, ..., x^(count - 1) of the element,
+    /// e.g. the powers of τ needed by a trusted setup.
+    fn powers(&self, count: usize) -> Result<Vec<Self>, ZKError> {
+        let mut powers = Vec::with_capacity(count);
+        let mut acc = Self::one(&self.params());
+        for _ in 0..count {
+            let next = acc.mul(self)?;
+            powers.push(acc);
+            acc = next;
+        }
+        Ok(powers)
+    }
+
+    /// Evaluates the polynomial with the given coefficients (lowest degree first) at `x`.
+    /// Backends may override this with a specialised loop.
+    fn evaluate_polynomial(coefficients: &[Self], x: &Self) -> Result<Self, ZKError> {
+        horner(coefficients, x)
+    }
+
+    /// Evaluates the polynomial with the given coefficients at each of the points.
+    /// Backends may override this to share per-polynomial work across the points.
+    fn evaluate_polynomial_batch(
+        coefficients: &[Self],
+        points: &[Self],
+    ) -> Result<Vec<Self>, ZKError> {
+        points
+            .iter()
+            .map(|x| Self::evaluate_polynomial(coefficients, x))
+            .collect()
+    }
+
+    /// Multiplies two polynomials given by their coefficients (lowest degree first).
+    /// Both inputs must be non-empty. Switches from the schoolbook algorithm to Karatsuba
+    /// for large operands. Backends may override this with a specialised loop.
+    fn multiply_polynomials(lhs: &[Self], rhs: &[Self]) -> Result<Vec<Self>, ZKError> {
+        let mut product = Vec::new();
+        Self::multiply_polynomials_into(lhs, rhs, &mut product)?;
+        Ok(product)
+    }
+
+    /// Like `multiply_polynomials`, but overwrites `out` with the product so that its
+    /// allocation can be reused across calls.
+    fn multiply_polynomials_into(
+        lhs: &[Self],
+        rhs: &[Self],
+        out: &mut Vec<Self>,
+    ) -> Result<(), ZKError> {
+        let ring = FieldRing::<Self>(lhs[0].params());
+        out.clear();
+        out.resize(lhs.len() + rhs.len() - 1, ring.zero());
+        multiply_accumulate(&ring, lhs, rhs, KARATSUBA_THRESHOLD, out)
+    }
+}
+
+// Evaluates a polynomial using Horner's method.
+fn horner<F: Field>(coefficients: &[F], x: &F) -> Result<F, ZKError> {
+    let mut result = F::zero(&x.params());
+    for coeff in coefficients.iter().rev() {
+        result = result.mul(x)?.add(coeff)?;
+    }
+    Ok(result)
+}
+
+// Minimum operand length for which polynomial multiplication switches from the
+// schoolbook loop to Karatsuba; below it the bookkeeping costs more than it saves.
+const KARATSUBA_THRESHOLD: usize = 64;
+
+// Coefficient arithmetic shared by the polynomial multiplication kernels, so the same
+// algorithms run on generic field elements and on raw Montgomery or Barrett values.
+trait CoefficientRing: Sync {
+    type Elem: Clone + Send + Sync;
+
+    fn zero(&self) -> Self::Elem;
+    fn add(&self, a: &Self::Elem, b: &Self::Elem) -> Result<Self::Elem, ZKError>;
+    fn sub(&self, a: &Self::Elem, b: &Self::Elem) -> Result<Self::Elem, ZKError>;
+    fn mul(&self, a: &Self::Elem, b: &Self::Elem) -> Result<Self::Elem, ZKError>;
+}
+
+// Any field backend, through its fallible trait methods.
+struct FieldRing<F: Field>(F::Params);
+
+impl<F: Field> CoefficientRing for FieldRing<F> {
+    type Elem = F;
+
+    fn zero(&self) -> F {
+        F::zero(&self.0)
+    }
+
+    fn add(&self, a: &F, b: &F) -> Result<F, ZKError> {
+        a.add(b)
+    }
+
+    fn sub(&self, a: &F, b: &F) -> Result<F, ZKError> {
+        a.sub(b)
+    }
+
+    fn mul(&self, a: &F, b: &F) -> Result<F, ZKError> {
+        a.mul(b)
+    }
+}
+
+// Values in Montgomery form, which are always reduced below the modulus.
+impl CoefficientRing for Montgomery {
+    type Elem = u64;
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> Result<u64, ZKError> {
+        Ok(Montgomery::add(self, *a, *b))
+    }
+
+    fn sub(&self, a: &u64, b: &u64) -> Result<u64, ZKError> {
+        Ok(if a >= b {
+            a - b
+        } else {
+            a + (self.modulus - b)
+        })
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> Result<u64, ZKError> {
+        Ok(Montgomery::mul(self, *a, *b))
+    }
+}
+
+impl CoefficientRing for FieldCtx {
+    type Elem = FieldElement;
+
+    fn zero(&self) -> FieldElement {
+        self.element(0)
+    }
+
+    fn add(&self, a: &FieldElement, b: &FieldElement) -> Result<FieldElement, ZKError> {
+        Ok(FieldCtx::add(self, a, b))
+    }
+
+    fn sub(&self, a: &FieldElement, b: &FieldElement) -> Result<FieldElement, ZKError> {
+        Ok(FieldCtx::sub(self, a, b))
+    }
+
+    fn mul(&self, a: &FieldElement, b: &FieldElement) -> Result<FieldElement, ZKError> {
+        Ok(FieldCtx::mul(self, a, b))
+    }
+}
+
+// Multiplies two non-empty polynomials, using Karatsuba once both operands have at
+// least `threshold` coefficients.
+fn multiply_in<R: CoefficientRing>(
+    ring: &R,
+    lhs: &[R::Elem],
+    rhs: &[R::Elem],
+    threshold: usize,
+) -> Result<Vec<R::Elem>, ZKError> {
+    let mut product = vec![ring.zero(); lhs.len() + rhs.len() - 1];
+    multiply_accumulate(ring, lhs, rhs, threshold, &mut product)?;
+    Ok(product)
+}
+
+// Adds the product of two non-empty polynomials into the front of `out`, which must
+// hold at least `lhs.len() + rhs.len() - 1` coefficients.
+fn multiply_accumulate<R: CoefficientRing>(
+    ring: &R,
+    lhs: &[R::Elem],
+    rhs: &[R::Elem],
+    threshold: usize,
+    out: &mut [R::Elem],
+) -> Result<(), ZKError> {
+    if lhs.len().min(rhs.len()) < threshold.max(2) {
+        return schoolbook_mul(ring, lhs, rhs, out);
+    }
+
+    let half = lhs.len().max(rhs.len()) / 2;
+
+    // Very unbalanced operands: cut the longer one into pieces as long as the shorter.
+    if lhs.len() <= half || rhs.len() <= half {
+        let (long, short) = if lhs.len() > rhs.len() {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        };
+        let chunks: Vec<&[R::Elem]> = long.chunks(short.len()).collect();
+        let partials =
+            parallel::try_map(&chunks, |chunk| multiply_in(ring, chunk, short, threshold))?;
+        for (k, partial) in partials.iter().enumerate() {
+            accumulate(ring, &mut out[k * short.len()..], partial)?;
+        }
+        return Ok(());
+    }
+
+    // (a0 + a1·x^h)(b0 + b1·x^h) = z0 + (z1 - z0 - z2)·x^h + z2·x^(2h),
+    // where z0 = a0·b0, z2 = a1·b1 and z1 = (a0 + a1)(b0 + b1).
+    let (a0, a1) = lhs.split_at(half);
+    let (b0, b1) = rhs.split_at(half);
+    let (z0, (z2, z1)) = parallel::join(
+        || multiply_in(ring, a0, b0, threshold),
+        || {
+            parallel::join(
+                || multiply_in(ring, a1, b1, threshold),
+                || {
+                    multiply_in(
+                        ring,
+                        &add_coefficients(ring, a0, a1)?,
+                        &add_coefficients(ring, b0, b1)?,
+                        threshold,
+                    )
+                },
+            )
+        },
+    );
+    let (z0, z2, mut z1) = (z0?, z2?, z1?);
+    for z in [&z0, &z2] {
+        for (c, t) in z1.iter_mut().zip(z) {
+            *c = ring.sub(c, t)?;
+        }
+    }
+
+    accumulate(ring, out, &z0)?;
+    accumulate(ring, &mut out[2 * half..], &z2)?;
+    // The middle term has degree below that of the product, so any extra
+    // coefficients of z1 beyond it are zero.
+    let product_len = lhs.len() + rhs.len() - 1;
+    accumulate(ring, &mut out[half..product_len], &z1)
+}
+
+// Adds the product of two polynomials into `out` with the quadratic schoolbook algorithm.
+fn schoolbook_mul<R: CoefficientRing>(
+    ring: &R,
+    lhs: &[R::Elem],
+    rhs: &[R::Elem],
+    out: &mut [R::Elem],
+) -> Result<(), ZKError> {
+    for (i, a) in lhs.iter().enumerate() {
+        for (j, b) in rhs.iter().enumerate() {
+            out[i + j] = ring.add(&out[i + j], &ring.mul(a, b)?)?;
+        }
+    }
+    Ok(())
+}
+
+// Adds two coefficient vectors of possibly different lengths.
+fn add_coefficients<R: CoefficientRing>(
+    ring: &R,
+    lhs: &[R::Elem],
+    rhs: &[R::Elem],
+) -> Result<Vec<R::Elem>, ZKError> {
+    let (long, short) = if lhs.len() >= rhs.len() {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    let mut sum = long.to_vec();
+    accumulate(ring, &mut sum, short)?;
+    Ok(sum)
+}
+
+// Adds `terms` into the front of `target`, ignoring terms past its end.
+fn accumulate<R: CoefficientRing>(
+    ring: &R,
+    target: &mut [R::Elem],
+    terms: &[R::Elem],
+) -> Result<(), ZKError> {
+    for (c, t) in target.iter_mut().zip(terms) {
+        *c = ring.add(c, t)?;
+    }
+    Ok(())
+}
+
+fn check_slice_lengths<F>(lhs: &[F], rhs: &[F]) -> Result<(), ZKError> {
+    if lhs.len() != rhs.len() {
+        return Err(ZKError::InvalidFieldElement(format!(
+            "Slices must have the same length, got {} and {}.",
+            lhs.len(),
+            rhs.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Precomputed powers of a fixed base, answering many exponentiations of that base
+/// cheaply.
+///
+/// The exponent is split into windows of `window` bits and the table stores
+/// base^(d · 2^(window · i)) for every window i and digit d, so an exponentiation only
+/// multiplies one table entry per window and needs no squarings.
+#[derive(Debug, Clone)]
+pub struct FixedBaseExp<F: Field = FieldElement> {
+    window: usize,
+    table: Vec<Vec<F>>,
+}
+
+impl<F: Field> FixedBaseExp<F> {
+    /// Precomputes the table for `base` using windows of `window` bits (between 1 and 16).
+    pub fn new(base: &F, window: usize) -> Result<Self, ZKError> {
+        if window == 0 || window > 16 {
+            return Err(ZKError::InvalidFieldElement(
+                "Fixed-base window must be between 1 and 16 bits.".into(),
+            ));
+        }
+
+        let num_windows = (u64::BITS as usize).div_ceil(window);
+        let mut table = Vec::with_capacity(num_windows);
+        let mut window_base = base.clone();
+        for _ in 0..num_windows {
+            let row = window_base.powers((1 << window) + 1)?;
+            // The extra entry is window_base^(2^window), the base of the next window.
+            window_base = row[1 << window].clone();
+            table.push(row[..1 << window].to_vec());
+        }
+
+        Ok(FixedBaseExp { window, table })
+    }
+
+    /// Exponentiate the fixed base by the provided exponent.
+    pub fn exp(&self, exponent: u64) -> Result<F, ZKError> {
+        let mask = (1u64 << self.window) - 1;
+        let mut result = self.table[0][(exponent & mask) as usize].clone();
+        for (i, row) in self.table.iter().enumerate().skip(1) {
+            let digit = exponent.checked_shr((i * self.window) as u32).unwrap_or(0) & mask;
+            if digit != 0 {
+                result = result.mul(&row[digit as usize])?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Exponentiate the fixed base by each of the provided exponents.
+    pub fn batch_exp(&self, exponents: &[u64]) -> Result<Vec<F>, ZKError> {
+        exponents
+            .iter()
+            .map(|&exponent| self.exp(exponent))
+            .collect()
+    }
+}
+
+/// Represents an element of the prime field with the given modulus.
+///
+/// Equality, hashing and ordering use the canonical value in [0, modulus), so unreduced
+/// representatives of the same element compare equal. Elements are ordered by modulus
+/// first, then by canonical value.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawFieldElement", into = "RawFieldElement")
+)]
 pub struct FieldElement {
     pub value: u64,
     pub modulus: u64,
 }
 
+// Unvalidated form of a field element, checked when deserializing. Elements are
+// serialized with their canonical value, so unreduced ones round-trip.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RawFieldElement {
+    value: u64,
+    modulus: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<FieldElement> for RawFieldElement {
+    fn from(fe: FieldElement) -> Self {
+        RawFieldElement {
+            value: fe.canonical_value(),
+            modulus: fe.modulus,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawFieldElement> for FieldElement {
+    type Error = ZKError;
+
+    fn try_from(raw: RawFieldElement) -> Result<Self, Self::Error> {
+        if raw.value >= raw.modulus {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "Value {} is not reduced modulo {}.",
+                raw.value, raw.modulus
+            )));
+        }
+        FieldElement::new(raw.value, raw.modulus)
+    }
+}
+
 impl FieldElement {
     /// Create a new field element with value and modulus.
     pub fn new(value: u64, modulus: u64) -> Result<Self, ZKError> {
         if modulus == 0 {
             return Err(ZKError::InvalidFieldElement(
-                "Modulus cannot be zero.".into(),
+                "Modulus cannot be zero.".into(),
+            ));
+        }
+        Ok(FieldElement { value, modulus })
+    }
+
+    /// Returns the additive identity modulo `modulus`.
+    pub fn zero(modulus: u64) -> Result<Self, ZKError> {
+        Self::new(0, modulus)
+    }
+
+    /// Returns the multiplicative identity modulo `modulus`.
+    pub fn one(modulus: u64) -> Result<Self, ZKError> {
+        let mut one = Self::zero(modulus)?;
+        one.value = 1 % modulus;
+        Ok(one)
+    }
+
+    /// Returns true if the element is zero.
+    pub fn is_zero(&self) -> bool {
+        self.value.is_multiple_of(self.modulus)
+    }
+
+    /// Returns true if the element is one.
+    pub fn is_one(&self) -> bool {
+        self.value % self.modulus == 1 % self.modulus
+    }
+
+    /// Create a new field element from a signed integer,
+    /// mapping negative values v to modulus - |v| (reduced).
+    pub fn from_i64(value: i64, modulus: u64) -> Result<Self, ZKError> {
+        if modulus == 0 {
+            return Err(ZKError::InvalidFieldElement(
+                "Modulus cannot be zero.".into(),
+            ));
+        }
+        Ok(<Self as Field>::from_i64(value, &modulus))
+    }
+
+    /// Parses a value in decimal or `0x`-prefixed hexadecimal as an element modulo `modulus`.
+    /// Values must be canonical, i.e. smaller than the modulus.
+    pub fn parse(value: &str, modulus: u64) -> Result<Self, ZKError> {
+        let value = parse_number(value)?;
+        if value >= modulus && modulus != 0 {
+            return Err(ZKError::FieldElementOutOfRange(format!(
+                "{} is not reduced modulo {}",
+                value, modulus
+            )));
+        }
+        Self::new(value, modulus)
+    }
+
+    /// Returns a value that displays the element in the given format.
+    pub fn display(&self, format: DisplayFormat) -> FormattedFieldElement<'_> {
+        FormattedFieldElement {
+            element: self,
+            format,
+        }
+    }
+
+    /// Create a new field element, additionally checking that the modulus is prime.
+    /// Composite moduli are accepted by `new`, but then `inv` fails for non-units.
+    pub fn new_checked(value: u64, modulus: u64) -> Result<Self, ZKError> {
+        <Self as Field>::validate_params(&modulus)?;
+        Self::new(value, modulus)
+    }
+
+    /// Samples a field element uniformly at random.
+    /// Uses rejection sampling on the bit length of the modulus, so the result is unbiased.
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R, modulus: u64) -> Result<Self, ZKError> {
+        if modulus == 0 {
+            return Err(ZKError::InvalidFieldElement(
+                "Modulus cannot be zero.".into(),
+            ));
+        }
+
+        // Mask covering every value below the modulus; each draw succeeds with probability > 1/2.
+        let mask = match modulus - 1 {
+            0 => 0,
+            max => u64::MAX >> max.leading_zeros(),
+        };
+        loop {
+            let candidate = rng.next_u64() & mask;
+            if candidate < modulus {
+                return FieldElement::new(candidate, modulus);
+            }
+        }
+    }
+
+    /// Add two field elements.
+    pub fn add(&self, other: &FieldElement) -> Result<Self, ZKError> {
+        self.checked_add(other).ok_or_else(|| {
+            ZKError::InvalidFieldElement("Moduli must be the same for addition.".into())
+        })
+    }
+
+    /// Subtract two field elements.
+    pub fn sub(&self, other: &FieldElement) -> Result<Self, ZKError> {
+        self.checked_sub(other).ok_or_else(|| {
+            ZKError::InvalidFieldElement("Moduli must be the same for subtraction.".into())
+        })
+    }
+
+    /// Multiply two field elements.
+    pub fn mul(&self, other: &FieldElement) -> Result<FieldElement, ZKError> {
+        self.checked_mul(other).ok_or_else(|| {
+            ZKError::InvalidFieldElement("Moduli must be the same for multiplication.".into())
+        })
+    }
+
+    /// Divide two field elements, i.e. multiply by the inverse of `other`.
+    pub fn div(&self, other: &FieldElement) -> Result<FieldElement, ZKError> {
+        self.mul(&other.inv()?)
+    }
+
+    /// Negate the field element.
+    pub fn neg(&self) -> FieldElement {
+        -self
+    }
+
+    /// Find the modular inverse of the field element.
+    pub fn inv(&self) -> Result<FieldElement, ZKError> {
+        self.checked_inv()
+            .ok_or_else(|| ZKError::InvalidFieldElement("Modular inverse does not exist.".into()))
+    }
+
+    /// Add two field elements, returning `None` if the moduli differ.
+    pub fn checked_add(&self, other: &FieldElement) -> Option<FieldElement> {
+        if self.modulus != other.modulus {
+            return None;
+        }
+
+        let sum = (self.value as u128 + other.value as u128) % self.modulus as u128;
+        Some(FieldElement {
+            value: sum as u64,
+            modulus: self.modulus,
+        })
+    }
+
+    /// Subtract two field elements, returning `None` if the moduli differ.
+    pub fn checked_sub(&self, other: &FieldElement) -> Option<FieldElement> {
+        if self.modulus != other.modulus {
+            return None;
+        }
+
+        // Ensure non-negative result by adding the modulus before subtracting.
+        let diff = (self.value as u128 + self.modulus as u128
+            - (other.value % self.modulus) as u128)
+            % self.modulus as u128;
+        Some(FieldElement {
+            value: diff as u64,
+            modulus: self.modulus,
+        })
+    }
+
+    /// Multiply two field elements, returning `None` if the moduli differ.
+    pub fn checked_mul(&self, other: &FieldElement) -> Option<FieldElement> {
+        if self.modulus != other.modulus {
+            return None;
+        }
+
+        let product = (self.value as u128 * other.value as u128) % self.modulus as u128;
+        Some(FieldElement {
+            value: product as u64,
+            modulus: self.modulus,
+        })
+    }
+
+    /// Divide two field elements, returning `None` if the moduli differ or `other`
+    /// is not invertible.
+    pub fn checked_div(&self, other: &FieldElement) -> Option<FieldElement> {
+        self.checked_mul(&other.checked_inv()?)
+    }
+
+    /// Find the modular inverse of the field element, returning `None` if it does not exist.
+    pub fn checked_inv(&self) -> Option<FieldElement> {
+        let v = self.value as i128;
+        let m = self.modulus as i128;
+
+        let (g, x, _) = Self::eegcd(v, m);
+        if g != 1 {
+            return None;
+        }
+
+        // Make sure the inverse is positive.
+        let x_pos = ((x % m) + m) % m;
+        Some(FieldElement {
+            value: x_pos as u64,
+            modulus: self.modulus,
+        })
+    }
+
+    /// Exponentiate the field element by the provided exponent.
+    /// Odd moduli use Montgomery multiplication for the square-and-multiply loop.
+    pub fn exp(&self, exponent: u64) -> Result<FieldElement, ZKError> {
+        if let Ok(mont) = Montgomery::new(self.modulus) {
+            let value = mont.from_montgomery(mont.exp(mont.to_montgomery(self.value), exponent));
+            return FieldElement::new(value, self.modulus);
+        }
+
+        let mut result = FieldElement::one(self.modulus)?;
+        let mut base = self.clone();
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp % 2 == 1 {
+                result = result.mul(&base)?;
+            }
+            base = base.mul(&base)?;
+            exp /= 2;
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the 2-adicity of the field, i.e. the largest s such that 2^s divides p - 1.
+    /// This bounds the size of radix-2 evaluation domains over the field.
+    pub fn two_adicity(modulus: u64) -> u32 {
+        match modulus {
+            0 | 1 => 0,
+            _ => (modulus - 1).trailing_zeros(),
+        }
+    }
+
+    /// Returns a primitive n-th root of unity, i.e. an element ω with ω^n = 1 and
+    /// ω^k ≠ 1 for 0 < k < n. Requires a prime modulus p with n dividing p - 1, and
+    /// fails with `CompositeModulus` otherwise, since a composite modulus may have no
+    /// such root and the search would not end in reasonable time.
+    /// Candidates are tried deterministically, so the same root is returned on every call.
+    pub fn root_of_unity(n: u64, modulus: u64) -> Result<FieldElement, ZKError> {
+        if modulus < 2 || n == 0 || !(modulus - 1).is_multiple_of(n) {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "No primitive {}-th root of unity modulo {}.",
+                n, modulus
+            )));
+        }
+        if !is_prime(modulus) {
+            return Err(ZKError::CompositeModulus(modulus));
+        }
+
+        // ω has exact order n iff ω^(n / q) ≠ 1 for every prime factor q of n.
+        let prime_factors = Self::prime_factors(n);
+        let cofactor = (modulus - 1) / n;
+        for candidate in 1..modulus {
+            let omega = FieldElement::new(candidate, modulus)?.exp(cofactor)?;
+            if prime_factors
+                .iter()
+                .all(|q| omega.exp(n / q).map(|x| x.value != 1).unwrap_or(false))
+            {
+                return Ok(omega);
+            }
+        }
+
+        Err(ZKError::InvalidFieldElement(format!(
+            "No primitive {}-th root of unity modulo {}.",
+            n, modulus
+        )))
+    }
+
+    // Distinct prime factors of n by trial division.
+    pub(crate) fn prime_factors(mut n: u64) -> Vec<u64> {
+        let mut factors = Vec::new();
+        let mut q = 2;
+        while q <= n / q {
+            if n.is_multiple_of(q) {
+                factors.push(q);
+                while n.is_multiple_of(q) {
+                    n /= q;
+                }
+            }
+            q += 1;
+        }
+        if n > 1 {
+            factors.push(n);
+        }
+        factors
+    }
+
+    /// Exponentiate the field element by a big integer exponent given as little-endian
+    /// 64-bit limbs, for exponents that do not fit in a u64 (e.g. orders of extension fields).
+    pub fn exp_limbs(&self, exponent: &[u64]) -> Result<FieldElement, ZKError> {
+        let mut result = FieldElement::one(self.modulus)?;
+        for limb in exponent.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.mul(&result)?;
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mul(self)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Exponentiate the field element by the canonical value of another field element,
+    /// e.g. a scalar from a different field.
+    pub fn exp_by_element(&self, exponent: &FieldElement) -> Result<FieldElement, ZKError> {
+        self.exp(exponent.value % exponent.modulus)
+    }
+
+    /// Computes the Legendre symbol (a / p) using Euler's criterion a^((p - 1) / 2):
+    /// 0 for zero, 1 for non-zero quadratic residues and -1 for non-residues.
+    /// Fails if the result is none of these, which means the modulus is not an odd prime.
+    pub fn legendre(&self) -> Result<i8, ZKError> {
+        let p = self.modulus;
+        if self.value.is_multiple_of(p) {
+            return Ok(0);
+        }
+        if p == 2 {
+            return Ok(1);
+        }
+
+        match self.exp((p - 1) / 2)?.value {
+            1 => Ok(1),
+            v if v == p - 1 => Ok(-1),
+            _ => Err(ZKError::InvalidFieldElement(
+                "Legendre symbol requires a prime modulus.".into(),
+            )),
+        }
+    }
+
+    /// Returns true if the element has a square root, counting zero as a residue.
+    pub fn is_quadratic_residue(&self) -> Result<bool, ZKError> {
+        Ok(self.legendre()? >= 0)
+    }
+
+    /// Computes a square root of the field element, returning `None` if the element is
+    /// not a quadratic residue. The modulus is assumed to be an odd prime; uses the
+    /// direct formula for p ≡ 3 mod 4 and Tonelli–Shanks otherwise.
+    pub fn sqrt(&self) -> Option<FieldElement> {
+        let p = self.modulus;
+        let a = FieldElement::new(self.value % p, p).ok()?;
+        if a.value == 0 || p == 2 {
+            return Some(a);
+        }
+
+        if a.legendre().ok()? != 1 {
+            return None;
+        }
+
+        let root = if p % 4 == 3 {
+            // (p + 1) / 4, written to avoid overflowing near u64::MAX.
+            a.exp(p / 4 + 1).ok()?
+        } else {
+            Self::tonelli_shanks(&a)?
+        };
+
+        // Guards against composite moduli, for which the above does not apply.
+        if root.mul(&root).ok()? == a {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    // Tonelli–Shanks for a quadratic residue `a`.
+    fn tonelli_shanks(a: &FieldElement) -> Option<FieldElement> {
+        let p = a.modulus;
+
+        // Write p - 1 = q * 2^s with q odd.
+        let mut q = p - 1;
+        let mut s = 0;
+        while q & 1 == 0 {
+            q /= 2;
+            s += 1;
+        }
+
+        // Find a quadratic non-residue z.
+        let z = (2..p)
+            .map(|z| FieldElement {
+                value: z,
+                modulus: p,
+            })
+            .find(|z| z.legendre().ok() == Some(-1))?;
+
+        let mut m = s;
+        let mut c = z.exp(q).ok()?;
+        let mut t = a.exp(q).ok()?;
+        let mut r = a.exp(q.div_ceil(2)).ok()?;
+
+        while t.value != 1 {
+            // Find the least i with t^(2^i) = 1.
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while t_pow.value != 1 {
+                t_pow = t_pow.mul(&t_pow).ok()?;
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+
+            let mut b = c.clone();
+            for _ in 0..(m - i - 1) {
+                b = b.mul(&b).ok()?;
+            }
+            m = i;
+            c = b.mul(&b).ok()?;
+            t = t.mul(&c).ok()?;
+            r = r.mul(&b).ok()?;
+        }
+
+        Some(r)
+    }
+
+    fn eegcd(a: i128, b: i128) -> (i128, i128, i128) {
+        if a == 0 {
+            (b, 0, 1)
+        } else {
+            let (g, x, y) = Self::eegcd(b % a, a);
+            (g, y - (b / a) * x, x)
+        }
+    }
+}
+
+/// Returns true if `n` is prime.
+/// Uses Miller–Rabin with the first twelve primes as bases, which is deterministic for all u64.
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // Write n - 1 = d * 2^s with d odd.
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mul_mod = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+
+    'witness: for a in BASES {
+        let mut x = 1;
+        let mut base = a;
+        let mut e = d;
+        while e > 0 {
+            if e & 1 == 1 {
+                x = mul_mod(x, base);
+            }
+            base = mul_mod(base, base);
+            e >>= 1;
+        }
+
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+impl Field for FieldElement {
+    type Params = u64;
+
+    fn params(&self) -> u64 {
+        self.modulus
+    }
+
+    fn zero(modulus: &u64) -> Self {
+        FieldElement {
+            value: 0,
+            modulus: *modulus,
+        }
+    }
+
+    fn one(modulus: &u64) -> Self {
+        FieldElement {
+            value: 1 % *modulus,
+            modulus: *modulus,
+        }
+    }
+
+    fn from_u64(value: u64, modulus: &u64) -> Self {
+        FieldElement {
+            value: value % *modulus,
+            modulus: *modulus,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        FieldElement::is_zero(self)
+    }
+
+    fn is_one(&self) -> bool {
+        FieldElement::is_one(self)
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, ZKError> {
+        FieldElement::add(self, other)
+    }
+
+    fn sub(&self, other: &Self) -> Result<Self, ZKError> {
+        FieldElement::sub(self, other)
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, ZKError> {
+        FieldElement::mul(self, other)
+    }
+
+    fn neg(&self) -> Self {
+        FieldElement::neg(self)
+    }
+
+    fn inv(&self) -> Result<Self, ZKError> {
+        FieldElement::inv(self)
+    }
+
+    fn div(&self, other: &Self) -> Result<Self, ZKError> {
+        FieldElement::div(self, other)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        FieldElement::checked_add(self, other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        FieldElement::checked_sub(self, other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        FieldElement::checked_mul(self, other)
+    }
+
+    fn checked_inv(&self) -> Option<Self> {
+        FieldElement::checked_inv(self)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        FieldElement::checked_div(self, other)
+    }
+
+    fn random<R: RngCore + ?Sized>(rng: &mut R, modulus: &u64) -> Result<Self, ZKError> {
+        FieldElement::random(rng, *modulus)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        (self.value % self.modulus).to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8], modulus: &u64) -> Result<Self, ZKError> {
+        let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+            ZKError::InvalidFieldElement("Field element encoding must be 8 bytes.".into())
+        })?;
+        let value = u64::from_le_bytes(bytes);
+        if value >= *modulus {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "Value {} is not reduced modulo {}.",
+                value, modulus
+            )));
+        }
+        FieldElement::new(value, *modulus)
+    }
+
+    fn validate_params(modulus: &u64) -> Result<(), ZKError> {
+        if !is_prime(*modulus) {
+            return Err(ZKError::CompositeModulus(*modulus));
+        }
+        Ok(())
+    }
+
+    fn exp(&self, exponent: u64) -> Result<Self, ZKError> {
+        FieldElement::exp(self, exponent)
+    }
+
+    fn root_of_unity(order: u64, modulus: &u64) -> Option<Self> {
+        if !order.is_power_of_two() || !is_prime(*modulus) {
+            return None;
+        }
+        FieldElement::root_of_unity(order, *modulus).ok()
+    }
+
+    // The slice operations check the moduli once up front and then run branch-light
+    // loops over the raw values, which the compiler can unroll and vectorize.
+    fn add_assign_slices(lhs: &mut [Self], rhs: &[Self]) -> Result<(), ZKError> {
+        let Some(modulus) = common_modulus(lhs, rhs)? else {
+            return Ok(());
+        };
+        for (a, b) in lhs.iter_mut().zip(rhs) {
+            let (x, y) = (reduce_once(a.value, modulus), reduce_once(b.value, modulus));
+            let (sum, overflow) = x.overflowing_add(y);
+            a.value = if overflow || sum >= modulus {
+                sum.wrapping_sub(modulus)
+            } else {
+                sum
+            };
+        }
+        Ok(())
+    }
+
+    fn sub_assign_slices(lhs: &mut [Self], rhs: &[Self]) -> Result<(), ZKError> {
+        let Some(modulus) = common_modulus(lhs, rhs)? else {
+            return Ok(());
+        };
+        for (a, b) in lhs.iter_mut().zip(rhs) {
+            let (x, y) = (reduce_once(a.value, modulus), reduce_once(b.value, modulus));
+            let (diff, borrow) = x.overflowing_sub(y);
+            a.value = if borrow {
+                diff.wrapping_add(modulus)
+            } else {
+                diff
+            };
+        }
+        Ok(())
+    }
+
+    fn mul_assign_slices(lhs: &mut [Self], rhs: &[Self]) -> Result<(), ZKError> {
+        let Some(modulus) = common_modulus(lhs, rhs)? else {
+            return Ok(());
+        };
+        let Ok(mont) = Montgomery::new(modulus) else {
+            for (a, b) in lhs.iter_mut().zip(rhs) {
+                *a = a.mul(b)?;
+            }
+            return Ok(());
+        };
+        // a · (b · R) · R^(-1) = a · b, and both inputs may be any u64.
+        for (a, b) in lhs.iter_mut().zip(rhs) {
+            a.value = mont.mul(a.value, mont.to_montgomery(b.value));
+        }
+        Ok(())
+    }
+
+    fn scale_slice(values: &mut [Self], scalar: &Self) -> Result<(), ZKError> {
+        let modulus = scalar.modulus;
+        if values.iter().any(|value| value.modulus != modulus) {
+            return Err(ZKError::InvalidFieldElement(
+                "Moduli must be the same for multiplication.".into(),
+            ));
+        }
+        let Ok(mont) = Montgomery::new(modulus) else {
+            for value in values.iter_mut() {
+                *value = value.mul(scalar)?;
+            }
+            return Ok(());
+        };
+        let scalar_mont = mont.to_montgomery(scalar.value);
+        for value in values.iter_mut() {
+            value.value = mont.mul(value.value, scalar_mont);
+        }
+        Ok(())
+    }
+
+    // Odd moduli evaluate in Montgomery form and even moduli with Barrett reduction,
+    // avoiding a division per step.
+    fn evaluate_polynomial(coefficients: &[Self], x: &Self) -> Result<Self, ZKError> {
+        let Ok(mont) = Montgomery::new(x.modulus) else {
+            let ctx = FieldCtx::new(x.modulus)?;
+            let mut acc = ctx.element(0);
+            for coeff in coefficients.iter().rev() {
+                acc = ctx.add(&ctx.mul(&acc, x), coeff);
+            }
+            return Ok(acc);
+        };
+
+        let x_mont = mont.to_montgomery(x.value);
+        let mut acc = 0;
+        for coeff in coefficients.iter().rev() {
+            acc = mont.add(mont.mul(acc, x_mont), mont.to_montgomery(coeff.value));
+        }
+        FieldElement::new(mont.from_montgomery(acc), x.modulus)
+    }
+
+    // Converts the coefficients into Montgomery form once for all points.
+    fn evaluate_polynomial_batch(
+        coefficients: &[Self],
+        points: &[Self],
+    ) -> Result<Vec<Self>, ZKError> {
+        let Some(first) = points.first() else {
+            return Ok(Vec::new());
+        };
+        let modulus = first.modulus;
+        let Ok(mont) = Montgomery::new(modulus) else {
+            return points
+                .iter()
+                .map(|x| Self::evaluate_polynomial(coefficients, x))
+                .collect();
+        };
+
+        let coefficients: Vec<u64> = coefficients
+            .iter()
+            .map(|c| mont.to_montgomery(c.value))
+            .collect();
+        points
+            .iter()
+            .map(|x| {
+                if x.modulus != modulus {
+                    return Err(ZKError::InvalidFieldElement(
+                        "Moduli must be the same for evaluation.".into(),
+                    ));
+                }
+                let x_mont = mont.to_montgomery(x.value);
+                let acc = coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, &c| mont.add(mont.mul(acc, x_mont), c));
+                FieldElement::new(mont.from_montgomery(acc), modulus)
+            })
+            .collect()
+    }
+
+    // Odd moduli multiply in Montgomery form and even moduli with Barrett reduction,
+    // avoiding a division per term.
+    fn multiply_polynomials_into(
+        lhs: &[Self],
+        rhs: &[Self],
+        out: &mut Vec<Self>,
+    ) -> Result<(), ZKError> {
+        let modulus = lhs[0].modulus;
+        out.clear();
+        let Ok(mont) = Montgomery::new(modulus) else {
+            let ctx = FieldCtx::new(modulus)?;
+            out.resize(lhs.len() + rhs.len() - 1, ctx.element(0));
+            return multiply_accumulate(&ctx, lhs, rhs, KARATSUBA_THRESHOLD, out);
+        };
+
+        let to_montgomery = |coefficients: &[Self]| -> Vec<u64> {
+            coefficients
+                .iter()
+                .map(|c| mont.to_montgomery(c.value))
+                .collect()
+        };
+        let product = multiply_in(
+            &mont,
+            &to_montgomery(lhs),
+            &to_montgomery(rhs),
+            KARATSUBA_THRESHOLD,
+        )?;
+        for c in product {
+            out.push(FieldElement::new(mont.from_montgomery(c), modulus)?);
+        }
+        Ok(())
+    }
+}
+
+/// Options for displaying a field element.
+/// The default prints the decimal value followed by the modulus, e.g. `5 mod 97`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayFormat {
+    /// Print numbers in hexadecimal with a `0x` prefix instead of decimal.
+    pub hex: bool,
+    /// Append ` mod <modulus>` after the value.
+    pub with_modulus: bool,
+}
+
+impl Default for DisplayFormat {
+    fn default() -> Self {
+        DisplayFormat {
+            hex: false,
+            with_modulus: true,
+        }
+    }
+}
+
+/// A field element paired with the format it is displayed in, see `FieldElement::display`.
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedFieldElement<'a> {
+    element: &'a FieldElement,
+    format: DisplayFormat,
+}
+
+impl std::fmt::Display for FormattedFieldElement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let write_number = |f: &mut std::fmt::Formatter<'_>, n: u64| {
+            if self.format.hex {
+                write!(f, "{:#x}", n)
+            } else {
+                write!(f, "{}", n)
+            }
+        };
+
+        write_number(f, self.element.canonical_value())?;
+        if self.format.with_modulus {
+            write!(f, " mod ")?;
+            write_number(f, self.element.modulus)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for FieldElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(DisplayFormat::default()))
+    }
+}
+
+/// Parses the output of `Display`: a value and a modulus separated by `mod`,
+/// each in decimal or `0x`-prefixed hexadecimal, e.g. `5 mod 97` or `0x5 mod 0x61`.
+impl std::str::FromStr for FieldElement {
+    type Err = ZKError;
+
+    fn from_str(s: &str) -> Result<Self, ZKError> {
+        let (value, modulus) = s.split_once(" mod ").ok_or_else(|| {
+            ZKError::MalformedFieldElement(format!("expected `<value> mod <modulus>`, got {:?}", s))
+        })?;
+        FieldElement::parse(value, parse_number(modulus)?)
+    }
+}
+
+// Parses a decimal or `0x`-prefixed hexadecimal u64, surrounded by optional whitespace.
+fn parse_number(s: &str) -> Result<u64, ZKError> {
+    let trimmed = s.trim();
+    let (digits, radix) = match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        Some(hex) => (hex, 16),
+        None => (trimmed, 10),
+    };
+
+    u64::from_str_radix(digits, radix).map_err(|e| match e.kind() {
+        std::num::IntErrorKind::PosOverflow => {
+            ZKError::FieldElementOutOfRange(format!("{:?} does not fit in 64 bits", trimmed))
+        }
+        _ => ZKError::MalformedFieldElement(format!("{:?} is not a number", trimmed)),
+    })
+}
+
+// Returns the shared modulus of two equally long slices, or `None` if they are empty.
+fn common_modulus(lhs: &[FieldElement], rhs: &[FieldElement]) -> Result<Option<u64>, ZKError> {
+    check_slice_lengths(lhs, rhs)?;
+    let Some(first) = lhs.first() else {
+        return Ok(None);
+    };
+    let modulus = first.modulus;
+    if lhs.iter().chain(rhs).any(|e| e.modulus != modulus) {
+        return Err(ZKError::InvalidFieldElement(
+            "Moduli must be the same for slice operations.".into(),
+        ));
+    }
+    Ok(Some(modulus))
+}
+
+// Brings a possibly unreduced value into [0, modulus); the division is rarely taken.
+fn reduce_once(value: u64, modulus: u64) -> u64 {
+    if value >= modulus {
+        value % modulus
+    } else {
+        value
+    }
+}
+
+// Implements a binary operator for all owned/borrowed combinations of field elements
+// by delegating to the fallible method, panicking on a modulus mismatch.
+macro_rules! impl_binary_op {
+    ($op_trait:ident, $op_fn:ident, $assign_trait:ident, $assign_fn:ident, $method:ident) => {
+        impl std::ops::$op_trait<&FieldElement> for &FieldElement {
+            type Output = FieldElement;
+
+            fn $op_fn(self, rhs: &FieldElement) -> FieldElement {
+                FieldElement::$method(self, rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl std::ops::$op_trait<FieldElement> for &FieldElement {
+            type Output = FieldElement;
+
+            fn $op_fn(self, rhs: FieldElement) -> FieldElement {
+                std::ops::$op_trait::$op_fn(self, &rhs)
+            }
+        }
+
+        impl std::ops::$op_trait<&FieldElement> for FieldElement {
+            type Output = FieldElement;
+
+            fn $op_fn(self, rhs: &FieldElement) -> FieldElement {
+                std::ops::$op_trait::$op_fn(&self, rhs)
+            }
+        }
+
+        impl std::ops::$op_trait<FieldElement> for FieldElement {
+            type Output = FieldElement;
+
+            fn $op_fn(self, rhs: FieldElement) -> FieldElement {
+                std::ops::$op_trait::$op_fn(&self, &rhs)
+            }
+        }
+
+        impl std::ops::$assign_trait<&FieldElement> for FieldElement {
+            fn $assign_fn(&mut self, rhs: &FieldElement) {
+                *self = std::ops::$op_trait::$op_fn(&*self, rhs);
+            }
+        }
+
+        impl std::ops::$assign_trait<FieldElement> for FieldElement {
+            fn $assign_fn(&mut self, rhs: FieldElement) {
+                *self = std::ops::$op_trait::$op_fn(&*self, &rhs);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign, add);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign, sub);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign, mul);
+
+impl std::ops::Neg for &FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        let value = (self.modulus - self.value % self.modulus) % self.modulus;
+        FieldElement {
+            value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl std::ops::Neg for FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        -&self
+    }
+}
+
+impl FieldElement {
+    // The value reduced into [0, modulus), tolerating a zero modulus built by hand.
+    fn canonical_value(&self) -> u64 {
+        self.value.checked_rem(self.modulus).unwrap_or(self.value)
+    }
+}
+
+impl PartialEq for FieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.modulus == other.modulus && self.canonical_value() == other.canonical_value()
+    }
+}
+
+impl Eq for FieldElement {}
+
+impl std::hash::Hash for FieldElement {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.modulus.hash(state);
+        self.canonical_value().hash(state);
+    }
+}
+
+impl PartialOrd for FieldElement {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FieldElement {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.modulus
+            .cmp(&other.modulus)
+            .then_with(|| self.canonical_value().cmp(&other.canonical_value()))
+    }
+}
+
+/// Precomputed constants for Montgomery multiplication modulo an odd modulus `m`,
+/// using `R = 2^64`.
+///
+/// A value `a` is represented in Montgomery form as `aR mod m`. Multiplying two
+/// such values only needs a Montgomery reduction (shifts and multiplications),
+/// so chains of multiplications avoid the division performed by `%`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Montgomery {
+    pub modulus: u64,
+    // -m^(-1) mod 2^64.
+    m_prime: u64,
+    // R^2 mod m, used to convert values into Montgomery form.
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Precomputes the Montgomery constants for the given modulus.
+    /// The modulus must be odd (and greater than one).
+    pub fn new(modulus: u64) -> Result<Self, ZKError> {
+        if modulus < 3 || modulus & 1 == 0 {
+            return Err(ZKError::InvalidFieldElement(
+                "Montgomery form requires an odd modulus greater than one.".into(),
             ));
         }
-        Ok(FieldElement { value, modulus })
-    }
 
-    /// Add two field elements.
-    pub fn add(&self, other: &FieldElement) -> Result<Self, ZKError> {
-        if self.modulus != other.modulus {
-            return Err(ZKError::InvalidFieldElement(
-                "Moduli must be the same for addition.".into(),
-            ));
+        // Newton iteration for m^(-1) mod 2^64; each step doubles the number of correct bits.
+        let mut inv: u64 = 1;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(modulus.wrapping_mul(inv)));
         }
 
-        FieldElement::new((self.value + other.value) % self.modulus, self.modulus)
+        let r = (1u128 << 64) % modulus as u128;
+        let r2 = (r * r) % modulus as u128;
+
+        Ok(Montgomery {
+            modulus,
+            m_prime: inv.wrapping_neg(),
+            r2: r2 as u64,
+        })
     }
 
-    /// Subtract two field elements.
-    pub fn sub(&self, other: &FieldElement) -> Result<Self, ZKError> {
-        if self.modulus != other.modulus {
-            return Err(ZKError::InvalidFieldElement(
-                "Moduli must be the same for subtraction.".into(),
-            ));
+    /// Converts a value into Montgomery form.
+    pub fn to_montgomery(&self, value: u64) -> u64 {
+        self.reduce(value as u128 * self.r2 as u128)
+    }
+
+    /// Converts a value out of Montgomery form.
+    pub fn from_montgomery(&self, value: u64) -> u64 {
+        self.reduce(value as u128)
+    }
+
+    /// Multiplies two values in Montgomery form.
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Adds two values in Montgomery form.
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let sum = a as u128 + b as u128;
+        if sum >= self.modulus as u128 {
+            (sum - self.modulus as u128) as u64
+        } else {
+            sum as u64
         }
+    }
 
-        // Ensure non-negative result by adding the modulus before subtracting.
-        let diff = (self.value + self.modulus - other.value) % self.modulus;
-        FieldElement::new(diff, self.modulus)
+    /// Exponentiates a value in Montgomery form.
+    pub fn exp(&self, base: u64, exponent: u64) -> u64 {
+        let mut result = self.to_montgomery(1);
+        let mut base = base;
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+
+        result
     }
 
-    /// Multiply two field elements.
-    pub fn mul(&self, other: &FieldElement) -> Result<FieldElement, ZKError> {
-        if self.modulus != other.modulus {
-            return Err(ZKError::InvalidFieldElement(
-                "Moduli must be the same for multiplication.".into(),
-            ));
+    // Montgomery reduction: returns t * R^(-1) mod m for t < m * R.
+    fn reduce(&self, t: u128) -> u64 {
+        let t_lo = t as u64;
+        let k = t_lo.wrapping_mul(self.m_prime);
+        let km = k as u128 * self.modulus as u128;
+
+        // The low 64 bits of t + km are zero by construction, so only the carry
+        // out of the low half needs to be propagated.
+        let carry = (t_lo != 0) as u128;
+        let u = (t >> 64) + (km >> 64) + carry;
+
+        if u >= self.modulus as u128 {
+            (u - self.modulus as u128) as u64
+        } else {
+            u as u64
         }
-        FieldElement::new((self.value * other.value) % self.modulus, self.modulus)
     }
+}
 
-    /// Find the modular inverse of the field element.
-    pub fn inv(&self) -> Result<FieldElement, ZKError> {
-        let v = self.value as i64;
-        let m = self.modulus as i64;
+/// Precomputed Barrett constants for reducing modulo any nonzero modulus `m`.
+///
+/// Reduction of a 128-bit value `t` estimates `t / m` as `(t · μ) >> 128` with
+/// `μ = ⌊(2^128 - 1) / m⌋`, which is off by at most two, so the `%` operator (a slow
+/// 128-bit division) is replaced by a few multiplications and conditional subtractions.
+/// Unlike `Montgomery`, values stay in their ordinary form and even moduli are supported.
+/// Build one context per modulus and pass it to hot loops; the operations assume the
+/// inputs belong to the context's modulus and do not check it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldCtx {
+    pub modulus: u64,
+    // ⌊(2^128 - 1) / m⌋.
+    mu: u128,
+}
 
-        let (g, x, _) = Self::eegcd(v, m);
-        if g != 1 {
+impl FieldCtx {
+    /// Precomputes the Barrett constant for the given modulus.
+    pub fn new(modulus: u64) -> Result<Self, ZKError> {
+        if modulus == 0 {
             return Err(ZKError::InvalidFieldElement(
-                "Modular inverse does not exist.".into(),
+                "Modulus cannot be zero.".into(),
             ));
         }
-
-        // Make sure the inverse is positive.
-        let x_pos = ((x % m) + m) % m;
-        FieldElement::new(x_pos as u64, self.modulus)
+        Ok(FieldCtx {
+            modulus,
+            mu: u128::MAX / modulus as u128,
+        })
     }
 
-    /// Exponentiate the field element by the provided exponent.
-    pub fn exp(&self, exponent: u64) -> Result<FieldElement, ZKError> {
-        let mut result = FieldElement::new(1, self.modulus)?;
-        let mut base = self.clone();
-        let mut exp = exponent;
+    /// Reduces a 128-bit value modulo m.
+    pub fn reduce(&self, t: u128) -> u64 {
+        let m = self.modulus as u128;
+        let q = mul_hi(t, self.mu);
+        let mut r = t - q * m;
+        while r >= m {
+            r -= m;
+        }
+        r as u64
+    }
 
-        while exp > 0 {
-            if exp % 2 == 1 {
-                result = result.mul(&base)?;
-            }
-            base = base.mul(&base)?;
-            exp /= 2;
+    /// Returns the field element with the given value, reduced modulo m.
+    pub fn element(&self, value: u64) -> FieldElement {
+        FieldElement {
+            value: self.reduce(value as u128),
+            modulus: self.modulus,
         }
+    }
 
-        Ok(result)
+    /// Add two field elements.
+    pub fn add(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        debug_assert!(a.modulus == self.modulus && b.modulus == self.modulus);
+        self.wrap(self.reduce(a.value as u128 + b.value as u128))
     }
 
-    fn eegcd(a: i64, b: i64) -> (i64, i64, i64) {
-        if a == 0 {
-            (b, 0, 1)
-        } else {
-            let (g, x, y) = Self::eegcd(b % a, a);
-            (g, y - (b / a) * x, x)
+    /// Subtract two field elements.
+    pub fn sub(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        debug_assert!(a.modulus == self.modulus && b.modulus == self.modulus);
+        let b = self.reduce(b.value as u128) as u128;
+        self.wrap(self.reduce(a.value as u128 + self.modulus as u128 - b))
+    }
+
+    /// Multiply two field elements.
+    pub fn mul(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        debug_assert!(a.modulus == self.modulus && b.modulus == self.modulus);
+        self.wrap(self.reduce(a.value as u128 * b.value as u128))
+    }
+
+    fn wrap(&self, value: u64) -> FieldElement {
+        FieldElement {
+            value,
+            modulus: self.modulus,
         }
     }
 }
 
+// High 128 bits of the 256-bit product a · b.
+fn mul_hi(a: u128, b: u128) -> u128 {
+    let (a_lo, a_hi) = (a as u64 as u128, a >> 64);
+    let (b_lo, b_hi) = (b as u64 as u128, b >> 64);
+
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+
+    // The middle column collects at most three 64-bit values, so it cannot overflow.
+    let mid = (lo_lo >> 64) + (lo_hi as u64 as u128) + (hi_lo as u64 as u128);
+    hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,4 +1778,631 @@ mod tests {
         let a_exp = a.exp(3).unwrap();
         assert_eq!(a_exp.value, 6);
     }
+
+    #[test]
+    fn test_montgomery_round_trip() {
+        let mont = Montgomery::new(97).unwrap();
+        for value in 0..97 {
+            assert_eq!(mont.from_montgomery(mont.to_montgomery(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_montgomery_mul() {
+        // Largest 64-bit prime, where intermediate sums overflow 128 bits without care.
+        let modulus = 18446744073709551557;
+        let mont = Montgomery::new(modulus).unwrap();
+        let a = FieldElement::new(modulus - 2, modulus).unwrap();
+        let b = FieldElement::new(modulus - 3, modulus).unwrap();
+
+        let product = mont
+            .from_montgomery(mont.mul(mont.to_montgomery(a.value), mont.to_montgomery(b.value)));
+        assert_eq!(product, a.mul(&b).unwrap().value);
+        assert_eq!(product, 6);
+    }
+
+    #[test]
+    fn test_montgomery_even_modulus() {
+        assert!(Montgomery::new(16).is_err());
+        // Exponentiation still works for even moduli through the plain path.
+        let a = FieldElement::new(3, 16).unwrap();
+        assert_eq!(a.exp(2).unwrap().value, 9);
+    }
+
+    #[test]
+    fn test_exp_large_modulus() {
+        // Fermat's little theorem: a^(p - 1) = 1 mod p.
+        let modulus = (1 << 61) - 1;
+        let a = FieldElement::new(123456789, modulus).unwrap();
+        assert_eq!(a.exp(modulus - 1).unwrap().value, 1);
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = FieldElement::new(3, 7).unwrap();
+        let b = FieldElement::new(4, 7).unwrap();
+
+        assert_eq!((&a + &b).value, 0);
+        assert_eq!((&a - &b).value, 6);
+        assert_eq!((&a * &b).value, 5);
+        assert_eq!((-&a).value, 4);
+        assert_eq!((a.clone() + b.clone()).value, 0);
+        assert_eq!((-FieldElement::new(0, 7).unwrap()).value, 0);
+
+        let mut c = a.clone();
+        c += &b;
+        assert_eq!(c.value, 0);
+        c -= &b;
+        assert_eq!(c, a);
+        c *= b;
+        assert_eq!(c.value, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Moduli must be the same for addition.")]
+    fn test_operator_modulus_mismatch() {
+        let a = FieldElement::new(3, 7).unwrap();
+        let b = FieldElement::new(4, 11).unwrap();
+        let _ = a + b;
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // 97 ≡ 1 mod 4 exercises Tonelli–Shanks, 103 ≡ 3 mod 4 the direct formula.
+        for modulus in [97, 103] {
+            let mut residues = 0;
+            for value in 0..modulus {
+                let a = FieldElement::new(value, modulus).unwrap();
+                match a.sqrt() {
+                    Some(root) => {
+                        assert_eq!(root.mul(&root).unwrap(), a);
+                        residues += 1;
+                    }
+                    None => assert_ne!(a.exp((modulus - 1) / 2).unwrap().value, 1),
+                }
+            }
+            // Zero plus (p - 1) / 2 non-zero squares.
+            assert_eq!(residues, (modulus - 1) / 2 + 1);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_large_modulus() {
+        // 2^64 - 59 has p - 1 = 4q, while 2^64 - 2^32 + 1 has p - 1 divisible by 2^32.
+        for modulus in [18446744073709551557, 18446744069414584321] {
+            let a = FieldElement::new(123456789, modulus).unwrap();
+            let square = a.mul(&a).unwrap();
+            let root = square.sqrt().unwrap();
+            assert!(root == a || root.add(&a).unwrap().value == 0);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_non_residue() {
+        // 5 is not a square modulo 7.
+        let a = FieldElement::new(5, 7).unwrap();
+        assert_eq!(a.sqrt(), None);
+    }
+
+    #[test]
+    fn test_random() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let modulus = 7;
+        let mut counts = [0usize; 7];
+        for _ in 0..7000 {
+            let a = FieldElement::random(&mut rng, modulus).unwrap();
+            assert_eq!(a.modulus, modulus);
+            counts[a.value as usize] += 1;
+        }
+        // Every residue is hit roughly 1000 times.
+        assert!(counts.iter().all(|&c| (850..1150).contains(&c)));
+
+        for modulus in [1, 2, 97, 18446744073709551557, u64::MAX] {
+            let a = FieldElement::random(&mut rng, modulus).unwrap();
+            assert!(a.value < modulus);
+        }
+        assert!(FieldElement::random(&mut rng, 0).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let a = FieldElement::new(3, 7).unwrap();
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, r#"{"value":3,"modulus":7}"#);
+        assert_eq!(serde_json::from_str::<FieldElement>(&json).unwrap(), a);
+
+        // Unreduced values and a zero modulus are rejected.
+        assert!(serde_json::from_str::<FieldElement>(r#"{"value":7,"modulus":7}"#).is_err());
+        assert!(serde_json::from_str::<FieldElement>(r#"{"value":0,"modulus":0}"#).is_err());
+
+        // An unreduced element is written with its canonical value.
+        let unreduced = FieldElement::new(10, 7).unwrap();
+        let json = serde_json::to_string(&unreduced).unwrap();
+        assert_eq!(json, r#"{"value":3,"modulus":7}"#);
+        assert_eq!(
+            serde_json::from_str::<FieldElement>(&json).unwrap(),
+            unreduced
+        );
+    }
+
+    #[test]
+    fn test_batch_inv() {
+        let elements: Vec<FieldElement> =
+            (1..97).map(|v| FieldElement::new(v, 97).unwrap()).collect();
+        let inverses = FieldElement::batch_inv(&elements).unwrap();
+        for (element, inverse) in elements.iter().zip(&inverses) {
+            assert_eq!(inverse, &element.inv().unwrap());
+        }
+
+        assert!(FieldElement::batch_inv(&[]).unwrap().is_empty());
+        // Zero has no inverse, and neither does the batch containing it.
+        let with_zero = [
+            FieldElement::new(3, 7).unwrap(),
+            FieldElement::new(0, 7).unwrap(),
+        ];
+        assert!(FieldElement::batch_inv(&with_zero).is_err());
+    }
+
+    #[test]
+    fn test_legendre() {
+        // Squares modulo 7 are 1, 2 and 4.
+        let symbols: Vec<i8> = (0..7)
+            .map(|v| FieldElement::new(v, 7).unwrap().legendre().unwrap())
+            .collect();
+        assert_eq!(symbols, vec![0, 1, 1, -1, 1, -1, -1]);
+
+        assert!(FieldElement::new(0, 7)
+            .unwrap()
+            .is_quadratic_residue()
+            .unwrap());
+        assert!(!FieldElement::new(3, 7)
+            .unwrap()
+            .is_quadratic_residue()
+            .unwrap());
+
+        // 2^((15 - 1) / 2) = 8 mod 15, which no prime modulus can produce.
+        assert!(FieldElement::new(2, 15).unwrap().legendre().is_err());
+    }
+
+    #[test]
+    fn test_two_adicity() {
+        assert_eq!(FieldElement::two_adicity(97), 5);
+        assert_eq!(FieldElement::two_adicity(103), 1);
+        assert_eq!(FieldElement::two_adicity(18446744069414584321), 32);
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        for n in [1, 2, 4, 8, 16, 32, 3, 6, 12, 48, 96] {
+            let omega = FieldElement::root_of_unity(n, 97).unwrap();
+            assert_eq!(omega.exp(n).unwrap().value, 1);
+            for k in 1..n {
+                assert_ne!(omega.exp(k).unwrap().value, 1);
+            }
+        }
+
+        let goldilocks = 18446744069414584321;
+        let omega = FieldElement::root_of_unity(1 << 32, goldilocks).unwrap();
+        assert_eq!(omega.exp(1 << 32).unwrap().value, 1);
+        assert_ne!(omega.exp(1 << 31).unwrap().value, 1);
+
+        // 64 does not divide 96.
+        assert!(FieldElement::root_of_unity(64, 97).is_err());
+        assert!(FieldElement::root_of_unity(0, 97).is_err());
+
+        // 1000000007 · 1000000009 is composite and rejected before any search.
+        assert!(matches!(
+            FieldElement::root_of_unity(2, 1000000016000000063),
+            Err(ZKError::CompositeModulus(1000000016000000063))
+        ));
+    }
+
+    #[test]
+    fn test_neg() {
+        let a = FieldElement::new(3, 7).unwrap();
+        assert_eq!(a.neg().value, 4);
+        assert_eq!(a.add(&a.neg()).unwrap().value, 0);
+        assert_eq!(FieldElement::new(0, 7).unwrap().neg().value, 0);
+    }
+
+    #[test]
+    fn test_inv_large_modulus() {
+        // Inversion via the extended Euclidean algorithm matches a^(p - 2).
+        let modulus = 18446744073709551557;
+        let a = FieldElement::new(modulus - 12345, modulus).unwrap();
+        let a_inv = a.inv().unwrap();
+        assert_eq!(a_inv, a.exp(modulus - 2).unwrap());
+        assert_eq!(a.mul(&a_inv).unwrap().value, 1);
+    }
+
+    #[test]
+    fn test_exp_limbs() {
+        let a = FieldElement::new(3, 97).unwrap();
+        assert_eq!(a.exp_limbs(&[]).unwrap().value, 1);
+        assert_eq!(a.exp_limbs(&[5]).unwrap(), a.exp(5).unwrap());
+
+        // 2^64 + 3 = 96 q + r reduces to a^r, since a^96 = 1.
+        let r = ((1u128 << 64) + 3) % 96;
+        assert_eq!(a.exp_limbs(&[3, 1]).unwrap(), a.exp(r as u64).unwrap());
+
+        let e = FieldElement::new(10, 13).unwrap();
+        assert_eq!(a.exp_by_element(&e).unwrap(), a.exp(10).unwrap());
+    }
+
+    #[test]
+    fn test_field_trait_bytes() {
+        let a = FieldElement::new(96, 97).unwrap();
+        let bytes = Field::to_bytes(&a);
+        assert_eq!(<FieldElement as Field>::from_bytes(&bytes, &97).unwrap(), a);
+        assert!(<FieldElement as Field>::from_bytes(&bytes, &13).is_err());
+        assert!(<FieldElement as Field>::from_bytes(&bytes[..4], &97).is_err());
+    }
+
+    #[test]
+    fn test_field_trait_constants() {
+        let zero = <FieldElement as Field>::zero(&97);
+        let one = <FieldElement as Field>::one(&97);
+        assert!(Field::is_zero(&zero));
+        assert_eq!(one.value, 1);
+        assert_eq!(<FieldElement as Field>::from_u64(100, &97).value, 3);
+        assert_eq!(
+            Field::exp(&one.add(&one).unwrap(), 10).unwrap().value,
+            1024 % 97
+        );
+    }
+
+    #[test]
+    fn test_is_prime() {
+        for p in [2, 3, 97, 103, (1u64 << 61) - 1, 18446744073709551557] {
+            assert!(is_prime(p), "{} is prime", p);
+        }
+        // Includes a Carmichael number and strong pseudoprimes to small bases.
+        for n in [0, 1, 4, 561, 3215031751, 3825123056546413051, u64::MAX] {
+            assert!(!is_prime(n), "{} is composite", n);
+        }
+    }
+
+    #[test]
+    fn test_new_checked() {
+        assert!(FieldElement::new_checked(5, 97).is_ok());
+        assert!(matches!(
+            FieldElement::new_checked(5, 91),
+            Err(ZKError::CompositeModulus(91))
+        ));
+        // The unchecked constructor still accepts composite moduli.
+        assert!(FieldElement::new(5, 91).is_ok());
+    }
+
+    #[test]
+    fn test_div_and_checked_ops() {
+        let a = FieldElement::new(10, 97).unwrap();
+        let b = FieldElement::new(7, 97).unwrap();
+        let quotient = a.div(&b).unwrap();
+        assert_eq!(quotient.mul(&b).unwrap(), a);
+        assert!(a.div(&FieldElement::new(0, 97).unwrap()).is_err());
+
+        assert_eq!(a.checked_add(&b), Some(FieldElement::new(17, 97).unwrap()));
+        assert_eq!(a.checked_sub(&b), Some(FieldElement::new(3, 97).unwrap()));
+        assert_eq!(a.checked_mul(&b), Some(FieldElement::new(70, 97).unwrap()));
+        assert_eq!(a.checked_div(&b), Some(quotient));
+        assert_eq!(FieldElement::new(0, 97).unwrap().checked_inv(), None);
+
+        let other = FieldElement::new(7, 101).unwrap();
+        assert_eq!(a.checked_add(&other), None);
+        assert_eq!(a.checked_sub(&other), None);
+        assert_eq!(a.checked_mul(&other), None);
+        assert_eq!(a.checked_div(&other), None);
+    }
+
+    #[test]
+    fn test_from_i64() {
+        assert_eq!(
+            FieldElement::from_i64(-1, 97).unwrap(),
+            FieldElement::new(96, 97).unwrap()
+        );
+        assert_eq!(FieldElement::from_i64(-195, 97).unwrap().value, 96);
+        assert_eq!(FieldElement::from_i64(195, 97).unwrap().value, 1);
+        assert_eq!(FieldElement::from_i64(-97, 97).unwrap().value, 0);
+
+        // i64::MIN + i64::MAX = -1.
+        let min = FieldElement::from_i64(i64::MIN, 97).unwrap();
+        let max = FieldElement::from_i64(i64::MAX, 97).unwrap();
+        assert_eq!(min.add(&max).unwrap().value, 96);
+        assert!(FieldElement::from_i64(-1, 0).is_err());
+    }
+
+    #[test]
+    fn test_zero_and_one() {
+        let zero = FieldElement::zero(97).unwrap();
+        let one = FieldElement::one(97).unwrap();
+        assert!(zero.is_zero() && !zero.is_one());
+        assert!(one.is_one() && !one.is_zero());
+        // Unreduced representatives are recognised too.
+        assert!(FieldElement::new(97, 97).unwrap().is_zero());
+        assert!(FieldElement::new(98, 97).unwrap().is_one());
+        // In the trivial ring modulo 1, zero and one coincide.
+        assert!(FieldElement::one(1).unwrap().is_zero());
+        assert!(FieldElement::zero(0).is_err());
+    }
+
+    #[test]
+    fn test_powers() {
+        let x = FieldElement::new(5, 97).unwrap();
+        let powers = x.powers(4).unwrap();
+        let values: Vec<u64> = powers.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![1, 5, 25, 28]);
+        assert!(x.powers(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fixed_base_exp() {
+        let modulus = 18446744073709551557;
+        let base = FieldElement::new(123456789, modulus).unwrap();
+        let exponents = [0, 1, 2, 255, 256, 1 << 40, u64::MAX - 1, u64::MAX];
+
+        for window in [1, 3, 4, 8, 16] {
+            let fixed = FixedBaseExp::new(&base, window).unwrap();
+            let expected: Vec<FieldElement> =
+                exponents.iter().map(|&e| base.exp(e).unwrap()).collect();
+            assert_eq!(fixed.batch_exp(&exponents).unwrap(), expected);
+        }
+
+        assert!(FixedBaseExp::new(&base, 0).is_err());
+        assert!(FixedBaseExp::new(&base, 17).is_err());
+    }
+
+    #[test]
+    fn test_slice_ops() {
+        // Odd and even moduli, with a few unreduced values.
+        for modulus in [97, 18446744073709551557, 1 << 40] {
+            let values = [0, 1, 5, modulus - 1, modulus, u64::MAX];
+            let lhs: Vec<FieldElement> = values
+                .iter()
+                .map(|&v| FieldElement { value: v, modulus })
+                .collect();
+            let rhs: Vec<FieldElement> = lhs.iter().rev().cloned().collect();
+            let scalar = FieldElement::new(modulus - 3, modulus).unwrap();
+
+            let expected: Vec<(FieldElement, FieldElement, FieldElement, FieldElement)> = lhs
+                .iter()
+                .zip(&rhs)
+                .map(|(a, b)| {
+                    (
+                        a.add(b).unwrap(),
+                        a.sub(b).unwrap(),
+                        a.mul(b).unwrap(),
+                        a.mul(&scalar).unwrap(),
+                    )
+                })
+                .collect();
+
+            let mut sum = lhs.clone();
+            let mut diff = lhs.clone();
+            let mut product = lhs.clone();
+            let mut scaled = lhs.clone();
+            FieldElement::add_assign_slices(&mut sum, &rhs).unwrap();
+            FieldElement::sub_assign_slices(&mut diff, &rhs).unwrap();
+            FieldElement::mul_assign_slices(&mut product, &rhs).unwrap();
+            FieldElement::scale_slice(&mut scaled, &scalar).unwrap();
+
+            for (i, (s, d, p, c)) in expected.into_iter().enumerate() {
+                assert_eq!(sum[i], s);
+                assert_eq!(diff[i], d);
+                assert_eq!(product[i], p);
+                assert_eq!(scaled[i], c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_ops_errors() {
+        let mut lhs = vec![FieldElement::new(1, 97).unwrap(); 2];
+        let short = vec![FieldElement::new(1, 97).unwrap()];
+        let other = vec![FieldElement::new(1, 101).unwrap(); 2];
+        assert!(FieldElement::add_assign_slices(&mut lhs, &short).is_err());
+        assert!(FieldElement::mul_assign_slices(&mut lhs, &other).is_err());
+        assert!(FieldElement::scale_slice(&mut lhs, &other[0]).is_err());
+        assert!(FieldElement::sub_assign_slices(&mut [], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_eq_hash_ord() {
+        use std::collections::HashSet;
+
+        let a = FieldElement::new(5, 97).unwrap();
+        let unreduced = FieldElement::new(102, 97).unwrap();
+        assert_eq!(a, unreduced);
+        assert_ne!(a, FieldElement::new(5, 101).unwrap());
+
+        let set: HashSet<FieldElement> = [a.clone(), unreduced, FieldElement::new(6, 97).unwrap()]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+
+        let mut elements = [
+            FieldElement::new(3, 101).unwrap(),
+            FieldElement::new(96, 97).unwrap(),
+            FieldElement::new(98, 97).unwrap(),
+            a,
+        ];
+        elements.sort();
+        let sorted: Vec<(u64, u64)> = elements
+            .iter()
+            .map(|e| (e.value % e.modulus, e.modulus))
+            .collect();
+        assert_eq!(sorted, vec![(1, 97), (5, 97), (96, 97), (3, 101)]);
+    }
+
+    #[test]
+    fn test_field_ctx_reduce() {
+        let samples = [
+            0u128,
+            1,
+            96,
+            97,
+            u64::MAX as u128,
+            1 << 100,
+            u128::MAX - 1,
+            u128::MAX,
+        ];
+        for modulus in [
+            1,
+            2,
+            97,
+            1 << 40,
+            (1 << 63) + 1,
+            18446744073709551557,
+            u64::MAX,
+        ] {
+            let ctx = FieldCtx::new(modulus).unwrap();
+            for t in samples {
+                assert_eq!(
+                    ctx.reduce(t) as u128,
+                    t % modulus as u128,
+                    "{} mod {}",
+                    t,
+                    modulus
+                );
+            }
+        }
+        assert!(FieldCtx::new(0).is_err());
+    }
+
+    #[test]
+    fn test_field_ctx_ops() {
+        for modulus in [97, 1 << 40, 18446744073709551557] {
+            let ctx = FieldCtx::new(modulus).unwrap();
+            let values = [0, 1, modulus - 1, modulus + 5, u64::MAX];
+            for &a in &values {
+                for &b in &values {
+                    let (x, y) = (
+                        FieldElement { value: a, modulus },
+                        FieldElement { value: b, modulus },
+                    );
+                    assert_eq!(ctx.add(&x, &y), x.add(&y).unwrap());
+                    assert_eq!(ctx.sub(&x, &y), x.sub(&y).unwrap());
+                    assert_eq!(ctx.mul(&x, &y), x.mul(&y).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_hi() {
+        assert_eq!(mul_hi(u128::MAX, u128::MAX), u128::MAX - 1);
+        assert_eq!(mul_hi(1 << 64, 1 << 64), 1);
+        assert_eq!(mul_hi(u128::MAX, 2), 1);
+        assert_eq!(mul_hi(12345, 67890), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let a = FieldElement::new(102, 97).unwrap();
+        assert_eq!(a.to_string(), "5 mod 97");
+
+        let hex = DisplayFormat {
+            hex: true,
+            ..DisplayFormat::default()
+        };
+        assert_eq!(a.display(hex).to_string(), "0x5 mod 0x61");
+
+        let value_only = DisplayFormat {
+            hex: false,
+            with_modulus: false,
+        };
+        assert_eq!(a.display(value_only).to_string(), "5");
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        let a = FieldElement::new(12345, 18446744073709551557).unwrap();
+        for hex in [false, true] {
+            let format = DisplayFormat {
+                hex,
+                with_modulus: true,
+            };
+            let parsed: FieldElement = a.display(format).to_string().parse().unwrap();
+            assert_eq!(parsed, a);
+        }
+        assert_eq!(
+            FieldElement::parse(" 0XfF ", 257).unwrap(),
+            FieldElement::new(255, 257).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        let malformed = ["", "5", "five mod 97", "5 mod", "0x mod 97", "-1 mod 97"];
+        for s in malformed {
+            assert!(
+                matches!(
+                    s.parse::<FieldElement>(),
+                    Err(ZKError::MalformedFieldElement(_))
+                ),
+                "{:?}",
+                s
+            );
+        }
+
+        let out_of_range = ["97 mod 97", "18446744073709551616 mod 97"];
+        for s in out_of_range {
+            assert!(
+                matches!(
+                    s.parse::<FieldElement>(),
+                    Err(ZKError::FieldElementOutOfRange(_))
+                ),
+                "{:?}",
+                s
+            );
+        }
+
+        assert!(matches!(
+            "5 mod 0".parse::<FieldElement>(),
+            Err(ZKError::InvalidFieldElement(_))
+        ));
+    }
+
+    #[test]
+    fn test_karatsuba_matches_schoolbook() {
+        let mut rng = rand::rng();
+        for modulus in [97, 1 << 40, 18446744073709551557] {
+            let ring = FieldRing::<FieldElement>(modulus);
+            let ctx = FieldCtx::new(modulus).unwrap();
+            for (n, m) in [(1, 1), (2, 2), (5, 3), (8, 8), (17, 4), (33, 31), (40, 3)] {
+                let lhs: Vec<FieldElement> = (0..n)
+                    .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                    .collect();
+                let rhs: Vec<FieldElement> = (0..m)
+                    .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                    .collect();
+                let expected = multiply_in(&ring, &lhs, &rhs, usize::MAX).unwrap();
+                for threshold in [2, 3, 8] {
+                    assert_eq!(multiply_in(&ring, &lhs, &rhs, threshold).unwrap(), expected);
+                    assert_eq!(multiply_in(&ctx, &lhs, &rhs, threshold).unwrap(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiply_polynomials_large() {
+        // Above the threshold every backend takes the Karatsuba path.
+        let mut rng = rand::rng();
+        for modulus in [97, 1 << 40] {
+            let ring = FieldRing::<FieldElement>(modulus);
+            let lhs: Vec<FieldElement> = (0..150)
+                .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                .collect();
+            let rhs: Vec<FieldElement> = (0..100)
+                .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                .collect();
+            assert_eq!(
+                FieldElement::multiply_polynomials(&lhs, &rhs).unwrap(),
+                multiply_in(&ring, &lhs, &rhs, usize::MAX).unwrap()
+            );
+        }
+    }
 }
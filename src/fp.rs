@@ -0,0 +1,250 @@
+use rand::RngCore;
+
+use crate::{
+    errors::ZKError,
+    field::{is_prime, Field, FieldElement},
+};
+
+/// A field of prime order whose modulus is part of the type.
+///
+/// Elements of two such fields have different types, so the compiler rejects mixing them
+/// up, e.g. adding a witness value from the scalar field of a curve to a coordinate from
+/// its base field. See [`crate::curve::Curve::ScalarField`].
+pub trait PrimeField: Field<Params = ()> + Copy {
+    /// The prime modulus.
+    const MODULUS: u64;
+
+    /// Returns the canonical representative in [0, MODULUS).
+    fn value(&self) -> u64;
+}
+
+/// Represents an element of F_p for a prime p fixed at compile time.
+///
+/// This is a `FieldElement` with the modulus moved into the type: it needs no runtime
+/// parameters, and elements of different fields cannot be combined. Converting to and
+/// from `FieldElement` checks the modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u64", into = "u64")
+)]
+pub struct Fp<const MODULUS: u64>(u64);
+
+impl<const MODULUS: u64> Fp<MODULUS> {
+    // Evaluated once per modulus, so a zero or one modulus fails to compile.
+    const NONTRIVIAL: () = assert!(MODULUS > 1, "Modulus must be greater than one.");
+
+    /// Creates a new element, reducing `value` modulo p.
+    pub fn new(value: u64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NONTRIVIAL;
+        Fp(value % MODULUS)
+    }
+
+    /// Returns the same element as a `FieldElement` modulo p.
+    pub fn to_field_element(&self) -> FieldElement {
+        FieldElement::new(self.0, MODULUS).expect("modulus is nonzero")
+    }
+}
+
+impl<const MODULUS: u64> PrimeField for Fp<MODULUS> {
+    const MODULUS: u64 = MODULUS;
+
+    fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<const MODULUS: u64> TryFrom<FieldElement> for Fp<MODULUS> {
+    type Error = ZKError;
+
+    fn try_from(element: FieldElement) -> Result<Self, ZKError> {
+        if element.modulus != MODULUS {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "Expected an element modulo {}, got one modulo {}.",
+                MODULUS, element.modulus
+            )));
+        }
+        Ok(Fp::new(element.value))
+    }
+}
+
+impl<const MODULUS: u64> TryFrom<u64> for Fp<MODULUS> {
+    type Error = ZKError;
+
+    // Unlike `new`, rejects unreduced values, so encodings stay canonical.
+    fn try_from(value: u64) -> Result<Self, ZKError> {
+        if value >= MODULUS {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "Value {} is not reduced modulo {}.",
+                value, MODULUS
+            )));
+        }
+        Ok(Fp::new(value))
+    }
+}
+
+impl<const MODULUS: u64> From<Fp<MODULUS>> for u64 {
+    fn from(element: Fp<MODULUS>) -> u64 {
+        element.0
+    }
+}
+
+impl<const MODULUS: u64> Field for Fp<MODULUS> {
+    // The modulus is fixed, so no runtime parameters are needed.
+    type Params = ();
+
+    fn params(&self) {}
+
+    fn zero(_params: &()) -> Self {
+        Fp::new(0)
+    }
+
+    fn one(_params: &()) -> Self {
+        Fp::new(1)
+    }
+
+    fn from_u64(value: u64, _params: &()) -> Self {
+        Fp::new(value)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, ZKError> {
+        Ok(Fp(
+            ((self.0 as u128 + other.0 as u128) % MODULUS as u128) as u64
+        ))
+    }
+
+    fn sub(&self, other: &Self) -> Result<Self, ZKError> {
+        Ok(Fp(
+            ((self.0 as u128 + MODULUS as u128 - other.0 as u128) % MODULUS as u128) as u64,
+        ))
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, ZKError> {
+        Ok(Fp(
+            ((self.0 as u128 * other.0 as u128) % MODULUS as u128) as u64
+        ))
+    }
+
+    fn neg(&self) -> Self {
+        Fp((MODULUS - self.0) % MODULUS)
+    }
+
+    fn inv(&self) -> Result<Self, ZKError> {
+        Ok(Fp(self.to_field_element().inv()?.value))
+    }
+
+    fn random<R: RngCore + ?Sized>(rng: &mut R, _params: &()) -> Result<Self, ZKError> {
+        Ok(Fp(FieldElement::random(rng, MODULUS)?.value))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8], _params: &()) -> Result<Self, ZKError> {
+        let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+            ZKError::InvalidFieldElement("Field element encoding must be 8 bytes.".into())
+        })?;
+        Fp::try_from(u64::from_le_bytes(bytes))
+    }
+
+    fn validate_params(_params: &()) -> Result<(), ZKError> {
+        if !is_prime(MODULUS) {
+            return Err(ZKError::CompositeModulus(MODULUS));
+        }
+        Ok(())
+    }
+
+    fn exp(&self, exponent: u64) -> Result<Self, ZKError> {
+        Ok(Fp(self.to_field_element().exp(exponent)?.value))
+    }
+
+    fn root_of_unity(order: u64, _params: &()) -> Option<Self> {
+        <FieldElement as Field>::root_of_unity(order, &MODULUS).map(|omega| Fp(omega.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::Polynomial;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type F97 = Fp<97>;
+    type F103 = Fp<103>;
+
+    #[test]
+    fn test_arithmetic_matches_field_element() {
+        for (a, b) in [(0, 0), (1, 96), (13, 42), (96, 96), (50, 3)] {
+            let (x, y) = (F97::new(a), F97::new(b));
+            let (fx, fy) = (x.to_field_element(), y.to_field_element());
+
+            assert_eq!(x.add(&y).unwrap().value(), fx.add(&fy).unwrap().value);
+            assert_eq!(x.sub(&y).unwrap().value(), fx.sub(&fy).unwrap().value);
+            assert_eq!(x.mul(&y).unwrap().value(), fx.mul(&fy).unwrap().value);
+            assert_eq!(Field::neg(&x).value(), Field::neg(&fx).value);
+            assert_eq!(x.exp(b).unwrap().value(), fx.exp(b).unwrap().value);
+            if a != 0 {
+                assert!(x.mul(&x.inv().unwrap()).unwrap().is_one());
+            }
+        }
+        assert!(F97::new(0).inv().is_err());
+        assert_eq!(F97::new(200).value(), 6);
+    }
+
+    #[test]
+    fn test_conversions_check_modulus() {
+        let element = FieldElement::new(5, 103).unwrap();
+        assert_eq!(F103::try_from(element.clone()).unwrap(), F103::new(5));
+        assert!(F97::try_from(element).is_err());
+
+        assert!(F97::try_from(97u64).is_err());
+        assert_eq!(u64::from(F97::try_from(96u64).unwrap()), 96);
+
+        let bytes = F97::new(42).to_bytes();
+        assert_eq!(F97::from_bytes(&bytes, &()).unwrap(), F97::new(42));
+        assert!(F97::from_bytes(&100u64.to_le_bytes(), &()).is_err());
+        assert!(F97::from_bytes(&[1, 2, 3], &()).is_err());
+
+        assert!(F97::validate_params(&()).is_ok());
+        assert!(matches!(
+            Fp::<91>::validate_params(&()),
+            Err(ZKError::CompositeModulus(91))
+        ));
+    }
+
+    #[test]
+    fn test_generic_use() {
+        // 97 - 1 = 2^5 · 3, so roots of unity exist up to order 32.
+        let omega = F97::root_of_unity(32, &()).unwrap();
+        assert!(omega.exp(32).unwrap().is_one());
+        assert!(!omega.exp(16).unwrap().is_one());
+        assert!(F97::root_of_unity(64, &()).is_none());
+
+        // (x + 1)(x - 1) = x^2 - 1.
+        let a = Polynomial::new(vec![F97::new(1), F97::new(1)]).unwrap();
+        let b = Polynomial::new(vec![Field::neg(&F97::new(1)), F97::new(1)]).unwrap();
+        let product = a.mul(&b).unwrap();
+        assert_eq!(product.evaluate(&F97::new(10)).unwrap(), F97::new(99));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            assert!(F97::random(&mut rng, &()).unwrap().value() < 97);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&F97::new(42)).unwrap();
+        assert_eq!(json, "42");
+        assert_eq!(serde_json::from_str::<F97>(&json).unwrap(), F97::new(42));
+        assert!(serde_json::from_str::<F97>("97").is_err());
+    }
+}
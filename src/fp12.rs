@@ -0,0 +1,473 @@
+use crate::{
+    errors::ZKError,
+    field::Field,
+    fp2::Fp2,
+    fp6::{Fp6, FrobeniusCoefficients},
+};
+
+/// Represents an element c0 + c1·w of the quadratic extension Fp12 = Fp6[w] / (w^2 - v).
+/// This is the field containing the pairing target group GT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fp12 {
+    pub c0: Fp6,
+    pub c1: Fp6,
+}
+
+impl Fp12 {
+    /// Creates a new Fp12 element.
+    pub fn new(c0: Fp6, c1: Fp6) -> Result<Self, ZKError> {
+        if c0.non_residue != c1.non_residue {
+            return Err(ZKError::InvalidFieldElement(
+                "Fp12 coefficients must belong to the same Fp6.".into(),
+            ));
+        }
+        Ok(Fp12 { c0, c1 })
+    }
+
+    /// Returns the additive identity of the tower defined by the Fp6 non-residue ξ.
+    pub fn zero(non_residue: &Fp2) -> Self {
+        Fp12 {
+            c0: Fp6::zero(non_residue),
+            c1: Fp6::zero(non_residue),
+        }
+    }
+
+    /// Returns the multiplicative identity of the tower defined by the Fp6 non-residue ξ.
+    pub fn one(non_residue: &Fp2) -> Self {
+        Fp12 {
+            c0: Fp6::one(non_residue),
+            c1: Fp6::zero(non_residue),
+        }
+    }
+
+    /// Returns true if the element is zero.
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    /// Returns true if the element is one.
+    pub fn is_one(&self) -> bool {
+        *self == Self::one(&self.c0.non_residue)
+    }
+
+    /// Add two Fp12 elements.
+    pub fn add(&self, other: &Fp12) -> Result<Fp12, ZKError> {
+        Ok(Fp12 {
+            c0: self.c0.add(&other.c0)?,
+            c1: self.c1.add(&other.c1)?,
+        })
+    }
+
+    /// Subtract two Fp12 elements.
+    pub fn sub(&self, other: &Fp12) -> Result<Fp12, ZKError> {
+        Ok(Fp12 {
+            c0: self.c0.sub(&other.c0)?,
+            c1: self.c1.sub(&other.c1)?,
+        })
+    }
+
+    /// Negate the element.
+    pub fn neg(&self) -> Fp12 {
+        Fp12 {
+            c0: self.c0.neg(),
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Multiply two Fp12 elements.
+    pub fn mul(&self, other: &Fp12) -> Result<Fp12, ZKError> {
+        // Karatsuba, reducing with w^2 = v:
+        // c0 = a0·b0 + v·a1·b1, c1 = (a0 + a1)(b0 + b1) - a0·b0 - a1·b1
+        let v0 = self.c0.mul(&other.c0)?;
+        let v1 = self.c1.mul(&other.c1)?;
+        let c0 = v0.add(&v1.mul_by_non_residue()?)?;
+        let c1 = self
+            .c0
+            .add(&self.c1)?
+            .mul(&other.c0.add(&other.c1)?)?
+            .sub(&v0)?
+            .sub(&v1)?;
+        Ok(Fp12 { c0, c1 })
+    }
+
+    /// Square the element.
+    pub fn square(&self) -> Result<Fp12, ZKError> {
+        // Complex squaring: c0 = (a0 + a1)(a0 + v·a1) - a0·a1 - v·a0·a1, c1 = 2·a0·a1
+        let ab = self.c0.mul(&self.c1)?;
+        let c0 = self
+            .c0
+            .add(&self.c1)?
+            .mul(&self.c0.add(&self.c1.mul_by_non_residue()?)?)?
+            .sub(&ab)?
+            .sub(&ab.mul_by_non_residue()?)?;
+        let c1 = ab.add(&ab)?;
+        Ok(Fp12 { c0, c1 })
+    }
+
+    /// Returns the conjugate c0 - c1·w, which equals x^(p^6).
+    pub fn conjugate(&self) -> Fp12 {
+        Fp12 {
+            c0: self.c0.clone(),
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Find the multiplicative inverse of the element.
+    pub fn inv(&self) -> Result<Fp12, ZKError> {
+        // (a0 + a1·w)^(-1) = (a0 - a1·w) / (a0^2 - v·a1^2)
+        let norm = self
+            .c0
+            .square()?
+            .sub(&self.c1.square()?.mul_by_non_residue()?)?;
+        let norm_inv = norm.inv()?;
+        Ok(Fp12 {
+            c0: self.c0.mul(&norm_inv)?,
+            c1: self.c1.neg().mul(&norm_inv)?,
+        })
+    }
+
+    /// Applies the Frobenius endomorphism x ↦ x^(p^power).
+    pub fn frobenius_map(&self, power: usize) -> Result<Fp12, ZKError> {
+        self.frobenius_map_with(power, &FrobeniusCoefficients::new(&self.c0.non_residue)?)
+    }
+
+    /// Applies the Frobenius endomorphism using precomputed coefficients:
+    /// (c0 + c1·w)^(p^k) = c0^(p^k) + c1^(p^k)·γ_k·w.
+    pub fn frobenius_map_with(
+        &self,
+        power: usize,
+        coefficients: &FrobeniusCoefficients,
+    ) -> Result<Fp12, ZKError> {
+        let k = power % 12;
+        Ok(Fp12 {
+            c0: self.c0.frobenius_map_with(k, coefficients)?,
+            c1: self
+                .c1
+                .frobenius_map_with(k, coefficients)?
+                .mul_by_fp2(&coefficients.w[k])?,
+        })
+    }
+
+    /// Exponentiate the element by the provided exponent.
+    pub fn exp(&self, exponent: u64) -> Result<Fp12, ZKError> {
+        let mut result = Self::one(&self.c0.non_residue);
+        let mut base = self.clone();
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base)?;
+            }
+            base = base.square()?;
+            exp >>= 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Inverse of an element of the cyclotomic subgroup (of order p^4 - p^2 + 1), where
+    /// inversion is just conjugation. This holds for any x^(p^6 - 1), so pairing values
+    /// qualify after the easy part of the final exponentiation.
+    pub fn cyclotomic_inv(&self) -> Fp12 {
+        self.conjugate()
+    }
+
+    /// Squares an element of the cyclotomic subgroup with the Granger–Scott formulas, at
+    /// about half the cost of a general squaring.
+    ///
+    /// Write the element as a + b·w + c·w^2 over Fp4 = Fp2[y] / (y^2 - ξ), where y = w^3.
+    /// In the cyclotomic subgroup its square is (3a^2 - 2ā) + (3y·c^2 + 2b̄)·w +
+    /// (3b^2 - 2c̄)·w^2, with x̄ the conjugate over Fp2. Other elements get wrong results.
+    pub fn cyclotomic_square(&self) -> Result<Fp12, ZKError> {
+        let xi = &self.c0.non_residue;
+        let a = [self.c0.c0.clone(), self.c1.c1.clone()];
+        let [b, c] = compressed_square(
+            &[self.c1.c0.clone(), self.c0.c2.clone()],
+            &[self.c0.c1.clone(), self.c1.c2.clone()],
+            xi,
+        )?;
+        let a_squared = fp4_square(&a, xi)?;
+        let a = [
+            triple(&a_squared[0])?.sub(&double(&a[0])?)?,
+            triple(&a_squared[1])?.add(&double(&a[1])?)?,
+        ];
+        Ok(Fp12 {
+            c0: Fp6::new(a[0].clone(), c[0].clone(), b[1].clone(), xi.clone())?,
+            c1: Fp6::new(b[0].clone(), a[1].clone(), c[1].clone(), xi.clone())?,
+        })
+    }
+
+    /// Exponentiation for elements of the cyclotomic subgroup with Karabina's compressed
+    /// squarings.
+    ///
+    /// Only the b and c parts of the element are squared, which is cheaper still than
+    /// `cyclotomic_square`. The powers x^(2^i) for the set bits of the exponent are then
+    /// decompressed, sharing one inversion, and multiplied together. This pays off for
+    /// long exponents with few set bits.
+    pub fn cyclotomic_exp_compressed(&self, exponent: u64) -> Result<Fp12, ZKError> {
+        let xi = &self.c0.non_residue;
+        let mut b = [self.c1.c0.clone(), self.c0.c2.clone()];
+        let mut c = [self.c0.c1.clone(), self.c1.c2.clone()];
+        let mut powers = Vec::new();
+        for i in 0..u64::BITS - exponent.leading_zeros() {
+            if (exponent >> i) & 1 == 1 {
+                powers.push((b.clone(), c.clone()));
+            }
+            [b, c] = compressed_square(&b, &c, xi)?;
+        }
+
+        // Decompression divides by 4·g3 = 4·b0, or by g2 = b1 when b0 is zero.
+        let denominators: Vec<Fp2> = powers
+            .iter()
+            .map(|(b, _)| {
+                if b[0].is_zero() {
+                    Ok(if b[1].is_zero() {
+                        Fp2::one(&xi.non_residue)
+                    } else {
+                        b[1].clone()
+                    })
+                } else {
+                    double(&double(&b[0])?)
+                }
+            })
+            .collect::<Result<_, ZKError>>()?;
+        let inverses = <Fp2 as Field>::batch_inv(&denominators)?;
+
+        let mut result = Self::one(xi);
+        for ((b, c), inverse) in powers.iter().zip(&inverses) {
+            result = result.mul(&decompress(b, c, inverse, xi)?)?;
+        }
+        Ok(result)
+    }
+
+    /// Exponentiation for elements of the cyclotomic subgroup.
+    /// Uses the non-adjacent form of the exponent, since inverses there are free, and
+    /// `cyclotomic_square` for the squarings.
+    pub fn cyclotomic_exp(&self, exponent: u64) -> Result<Fp12, ZKError> {
+        let mut result = Self::one(&self.c0.non_residue);
+        let inverse = self.cyclotomic_inv();
+
+        for digit in non_adjacent_form(exponent).iter().rev() {
+            result = result.cyclotomic_square()?;
+            match digit {
+                1 => result = result.mul(self)?,
+                -1 => result = result.mul(&inverse)?,
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+// Squares x0 + x1·y in Fp4 = Fp2[y] / (y^2 - ξ): x0^2 + ξ·x1^2 + 2·x0·x1·y.
+fn fp4_square(x: &[Fp2; 2], xi: &Fp2) -> Result<[Fp2; 2], ZKError> {
+    let product = x[0].mul(&x[1])?;
+    Ok([
+        x[0].square().add(&x[1].square().mul(xi)?)?,
+        double(&product)?,
+    ])
+}
+
+// The b and c parts of the Granger–Scott squaring, which do not depend on a:
+// b' = 3y·c^2 + 2b̄ and c' = 3b^2 - 2c̄. Karabina's compressed squaring iterates these.
+fn compressed_square(b: &[Fp2; 2], c: &[Fp2; 2], xi: &Fp2) -> Result<[[Fp2; 2]; 2], ZKError> {
+    let b_squared = fp4_square(b, xi)?;
+    let c_squared = fp4_square(c, xi)?;
+    Ok([
+        [
+            triple(&c_squared[1].mul(xi)?)?.add(&double(&b[0])?)?,
+            triple(&c_squared[0])?.sub(&double(&b[1])?)?,
+        ],
+        [
+            triple(&b_squared[0])?.sub(&double(&c[0])?)?,
+            triple(&b_squared[1])?.add(&double(&c[1])?)?,
+        ],
+    ])
+}
+
+// Recovers a = g0 + g4·y from b = g3 + g2·y and c = g1 + g5·y (Karabina, Theorem 3.1):
+// g4 = (ξ·g5^2 + 3·g1^2 - 2·g2) / (4·g3), or 2·g1·g5 / g2 if g3 = 0, and
+// g0 = ξ·(2·g4^2 + g3·g5 - 3·g2·g1) + 1. `inverse` is the inverted denominator.
+fn decompress(b: &[Fp2; 2], c: &[Fp2; 2], inverse: &Fp2, xi: &Fp2) -> Result<Fp12, ZKError> {
+    let ([g3, g2], [g1, g5]) = (b, c);
+    let numerator = if g3.is_zero() {
+        double(&g1.mul(g5)?)?
+    } else {
+        xi.mul(&g5.square())?
+            .add(&triple(&g1.square())?)?
+            .sub(&double(g2)?)?
+    };
+    let g4 = numerator.mul(inverse)?;
+    let g0 = xi
+        .mul(
+            &double(&g4.square())?
+                .add(&g3.mul(g5)?)?
+                .sub(&triple(&g2.mul(g1)?)?)?,
+        )?
+        .add(&Fp2::one(&xi.non_residue))?;
+    Ok(Fp12 {
+        c0: Fp6::new(g0, g1.clone(), g2.clone(), xi.clone())?,
+        c1: Fp6::new(g3.clone(), g4, g5.clone(), xi.clone())?,
+    })
+}
+
+fn double(x: &Fp2) -> Result<Fp2, ZKError> {
+    x.add(x)
+}
+
+fn triple(x: &Fp2) -> Result<Fp2, ZKError> {
+    x.add(x)?.add(x)
+}
+
+// Returns the non-adjacent form of `n`, least significant digit first.
+fn non_adjacent_form(n: u64) -> Vec<i8> {
+    let mut digits = Vec::new();
+    let mut k = n as u128;
+    while k > 0 {
+        if k & 1 == 1 {
+            let digit = 2 - (k % 4) as i8;
+            digits.push(digit);
+            if digit == 1 {
+                k -= 1;
+            } else {
+                k += 1;
+            }
+        } else {
+            digits.push(0);
+        }
+        k >>= 1;
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldElement;
+
+    const MODULUS: u64 = 103;
+
+    fn fp2(c0: u64, c1: u64) -> Fp2 {
+        Fp2::new(
+            FieldElement::new(c0, MODULUS).unwrap(),
+            FieldElement::new(c1, MODULUS).unwrap(),
+            FieldElement::new(MODULUS - 1, MODULUS).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn fp6(c: [u64; 6]) -> Fp6 {
+        Fp6::new(fp2(c[0], c[1]), fp2(c[2], c[3]), fp2(c[4], c[5]), fp2(2, 1)).unwrap()
+    }
+
+    fn sample() -> Fp12 {
+        Fp12::new(fp6([1, 2, 3, 4, 5, 6]), fp6([7, 8, 9, 10, 11, 12])).unwrap()
+    }
+
+    #[test]
+    fn test_w_squared_is_v() {
+        let w = Fp12::new(fp6([0; 6]), fp6([1, 0, 0, 0, 0, 0])).unwrap();
+        let v = Fp12::new(fp6([0, 0, 1, 0, 0, 0]), fp6([0; 6])).unwrap();
+        assert_eq!(w.square().unwrap(), v);
+        assert_eq!(w.mul(&w).unwrap(), v);
+    }
+
+    #[test]
+    fn test_square_matches_mul() {
+        let a = sample();
+        assert_eq!(a.square().unwrap(), a.mul(&a).unwrap());
+    }
+
+    #[test]
+    fn test_inv() {
+        let a = sample();
+        assert!(a.mul(&a.inv().unwrap()).unwrap().is_one());
+        assert!(Fp12::zero(&fp2(2, 1)).inv().is_err());
+    }
+
+    #[test]
+    fn test_frobenius_map() {
+        let a = sample();
+        let coefficients = FrobeniusCoefficients::new(&fp2(2, 1)).unwrap();
+
+        let mut expected = a.clone();
+        for power in 1..=12 {
+            expected = expected.exp(MODULUS).unwrap();
+            assert_eq!(
+                a.frobenius_map_with(power, &coefficients).unwrap(),
+                expected
+            );
+        }
+        assert_eq!(a.frobenius_map(12).unwrap(), a);
+        assert_eq!(a.frobenius_map(6).unwrap(), a.conjugate());
+    }
+
+    #[test]
+    fn test_cyclotomic_exp() {
+        // f^(p^6 - 1) lies in the subgroup of order p^6 + 1, where conjugation inverts.
+        let a = sample();
+        let f = a.conjugate().mul(&a.inv().unwrap()).unwrap();
+        assert!(f.mul(&f.cyclotomic_inv()).unwrap().is_one());
+
+        // The exponentiation squares with the Granger–Scott formulas, so it needs the
+        // cyclotomic subgroup proper, of order p^4 - p^2 + 1.
+        let f = cyclotomic_sample();
+
+        for exponent in [0, 1, 2, 3, 7, 97, 1160209] {
+            assert_eq!(
+                f.cyclotomic_exp(exponent).unwrap(),
+                f.exp(exponent).unwrap()
+            );
+        }
+    }
+
+    // Maps an element into the cyclotomic subgroup with the easy part of the final
+    // exponentiation, a^((p^6 - 1)(p^2 + 1)).
+    fn cyclotomic_sample() -> Fp12 {
+        let a = sample();
+        let f = a.conjugate().mul(&a.inv().unwrap()).unwrap();
+        f.frobenius_map(2).unwrap().mul(&f).unwrap()
+    }
+
+    #[test]
+    fn test_cyclotomic_square() {
+        let mut f = cyclotomic_sample();
+        for _ in 0..5 {
+            assert_eq!(f.cyclotomic_square().unwrap(), f.square().unwrap());
+            f = f
+                .mul(&cyclotomic_sample())
+                .unwrap()
+                .cyclotomic_square()
+                .unwrap();
+        }
+        let one = Fp12::one(&fp2(2, 1));
+        assert_eq!(one.cyclotomic_square().unwrap(), one);
+    }
+
+    #[test]
+    fn test_cyclotomic_exp_compressed() {
+        let f = cyclotomic_sample();
+        for exponent in [0, 1, 2, 3, 7, 97, 1 << 40, 1160209, u64::MAX] {
+            assert_eq!(
+                f.cyclotomic_exp_compressed(exponent).unwrap(),
+                f.cyclotomic_exp(exponent).unwrap()
+            );
+        }
+        let one = Fp12::one(&fp2(2, 1));
+        assert_eq!(one.cyclotomic_exp_compressed(12345).unwrap(), one);
+    }
+
+    #[test]
+    fn test_non_adjacent_form() {
+        for n in [0u64, 1, 7, 12, 255, u64::MAX] {
+            let digits = non_adjacent_form(n);
+            let value = digits
+                .iter()
+                .rev()
+                .fold(0i128, |acc, &d| 2 * acc + d as i128);
+            assert_eq!(value, n as i128);
+            assert!(digits.windows(2).all(|w| w[0] == 0 || w[1] == 0));
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use rand::RngCore;
+
+use crate::{
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// Represents an element c0 + c1·u of the quadratic extension Fp2 = Fp[u] / (u^2 - β),
+/// where β is a quadratic non-residue in Fp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fp2 {
+    pub c0: FieldElement,
+    pub c1: FieldElement,
+    // The non-residue β defining the extension.
+    pub non_residue: FieldElement,
+}
+
+impl Fp2 {
+    /// Creates a new Fp2 element.
+    pub fn new(
+        c0: FieldElement,
+        c1: FieldElement,
+        non_residue: FieldElement,
+    ) -> Result<Self, ZKError> {
+        if c0.modulus != c1.modulus || c0.modulus != non_residue.modulus {
+            return Err(ZKError::InvalidFieldElement(
+                "Fp2 coefficients and non-residue must share a modulus.".into(),
+            ));
+        }
+        Ok(Fp2 {
+            c0,
+            c1,
+            non_residue,
+        })
+    }
+
+    /// Returns the additive identity of the extension defined by `non_residue`.
+    pub fn zero(non_residue: &FieldElement) -> Self {
+        let zero = FieldElement {
+            value: 0,
+            modulus: non_residue.modulus,
+        };
+        Fp2 {
+            c0: zero.clone(),
+            c1: zero,
+            non_residue: non_residue.clone(),
+        }
+    }
+
+    /// Returns the multiplicative identity of the extension defined by `non_residue`.
+    pub fn one(non_residue: &FieldElement) -> Self {
+        let mut one = Self::zero(non_residue);
+        one.c0.value = 1 % non_residue.modulus;
+        one
+    }
+
+    /// Embeds a base field element into Fp2.
+    pub fn from_base(c0: FieldElement, non_residue: &FieldElement) -> Result<Self, ZKError> {
+        let c1 = FieldElement::new(0, c0.modulus)?;
+        Self::new(c0, c1, non_residue.clone())
+    }
+
+    /// Returns true if the element is zero.
+    pub fn is_zero(&self) -> bool {
+        self.c0.value.is_multiple_of(self.c0.modulus)
+            && self.c1.value.is_multiple_of(self.c1.modulus)
+    }
+
+    /// Add two Fp2 elements.
+    pub fn add(&self, other: &Fp2) -> Result<Fp2, ZKError> {
+        self.check_same_field(other, "addition")?;
+        Ok(self.with_coefficients(&self.c0 + &other.c0, &self.c1 + &other.c1))
+    }
+
+    /// Subtract two Fp2 elements.
+    pub fn sub(&self, other: &Fp2) -> Result<Fp2, ZKError> {
+        self.check_same_field(other, "subtraction")?;
+        Ok(self.with_coefficients(&self.c0 - &other.c0, &self.c1 - &other.c1))
+    }
+
+    /// Negate the element.
+    pub fn neg(&self) -> Fp2 {
+        self.with_coefficients(-&self.c0, -&self.c1)
+    }
+
+    /// Multiply two Fp2 elements.
+    pub fn mul(&self, other: &Fp2) -> Result<Fp2, ZKError> {
+        self.check_same_field(other, "multiplication")?;
+
+        // Karatsuba: (a0 + a1·u)(b0 + b1·u) = a0·b0 + β·a1·b1 + ((a0 + a1)(b0 + b1) - a0·b0 - a1·b1)·u
+        let v0 = &self.c0 * &other.c0;
+        let v1 = &self.c1 * &other.c1;
+        let c0 = &v0 + &(&self.non_residue * &v1);
+        let c1 = &(&(&self.c0 + &self.c1) * &(&other.c0 + &other.c1)) - &(&v0 + &v1);
+        Ok(self.with_coefficients(c0, c1))
+    }
+
+    /// Square the element.
+    pub fn square(&self) -> Fp2 {
+        // (a0 + a1·u)^2 = a0^2 + β·a1^2 + 2·a0·a1·u
+        let a0a1 = &self.c0 * &self.c1;
+        let c0 = &(&self.c0 * &self.c0) + &(&self.non_residue * &(&self.c1 * &self.c1));
+        let c1 = &a0a1 + &a0a1;
+        self.with_coefficients(c0, c1)
+    }
+
+    /// Multiply the element by a base field scalar.
+    pub fn mul_by_fp(&self, scalar: &FieldElement) -> Result<Fp2, ZKError> {
+        Ok(self.with_coefficients(self.c0.mul(scalar)?, self.c1.mul(scalar)?))
+    }
+
+    /// Returns the conjugate c0 - c1·u.
+    pub fn conjugate(&self) -> Fp2 {
+        self.with_coefficients(self.c0.clone(), -&self.c1)
+    }
+
+    /// Returns the norm c0^2 - β·c1^2, an element of the base field.
+    pub fn norm(&self) -> FieldElement {
+        &(&self.c0 * &self.c0) - &(&self.non_residue * &(&self.c1 * &self.c1))
+    }
+
+    /// Find the multiplicative inverse of the element.
+    pub fn inv(&self) -> Result<Fp2, ZKError> {
+        // (a0 + a1·u)^(-1) = (a0 - a1·u) / (a0^2 - β·a1^2)
+        let norm_inv = self.norm().inv()?;
+        self.conjugate().mul_by_fp(&norm_inv)
+    }
+
+    /// Applies the Frobenius endomorphism x ↦ x^(p^power).
+    /// Since u^p = β^((p - 1) / 2)·u = -u, odd powers conjugate and even powers are the identity.
+    pub fn frobenius_map(&self, power: usize) -> Fp2 {
+        if power % 2 == 1 {
+            self.conjugate()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Exponentiate the element by the provided exponent.
+    pub fn exp(&self, exponent: u64) -> Result<Fp2, ZKError> {
+        let mut result = Self::one(&self.non_residue);
+        let mut base = self.clone();
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base)?;
+            }
+            base = base.square();
+            exp >>= 1;
+        }
+
+        Ok(result)
+    }
+
+    fn with_coefficients(&self, c0: FieldElement, c1: FieldElement) -> Fp2 {
+        Fp2 {
+            c0,
+            c1,
+            non_residue: self.non_residue.clone(),
+        }
+    }
+
+    fn check_same_field(&self, other: &Fp2, operation: &str) -> Result<(), ZKError> {
+        if self.non_residue != other.non_residue
+            || self.c0.modulus != other.c0.modulus
+            || self.c1.modulus != other.c1.modulus
+        {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "Fp2 elements must belong to the same extension for {}.",
+                operation
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Field for Fp2 {
+    // The non-residue β, which also carries the base field modulus.
+    type Params = FieldElement;
+
+    fn params(&self) -> FieldElement {
+        self.non_residue.clone()
+    }
+
+    fn zero(non_residue: &FieldElement) -> Self {
+        Fp2::zero(non_residue)
+    }
+
+    fn one(non_residue: &FieldElement) -> Self {
+        Fp2::one(non_residue)
+    }
+
+    fn from_u64(value: u64, non_residue: &FieldElement) -> Self {
+        let mut element = Fp2::zero(non_residue);
+        element.c0 = <FieldElement as Field>::from_u64(value, &non_residue.modulus);
+        element
+    }
+
+    fn is_zero(&self) -> bool {
+        Fp2::is_zero(self)
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, ZKError> {
+        Fp2::add(self, other)
+    }
+
+    fn sub(&self, other: &Self) -> Result<Self, ZKError> {
+        Fp2::sub(self, other)
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, ZKError> {
+        Fp2::mul(self, other)
+    }
+
+    fn neg(&self) -> Self {
+        Fp2::neg(self)
+    }
+
+    fn inv(&self) -> Result<Self, ZKError> {
+        Fp2::inv(self)
+    }
+
+    fn random<R: RngCore + ?Sized>(
+        rng: &mut R,
+        non_residue: &FieldElement,
+    ) -> Result<Self, ZKError> {
+        let c0 = FieldElement::random(rng, non_residue.modulus)?;
+        let c1 = FieldElement::random(rng, non_residue.modulus)?;
+        Fp2::new(c0, c1, non_residue.clone())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Field::to_bytes(&self.c0);
+        bytes.extend(Field::to_bytes(&self.c1));
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8], non_residue: &FieldElement) -> Result<Self, ZKError> {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(ZKError::InvalidFieldElement(
+                "Fp2 encoding must have an even length.".into(),
+            ));
+        }
+        let (c0, c1) = bytes.split_at(bytes.len() / 2);
+        Fp2::new(
+            <FieldElement as Field>::from_bytes(c0, &non_residue.modulus)?,
+            <FieldElement as Field>::from_bytes(c1, &non_residue.modulus)?,
+            non_residue.clone(),
+        )
+    }
+
+    fn validate_params(non_residue: &FieldElement) -> Result<(), ZKError> {
+        <FieldElement as Field>::validate_params(&non_residue.modulus)?;
+        if non_residue.legendre()? != -1 {
+            return Err(ZKError::InvalidFieldElement(
+                "Fp2 requires a quadratic non-residue.".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn exp(&self, exponent: u64) -> Result<Self, ZKError> {
+        Fp2::exp(self, exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULUS: u64 = 103;
+
+    fn fp2(c0: u64, c1: u64) -> Fp2 {
+        // 103 ≡ 3 mod 4, so β = -1 is a non-residue.
+        Fp2::new(
+            FieldElement::new(c0, MODULUS).unwrap(),
+            FieldElement::new(c1, MODULUS).unwrap(),
+            FieldElement::new(MODULUS - 1, MODULUS).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mul() {
+        // (3 + 4u)(5 + 6u) = 15 - 24 + (18 + 20)u = -9 + 38u.
+        let result = fp2(3, 4).mul(&fp2(5, 6)).unwrap();
+        assert_eq!(result, fp2(MODULUS - 9, 38));
+        assert_eq!(fp2(3, 4).square(), fp2(3, 4).mul(&fp2(3, 4)).unwrap());
+    }
+
+    #[test]
+    fn test_inv() {
+        let a = fp2(17, 29);
+        let one = a.mul(&a.inv().unwrap()).unwrap();
+        assert_eq!(one, fp2(1, 0));
+        assert!(fp2(0, 0).inv().is_err());
+    }
+
+    #[test]
+    fn test_exp() {
+        // The multiplicative group of Fp2 has order p^2 - 1.
+        let a = fp2(7, 11);
+        assert_eq!(a.exp(MODULUS * MODULUS - 1).unwrap(), fp2(1, 0));
+        assert_eq!(a.exp(3).unwrap(), a.square().mul(&a).unwrap());
+    }
+
+    #[test]
+    fn test_frobenius_map() {
+        let a = fp2(7, 11);
+        assert_eq!(a.frobenius_map(1), a.exp(MODULUS).unwrap());
+        assert_eq!(a.frobenius_map(2), a);
+        assert_eq!(a.frobenius_map(3), a.exp(MODULUS).unwrap());
+    }
+
+    #[test]
+    fn test_field_trait() {
+        let a = fp2(17, 29);
+        let bytes = Field::to_bytes(&a);
+        assert_eq!(
+            <Fp2 as Field>::from_bytes(&bytes, &a.non_residue).unwrap(),
+            a
+        );
+        assert_eq!(<Fp2 as Field>::from_u64(105, &a.non_residue), fp2(2, 0));
+
+        // Montgomery's trick works for any backend.
+        let elements = vec![fp2(1, 2), fp2(3, 4), fp2(5, 6)];
+        let inverses = <Fp2 as Field>::batch_inv(&elements).unwrap();
+        for (element, inverse) in elements.iter().zip(&inverses) {
+            assert_eq!(inverse, &element.inv().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_mismatched_non_residue() {
+        let a = fp2(1, 2);
+        let b = Fp2::new(
+            FieldElement::new(1, MODULUS).unwrap(),
+            FieldElement::new(2, MODULUS).unwrap(),
+            FieldElement::new(5, MODULUS).unwrap(),
+        )
+        .unwrap();
+        assert!(a.add(&b).is_err());
+    }
+
+    #[test]
+    fn test_validate_params() {
+        let beta = FieldElement::new(MODULUS - 1, MODULUS).unwrap();
+        assert!(<Fp2 as Field>::validate_params(&beta).is_ok());
+        // 4 is a square, so it does not define a quadratic extension.
+        let square = FieldElement::new(4, MODULUS).unwrap();
+        assert!(<Fp2 as Field>::validate_params(&square).is_err());
+    }
+}
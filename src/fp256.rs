@@ -0,0 +1,429 @@
+use std::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+};
+
+use rand::RngCore;
+
+use crate::{errors::ZKError, field::Field};
+
+/// The modulus of a prime field below 2^256, fixed at compile time.
+///
+/// Implementors are marker types; see `curves::secp256k1` for the fields of secp256k1.
+pub trait Modulus256: Copy + Debug + PartialEq + Eq + Send + Sync + 'static {
+    /// The odd prime modulus as four 64-bit limbs, least significant first. Use
+    /// `limbs_from_hex` to write it as a hexadecimal constant.
+    const MODULUS: [u64; 4];
+}
+
+/// Represents an element of F_p for a prime p < 2^256 fixed at compile time, for curves
+/// such as secp256k1 whose fields do not fit the 64-bit backends.
+///
+/// Elements are held in Montgomery form, aR mod p with R = 2^256, in four 64-bit limbs,
+/// so multiplication needs no division. The representation is always fully reduced, so
+/// equal elements have equal limbs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp256<M: Modulus256> {
+    limbs: [u64; 4],
+    modulus: PhantomData<M>,
+}
+
+/// Parses a hexadecimal number below 2^256, with or without a `0x` prefix, into four
+/// 64-bit limbs, least significant first. Returns `None` for empty, malformed or
+/// too-long input. This is a `const fn`, so moduli can be written as hex constants.
+pub const fn limbs_from_hex(hex: &str) -> Option<[u64; 4]> {
+    let bytes = hex.as_bytes();
+    let mut start = 0;
+    if bytes.len() >= 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+        start = 2;
+    }
+    if start == bytes.len() {
+        return None;
+    }
+
+    let mut limbs = [0u64; 4];
+    let mut i = start;
+    while i < bytes.len() {
+        let digit = match bytes[i] {
+            b'0'..=b'9' => bytes[i] - b'0',
+            b'a'..=b'f' => bytes[i] - b'a' + 10,
+            b'A'..=b'F' => bytes[i] - b'A' + 10,
+            _ => return None,
+        };
+        // Shift left by four bits, failing if a non-zero nibble falls off the top.
+        if limbs[3] >> 60 != 0 {
+            return None;
+        }
+        limbs[3] = (limbs[3] << 4) | (limbs[2] >> 60);
+        limbs[2] = (limbs[2] << 4) | (limbs[1] >> 60);
+        limbs[1] = (limbs[1] << 4) | (limbs[0] >> 60);
+        limbs[0] = (limbs[0] << 4) | digit as u64;
+        i += 1;
+    }
+    Some(limbs)
+}
+
+// Returns a + b + carry as (sum, carry).
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+// Returns a - b - borrow as (difference, borrow), with borrow 0 or 1.
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let difference = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (difference as u64, (difference >> 127) as u64)
+}
+
+// Returns a + b · c + carry as (low, high).
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 * c as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+// Returns a - b and whether it borrowed.
+const fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], bool) {
+    let mut out = [0u64; 4];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < 4 {
+        (out[i], borrow) = sbb(a[i], b[i], borrow);
+        i += 1;
+    }
+    (out, borrow != 0)
+}
+
+// Returns 2a mod p for a < p.
+const fn double_mod(a: &[u64; 4], p: &[u64; 4]) -> [u64; 4] {
+    let mut doubled = [0u64; 4];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < 4 {
+        (doubled[i], carry) = adc(a[i], a[i], carry);
+        i += 1;
+    }
+    let (reduced, borrow) = sub_limbs(&doubled, p);
+    if carry != 0 || !borrow {
+        reduced
+    } else {
+        doubled
+    }
+}
+
+// Returns 2^bits mod p.
+const fn power_of_two_mod(bits: u32, p: &[u64; 4]) -> [u64; 4] {
+    let mut value = [1, 0, 0, 0];
+    let mut i = 0;
+    while i < bits {
+        value = double_mod(&value, p);
+        i += 1;
+    }
+    value
+}
+
+impl<M: Modulus256> Fp256<M> {
+    // -p^(-1) mod 2^64, by Newton iteration: each step doubles the correct low bits.
+    const INV: u64 = {
+        let p0 = M::MODULUS[0];
+        assert!(p0 & 1 == 1, "Modulus must be odd.");
+        let mut inv: u64 = 1;
+        let mut i = 0;
+        while i < 6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+            i += 1;
+        }
+        inv.wrapping_neg()
+    };
+    // R mod p, the Montgomery form of one.
+    const R: [u64; 4] = power_of_two_mod(256, &M::MODULUS);
+    // R^2 mod p, which converts into Montgomery form.
+    const R2: [u64; 4] = power_of_two_mod(512, &M::MODULUS);
+
+    fn from_limbs(limbs: [u64; 4]) -> Self {
+        Fp256 {
+            limbs,
+            modulus: PhantomData,
+        }
+    }
+
+    /// Creates an element from its canonical value as limbs, least significant first,
+    /// rejecting values that are not reduced modulo p.
+    pub fn from_canonical_limbs(limbs: [u64; 4]) -> Result<Self, ZKError> {
+        if !sub_limbs(&limbs, &M::MODULUS).1 {
+            return Err(ZKError::InvalidFieldElement(
+                "Value is not reduced modulo the 256-bit modulus.".into(),
+            ));
+        }
+        Ok(Self::from_limbs(Self::mont_mul(&limbs, &Self::R2)))
+    }
+
+    /// Parses an element from its canonical value in hexadecimal, with or without a
+    /// `0x` prefix, rejecting values that are not reduced modulo p.
+    pub fn from_hex(hex: &str) -> Result<Self, ZKError> {
+        let limbs = limbs_from_hex(hex).ok_or_else(|| {
+            ZKError::MalformedFieldElement(format!("Invalid 256-bit hexadecimal: {}", hex))
+        })?;
+        Self::from_canonical_limbs(limbs)
+    }
+
+    /// Returns the canonical value in [0, p) as limbs, least significant first.
+    pub fn to_canonical_limbs(&self) -> [u64; 4] {
+        Self::mont_mul(&self.limbs, &[1, 0, 0, 0])
+    }
+
+    /// Raises the element to a 256-bit exponent given as limbs, least significant first.
+    pub fn pow(&self, exponent: &[u64; 4]) -> Self {
+        let mut result = Self::from_limbs(Self::R);
+        for limb in exponent.iter().rev() {
+            for bit in (0..64).rev() {
+                result = Self::from_limbs(Self::mont_mul(&result.limbs, &result.limbs));
+                if (limb >> bit) & 1 == 1 {
+                    result = Self::from_limbs(Self::mont_mul(&result.limbs, &self.limbs));
+                }
+            }
+        }
+        result
+    }
+
+    // Montgomery multiplication a · b · R^(-1) mod p, by coarsely integrated operand
+    // scanning. The extra top limb absorbs carries for moduli close to 2^256.
+    fn mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let p = &M::MODULUS;
+        let mut t = [0u64; 6];
+        for &b_i in b {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], a[j], b_i, carry);
+            }
+            (t[4], carry) = adc(t[4], carry, 0);
+            t[5] = carry;
+
+            let m = t[0].wrapping_mul(Self::INV);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+            }
+            (t[3], carry) = adc(t[4], carry, 0);
+            t[4] = t[5] + carry;
+        }
+
+        let result = [t[0], t[1], t[2], t[3]];
+        let (reduced, borrow) = sub_limbs(&result, p);
+        if t[4] != 0 || !borrow {
+            reduced
+        } else {
+            result
+        }
+    }
+}
+
+impl<M: Modulus256> Debug for Fp256<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fp256({})", self)
+    }
+}
+
+/// Formats the canonical value as 64 hexadecimal digits with a `0x` prefix.
+impl<M: Modulus256> Display for Fp256<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limbs = self.to_canonical_limbs();
+        write!(
+            f,
+            "0x{:016x}{:016x}{:016x}{:016x}",
+            limbs[3], limbs[2], limbs[1], limbs[0]
+        )
+    }
+}
+
+impl<M: Modulus256> Field for Fp256<M> {
+    // The modulus is fixed, so no runtime parameters are needed.
+    type Params = ();
+
+    fn params(&self) {}
+
+    fn zero(_params: &()) -> Self {
+        Self::from_limbs([0; 4])
+    }
+
+    fn one(_params: &()) -> Self {
+        Self::from_limbs(Self::R)
+    }
+
+    fn from_u64(value: u64, _params: &()) -> Self {
+        // Any u64 is below p unless p is tiny, so reduce it through R^2 either way.
+        let reduced = if M::MODULUS[1..].iter().all(|&limb| limb == 0) {
+            value % M::MODULUS[0]
+        } else {
+            value
+        };
+        Self::from_limbs(Self::mont_mul(&[reduced, 0, 0, 0], &Self::R2))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs == [0; 4]
+    }
+
+    fn add(&self, other: &Self) -> Result<Self, ZKError> {
+        let mut sum = [0u64; 4];
+        let mut carry = 0;
+        for (i, limb) in sum.iter_mut().enumerate() {
+            (*limb, carry) = adc(self.limbs[i], other.limbs[i], carry);
+        }
+        let (reduced, borrow) = sub_limbs(&sum, &M::MODULUS);
+        Ok(Self::from_limbs(if carry != 0 || !borrow {
+            reduced
+        } else {
+            sum
+        }))
+    }
+
+    fn sub(&self, other: &Self) -> Result<Self, ZKError> {
+        let (difference, borrow) = sub_limbs(&self.limbs, &other.limbs);
+        if !borrow {
+            return Ok(Self::from_limbs(difference));
+        }
+        // Wrapped below zero, so add p back; the final carry cancels the borrow.
+        let mut result = [0u64; 4];
+        let mut carry = 0;
+        for (i, limb) in result.iter_mut().enumerate() {
+            (*limb, carry) = adc(difference[i], M::MODULUS[i], carry);
+        }
+        Ok(Self::from_limbs(result))
+    }
+
+    fn mul(&self, other: &Self) -> Result<Self, ZKError> {
+        Ok(Self::from_limbs(Self::mont_mul(&self.limbs, &other.limbs)))
+    }
+
+    fn neg(&self) -> Self {
+        if self.is_zero() {
+            return *self;
+        }
+        Self::from_limbs(sub_limbs(&M::MODULUS, &self.limbs).0)
+    }
+
+    // a^(p - 2) by Fermat's little theorem.
+    fn inv(&self) -> Result<Self, ZKError> {
+        if self.is_zero() {
+            return Err(ZKError::InvalidFieldElement(
+                "Zero has no multiplicative inverse.".into(),
+            ));
+        }
+        let (exponent, _) = sub_limbs(&M::MODULUS, &[2, 0, 0, 0]);
+        Ok(self.pow(&exponent))
+    }
+
+    // Rejection sampling of values below the bit length of p, as for `FieldElement`.
+    fn random<R: RngCore + ?Sized>(rng: &mut R, _params: &()) -> Result<Self, ZKError> {
+        let top = M::MODULUS[3];
+        let mask = if top == 0 {
+            0
+        } else {
+            u64::MAX >> top.leading_zeros()
+        };
+        loop {
+            let mut limbs = [0u64; 4];
+            for limb in limbs.iter_mut() {
+                *limb = rng.next_u64();
+            }
+            limbs[3] &= mask;
+            if let Ok(element) = Self::from_canonical_limbs(limbs) {
+                return Ok(element);
+            }
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_canonical_limbs()
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect()
+    }
+
+    fn from_bytes(bytes: &[u8], _params: &()) -> Result<Self, ZKError> {
+        if bytes.len() != 32 {
+            return Err(ZKError::InvalidFieldElement(
+                "Field element encoding must be 32 bytes.".into(),
+            ));
+        }
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().expect("chunks are 8 bytes"));
+        }
+        Self::from_canonical_limbs(limbs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        curves::secp256k1::{BaseField, ScalarField},
+        field::Field,
+    };
+
+    use super::limbs_from_hex;
+
+    type Fq = BaseField;
+
+    #[test]
+    fn test_limbs_from_hex() {
+        assert_eq!(limbs_from_hex("0x1"), Some([1, 0, 0, 0]));
+        assert_eq!(
+            limbs_from_hex("10000000000000000ff"),
+            Some([0xff, 0x100, 0, 0])
+        );
+        assert_eq!(limbs_from_hex(&"f".repeat(64)), Some([u64::MAX; 4]));
+        assert_eq!(limbs_from_hex(&format!("1{}", "0".repeat(64))), None);
+        assert_eq!(limbs_from_hex("0x"), None);
+        assert_eq!(limbs_from_hex("0xg"), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let hex = |s: &str| Fq::from_hex(s).unwrap();
+        let a = hex("0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let b = hex("0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+
+        // Expected values computed with arbitrary-precision integers.
+        assert_eq!(
+            a.add(&b).unwrap(),
+            hex("0xc1f940f620808011b3455e91dc9813afffb3b123d4537cf2f63a51eb1208ec50")
+        );
+        assert_eq!(
+            b.sub(&a).unwrap(),
+            hex("0xce7c73f82cc708b9080499663f89fda1fa7bb76d78b72b4042554f33e418b94f")
+        );
+        assert_eq!(
+            a.mul(&b).unwrap(),
+            hex("0xfd3dc529c6eb60fb9d166034cf3c1a5a72324aa9dfd3428a56d7e1ce0179fd9b")
+        );
+
+        // p - 1 + 1 wraps to zero, and inverses and negations cancel.
+        let minus_one = hex("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e");
+        let one = Fq::one(&());
+        assert!(minus_one.add(&one).unwrap().is_zero());
+        assert_eq!(minus_one, one.neg());
+        assert_eq!(a.mul(&a.inv().unwrap()).unwrap(), one);
+        assert!(Fq::zero(&()).inv().is_err());
+        assert_eq!(Fq::from_u64(7, &()).exp(2).unwrap(), Fq::from_u64(49, &()));
+        assert_eq!(Fq::from_i64(-1, &()), minus_one);
+
+        // Encodings are canonical.
+        assert_eq!(Fq::from_bytes(&a.to_bytes(), &()).unwrap(), a);
+        assert!(
+            Fq::from_hex("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+                .is_err()
+        );
+        assert!(Fq::from_bytes(&[0xff; 32], &()).is_err());
+        assert_eq!(
+            a.to_string(),
+            "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+
+        let mut rng = rand::rng();
+        for _ in 0..10 {
+            let x = ScalarField::random(&mut rng, &()).unwrap();
+            assert_eq!(ScalarField::from_bytes(&x.to_bytes(), &()).unwrap(), x);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{errors::ZKError, field::Field, fp2::Fp2};
+
+/// Represents an element c0 + c1·v + c2·v^2 of the cubic extension Fp6 = Fp2[v] / (v^3 - ξ),
+/// where ξ is neither a square nor a cube in Fp2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fp6 {
+    pub c0: Fp2,
+    pub c1: Fp2,
+    pub c2: Fp2,
+    // The non-residue ξ defining the extension.
+    pub non_residue: Fp2,
+}
+
+impl Fp6 {
+    /// Creates a new Fp6 element.
+    pub fn new(c0: Fp2, c1: Fp2, c2: Fp2, non_residue: Fp2) -> Result<Self, ZKError> {
+        if c0.non_residue != non_residue.non_residue
+            || c1.non_residue != non_residue.non_residue
+            || c2.non_residue != non_residue.non_residue
+        {
+            return Err(ZKError::InvalidFieldElement(
+                "Fp6 coefficients and non-residue must belong to the same Fp2.".into(),
+            ));
+        }
+        Ok(Fp6 {
+            c0,
+            c1,
+            c2,
+            non_residue,
+        })
+    }
+
+    /// Returns the additive identity of the extension defined by `non_residue`.
+    pub fn zero(non_residue: &Fp2) -> Self {
+        let zero = Fp2::zero(&non_residue.non_residue);
+        Fp6 {
+            c0: zero.clone(),
+            c1: zero.clone(),
+            c2: zero,
+            non_residue: non_residue.clone(),
+        }
+    }
+
+    /// Returns the multiplicative identity of the extension defined by `non_residue`.
+    pub fn one(non_residue: &Fp2) -> Self {
+        let mut one = Self::zero(non_residue);
+        one.c0 = Fp2::one(&non_residue.non_residue);
+        one
+    }
+
+    /// Embeds an Fp2 element into Fp6.
+    pub fn from_fp2(c0: Fp2, non_residue: &Fp2) -> Result<Self, ZKError> {
+        let zero = Fp2::zero(&non_residue.non_residue);
+        Self::new(c0, zero.clone(), zero, non_residue.clone())
+    }
+
+    /// Returns true if the element is zero.
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero() && self.c2.is_zero()
+    }
+
+    /// Add two Fp6 elements.
+    pub fn add(&self, other: &Fp6) -> Result<Fp6, ZKError> {
+        self.check_same_field(other, "addition")?;
+        Ok(self.with_coefficients(
+            self.c0.add(&other.c0)?,
+            self.c1.add(&other.c1)?,
+            self.c2.add(&other.c2)?,
+        ))
+    }
+
+    /// Subtract two Fp6 elements.
+    pub fn sub(&self, other: &Fp6) -> Result<Fp6, ZKError> {
+        self.check_same_field(other, "subtraction")?;
+        Ok(self.with_coefficients(
+            self.c0.sub(&other.c0)?,
+            self.c1.sub(&other.c1)?,
+            self.c2.sub(&other.c2)?,
+        ))
+    }
+
+    /// Negate the element.
+    pub fn neg(&self) -> Fp6 {
+        self.with_coefficients(self.c0.neg(), self.c1.neg(), self.c2.neg())
+    }
+
+    /// Multiply two Fp6 elements.
+    pub fn mul(&self, other: &Fp6) -> Result<Fp6, ZKError> {
+        self.check_same_field(other, "multiplication")?;
+        let xi = &self.non_residue;
+
+        // Karatsuba-style products, reducing with v^3 = ξ.
+        let v0 = self.c0.mul(&other.c0)?;
+        let v1 = self.c1.mul(&other.c1)?;
+        let v2 = self.c2.mul(&other.c2)?;
+
+        // c0 = v0 + ξ((a1 + a2)(b1 + b2) - v1 - v2)
+        let c0 = self
+            .c1
+            .add(&self.c2)?
+            .mul(&other.c1.add(&other.c2)?)?
+            .sub(&v1)?
+            .sub(&v2)?
+            .mul(xi)?
+            .add(&v0)?;
+        // c1 = (a0 + a1)(b0 + b1) - v0 - v1 + ξ·v2
+        let c1 = self
+            .c0
+            .add(&self.c1)?
+            .mul(&other.c0.add(&other.c1)?)?
+            .sub(&v0)?
+            .sub(&v1)?
+            .add(&v2.mul(xi)?)?;
+        // c2 = (a0 + a2)(b0 + b2) - v0 - v2 + v1
+        let c2 = self
+            .c0
+            .add(&self.c2)?
+            .mul(&other.c0.add(&other.c2)?)?
+            .sub(&v0)?
+            .sub(&v2)?
+            .add(&v1)?;
+
+        Ok(self.with_coefficients(c0, c1, c2))
+    }
+
+    /// Square the element.
+    pub fn square(&self) -> Result<Fp6, ZKError> {
+        self.mul(self)
+    }
+
+    /// Multiply the element by an Fp2 scalar.
+    pub fn mul_by_fp2(&self, scalar: &Fp2) -> Result<Fp6, ZKError> {
+        Ok(self.with_coefficients(
+            self.c0.mul(scalar)?,
+            self.c1.mul(scalar)?,
+            self.c2.mul(scalar)?,
+        ))
+    }
+
+    /// Multiply the element by v, i.e. (c0, c1, c2) ↦ (ξ·c2, c0, c1).
+    pub fn mul_by_non_residue(&self) -> Result<Fp6, ZKError> {
+        Ok(self.with_coefficients(
+            self.c2.mul(&self.non_residue)?,
+            self.c0.clone(),
+            self.c1.clone(),
+        ))
+    }
+
+    /// Find the multiplicative inverse of the element.
+    pub fn inv(&self) -> Result<Fp6, ZKError> {
+        let xi = &self.non_residue;
+
+        // t0 = a0^2 - ξ·a1·a2, t1 = ξ·a2^2 - a0·a1, t2 = a1^2 - a0·a2
+        let t0 = self.c0.square().sub(&self.c1.mul(&self.c2)?.mul(xi)?)?;
+        let t1 = self.c2.square().mul(xi)?.sub(&self.c0.mul(&self.c1)?)?;
+        let t2 = self.c1.square().sub(&self.c0.mul(&self.c2)?)?;
+
+        // det = a0·t0 + ξ(a2·t1 + a1·t2), an element of Fp2.
+        let det = self
+            .c2
+            .mul(&t1)?
+            .add(&self.c1.mul(&t2)?)?
+            .mul(xi)?
+            .add(&self.c0.mul(&t0)?)?;
+        let det_inv = det.inv()?;
+
+        Ok(self.with_coefficients(t0.mul(&det_inv)?, t1.mul(&det_inv)?, t2.mul(&det_inv)?))
+    }
+
+    /// Applies the Frobenius endomorphism x ↦ x^(p^power).
+    pub fn frobenius_map(&self, power: usize) -> Result<Fp6, ZKError> {
+        self.frobenius_map_with(power, &FrobeniusCoefficients::new(&self.non_residue)?)
+    }
+
+    /// Applies the Frobenius endomorphism using precomputed coefficients:
+    /// (c0 + c1·v + c2·v^2)^(p^k) = c0^(p^k) + c1^(p^k)·γ_k^2·v + c2^(p^k)·γ_k^4·v^2.
+    pub fn frobenius_map_with(
+        &self,
+        power: usize,
+        coefficients: &FrobeniusCoefficients,
+    ) -> Result<Fp6, ZKError> {
+        let k = power % 12;
+        Ok(self.with_coefficients(
+            self.c0.frobenius_map(k),
+            self.c1.frobenius_map(k).mul(&coefficients.v[k])?,
+            self.c2.frobenius_map(k).mul(&coefficients.v2[k])?,
+        ))
+    }
+
+    /// Exponentiate the element by the provided exponent.
+    pub fn exp(&self, exponent: u64) -> Result<Fp6, ZKError> {
+        let mut result = Self::one(&self.non_residue);
+        let mut base = self.clone();
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base)?;
+            }
+            base = base.square()?;
+            exp >>= 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Serializes the coefficients c0, c1, c2 as little-endian bytes, in that order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Field::to_bytes(&self.c0);
+        bytes.extend(Field::to_bytes(&self.c1));
+        bytes.extend(Field::to_bytes(&self.c2));
+        bytes
+    }
+
+    /// Deserializes an element of the extension defined by `non_residue` from the output
+    /// of `to_bytes`, rejecting non-canonical input.
+    pub fn from_bytes(bytes: &[u8], non_residue: &Fp2) -> Result<Self, ZKError> {
+        if !bytes.len().is_multiple_of(3) {
+            return Err(ZKError::InvalidFieldElement(
+                "Fp6 encoding must have a length divisible by three.".into(),
+            ));
+        }
+        let (c0, rest) = bytes.split_at(bytes.len() / 3);
+        let (c1, c2) = rest.split_at(bytes.len() / 3);
+        let beta = &non_residue.non_residue;
+        Self::new(
+            <Fp2 as Field>::from_bytes(c0, beta)?,
+            <Fp2 as Field>::from_bytes(c1, beta)?,
+            <Fp2 as Field>::from_bytes(c2, beta)?,
+            non_residue.clone(),
+        )
+    }
+
+    fn with_coefficients(&self, c0: Fp2, c1: Fp2, c2: Fp2) -> Fp6 {
+        Fp6 {
+            c0,
+            c1,
+            c2,
+            non_residue: self.non_residue.clone(),
+        }
+    }
+
+    fn check_same_field(&self, other: &Fp6, operation: &str) -> Result<(), ZKError> {
+        if self.non_residue != other.non_residue {
+            return Err(ZKError::InvalidFieldElement(format!(
+                "Fp6 elements must belong to the same extension for {}.",
+                operation
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Precomputed Frobenius coefficients for the tower Fp2 → Fp6 → Fp12 with Fp6 non-residue ξ.
+///
+/// With w^6 = v^3 = ξ, raising the generators to p^k gives w^(p^k) = γ_k·w,
+/// v^(p^k) = γ_k^2·v and v^(2p^k) = γ_k^4·v^2, where γ_k = ξ^((p^k - 1) / 6).
+/// Coefficients are stored for k = 0..12, as the Frobenius map of Fp12 has order 12.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrobeniusCoefficients {
+    // γ_k, used for the w coefficient of Fp12.
+    pub w: Vec<Fp2>,
+    // γ_k^2, used for the v coefficient of Fp6.
+    pub v: Vec<Fp2>,
+    // γ_k^4, used for the v^2 coefficient of Fp6.
+    pub v2: Vec<Fp2>,
+}
+
+impl FrobeniusCoefficients {
+    /// Computes the coefficients for the given Fp6 non-residue ξ.
+    /// The base field modulus must satisfy p ≡ 1 mod 6, as for BN and BLS curves.
+    pub fn new(non_residue: &Fp2) -> Result<Self, ZKError> {
+        let p = non_residue.c0.modulus;
+        if p % 6 != 1 {
+            return Err(ZKError::InvalidFieldElement(
+                "Frobenius coefficients require p ≡ 1 mod 6.".into(),
+            ));
+        }
+
+        // γ_k = γ_(k-1)^p · γ_1, since (p^k - 1) / 6 = p·(p^(k-1) - 1) / 6 + (p - 1) / 6.
+        let gamma_1 = non_residue.exp((p - 1) / 6)?;
+        let mut w = vec![Fp2::one(&non_residue.non_residue)];
+        for k in 1..12 {
+            let previous = &w[k - 1];
+            w.push(previous.exp(p)?.mul(&gamma_1)?);
+        }
+
+        let v: Vec<Fp2> = w.iter().map(|gamma| gamma.square()).collect();
+        let v2 = v.iter().map(|gamma| gamma.square()).collect();
+
+        Ok(FrobeniusCoefficients { w, v, v2 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldElement;
+
+    const MODULUS: u64 = 103;
+
+    fn fp2(c0: u64, c1: u64) -> Fp2 {
+        Fp2::new(
+            FieldElement::new(c0, MODULUS).unwrap(),
+            FieldElement::new(c1, MODULUS).unwrap(),
+            FieldElement::new(MODULUS - 1, MODULUS).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn fp6(c: [u64; 6]) -> Fp6 {
+        // ξ = 2 + u is neither a square nor a cube in Fp2 for p = 103.
+        Fp6::new(fp2(c[0], c[1]), fp2(c[2], c[3]), fp2(c[4], c[5]), fp2(2, 1)).unwrap()
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let a = fp6([1, 2, 3, 4, 5, 102]);
+        let bytes = a.to_bytes();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(Fp6::from_bytes(&bytes, &fp2(2, 1)).unwrap(), a);
+        assert!(Fp6::from_bytes(&bytes[..47], &fp2(2, 1)).is_err());
+
+        // 103 is not a canonical coefficient.
+        let mut bytes = bytes;
+        bytes[0] = 103;
+        assert!(Fp6::from_bytes(&bytes, &fp2(2, 1)).is_err());
+    }
+
+    #[test]
+    fn test_mul_by_v() {
+        // v · v^2 = v^3 = ξ.
+        let v = fp6([0, 0, 1, 0, 0, 0]);
+        let v2 = fp6([0, 0, 0, 0, 1, 0]);
+        assert_eq!(v.mul(&v2).unwrap(), fp6([2, 1, 0, 0, 0, 0]));
+        assert_eq!(v2.mul_by_non_residue().unwrap(), fp6([2, 1, 0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_mul_commutative_and_distributive() {
+        let a = fp6([1, 2, 3, 4, 5, 6]);
+        let b = fp6([7, 8, 9, 10, 11, 12]);
+        let c = fp6([13, 14, 15, 16, 17, 18]);
+        assert_eq!(a.mul(&b).unwrap(), b.mul(&a).unwrap());
+        assert_eq!(
+            a.mul(&b.add(&c).unwrap()).unwrap(),
+            a.mul(&b).unwrap().add(&a.mul(&c).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_inv() {
+        let a = fp6([3, 1, 4, 1, 5, 9]);
+        let one = a.mul(&a.inv().unwrap()).unwrap();
+        assert_eq!(one, Fp6::one(&fp2(2, 1)));
+        assert!(Fp6::zero(&fp2(2, 1)).inv().is_err());
+    }
+
+    #[test]
+    fn test_frobenius_map() {
+        let a = fp6([3, 1, 4, 1, 5, 9]);
+        let coefficients = FrobeniusCoefficients::new(&fp2(2, 1)).unwrap();
+
+        let mut expected = a.clone();
+        for power in 1..=6 {
+            expected = expected.exp(MODULUS).unwrap();
+            assert_eq!(
+                a.frobenius_map_with(power, &coefficients).unwrap(),
+                expected
+            );
+        }
+        // The Frobenius map of Fp6 has order 6.
+        assert_eq!(a.frobenius_map(6).unwrap(), a);
+    }
+}
@@ -0,0 +1,542 @@
+use crate::{
+    builder::{CircuitBuilder, Hint, Var},
+    circuit::LinearCombination,
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// The number of constraints and variables a gadget adds to a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Cost {
+    pub constraints: usize,
+    pub variables: usize,
+}
+
+/// A reusable piece of circuit: it takes input variables, adds its constraints and
+/// returns output variables, and can report what that costs before it runs.
+pub trait Gadget<F: Field = FieldElement> {
+    /// The variables the gadget reads, e.g. `Var` or `Vec<UInt8>`.
+    type Input: Clone;
+    /// The variables holding the results.
+    type Output;
+
+    /// Adds the gadget's variables and constraints to `builder`.
+    fn synthesize(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        input: Self::Input,
+    ) -> Result<Self::Output, ZKError>;
+
+    /// Returns what `synthesize` would add to `builder` for `input`, without changing
+    /// it. By default this synthesizes into a copy of the builder, which is exact but
+    /// costs as much as the gadget; gadgets with a closed form override it.
+    fn cost(&self, builder: &CircuitBuilder<F>, input: &Self::Input) -> Result<Cost, ZKError> {
+        let mut scratch = builder.clone();
+        self.synthesize(&mut scratch, input.clone())?;
+        let (before, after) = (builder.constraint_system(), scratch.constraint_system());
+        Ok(Cost {
+            constraints: after.constraints.len() - before.constraints.len(),
+            variables: after.num_variables - before.num_variables,
+        })
+    }
+}
+
+/// Decomposes a variable into `num_bits` bits, see `CircuitBuilder::to_bits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToBits {
+    pub num_bits: usize,
+}
+
+/// Compares two variables known to lie in [0, 2^num_bits), see
+/// `CircuitBuilder::less_than`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LessThan {
+    pub num_bits: usize,
+}
+
+/// Tests a variable for zero, see `CircuitBuilder::is_zero`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsZero;
+
+/// Checks that two lists of variables are permutations of each other under a
+/// challenge, see `CircuitBuilder::enforce_permutation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permutation;
+
+// Gadgets built on `CircuitBuilder`. Each allocates its own intermediate variables with
+// hints, so `generate_witness` fills them in, and returns variables constrained to hold
+// its results. Boolean results are constrained to 0 or 1.
+impl<F: Field> CircuitBuilder<F> {
+    /// Constrains `var` to be 0 or 1: var · (var − 1) = 0.
+    pub fn enforce_boolean(&mut self, var: Var) {
+        let minus_one = self.lc(&[
+            (var, F::one(&self.params)),
+            (self.one(), F::one(&self.params).neg()),
+        ]);
+        self.emit(self.single(var), minus_one, LinearCombination::new());
+    }
+
+    /// Decomposes `var` into `num_bits` boolean variables, least significant first, and
+    /// constrains them to recompose to `var`. This also proves var < 2^num_bits.
+    pub fn to_bits(&mut self, var: Var, num_bits: usize) -> Result<Vec<Var>, ZKError> {
+        self.check_bits(num_bits)?;
+        let mut bits = Vec::with_capacity(num_bits);
+        let mut terms = Vec::with_capacity(num_bits);
+        let mut weight = F::one(&self.params);
+        for i in 0..num_bits {
+            let bit = self.allocate(Hint::Bit(var, i));
+            self.enforce_boolean(bit);
+            terms.push((bit, weight.clone()));
+            weight = weight.add(&weight)?;
+            bits.push(bit);
+        }
+        // sum 2^i · b_i · 1 = var.
+        self.emit(self.lc(&terms), self.single(self.one()), self.single(var));
+        Ok(bits)
+    }
+
+    /// Constrains `var` to lie in [0, 2^num_bits).
+    pub fn range_check(&mut self, var: Var, num_bits: usize) -> Result<(), ZKError> {
+        self.to_bits(var, num_bits).map(|_| ())
+    }
+
+    /// Returns a boolean that is 1 if and only if a < b, for a and b known to lie in
+    /// [0, 2^num_bits), e.g. through `range_check`; for other inputs the result is
+    /// meaningless.
+    ///
+    /// The difference d = a − b + 2^num_bits lies in [1, 2^(num_bits + 1)), and its top
+    /// bit is set exactly when a ≥ b. Decomposing d range checks it.
+    pub fn less_than(&mut self, a: Var, b: Var, num_bits: usize) -> Result<Var, ZKError> {
+        let offset = F::from_u64(2, &self.params).exp(num_bits as u64)?;
+        let difference = self.lc(&[
+            (a, F::one(&self.params)),
+            (b, F::one(&self.params).neg()),
+            (self.one(), offset),
+        ]);
+        let d = self.allocate(Hint::Linear(difference.clone()));
+        self.emit(difference, self.single(self.one()), self.single(d));
+
+        let bits = self.to_bits(d, num_bits + 1)?;
+        Ok(self.not(bits[num_bits]))
+    }
+
+    /// Returns a boolean that is 1 if and only if a ≤ b; see `less_than`.
+    pub fn less_or_equal(&mut self, a: Var, b: Var, num_bits: usize) -> Result<Var, ZKError> {
+        let greater = self.less_than(b, a, num_bits)?;
+        Ok(self.not(greater))
+    }
+
+    /// Returns a boolean that is 1 if and only if a > b; see `less_than`.
+    pub fn greater_than(&mut self, a: Var, b: Var, num_bits: usize) -> Result<Var, ZKError> {
+        self.less_than(b, a, num_bits)
+    }
+
+    /// Returns a boolean that is 1 if and only if var = 0.
+    ///
+    /// The prover supplies the inverse of var (zero when var is zero), and the
+    /// constraints var · inv = 1 − z and var · z = 0 leave z no choice: a nonzero var
+    /// forces z = 0, and var = 0 forces z = 1.
+    pub fn is_zero(&mut self, var: Var) -> Var {
+        let inverse = self.allocate(Hint::Inverse(var));
+        let z = self.allocate(Hint::IsZero(var));
+        let complement = self.lc(&[
+            (self.one(), F::one(&self.params)),
+            (z, F::one(&self.params).neg()),
+        ]);
+        self.emit(self.single(var), self.single(inverse), complement);
+        self.emit(self.single(var), self.single(z), LinearCombination::new());
+        z
+    }
+
+    /// Returns a boolean that is 1 if and only if a = b.
+    pub fn is_equal(&mut self, a: Var, b: Var) -> Var {
+        let difference = self.sub(a, b);
+        self.is_zero(difference)
+    }
+
+    /// Returns the inverse of var, constrained by var · inv = 1, which also proves that
+    /// var is nonzero.
+    pub fn inv(&mut self, var: Var) -> Var {
+        let inverse = self.allocate(Hint::Inverse(var));
+        self.emit(
+            self.single(var),
+            self.single(inverse),
+            self.single(self.one()),
+        );
+        inverse
+    }
+
+    /// Returns a / b, constrained by b · q = a. Without a nonzero b, any q would
+    /// satisfy 0 · q = 0, so b is also constrained to have an inverse.
+    pub fn div(&mut self, a: Var, b: Var) -> Var {
+        let quotient = self.allocate(Hint::Quotient(self.single(a), self.single(b)));
+        self.emit(self.single(b), self.single(quotient), self.single(a));
+        self.inv(b);
+        quotient
+    }
+
+    /// Constrains `b` to be a permutation of `a`, with the randomized grand product
+    /// prod (a_i + γ) = prod (b_i + γ) for the challenge γ, in 2 · len constraints.
+    ///
+    /// Both products are polynomials in γ, equal exactly when the multisets agree, so a
+    /// non-permutation passes with probability at most len / p over the choice of γ. The
+    /// challenge must therefore be random and chosen after a and b are fixed, e.g. a
+    /// public input derived from a commitment to the witness by Fiat–Shamir.
+    pub fn enforce_permutation(
+        &mut self,
+        a: &[Var],
+        b: &[Var],
+        challenge: Var,
+    ) -> Result<(), ZKError> {
+        if a.len() != b.len() {
+            return Err(ZKError::CircuitError(format!(
+                "Cannot permute {} values into {}.",
+                a.len(),
+                b.len()
+            )));
+        }
+        if a.is_empty() {
+            return Ok(());
+        }
+        let lhs = self.grand_product(a, challenge);
+        let rhs = self.grand_product(b, challenge);
+        self.enforce_equal(lhs, rhs);
+        Ok(())
+    }
+
+    // Returns prod (values_i + challenge) for a nonempty slice.
+    fn grand_product(&mut self, values: &[Var], challenge: Var) -> Var {
+        let one = F::one(&self.params);
+        let factor = |builder: &Self, value: Var| {
+            builder.lc(&[(value, one.clone()), (challenge, one.clone())])
+        };
+
+        let first = factor(self, values[0]);
+        let mut product = self.allocate(Hint::Linear(first.clone()));
+        self.emit(first, self.single(self.one()), self.single(product));
+        for &value in &values[1..] {
+            let factor = factor(self, value);
+            let hint = Hint::Quadratic(
+                self.single(product),
+                factor.clone(),
+                LinearCombination::new(),
+            );
+            let next = self.allocate(hint);
+            self.emit(self.single(product), factor, self.single(next));
+            product = next;
+        }
+        product
+    }
+
+    // Beyond the capacity, the weighted sum of `to_bits` could wrap around the modulus and
+    // a value would have several decompositions.
+    fn check_bits(&self, num_bits: usize) -> Result<(), ZKError> {
+        if num_bits > self.capacity() {
+            return Err(ZKError::CircuitError(format!(
+                "Cannot decompose into {} bits; the field holds only {}.",
+                num_bits,
+                self.capacity()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns 1 − bit, the negation of a boolean.
+    pub fn not(&mut self, bit: Var) -> Var {
+        let complement = self.lc(&[
+            (self.one(), F::one(&self.params)),
+            (bit, F::one(&self.params).neg()),
+        ]);
+        let result = self.allocate(Hint::Linear(complement.clone()));
+        self.emit(complement, self.single(self.one()), self.single(result));
+        result
+    }
+}
+
+impl<F: Field> Gadget<F> for ToBits {
+    type Input = Var;
+    type Output = Vec<Var>;
+
+    fn synthesize(&self, builder: &mut CircuitBuilder<F>, var: Var) -> Result<Vec<Var>, ZKError> {
+        builder.to_bits(var, self.num_bits)
+    }
+
+    // One boolean constraint per bit, and the recomposition.
+    fn cost(&self, builder: &CircuitBuilder<F>, _var: &Var) -> Result<Cost, ZKError> {
+        builder.check_bits(self.num_bits)?;
+        Ok(Cost {
+            constraints: self.num_bits + 1,
+            variables: self.num_bits,
+        })
+    }
+}
+
+impl<F: Field> Gadget<F> for LessThan {
+    type Input = (Var, Var);
+    type Output = Var;
+
+    fn synthesize(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        (a, b): (Var, Var),
+    ) -> Result<Var, ZKError> {
+        builder.less_than(a, b, self.num_bits)
+    }
+
+    // The difference, its num_bits + 1 bits, and the negated top bit.
+    fn cost(&self, builder: &CircuitBuilder<F>, _input: &(Var, Var)) -> Result<Cost, ZKError> {
+        let bits = ToBits {
+            num_bits: self.num_bits + 1,
+        };
+        let bits = Gadget::<F>::cost(&bits, builder, &builder.one())?;
+        Ok(Cost {
+            constraints: bits.constraints + 2,
+            variables: bits.variables + 2,
+        })
+    }
+}
+
+impl<F: Field> Gadget<F> for IsZero {
+    type Input = Var;
+    type Output = Var;
+
+    fn synthesize(&self, builder: &mut CircuitBuilder<F>, var: Var) -> Result<Var, ZKError> {
+        Ok(builder.is_zero(var))
+    }
+
+    // The inverse and the result.
+    fn cost(&self, _builder: &CircuitBuilder<F>, _var: &Var) -> Result<Cost, ZKError> {
+        Ok(Cost {
+            constraints: 2,
+            variables: 2,
+        })
+    }
+}
+
+impl<F: Field> Gadget<F> for Permutation {
+    /// The two lists and the challenge.
+    type Input = (Vec<Var>, Vec<Var>, Var);
+    type Output = ();
+
+    fn synthesize(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        (a, b, challenge): Self::Input,
+    ) -> Result<(), ZKError> {
+        builder.enforce_permutation(&a, &b, challenge)
+    }
+
+    // A running product per list, and their equality.
+    fn cost(&self, _builder: &CircuitBuilder<F>, (a, _, _): &Self::Input) -> Result<Cost, ZKError> {
+        if a.is_empty() {
+            return Ok(Cost::default());
+        }
+        Ok(Cost {
+            constraints: 2 * a.len() + 1,
+            variables: 2 * a.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldElement;
+
+    fn fe(value: u64) -> FieldElement {
+        FieldElement::new(value, 97).unwrap()
+    }
+
+    // Checks that the cost a gadget reports is what it adds.
+    fn check_cost<G: Gadget>(gadget: &G, builder: &mut CircuitBuilder, input: G::Input) {
+        let cost = gadget.cost(builder, &input).unwrap();
+        let (constraints, variables) = {
+            let cs = builder.constraint_system();
+            (cs.constraints.len(), cs.num_variables)
+        };
+        gadget.synthesize(builder, input).unwrap();
+        let cs = builder.constraint_system();
+        assert_eq!(
+            cost,
+            Cost {
+                constraints: cs.constraints.len() - constraints,
+                variables: cs.num_variables - variables,
+            }
+        );
+    }
+
+    #[test]
+    fn test_gadget_costs() {
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let challenge = builder.public_input().unwrap();
+        let a = builder.private_witness();
+        let b = builder.private_witness();
+        check_cost(&ToBits { num_bits: 5 }, &mut builder, a);
+        check_cost(&LessThan { num_bits: 4 }, &mut builder, (a, b));
+        check_cost(&IsZero, &mut builder, a);
+        check_cost(
+            &Permutation,
+            &mut builder,
+            (vec![a, b], vec![b, a], challenge),
+        );
+        check_cost(&Permutation, &mut builder, (vec![], vec![], challenge));
+        // Like synthesis, costing fails beyond the 6 bits that F_97 holds.
+        assert!(ToBits { num_bits: 7 }.cost(&builder, &a).is_err());
+        assert!(LessThan { num_bits: 6 }.cost(&builder, &(a, b)).is_err());
+        check_cost(&LessThan { num_bits: 5 }, &mut builder, (a, b));
+
+        // The default cost runs the gadget on a copy, leaving the builder unchanged.
+        struct Square;
+        impl Gadget for Square {
+            type Input = Var;
+            type Output = Var;
+
+            fn synthesize(&self, builder: &mut CircuitBuilder, var: Var) -> Result<Var, ZKError> {
+                Ok(builder.mul(var, var))
+            }
+        }
+        let before = builder.constraint_system().clone();
+        let cost = Square.cost(&builder, &a).unwrap();
+        assert_eq!(
+            cost,
+            Cost {
+                constraints: 1,
+                variables: 1
+            }
+        );
+        assert_eq!(builder.constraint_system(), &before);
+        check_cost(&Square, &mut builder, a);
+    }
+
+    #[test]
+    fn test_to_bits() {
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        // 96 = 0b1100000, so 6-bit values always fit below 97.
+        assert_eq!(builder.capacity(), 6);
+
+        let x = builder.private_witness();
+        let bits = builder.to_bits(x, 5).unwrap();
+        assert!(builder.to_bits(x, 7).is_err());
+
+        let witness = builder.generate_witness(&[], &[fe(22)]).unwrap();
+        let values: Vec<FieldElement> = bits.iter().map(|b| witness[b.index()].clone()).collect();
+        assert_eq!(values, [0, 1, 1, 0, 1].map(fe));
+
+        // 32 does not fit in 5 bits.
+        assert!(builder.generate_witness(&[], &[fe(32)]).is_err());
+
+        // A non-boolean "bit" is rejected even if the weighted sum matches.
+        let mut forged = witness.clone();
+        forged[bits[0].index()] = fe(2);
+        forged[bits[1].index()] = fe(0);
+        assert!(builder.constraint_system().evaluate(&forged).is_err());
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let a = builder.private_witness();
+        let b = builder.private_witness();
+        builder.range_check(a, 5).unwrap();
+        builder.range_check(b, 5).unwrap();
+        let lt = builder.less_than(a, b, 5).unwrap();
+        let le = builder.less_or_equal(a, b, 5).unwrap();
+        let gt = builder.greater_than(a, b, 5).unwrap();
+        // The difference needs one bit more than the inputs.
+        assert!(builder.less_than(a, b, 6).is_err());
+
+        let boolean = |condition: bool| fe(condition as u64);
+        for x in (0..32).step_by(3) {
+            for y in (0..32).step_by(5) {
+                let witness = builder.generate_witness(&[], &[fe(x), fe(y)]).unwrap();
+                assert_eq!(witness[lt.index()], boolean(x < y));
+                assert_eq!(witness[le.index()], boolean(x <= y));
+                assert_eq!(witness[gt.index()], boolean(x > y));
+            }
+        }
+        assert!(builder.generate_witness(&[], &[fe(32), fe(0)]).is_err());
+
+        // Claiming the wrong result breaks the constraints.
+        let mut witness = builder.generate_witness(&[], &[fe(4), fe(9)]).unwrap();
+        witness[lt.index()] = fe(0);
+        assert!(builder.constraint_system().evaluate(&witness).is_err());
+    }
+
+    #[test]
+    fn test_inv_and_div() {
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let a = builder.private_witness();
+        let b = builder.private_witness();
+        let inverse = builder.inv(a);
+        let quotient = builder.div(a, b);
+
+        let witness = builder.generate_witness(&[], &[fe(10), fe(5)]).unwrap();
+        assert_eq!(witness[quotient.index()], fe(2));
+        assert_eq!(witness[inverse.index()].clone() * fe(10), fe(1));
+        let witness = builder.generate_witness(&[], &[fe(1), fe(2)]).unwrap();
+        assert_eq!(witness[quotient.index()], fe(49));
+
+        // Zero has no inverse, and nothing may be divided by zero.
+        assert!(builder.generate_witness(&[], &[fe(0), fe(5)]).is_err());
+        assert!(builder.generate_witness(&[], &[fe(10), fe(0)]).is_err());
+
+        // 0 / 0 cannot be forged to an arbitrary quotient either.
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let a = builder.private_witness();
+        let b = builder.private_witness();
+        let quotient = builder.div(a, b);
+        let forged: Vec<FieldElement> = [1, 0, 0, 42, 0].map(fe).to_vec();
+        assert_eq!(quotient.index(), 3);
+        assert!(builder.constraint_system().evaluate(&forged).is_err());
+    }
+
+    #[test]
+    fn test_enforce_permutation() {
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let challenge = builder.public_input().unwrap();
+        let a: Vec<Var> = (0..4).map(|_| builder.private_witness()).collect();
+        let b: Vec<Var> = (0..4).map(|_| builder.private_witness()).collect();
+        builder.enforce_permutation(&a, &b, challenge).unwrap();
+        assert_eq!(builder.constraint_system().constraints.len(), 9);
+        assert!(builder.enforce_permutation(&a, &b[1..], challenge).is_err());
+        builder.enforce_permutation(&[], &[], challenge).unwrap();
+
+        let values = |v: [u64; 8]| v.map(fe).to_vec();
+        for gamma in [0, 1, 42] {
+            let inputs = values([3, 1, 4, 1, 1, 4, 3, 1]);
+            assert!(builder.generate_witness(&[fe(gamma)], &inputs).is_ok());
+            // Multiplicities matter: {3, 1, 4, 1} is not {3, 4, 4, 1}.
+            let inputs = values([3, 1, 4, 1, 3, 4, 4, 1]);
+            assert!(builder.generate_witness(&[fe(gamma)], &inputs).is_err());
+        }
+    }
+
+    #[test]
+    fn test_is_zero_and_is_equal() {
+        let mut builder = CircuitBuilder::new(&97).unwrap();
+        let a = builder.private_witness();
+        let b = builder.private_witness();
+        let zero = builder.is_zero(a);
+        let equal = builder.is_equal(a, b);
+
+        for (x, y) in [(0, 0), (0, 5), (5, 5), (96, 1), (13, 96)] {
+            let witness = builder.generate_witness(&[], &[fe(x), fe(y)]).unwrap();
+            assert_eq!(witness[zero.index()], fe((x == 0) as u64));
+            assert_eq!(witness[equal.index()], fe((x == y) as u64));
+        }
+
+        // Neither answer can be flipped, whatever inverse the prover claims.
+        let witness = builder.generate_witness(&[], &[fe(0), fe(7)]).unwrap();
+        for inverse in [0, 1, 50] {
+            let mut forged = witness.clone();
+            forged[zero.index()] = fe(0);
+            forged[zero.index() - 1] = fe(inverse);
+            assert!(builder.constraint_system().evaluate(&forged).is_err());
+        }
+        let witness = builder.generate_witness(&[], &[fe(3), fe(7)]).unwrap();
+        let mut forged = witness.clone();
+        forged[zero.index()] = fe(1);
+        forged[zero.index() - 1] = fe(0);
+        assert!(builder.constraint_system().evaluate(&forged).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use sha2::{Digest, Sha256};
+
+use crate::{
+    curve::{EllipticCurve, EllipticCurvePoint},
+    errors::ZKError,
+    field::FieldElement,
+};
+
+// Output and block sizes of SHA-256 in bytes.
+const HASH_BYTES: usize = 32;
+const BLOCK_BYTES: usize = 64;
+
+// Target security level in bits; each element is derived from this many extra bits
+// so that the bias of the modular reduction is negligible.
+const SECURITY_BITS: usize = 128;
+
+/// Expands `msg` into `len_in_bytes` uniformly random bytes bound to the domain
+/// separation tag `dst`, following expand_message_xmd from RFC 9380 with SHA-256.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ZKError> {
+    let ell = len_in_bytes.div_ceil(HASH_BYTES);
+    if ell > 255 || len_in_bytes > u16::MAX as usize {
+        return Err(ZKError::InvalidFieldElement(
+            "Requested too many bytes from expand_message_xmd.".into(),
+        ));
+    }
+    if dst.len() > 255 {
+        return Err(ZKError::InvalidFieldElement(
+            "Domain separation tag must be at most 255 bytes.".into(),
+        ));
+    }
+
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    // b_0 = H(Z_pad || msg || I2OSP(len_in_bytes, 2) || I2OSP(0, 1) || DST_prime)
+    let b0 = Sha256::new()
+        .chain_update([0u8; BLOCK_BYTES])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+    // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+    let mut uniform_bytes = Vec::with_capacity(ell * HASH_BYTES);
+    let mut previous = [0u8; HASH_BYTES];
+    for i in 1..=ell {
+        let mut input = [0u8; HASH_BYTES];
+        for (byte, (a, b)) in input.iter_mut().zip(b0.iter().zip(&previous)) {
+            *byte = a ^ b;
+        }
+        previous = Sha256::new()
+            .chain_update(input)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize()
+            .into();
+        uniform_bytes.extend_from_slice(&previous);
+    }
+
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Hashes `msg` to `count` field elements modulo `modulus`, bound to the domain
+/// separation tag `dst`. The output is statistically close to uniform, which makes it
+/// suitable for Fiat–Shamir challenges and hash-to-curve.
+pub fn hash_to_field(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+    modulus: u64,
+) -> Result<Vec<FieldElement>, ZKError> {
+    if modulus == 0 {
+        return Err(ZKError::InvalidFieldElement(
+            "Modulus must be nonzero.".into(),
+        ));
+    }
+
+    // L = ceil((ceil(log2(p)) + k) / 8)
+    let modulus_bits = (u64::BITS - modulus.leading_zeros()) as usize;
+    let chunk_len = (modulus_bits + SECURITY_BITS).div_ceil(8);
+    let uniform_bytes = expand_message_xmd(msg, dst, count * chunk_len)?;
+
+    uniform_bytes
+        .chunks(chunk_len)
+        .map(|chunk| {
+            // Reduce the big-endian chunk modulo p one byte at a time.
+            let value = chunk.iter().fold(0u128, |acc, &byte| {
+                ((acc << 8) | byte as u128) % modulus as u128
+            });
+            FieldElement::new(value as u64, modulus)
+        })
+        .collect()
+}
+
+/// Hashes `msg` to a point of the curve's prime-order subgroup (or of the whole curve,
+/// if it describes none) by try-and-increment.
+///
+/// Each attempt hashes `msg` and a counter byte to a candidate x and a sign bit, until
+/// x^3 + ax + b is a square; the cofactor is then cleared. The number of attempts depends
+/// on the message, so unlike the maps of RFC 9380 this is not constant time.
+pub fn hash_to_curve(
+    msg: &[u8],
+    dst: &[u8],
+    curve: &EllipticCurve,
+) -> Result<EllipticCurvePoint, ZKError> {
+    let modulus = curve.a.modulus;
+    for counter in 0..=u8::MAX {
+        let input = [msg, &[counter]].concat();
+        let elements = hash_to_field(&input, dst, 2, modulus)?;
+        let (x, sign) = (&elements[0], &elements[1]);
+
+        let rhs = x.mul(x)?.mul(x)?.add(&curve.a.mul(x)?)?.add(&curve.b)?;
+        let y = if rhs.is_zero() {
+            rhs
+        } else {
+            match rhs.sqrt() {
+                Some(y) if y.value % 2 == sign.value % 2 => y,
+                Some(y) => y.neg(),
+                None => continue,
+            }
+        };
+
+        let point = EllipticCurvePoint::Point { x: x.clone(), y };
+        let point = match &curve.subgroup {
+            Some(subgroup) => curve.clear_cofactor(&point, subgroup.cofactor)?,
+            None => point,
+        };
+        if point != EllipticCurvePoint::Infinity {
+            return Ok(point);
+        }
+    }
+    Err(ZKError::CurveError(
+        "Could not hash the message to a point of the curve.".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_expand_message_xmd_vectors() {
+        // Test vectors from RFC 9380, Appendix K.1.
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex(&expand_message_xmd(b"", dst, 0x20).unwrap()),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex(&expand_message_xmd(b"abc", dst, 0x20).unwrap()),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+
+        let long = expand_message_xmd(b"abc", dst, 0x80).unwrap();
+        assert_eq!(long.len(), 0x80);
+        assert_eq!(
+            hex(&long[..32]),
+            "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a"
+        );
+    }
+
+    #[test]
+    fn test_expand_message_xmd_limits() {
+        assert!(expand_message_xmd(b"abc", &[0u8; 256], 32).is_err());
+        assert!(expand_message_xmd(b"abc", b"DST", 255 * 32 + 1).is_err());
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        let elements = hash_to_field(b"abc", b"ZKSFS-TEST", 2, 97).unwrap();
+        assert_eq!(elements.len(), 2);
+        let values: Vec<u64> = elements.iter().map(|e| e.value).collect();
+        assert_eq!(values, vec![81, 93]);
+
+        // Deterministic, and separated by domain tag.
+        assert_eq!(
+            elements,
+            hash_to_field(b"abc", b"ZKSFS-TEST", 2, 97).unwrap()
+        );
+        let large = (1u64 << 61) - 1;
+        assert_eq!(
+            hash_to_field(b"abc", b"ZKSFS-TEST", 2, large).unwrap()[0].value,
+            2000339378586030910
+        );
+        assert_ne!(
+            hash_to_field(b"abc", b"ZKSFS-TEST", 1, large).unwrap(),
+            hash_to_field(b"abc", b"ZKSFS-OTHER", 1, large).unwrap()
+        );
+
+        assert!(hash_to_field(b"abc", b"ZKSFS-TEST", 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let curve = EllipticCurve::from_name("toy-bls12-37").unwrap();
+        let subgroup = curve.subgroup().unwrap();
+        let point = hash_to_curve(b"abc", b"ZKSFS-TEST", &curve).unwrap();
+        assert!(curve.is_in_prime_subgroup(&point, subgroup.order).unwrap());
+        assert_ne!(point, EllipticCurvePoint::Infinity);
+        assert_eq!(point, hash_to_curve(b"abc", b"ZKSFS-TEST", &curve).unwrap());
+
+        // Messages spread over the subgroup.
+        let mut points: Vec<_> = (0..64u8)
+            .map(|i| format!("{:?}", hash_to_curve(&[i], b"ZKSFS-TEST", &curve).unwrap()))
+            .collect();
+        points.sort();
+        points.dedup();
+        assert_eq!(points.len() as u64, subgroup.order - 1);
+    }
+}
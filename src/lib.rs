@@ -1,8 +1,31 @@
+pub mod blake2s;
+pub mod bls;
+pub mod builder;
+pub mod circom;
 pub mod circuit;
 pub mod curve;
+pub mod curves;
+pub mod edwards;
 pub mod errors;
 pub mod field;
+pub mod fp;
+pub mod fp12;
+pub mod fp2;
+pub mod fp256;
+pub mod fp6;
+pub mod gadgets;
+pub mod goldilocks;
+pub mod hash;
+pub mod memory;
+pub mod montgomery;
+pub mod multilinear;
+pub mod packing;
 pub mod pairing;
+mod parallel;
+pub mod pedersen;
 pub mod polynomial;
 pub mod qap;
 pub mod snark;
+pub mod store;
+pub mod uint;
+pub mod zkinterface;
@@ -0,0 +1,425 @@
+use std::{
+    cmp::Ordering,
+    sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard},
+    thread::{self, ThreadId},
+};
+
+use crate::{
+    builder::{CircuitBuilder, Hint, Var},
+    circuit::LinearCombination,
+    errors::ZKError,
+    field::Field,
+};
+
+/// A read/write memory inside a circuit, for circuits that index arrays by witness
+/// values or check the trace of a simple virtual machine.
+///
+/// Reads and writes are only logged as (address, timestamp, value) tuples, timestamp
+/// being the position of the access, and `finalize` checks the whole log at once: the
+/// prover supplies the log sorted by address and then timestamp, which is constrained to
+/// be a permutation of the log and, between neighbours, to be sorted and consistent.
+/// Every read then returns the value of the latest write to its address, or zero for an
+/// address never written. This costs O(address_bits) constraints per access, whereas
+/// selecting from every cell would cost one per cell.
+#[derive(Debug)]
+pub struct Memory {
+    address_bits: usize,
+    // The log, shared with the hints of the reads, which look up the writes before them.
+    accesses: Arc<RwLock<Vec<Access>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Access {
+    address: Var,
+    value: Var,
+    is_write: bool,
+}
+
+// The log sorted by address and then timestamp, shared by the hints of its entries. The
+// first of them sorts the log in each witness generation and the others index into its
+// order, which is tagged with the thread generating the witness in case one builder
+// generates several at once.
+struct SortedLog {
+    accesses: Arc<[Access]>,
+    order: Mutex<Option<(ThreadId, Vec<usize>)>>,
+}
+
+// An access of the sorted log, where the timestamp and kind are witnesses too.
+struct Entry {
+    address: Var,
+    time: Var,
+    value: Var,
+    is_write: Var,
+}
+
+#[derive(Clone, Copy)]
+enum Part {
+    Address,
+    Time,
+    Value,
+    IsWrite,
+}
+
+impl Memory {
+    /// Creates an empty memory whose addresses lie in [0, 2^address_bits); `finalize`
+    /// range checks them.
+    pub fn new(address_bits: usize) -> Self {
+        Memory {
+            address_bits,
+            accesses: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Returns the number of reads and writes so far.
+    pub fn len(&self) -> usize {
+        self.log().len()
+    }
+
+    /// Returns true if there were no reads or writes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a variable holding the value at `address`. The witness generator reads
+    /// it from the earlier writes, and `finalize` constrains it.
+    pub fn read<F: Field>(&mut self, builder: &mut CircuitBuilder<F>, address: Var) -> Var {
+        let (accesses, time) = (self.accesses.clone(), self.len());
+        let value = builder.allocate_with(move |witness: &[F]| {
+            let target = address.value(witness)?;
+            let accesses = accesses.read().unwrap_or_else(PoisonError::into_inner);
+            let earlier = &accesses[..time];
+            for access in earlier.iter().rev().filter(|access| access.is_write) {
+                if access.address.value(witness)? == target {
+                    return access.value.value(witness).cloned();
+                }
+            }
+            Ok(F::zero(&witness[0].params()))
+        });
+        self.push(Access {
+            address,
+            value,
+            is_write: false,
+        });
+        value
+    }
+
+    /// Stores `value` at `address`.
+    pub fn write(&mut self, address: Var, value: Var) {
+        self.push(Access {
+            address,
+            value,
+            is_write: true,
+        });
+    }
+
+    fn log(&self) -> RwLockReadGuard<'_, Vec<Access>> {
+        self.accesses.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&mut self, access: Access) {
+        let mut accesses = self
+            .accesses
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        accesses.push(access);
+    }
+
+    /// Adds the constraints checking every access. `compression` folds each tuple
+    /// into one field element, and `challenge` drives the permutation check, see
+    /// `CircuitBuilder::enforce_permutation`. Both must be random and chosen after the
+    /// accesses are fixed, e.g. public inputs derived from a commitment to the witness,
+    /// so the log passes with probability at most about 4 · len / p if it is
+    /// inconsistent. Fails if the field cannot compare addresses or timestamps.
+    pub fn finalize<F: Field>(
+        self,
+        builder: &mut CircuitBuilder<F>,
+        compression: Var,
+        challenge: Var,
+    ) -> Result<(), ZKError> {
+        let accesses: Arc<[Access]> = self.log().as_slice().into();
+        let n = accesses.len();
+        if n == 0 {
+            return Ok(());
+        }
+        let time_bits = ((usize::BITS - (n - 1).leading_zeros()) as usize).max(1);
+        let bits = self.address_bits.max(time_bits);
+        if bits + 1 > builder.capacity() {
+            return Err(ZKError::CircuitError(format!(
+                "Cannot compare {}-bit addresses and timestamps; the field holds only {} bits.",
+                bits,
+                builder.capacity()
+            )));
+        }
+
+        // The prover sorts the log when the witness is generated.
+        let log = Arc::new(SortedLog {
+            accesses: accesses.clone(),
+            order: Mutex::new(None),
+        });
+        let sorted: Vec<Entry> = (0..n)
+            .map(|k| {
+                let mut part = |part| {
+                    let log = log.clone();
+                    // The address of the first entry is the first of these hints.
+                    let first = k == 0 && matches!(part, Part::Address);
+                    builder.allocate_with(move |witness: &[F]| log.part(witness, k, part, first))
+                };
+                Entry {
+                    address: part(Part::Address),
+                    time: part(Part::Time),
+                    value: part(Part::Value),
+                    is_write: part(Part::IsWrite),
+                }
+            })
+            .collect();
+        for entry in &sorted {
+            builder.range_check(entry.address, self.address_bits)?;
+            builder.range_check(entry.time, time_bits)?;
+            builder.enforce_boolean(entry.is_write);
+        }
+
+        // The sorted log is a permutation of the log, comparing the tuples
+        // address + α · time + α^2 · value + α^3 · is_write for α = compression.
+        let one = F::one(&builder.params);
+        let squared = builder.mul(compression, compression);
+        let cubed = builder.mul(squared, compression);
+        let mut logged = Vec::with_capacity(n);
+        for (time, access) in accesses.iter().enumerate() {
+            let weighted = builder.mul(squared, access.value);
+            let mut terms = vec![
+                (access.address, one.clone()),
+                (compression, F::from_u64(time as u64, &builder.params)),
+                (weighted, one.clone()),
+            ];
+            if access.is_write {
+                terms.push((cubed, one.clone()));
+            }
+            logged.push(fold(builder, &terms));
+        }
+        let mut reordered = Vec::with_capacity(n);
+        for entry in &sorted {
+            let terms = [
+                (entry.address, one.clone()),
+                (builder.mul(compression, entry.time), one.clone()),
+                (builder.mul(squared, entry.value), one.clone()),
+                (builder.mul(cubed, entry.is_write), one.clone()),
+            ];
+            reordered.push(fold(builder, &terms));
+        }
+        builder.enforce_permutation(&logged, &reordered, challenge)?;
+
+        // A first read of an address returns zero: (1 − is_write) · value = 0.
+        let first = &sorted[0];
+        let read = builder.not(first.is_write);
+        builder.enforce(
+            builder.variable(read),
+            builder.variable(first.value),
+            LinearCombination::new(),
+        );
+        for pair in sorted.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            // With same = [next.address = prev.address], the entries are ordered by
+            // timestamp within an address and by address otherwise:
+            // same · (time_lt − address_lt) = 1 − address_lt.
+            let same = builder.is_equal(next.address, prev.address);
+            let address_lt = builder.less_than(prev.address, next.address, bits)?;
+            let time_lt = builder.less_than(prev.time, next.time, bits)?;
+            let (one, address_lt, time_lt) = (
+                builder.variable(builder.one()),
+                builder.variable(address_lt),
+                builder.variable(time_lt),
+            );
+            builder.enforce(
+                builder.variable(same),
+                time_lt - address_lt.clone(),
+                one - address_lt,
+            );
+
+            // A read returns the value of the entry before it at the same address, or
+            // zero if it is the first at its address.
+            let read = builder.not(next.is_write);
+            let repeated = builder.mul(same, read);
+            builder.enforce(
+                builder.variable(repeated),
+                builder.variable(next.value) - builder.variable(prev.value),
+                LinearCombination::new(),
+            );
+            let fresh = builder.not(same);
+            let first_read = builder.mul(fresh, read);
+            builder.enforce(
+                builder.variable(first_read),
+                builder.variable(next.value),
+                LinearCombination::new(),
+            );
+        }
+        Ok(())
+    }
+}
+
+// Returns a variable constrained to equal sum c_i · v_i.
+fn fold<F: Field>(builder: &mut CircuitBuilder<F>, terms: &[(Var, F)]) -> Var {
+    let sum = builder.lc(terms);
+    let var = builder.allocate(Hint::Linear(sum.clone()));
+    builder.emit(sum, builder.single(builder.one()), builder.single(var));
+    var
+}
+
+impl Clone for Memory {
+    // The reads so far keep looking up the log they were made in, whose beginning the
+    // copy shares.
+    fn clone(&self) -> Self {
+        Memory {
+            address_bits: self.address_bits,
+            accesses: Arc::new(RwLock::new(self.log().clone())),
+        }
+    }
+}
+
+impl SortedLog {
+    // Returns a part of entry k, sorting the log first if this is the first hint.
+    fn part<F: Field>(
+        &self,
+        witness: &[F],
+        k: usize,
+        part: Part,
+        first: bool,
+    ) -> Result<F, ZKError> {
+        let thread = thread::current().id();
+        let mut order = self.order.lock().unwrap_or_else(PoisonError::into_inner);
+        let time = match &*order {
+            Some((sorted_by, order)) if !first && *sorted_by == thread => order[k],
+            _ => {
+                let sorted = sort(&self.accesses, witness)?;
+                let time = sorted[k];
+                *order = Some((thread, sorted));
+                time
+            }
+        };
+
+        let access = &self.accesses[time];
+        let params = witness[0].params();
+        match part {
+            Part::Address => access.address.value(witness).cloned(),
+            Part::Time => Ok(F::from_u64(time as u64, &params)),
+            Part::Value => access.value.value(witness).cloned(),
+            Part::IsWrite => Ok(F::from_u64(access.is_write as u64, &params)),
+        }
+    }
+}
+
+// Returns the timestamps of the log sorted by address and then timestamp.
+fn sort<F: Field>(accesses: &[Access], witness: &[F]) -> Result<Vec<usize>, ZKError> {
+    // Canonical values compare as integers from the most significant byte down.
+    let keys = accesses
+        .iter()
+        .map(|access| {
+            let mut key = access.address.value(witness)?.to_bytes();
+            key.reverse();
+            Ok(key)
+        })
+        .collect::<Result<Vec<_>, ZKError>>()?;
+    let mut order: Vec<usize> = (0..accesses.len()).collect();
+    // The sort is stable, so timestamps stay increasing within an address.
+    order.sort_by(|&i, &j| match keys[i].cmp(&keys[j]) {
+        Ordering::Equal => i.cmp(&j),
+        ordering => ordering,
+    });
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goldilocks::Goldilocks;
+
+    fn g(value: u64) -> Goldilocks {
+        Goldilocks::new(value)
+    }
+
+    #[test]
+    fn test_memory() {
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let compression = builder.public_input().unwrap();
+        let challenge = builder.public_input().unwrap();
+        let [three, five, seven, ten, twenty, thirty] =
+            [3, 5, 7, 10, 20, 30].map(|v| builder.constant(g(v)));
+
+        let mut memory = Memory::new(4);
+        memory.write(three, ten);
+        memory.write(five, twenty);
+        let first = memory.read(&mut builder, three);
+        memory.write(three, thirty);
+        let second = memory.read(&mut builder, three);
+        let unwritten = memory.read(&mut builder, seven);
+        // The address is a witness, as for an array indexed by a private value.
+        let index = builder.private_witness();
+        let indexed = memory.read(&mut builder, index);
+        assert_eq!(memory.len(), 7);
+        memory
+            .finalize(&mut builder, compression, challenge)
+            .unwrap();
+
+        let public = [g(123_456_789), g(987_654_321)];
+        let witness = builder.generate_witness(&public, &[g(5)]).unwrap();
+        let values: Vec<Goldilocks> = [first, second, unwritten, indexed]
+            .iter()
+            .map(|var| witness[var.index()])
+            .collect();
+        assert_eq!(values, [g(10), g(30), g(0), g(20)]);
+        // Another index sorts the log differently, and the builder sorts it again.
+        let other = builder.generate_witness(&public, &[g(3)]).unwrap();
+        assert_eq!(other[indexed.index()], g(30));
+
+        // A read returning another value breaks the permutation or the sorted checks.
+        let cs = builder.constraint_system();
+        for var in [first, second, unwritten, indexed] {
+            let mut forged = witness.clone();
+            forged[var.index()] = g(11);
+            assert!(cs.evaluate(&forged).is_err());
+        }
+        // Addresses must fit in 4 bits.
+        assert!(builder.generate_witness(&public, &[g(16)]).is_err());
+    }
+
+    #[test]
+    fn test_forged_sorted_log() {
+        // Reading back a never-written address as 42 needs a sorted log that claims
+        // it: the value check on the first entry rejects it.
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let compression = builder.public_input().unwrap();
+        let challenge = builder.public_input().unwrap();
+        let address = builder.constant(g(1));
+        let mut memory = Memory::new(2);
+        let value = memory.read(&mut builder, address);
+        let before = builder.constraint_system().num_variables;
+        memory
+            .finalize(&mut builder, compression, challenge)
+            .unwrap();
+
+        let public = [g(1_000_003), g(2_000_003)];
+        let mut witness = builder.generate_witness(&public, &[]).unwrap();
+        assert!(builder.constraint_system().evaluate(&witness).unwrap());
+        // The sorted entry's value follows its address and timestamp.
+        witness[value.index()] = g(42);
+        witness[before + 2] = g(42);
+        let failing = builder
+            .constraint_system()
+            .unsatisfied_constraints(&witness)
+            .unwrap();
+        assert!(!failing.is_empty());
+    }
+
+    #[test]
+    fn test_empty_and_small_fields() {
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let one = builder.one();
+        assert!(Memory::new(8).is_empty());
+        assert!(Memory::new(8).finalize(&mut builder, one, one).is_ok());
+
+        // Goldilocks holds 63 bits, which leaves no room for the comparison bit of
+        // 63-bit addresses.
+        let mut memory = Memory::new(63);
+        memory.write(one, one);
+        assert!(memory.finalize(&mut builder, one, one).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{
+    curve::{EllipticCurve, EllipticCurvePoint},
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// Represents an elliptic curve in Montgomery form:
+/// B y^2 = x^3 + A x^2 + x
+///
+/// Curve25519 is the best known example. Scalar multiplication uses the x-only
+/// Montgomery ladder, and points can be mapped to the equivalent short Weierstrass
+/// curve to use the rest of the crate.
+#[derive(Debug, Clone)]
+pub struct MontgomeryCurve<F: Field = FieldElement> {
+    pub a: F,
+    pub b: F,
+}
+
+impl<F: Field> MontgomeryCurve<F> {
+    /// Creates a Montgomery curve, rejecting the singular cases B = 0 and A^2 = 4.
+    pub fn new(a: F, b: F) -> Result<Self, ZKError> {
+        if a.params() != b.params() {
+            return Err(ZKError::InvalidFieldElement(
+                "Curve coefficients must belong to the same field.".into(),
+            ));
+        }
+        let four = F::from_u64(4, &a.params());
+        if b.is_zero() || a.mul(&a)? == four {
+            return Err(ZKError::CurveError(
+                "Montgomery curve requires B != 0 and A^2 != 4.".into(),
+            ));
+        }
+        Ok(MontgomeryCurve { a, b })
+    }
+
+    /// Check if the given point lies on the curve.
+    pub fn is_on_curve(&self, point: &EllipticCurvePoint<F>) -> Result<bool, ZKError> {
+        match point {
+            EllipticCurvePoint::Infinity => Ok(true),
+            EllipticCurvePoint::Point { x, y } => {
+                let lhs = self.b.mul(y)?.mul(y)?;
+                let xx = x.mul(x)?;
+                let rhs = xx.mul(x)?.add(&self.a.mul(&xx)?)?.add(x)?;
+                Ok(lhs == rhs)
+            }
+        }
+    }
+
+    /// Returns the short Weierstrass curve y^2 = x^3 + ax + b isomorphic to this one,
+    /// with a = (3 - A^2) / (3B^2) and b = (2A^3 - 9A) / (27B^3).
+    ///
+    /// The field must not have characteristic 3.
+    pub fn to_weierstrass(&self) -> Result<EllipticCurve<F>, ZKError> {
+        let params = self.a.params();
+        let three = F::from_u64(3, &params);
+        let aa = self.a.mul(&self.a)?;
+        let bb = self.b.mul(&self.b)?;
+        let a = three.sub(&aa)?.div(&three.mul(&bb)?)?;
+        let b = aa
+            .add(&aa)?
+            .mul(&self.a)?
+            .sub(&F::from_u64(9, &params).mul(&self.a)?)?
+            .div(&F::from_u64(27, &params).mul(&bb)?.mul(&self.b)?)?;
+        Ok(EllipticCurve {
+            a,
+            b,
+            subgroup: None,
+        })
+    }
+
+    /// Maps a point to the curve returned by `to_weierstrass`, via
+    /// (x, y) -> (x / B + A / 3B, y / B).
+    pub fn to_weierstrass_point(
+        &self,
+        point: &EllipticCurvePoint<F>,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        match point {
+            EllipticCurvePoint::Infinity => Ok(EllipticCurvePoint::Infinity),
+            EllipticCurvePoint::Point { x, y } => {
+                let three = F::from_u64(3, &self.a.params());
+                let shift = self.a.div(&three)?;
+                Ok(EllipticCurvePoint::Point {
+                    x: x.add(&shift)?.div(&self.b)?,
+                    y: y.div(&self.b)?,
+                })
+            }
+        }
+    }
+
+    /// Maps a point on the curve returned by `to_weierstrass` back to this curve.
+    pub fn from_weierstrass_point(
+        &self,
+        point: &EllipticCurvePoint<F>,
+    ) -> Result<EllipticCurvePoint<F>, ZKError> {
+        match point {
+            EllipticCurvePoint::Infinity => Ok(EllipticCurvePoint::Infinity),
+            EllipticCurvePoint::Point { x, y } => {
+                let three = F::from_u64(3, &self.a.params());
+                let shift = self.a.div(&three)?;
+                Ok(EllipticCurvePoint::Point {
+                    x: x.mul(&self.b)?.sub(&shift)?,
+                    y: y.mul(&self.b)?,
+                })
+            }
+        }
+    }
+
+    /// Computes the x-coordinate of k * P from the x-coordinate of P with the
+    /// Montgomery ladder (RFC 7748), returning `None` when k * P is the point at infinity.
+    ///
+    /// The ladder runs the same sequence of field operations for every 64-bit scalar, so
+    /// its structure does not depend on the scalar's bits. The field arithmetic itself is
+    /// not constant time.
+    pub fn ladder(&self, x: &F, scalar: u64) -> Result<Option<F>, ZKError> {
+        if x.params() != self.a.params() {
+            return Err(ZKError::InvalidFieldElement(
+                "Point coordinates must belong to the curve's field.".into(),
+            ));
+        }
+        let params = self.a.params();
+        let a24 = self
+            .a
+            .sub(&F::from_u64(2, &params))?
+            .div(&F::from_u64(4, &params))?;
+
+        let (mut x2, mut z2) = (F::one(&params), F::zero(&params));
+        let (mut x3, mut z3) = (x.clone(), F::one(&params));
+        let mut swap = false;
+        for i in (0..u64::BITS).rev() {
+            let bit = (scalar >> i) & 1 == 1;
+            if swap != bit {
+                std::mem::swap(&mut x2, &mut x3);
+                std::mem::swap(&mut z2, &mut z3);
+            }
+            swap = bit;
+
+            let a = x2.add(&z2)?;
+            let aa = a.mul(&a)?;
+            let b = x2.sub(&z2)?;
+            let bb = b.mul(&b)?;
+            let e = aa.sub(&bb)?;
+            let c = x3.add(&z3)?;
+            let d = x3.sub(&z3)?;
+            let da = d.mul(&a)?;
+            let cb = c.mul(&b)?;
+
+            let sum = da.add(&cb)?;
+            let difference = da.sub(&cb)?;
+            x3 = sum.mul(&sum)?;
+            z3 = x.mul(&difference.mul(&difference)?)?;
+            z2 = e.mul(&aa.add(&a24.mul(&e)?)?)?;
+            x2 = aa.mul(&bb)?;
+        }
+        if swap {
+            std::mem::swap(&mut x2, &mut x3);
+            std::mem::swap(&mut z2, &mut z3);
+        }
+
+        if z2.is_zero() {
+            Ok(None)
+        } else {
+            Ok(Some(x2.div(&z2)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_values() -> (MontgomeryCurve, Vec<EllipticCurvePoint>) {
+        let modulus = 101;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+
+        // Test curve with the equation 2y^2 = x^3 + 5x^2 + x.
+        let curve = MontgomeryCurve::new(fe(5), fe(2)).unwrap();
+        let mut points = Vec::new();
+        for x in 0..modulus {
+            for y in 0..modulus {
+                let point = EllipticCurvePoint::Point { x: fe(x), y: fe(y) };
+                if curve.is_on_curve(&point).unwrap() {
+                    points.push(point);
+                }
+            }
+        }
+        (curve, points)
+    }
+
+    #[test]
+    fn test_new_rejects_singular_curves() {
+        let fe = |v| FieldElement::new(v, 101).unwrap();
+        assert!(MontgomeryCurve::new(fe(2), fe(1)).is_err());
+        assert!(MontgomeryCurve::new(fe(99), fe(1)).is_err());
+        assert!(MontgomeryCurve::new(fe(5), fe(0)).is_err());
+    }
+
+    #[test]
+    fn test_weierstrass_map() {
+        let (curve, points) = get_test_values();
+        let weierstrass = curve.to_weierstrass().unwrap();
+        for point in &points {
+            let mapped = curve.to_weierstrass_point(point).unwrap();
+            assert!(weierstrass.is_on_curve(&mapped).unwrap());
+            assert_eq!(&curve.from_weierstrass_point(&mapped).unwrap(), point);
+        }
+    }
+
+    #[test]
+    fn test_ladder_matches_weierstrass() {
+        let (curve, points) = get_test_values();
+        let weierstrass = curve.to_weierstrass().unwrap();
+        for point in points.iter().step_by(7) {
+            let EllipticCurvePoint::Point { x, .. } = point else {
+                unreachable!()
+            };
+            if x.is_zero() {
+                continue;
+            }
+            let mapped = curve.to_weierstrass_point(point).unwrap();
+            for k in [0, 1, 2, 3, 10, 57, 1 << 40, u64::MAX] {
+                let expected = curve
+                    .from_weierstrass_point(&weierstrass.mul_scalar(&mapped, k).unwrap())
+                    .unwrap();
+                let expected_x = match expected {
+                    EllipticCurvePoint::Infinity => None,
+                    EllipticCurvePoint::Point { x, .. } => Some(x),
+                };
+                assert_eq!(curve.ladder(x, k).unwrap(), expected_x);
+            }
+        }
+    }
+}
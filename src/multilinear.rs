@@ -0,0 +1,282 @@
+use crate::{
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// Represents a multilinear polynomial in n variables by its evaluations over the
+/// boolean hypercube {0, 1}^n.
+///
+/// The evaluation at (x_0, ..., x_(n - 1)) is stored at index Σ x_i · 2^i, so the
+/// first variable selects between neighbouring entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultilinearPolynomial<F: Field = FieldElement> {
+    evaluations: Vec<F>,
+    num_variables: usize,
+}
+
+impl<F: Field> MultilinearPolynomial<F> {
+    /// Creates the multilinear extension of the given hypercube evaluations, whose
+    /// number must be a power of two.
+    pub fn new(evaluations: Vec<F>) -> Result<Self, ZKError> {
+        if !evaluations.len().is_power_of_two() {
+            return Err(ZKError::PolynomialError(format!(
+                "Number of evaluations must be a power of two, got {}",
+                evaluations.len()
+            )));
+        }
+
+        let params = evaluations[0].params();
+        if evaluations.iter().any(|e| e.params() != params) {
+            return Err(ZKError::PolynomialError(
+                "All evaluations must have the same modulus".to_string(),
+            ));
+        }
+
+        let num_variables = evaluations.len().trailing_zeros() as usize;
+        Ok(Self {
+            evaluations,
+            num_variables,
+        })
+    }
+
+    /// Returns eq(·, r) as a multilinear polynomial: its evaluation at a hypercube
+    /// point b is 1 if b = r and 0 otherwise, whenever r itself is boolean.
+    pub fn eq_polynomial(r: &[F], params: &F::Params) -> Result<Self, ZKError> {
+        let one = F::one(params);
+        let mut evaluations = vec![one.clone()];
+        for r_i in r {
+            if &r_i.params() != params {
+                return Err(ZKError::PolynomialError(
+                    "Moduli must be the same for eq".to_string(),
+                ));
+            }
+            // Entries with x_i = 0 are scaled by 1 - r_i, and those with x_i = 1 by r_i.
+            let one_minus_r = one.sub(r_i)?;
+            let high = evaluations
+                .iter()
+                .map(|e| e.mul(r_i))
+                .collect::<Result<Vec<F>, ZKError>>()?;
+            F::scale_slice(&mut evaluations, &one_minus_r)?;
+            evaluations.extend(high);
+        }
+        Self::new(evaluations)
+    }
+
+    /// Returns the number of variables.
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    /// Returns the evaluations over the boolean hypercube.
+    pub fn evaluations(&self) -> &[F] {
+        &self.evaluations
+    }
+
+    /// Fixes the first variable to `r`, returning a polynomial in the remaining
+    /// variables. This is the per-round update of a sumcheck prover.
+    pub fn fix_variable(&self, r: &F) -> Result<Self, ZKError> {
+        if self.num_variables == 0 {
+            return Err(ZKError::PolynomialError(
+                "Polynomial has no variables left to fix".to_string(),
+            ));
+        }
+        if self.evaluations[0].params() != r.params() {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for evaluation".to_string(),
+            ));
+        }
+
+        // f(r, x') = f(0, x') + r · (f(1, x') - f(0, x')).
+        let evaluations = self
+            .evaluations
+            .chunks(2)
+            .map(|pair| pair[1].sub(&pair[0])?.mul(r)?.add(&pair[0]))
+            .collect::<Result<Vec<F>, ZKError>>()?;
+        Ok(Self {
+            evaluations,
+            num_variables: self.num_variables - 1,
+        })
+    }
+
+    /// Evaluates the polynomial at an arbitrary point with one coordinate per variable.
+    pub fn evaluate(&self, point: &[F]) -> Result<F, ZKError> {
+        if point.len() != self.num_variables {
+            return Err(ZKError::PolynomialError(format!(
+                "Expected {} coordinates, got {}",
+                self.num_variables,
+                point.len()
+            )));
+        }
+
+        let mut current = self.clone();
+        for r in point {
+            current = current.fix_variable(r)?;
+        }
+        Ok(current.evaluations[0].clone())
+    }
+
+    /// Returns the sum of the evaluations over the boolean hypercube, the claim a
+    /// sumcheck proves.
+    pub fn sum_over_hypercube(&self) -> Result<F, ZKError> {
+        let params = self.evaluations[0].params();
+        self.evaluations
+            .iter()
+            .try_fold(F::zero(&params), |acc, e| acc.add(e))
+    }
+}
+
+/// Evaluates eq(x, r) = Π (x_i · r_i + (1 - x_i)(1 - r_i)), the multilinear
+/// extension of equality on the boolean hypercube.
+pub fn eq<F: Field>(x: &[F], r: &[F], params: &F::Params) -> Result<F, ZKError> {
+    if x.len() != r.len() {
+        return Err(ZKError::PolynomialError(format!(
+            "Points must have the same length, got {} and {}",
+            x.len(),
+            r.len()
+        )));
+    }
+
+    let one = F::one(params);
+    let mut result = one.clone();
+    for (x_i, r_i) in x.iter().zip(r) {
+        if &x_i.params() != params || &r_i.params() != params {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for eq".to_string(),
+            ));
+        }
+        let both = x_i.mul(r_i)?;
+        let neither = one.sub(x_i)?.mul(&one.sub(r_i)?)?;
+        result = result.mul(&both.add(&neither)?)?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULUS: u64 = 97;
+
+    fn fe(value: u64) -> FieldElement {
+        FieldElement::new(value, MODULUS).unwrap()
+    }
+
+    // The hypercube point whose coordinates are the bits of `index`.
+    fn hypercube_point(index: usize, num_variables: usize) -> Vec<FieldElement> {
+        (0..num_variables)
+            .map(|i| fe(((index >> i) & 1) as u64))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let f = MultilinearPolynomial::new((1..=8).map(fe).collect()).unwrap();
+        assert_eq!(f.num_variables(), 3);
+        assert_eq!(f.evaluations().len(), 8);
+
+        assert!(MultilinearPolynomial::new((1..=6).map(fe).collect()).is_err());
+        assert!(MultilinearPolynomial::<FieldElement>::new(Vec::new()).is_err());
+        let mixed = vec![fe(1), FieldElement::new(1, 101).unwrap()];
+        assert!(MultilinearPolynomial::new(mixed).is_err());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let f = MultilinearPolynomial::new([3, 1, 4, 1, 5, 9, 2, 6].map(fe).to_vec()).unwrap();
+
+        // The extension agrees with the table on the hypercube.
+        for (index, value) in f.evaluations().iter().enumerate() {
+            assert_eq!(&f.evaluate(&hypercube_point(index, 3)).unwrap(), value);
+        }
+
+        // f(x0, x1, x2) is affine in each variable.
+        let (a, b) = ([fe(10), fe(20), fe(30)], [fe(11), fe(20), fe(30)]);
+        let c = [fe(12), fe(20), fe(30)];
+        let (fa, fb, fc) = (
+            f.evaluate(&a).unwrap(),
+            f.evaluate(&b).unwrap(),
+            f.evaluate(&c).unwrap(),
+        );
+        assert_eq!(fb.sub(&fa).unwrap(), fc.sub(&fb).unwrap());
+
+        assert!(f.evaluate(&[fe(1), fe(2)]).is_err());
+        let constant = MultilinearPolynomial::new(vec![fe(42)]).unwrap();
+        assert_eq!(constant.evaluate(&[]).unwrap(), fe(42));
+        assert!(constant.fix_variable(&fe(1)).is_err());
+    }
+
+    #[test]
+    fn test_fix_variable() {
+        let f = MultilinearPolynomial::new([3, 1, 4, 1, 5, 9, 2, 6].map(fe).to_vec()).unwrap();
+        let r = fe(17);
+        let g = f.fix_variable(&r).unwrap();
+        assert_eq!(g.num_variables(), 2);
+
+        let rest = [fe(5), fe(60)];
+        assert_eq!(
+            g.evaluate(&rest).unwrap(),
+            f.evaluate(&[r, rest[0].clone(), rest[1].clone()]).unwrap()
+        );
+        assert!(f.fix_variable(&FieldElement::new(1, 101).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_eq() {
+        let r = [fe(7), fe(20), fe(33)];
+        let eq_r = MultilinearPolynomial::eq_polynomial(&r, &MODULUS).unwrap();
+        for index in 0..8 {
+            let b = hypercube_point(index, 3);
+            assert_eq!(eq_r.evaluations()[index], eq(&b, &r, &MODULUS).unwrap());
+            // On boolean inputs eq is the indicator of equality.
+            for other in 0..8 {
+                let expected = fe((index == other) as u64);
+                assert_eq!(
+                    eq(&b, &hypercube_point(other, 3), &MODULUS).unwrap(),
+                    expected
+                );
+            }
+        }
+
+        // Σ_b f(b) · eq(b, r) = f(r).
+        let f = MultilinearPolynomial::new([3, 1, 4, 1, 5, 9, 2, 6].map(fe).to_vec()).unwrap();
+        let mut weighted = f.evaluations().to_vec();
+        FieldElement::mul_assign_slices(&mut weighted, eq_r.evaluations()).unwrap();
+        let weighted = MultilinearPolynomial::new(weighted).unwrap();
+        assert_eq!(
+            weighted.sum_over_hypercube().unwrap(),
+            f.evaluate(&r).unwrap()
+        );
+
+        assert!(eq(&r[..2], &r, &MODULUS).is_err());
+        assert_eq!(eq::<FieldElement>(&[], &[], &MODULUS).unwrap(), fe(1));
+    }
+
+    #[test]
+    fn test_sumcheck_round() {
+        // In each round the prover sends g(X) = Σ f(X, b'), which is linear, as g(0)
+        // and g(1); the verifier checks g(0) + g(1) against the running claim.
+        let f = MultilinearPolynomial::new([3, 1, 4, 1, 5, 9, 2, 6].map(fe).to_vec()).unwrap();
+        let challenges = [fe(13), fe(58), fe(90)];
+
+        let mut claim = f.sum_over_hypercube().unwrap();
+        let mut current = f.clone();
+        for r in &challenges {
+            let g0 = current
+                .fix_variable(&fe(0))
+                .unwrap()
+                .sum_over_hypercube()
+                .unwrap();
+            let g1 = current
+                .fix_variable(&fe(1))
+                .unwrap()
+                .sum_over_hypercube()
+                .unwrap();
+            assert_eq!(g0.add(&g1).unwrap(), claim);
+
+            // g(r) = g(0) + r · (g(1) - g(0)).
+            claim = g1.sub(&g0).unwrap().mul(r).unwrap().add(&g0).unwrap();
+            current = current.fix_variable(r).unwrap();
+        }
+        assert_eq!(claim, f.evaluate(&challenges).unwrap());
+    }
+}
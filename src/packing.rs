@@ -0,0 +1,201 @@
+use crate::{
+    blake2s::UInt8,
+    builder::{self, CircuitBuilder, Hint, Var},
+    errors::ZKError,
+    field::Field,
+    gadgets::{Cost, Gadget},
+};
+
+// Packing moves data between bytes, as produced by the hash gadgets, and field elements,
+// as used for public inputs and commitments. Bytes are packed little-endian, as many per
+// element as fit below the capacity of the field, so each element has exactly one
+// packing and the last element may hold fewer bytes.
+
+/// Returns the number of bytes packed into each field element: the capacity of the
+/// field, rounded down to whole bytes.
+pub fn bytes_per_element<F: Field>(params: &F::Params) -> usize {
+    builder::capacity::<F>(params) / 8
+}
+
+/// Packs bytes into field elements, each holding `bytes_per_element` bytes, least
+/// significant first, and constrains the elements to equal the packed bytes. The bytes
+/// must already be range checked, e.g. by `UInt8::from_var`. Fails if the field cannot
+/// hold a byte.
+pub fn pack_bytes<F: Field>(
+    builder: &mut CircuitBuilder<F>,
+    bytes: &[UInt8],
+) -> Result<Vec<Var>, ZKError> {
+    let chunk_size = chunk_size::<F>(&builder.params)?;
+    bytes
+        .chunks(chunk_size)
+        .map(|chunk| {
+            let mut terms = Vec::with_capacity(8 * chunk.len());
+            let mut weight = F::one(&builder.params);
+            for &bit in chunk.iter().flat_map(|byte| byte.bits()) {
+                terms.push((bit, weight.clone()));
+                weight = weight.add(&weight)?;
+            }
+            let packed = builder.lc(&terms);
+            let element = builder.allocate(Hint::Linear(packed.clone()));
+            builder.emit(
+                packed,
+                builder.single(builder.one()),
+                builder.single(element),
+            );
+            Ok(element)
+        })
+        .collect()
+}
+
+/// Unpacks `num_bytes` bytes from field elements laid out as by `pack_bytes`. Each
+/// element is decomposed into the bits of its bytes, which proves that it holds no more
+/// than that many bytes. Fails if the number of elements does not match `num_bytes`.
+pub fn unpack_bytes<F: Field>(
+    builder: &mut CircuitBuilder<F>,
+    elements: &[Var],
+    num_bytes: usize,
+) -> Result<Vec<UInt8>, ZKError> {
+    let chunk_size = chunk_size::<F>(&builder.params)?;
+    let expected = num_bytes.div_ceil(chunk_size);
+    if elements.len() != expected {
+        return Err(ZKError::CircuitError(format!(
+            "Expected {} elements for {} bytes, got {}.",
+            expected,
+            num_bytes,
+            elements.len()
+        )));
+    }
+
+    let mut bytes = Vec::with_capacity(num_bytes);
+    for (i, &element) in elements.iter().enumerate() {
+        let len = chunk_size.min(num_bytes - i * chunk_size);
+        let bits = builder.to_bits(element, 8 * len)?;
+        bytes.extend(bits.chunks(8).map(|bits| UInt8::from_bits(bits.to_vec())));
+    }
+    Ok(bytes)
+}
+
+/// Packs bytes into field element values the way `pack_bytes` does in a circuit, e.g.
+/// to supply external data as public inputs.
+pub fn pack_bytes_native<F: Field>(bytes: &[u8], params: &F::Params) -> Result<Vec<F>, ZKError> {
+    let chunk_size = chunk_size::<F>(params)?;
+    let base = F::from_u64(256, params);
+    bytes
+        .chunks(chunk_size)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .try_fold(F::zero(params), |packed, &byte| {
+                    packed.mul(&base)?.add(&F::from_u64(byte as u64, params))
+                })
+        })
+        .collect()
+}
+
+/// The `pack_bytes` function as a `Gadget`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackBytes;
+
+impl<F: Field> Gadget<F> for PackBytes {
+    type Input = Vec<UInt8>;
+    type Output = Vec<Var>;
+
+    fn synthesize(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        bytes: Vec<UInt8>,
+    ) -> Result<Vec<Var>, ZKError> {
+        pack_bytes(builder, &bytes)
+    }
+
+    // One variable and constraint per element.
+    fn cost(&self, builder: &CircuitBuilder<F>, bytes: &Vec<UInt8>) -> Result<Cost, ZKError> {
+        let elements = bytes.len().div_ceil(chunk_size::<F>(&builder.params)?);
+        Ok(Cost {
+            constraints: elements,
+            variables: elements,
+        })
+    }
+}
+
+fn chunk_size<F: Field>(params: &F::Params) -> Result<usize, ZKError> {
+    match bytes_per_element::<F>(params) {
+        0 => Err(ZKError::CircuitError(format!(
+            "Cannot pack bytes; the field holds only {} bits.",
+            builder::capacity::<F>(params)
+        ))),
+        size => Ok(size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field::FieldElement, goldilocks::Goldilocks};
+
+    #[test]
+    fn test_pack_and_unpack() {
+        // Goldilocks holds 63 bits, so 7 bytes per element: 10 bytes take 2 elements.
+        assert_eq!(bytes_per_element::<Goldilocks>(&()), 7);
+        let data: Vec<u8> = (1..=10).map(|i| i * 25).collect();
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let bytes: Vec<UInt8> = data
+            .iter()
+            .map(|_| {
+                let byte = builder.private_witness();
+                UInt8::from_var(&mut builder, byte).unwrap()
+            })
+            .collect();
+        let cost = PackBytes.cost(&builder, &bytes).unwrap();
+        let constraints = builder.constraint_system().constraints.len();
+        let elements = pack_bytes(&mut builder, &bytes).unwrap();
+        assert_eq!(
+            cost,
+            Cost {
+                constraints: builder.constraint_system().constraints.len() - constraints,
+                variables: 2,
+            }
+        );
+        assert_eq!(elements.len(), 2);
+        let unpacked = unpack_bytes(&mut builder, &elements, data.len()).unwrap();
+        assert!(unpack_bytes(&mut builder, &elements, 15).is_err());
+
+        let inputs: Vec<Goldilocks> = data.iter().map(|&b| Goldilocks::new(b as u64)).collect();
+        let witness = builder.generate_witness(&[], &inputs).unwrap();
+        let native = pack_bytes_native::<Goldilocks>(&data, &()).unwrap();
+        let packed: Vec<Goldilocks> = elements.iter().map(|e| witness[e.index()]).collect();
+        assert_eq!(packed, native);
+        // 25 + 50 · 2^8 + ... + 175 · 2^48, then 200 + 225 · 2^8 + 250 · 2^16.
+        assert_eq!(native[0], Goldilocks::new(0x00af_967d_644b_3219));
+        assert_eq!(native[1], Goldilocks::new(0x00fa_e1c8));
+        let values: Vec<u64> = unpacked
+            .iter()
+            .map(|b| b.value(&witness).unwrap())
+            .collect();
+        assert_eq!(values, data.iter().map(|&b| b as u64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unpack_range_checks() {
+        // An element above 2^16 cannot be unpacked into 2 bytes.
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let element = builder.private_witness();
+        unpack_bytes(&mut builder, &[element], 2).unwrap();
+        assert!(builder
+            .generate_witness(&[], &[Goldilocks::new(0xffff)])
+            .is_ok());
+        assert!(builder
+            .generate_witness(&[], &[Goldilocks::new(0x1_0000)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_small_field() {
+        // Bytes do not fit below 97.
+        let mut builder = CircuitBuilder::<FieldElement>::new(&97).unwrap();
+        assert_eq!(bytes_per_element::<FieldElement>(&97), 0);
+        assert!(pack_bytes(&mut builder, &[]).is_err());
+        assert!(pack_bytes_native::<FieldElement>(&[1], &97).is_err());
+    }
+}
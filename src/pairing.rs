@@ -1,11 +1,21 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use rand::RngCore;
+
 use crate::{
-    curve::{EllipticCurve, EllipticCurvePoint},
+    curve::{Curve, EllipticCurve, EllipticCurvePoint, TwistType},
     errors::ZKError,
-    field::FieldElement,
+    field::{Field, FieldElement},
+    fp::PrimeField,
+    fp12::Fp12,
+    fp2::Fp2,
+    fp6::{Fp6, FrobeniusCoefficients},
+    parallel,
 };
 
 /// Represents the result of a pairing operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pairing {
     pub value: FieldElement,
 }
@@ -43,13 +53,710 @@ impl Pairing {
             }
         }
     }
+
+    /// Serializes the value as little-endian bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Field::to_bytes(&self.value)
+    }
+
+    /// Deserializes a value modulo `modulus` from the output of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8], modulus: u64) -> Result<Self, ZKError> {
+        Ok(Pairing {
+            value: <FieldElement as Field>::from_bytes(bytes, &modulus)?,
+        })
+    }
+}
+
+// Tags of the compressed GT encoding.
+const COMPRESSED_GENERIC: u8 = 0;
+const COMPRESSED_ONE: u8 = 1;
+
+/// An element of the pairing target group GT, the order-r subgroup of Fp12*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gt {
+    pub value: Fp12,
+}
+
+impl Gt {
+    /// Returns the identity of GT for the tower defined by the Fp6 non-residue ξ.
+    pub fn one(non_residue: &Fp2) -> Self {
+        Gt {
+            value: Fp12::one(non_residue),
+        }
+    }
+
+    /// Returns true if the element is the identity.
+    pub fn is_one(&self) -> bool {
+        self.value.is_one()
+    }
+
+    /// Multiply two elements of GT.
+    pub fn mul(&self, other: &Gt) -> Result<Gt, ZKError> {
+        Ok(Gt {
+            value: self.value.mul(&other.value)?,
+        })
+    }
+
+    /// Inverse of the element, which is its conjugate since GT lies in the cyclotomic
+    /// subgroup.
+    pub fn inv(&self) -> Gt {
+        Gt {
+            value: self.value.cyclotomic_inv(),
+        }
+    }
+
+    /// Exponentiate the element by the provided exponent.
+    pub fn exp(&self, exponent: u64) -> Result<Gt, ZKError> {
+        Ok(Gt {
+            value: self.value.cyclotomic_exp(exponent)?,
+        })
+    }
+
+    /// Exponentiate the element with compressed squarings, which is faster for long
+    /// exponents with few set bits.
+    pub fn exp_compressed(&self, exponent: u64) -> Result<Gt, ZKError> {
+        Ok(Gt {
+            value: self.value.cyclotomic_exp_compressed(exponent)?,
+        })
+    }
+
+    /// Returns true if the element lies in the cyclotomic subgroup of Fp12*, of order
+    /// p^4 - p^2 + 1, which contains GT. `inv` and `exp` rely on this, so elements that
+    /// did not come out of a pairing, e.g. deserialized ones, should be checked first.
+    pub fn is_valid(&self) -> Result<bool, ZKError> {
+        if self.value.is_zero() {
+            return Ok(false);
+        }
+        // x^(p^4 - p^2 + 1) = 1 exactly when x^(p^4) · x = x^(p^2).
+        let coefficients = FrobeniusCoefficients::new(&self.value.c0.non_residue)?;
+        let frobenius_2 = self.value.frobenius_map_with(2, &coefficients)?;
+        let frobenius_4 = frobenius_2.frobenius_map_with(2, &coefficients)?;
+        Ok(frobenius_4.mul(&self.value)? == frobenius_2)
+    }
+
+    /// Serializes the element in compressed form, half the size of its Fp12 value.
+    ///
+    /// An element c0 + c1·w of the cyclotomic subgroup has norm c0^2 - v·c1^2 = 1, so it
+    /// is determined by g = (1 + c0) / c1 ∈ Fp6 and recovered as (g + w) / (g - w). The
+    /// encoding is a tag byte followed by g. Only the identity has c1 = 0 (-1 lies outside
+    /// the subgroup), and it has its own tag and no payload.
+    pub fn to_compressed(&self) -> Result<Vec<u8>, ZKError> {
+        if !self.is_valid()? {
+            return Err(ZKError::InvalidFieldElement(
+                "Only elements of the cyclotomic subgroup can be compressed.".into(),
+            ));
+        }
+        let Fp12 { c0, c1 } = &self.value;
+        if c1.is_zero() {
+            return Ok(vec![COMPRESSED_ONE]);
+        }
+        let g = Fp6::one(&c0.non_residue).add(c0)?.mul(&c1.inv()?)?;
+        let mut bytes = vec![COMPRESSED_GENERIC];
+        bytes.extend(g.to_bytes());
+        Ok(bytes)
+    }
+
+    /// Deserializes an element from the output of `to_compressed`, in the tower defined
+    /// by the Fp6 non-residue ξ. Rejects encodings of elements outside the cyclotomic
+    /// subgroup.
+    pub fn from_compressed(bytes: &[u8], non_residue: &Fp2) -> Result<Gt, ZKError> {
+        let one = Fp6::one(non_residue);
+        let value = match bytes.split_first() {
+            Some((&COMPRESSED_ONE, [])) => Fp12::new(one, Fp6::zero(non_residue))?,
+            Some((&COMPRESSED_GENERIC, payload)) => {
+                // (g + w) / (g - w) = (g^2 + v + 2g·w) / (g^2 - v). v is not a square in
+                // Fp6, so the denominator never vanishes.
+                let g = Fp6::from_bytes(payload, non_residue)?;
+                let zero = Fp2::zero(&non_residue.non_residue);
+                let v = Fp6::new(
+                    zero.clone(),
+                    Fp2::one(&non_residue.non_residue),
+                    zero,
+                    non_residue.clone(),
+                )?;
+                let g_squared = g.square()?;
+                let denominator = g_squared.sub(&v)?.inv()?;
+                Fp12::new(
+                    g_squared.add(&v)?.mul(&denominator)?,
+                    g.add(&g)?.mul(&denominator)?,
+                )?
+            }
+            _ => {
+                return Err(ZKError::MalformedFieldElement(
+                    "Invalid compressed GT encoding.".into(),
+                ))
+            }
+        };
+
+        let gt = Gt { value };
+        if !gt.is_valid()? {
+            return Err(ZKError::InvalidFieldElement(
+                "Decompressed element is not in the cyclotomic subgroup.".into(),
+            ));
+        }
+        Ok(gt)
+    }
+}
+
+impl Pairing {
+    /// Computes the reduced Tate pairing e(P, Q) = f_(r,ψ(Q))(P)^((p^12 - 1) / r) of a
+    /// point of G1 and a point of G2 on the curve `C`.
+    ///
+    /// ψ maps Q from the twist E'(Fp2) into E(Fp12). Its x-coordinate then lies in Fp6,
+    /// so the vertical lines of the Miller loop are erased by the final exponentiation
+    /// and are skipped.
+    pub fn compute<C: Curve>(
+        p: &EllipticCurvePoint,
+        q: &EllipticCurvePoint<Fp2>,
+    ) -> Result<Gt, ZKError> {
+        Self::multi_pairing::<C>(&[(p.clone(), q.clone())])
+    }
+
+    /// Computes the product ∏ e(P_i, Q_i) of several pairings on the curve `C`.
+    ///
+    /// The Miller loops are multiplied together before a single final exponentiation,
+    /// which is the expensive part of a pairing. Verifiers that check a product of
+    /// pairings, such as Groth16's, should prefer this over multiplying `compute` results.
+    pub fn multi_pairing<C: Curve>(
+        pairs: &[(EllipticCurvePoint, EllipticCurvePoint<Fp2>)],
+    ) -> Result<Gt, ZKError> {
+        let prepared = parallel::try_map(pairs, |(_, q)| PreparedG2::<C>::new(q))?;
+        let pairs: Vec<_> = pairs
+            .iter()
+            .zip(&prepared)
+            .map(|((p, _), q)| (p.clone(), q))
+            .collect();
+        Self::multi_pairing_prepared::<C>(&pairs)
+    }
+
+    /// Computes the product ∏ e(P_i, Q_i) like `multi_pairing`, with the G2 points
+    /// already prepared.
+    pub fn multi_pairing_prepared<C: Curve>(
+        pairs: &[(EllipticCurvePoint, &PreparedG2<C>)],
+    ) -> Result<Gt, ZKError> {
+        C::check_embedding_degree()?;
+        let g1 = C::g1_curve()?;
+        let xi = C::fp6_non_residue()?;
+        let loops = parallel::try_map(pairs, |(p, q)| {
+            if !g1.is_on_curve(p)? {
+                return Err(ZKError::CurveError(
+                    "Pairing inputs must lie on the curve and its twist.".into(),
+                ));
+            }
+            let (x_p, y_p) = match (p, &q.point) {
+                (EllipticCurvePoint::Point { x, y }, EllipticCurvePoint::Point { .. }) => (x, y),
+                // Pairs involving infinity contribute the identity.
+                _ => return Ok(None),
+            };
+            if x_p.modulus != C::BASE_MODULUS {
+                return Err(ZKError::InvalidFieldElement(
+                    "Pairing inputs must belong to the curve's base field.".into(),
+                ));
+            }
+
+            miller_loop(q, &embed(x_p, &xi)?, &embed(y_p, &xi)?, &xi).map(Some)
+        })?;
+
+        let mut f = Fp12::one(&xi);
+        for value in loops.into_iter().flatten() {
+            f = f.mul(&value)?;
+        }
+        final_exponentiation::<C>(&f).map(|value| Gt { value })
+    }
+
+    /// Checks that ∏ e(P_i, Q_i) = 1 on the curve `C`, with a single final
+    /// exponentiation and no comparison of GT elements.
+    pub fn check<C: Curve>(
+        pairs: &[(EllipticCurvePoint, EllipticCurvePoint<Fp2>)],
+    ) -> Result<bool, ZKError> {
+        Ok(Self::multi_pairing::<C>(pairs)?.is_one())
+    }
+
+    /// Checks that ∏ e(P_i, Q_i) = ∏ e(R_j, S_j) on the curve `C`, by negating the R_j and
+    /// testing the combined product against the identity with `check`.
+    pub fn check_equal<C: Curve>(
+        lhs: &[(EllipticCurvePoint, EllipticCurvePoint<Fp2>)],
+        rhs: &[(EllipticCurvePoint, EllipticCurvePoint<Fp2>)],
+    ) -> Result<bool, ZKError> {
+        let pairs: Vec<_> = lhs
+            .iter()
+            .cloned()
+            .chain(rhs.iter().map(|(r, s)| (r.neg(), s.clone())))
+            .collect();
+        Self::check::<C>(&pairs)
+    }
+
+    /// Checks many pairing equations ∏_j e(P_ij, Q_ij) = 1 on the curve `C` at once.
+    ///
+    /// Equation i is raised to a random nonzero scalar ρ_i, by scaling its G1 points, and
+    /// all of them are checked with a single multi-pairing. If any equation fails, the
+    /// combination passes only with probability about 1 / r, so `rng` must be chosen by
+    /// the verifier and unpredictable to the prover.
+    pub fn batch_check<C: Curve, R: RngCore + ?Sized>(
+        equations: &[Vec<(EllipticCurvePoint, EllipticCurvePoint<Fp2>)>],
+        rng: &mut R,
+    ) -> Result<bool, ZKError> {
+        let mut pairs = Vec::with_capacity(equations.iter().map(Vec::len).sum());
+        for (i, equation) in equations.iter().enumerate() {
+            // Scaling every equation but one is enough, so the first keeps ρ = 1.
+            let rho = if i == 0 {
+                C::ScalarField::one(&())
+            } else {
+                loop {
+                    let rho = C::ScalarField::random(rng, &())?;
+                    if !rho.is_zero() {
+                        break rho;
+                    }
+                }
+            };
+            for (p, q) in equation {
+                pairs.push((C::g1_mul(p, &rho)?, q.clone()));
+            }
+        }
+        Self::check::<C>(&pairs)
+    }
+
+    /// Tests e(aP, bQ) = e(P, Q)^(ab) for `samples` random scalars a and b.
+    ///
+    /// This is a sanity check for new curve parameter sets, e.g. a wrong twist or tower
+    /// non-residue, rather than something to run in production.
+    pub fn check_bilinearity<C: Curve, R: RngCore + ?Sized>(
+        p: &EllipticCurvePoint,
+        q: &EllipticCurvePoint<Fp2>,
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<bool, ZKError> {
+        let e = Self::compute::<C>(p, q)?;
+        for _ in 0..samples {
+            let a = C::ScalarField::random(rng, &())?;
+            let b = C::ScalarField::random(rng, &())?;
+            let lhs = Self::compute::<C>(&C::g1_mul(p, &a)?, &C::g2_mul(q, &b)?)?;
+            if lhs != e.exp(a.mul(&b)?.value())? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// A point Q of G2 with the lines of its Miller loop precomputed.
+///
+/// The Miller loop walks the multiples of Q, so each line it multiplies in depends on Q
+/// alone. They are stored as y = λx + μ over Fp12, and pairing with a point P of G1 only
+/// evaluates them at P. Verifiers that reuse the same G2 elements, such as those of a
+/// verifying key, across many pairings should prepare them once.
+pub struct PreparedG2<C: Curve> {
+    point: EllipticCurvePoint<Fp2>,
+    // (λ, μ) for every step of the loop, in order, or `None` for a vertical line.
+    lines: Vec<Option<(Fp12, Fp12)>>,
+    _curve: PhantomData<fn() -> C>,
+}
+
+impl<C: Curve> PreparedG2<C> {
+    /// Runs the Miller loop of Q on the twist, recording its lines.
+    pub fn new(q: &EllipticCurvePoint<Fp2>) -> Result<Self, ZKError> {
+        C::check_embedding_degree()?;
+        let g2 = C::g2_curve()?;
+        if !g2.is_on_curve(q)? {
+            return Err(ZKError::CurveError(
+                "Pairing inputs must lie on the curve and its twist.".into(),
+            ));
+        }
+
+        let mut lines = Vec::new();
+        if let EllipticCurvePoint::Point { .. } = q {
+            let a = embed(&C::g1_curve()?.a, &C::fp6_non_residue()?)?;
+            let order = C::SCALAR_MODULUS;
+            let mut t = q.clone();
+            for i in (0..u64::BITS - 1 - order.leading_zeros()).rev() {
+                lines.push(line::<C>(&t, &t, &a)?);
+                t = g2.double(&t)?;
+
+                if (order >> i) & 1 == 1 {
+                    lines.push(line::<C>(&t, q, &a)?);
+                    t = g2.add_points(&t, q)?;
+                }
+            }
+        }
+
+        Ok(PreparedG2 {
+            point: q.clone(),
+            lines,
+            _curve: PhantomData,
+        })
+    }
+
+    /// Returns the prepared point.
+    pub fn point(&self) -> &EllipticCurvePoint<Fp2> {
+        &self.point
+    }
+}
+
+impl<C: Curve> Clone for PreparedG2<C> {
+    fn clone(&self) -> Self {
+        PreparedG2 {
+            point: self.point.clone(),
+            lines: self.lines.clone(),
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: Curve> Debug for PreparedG2<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreparedG2")
+            .field("curve", &C::NAME)
+            .field("point", &self.point)
+            .field("lines", &self.lines.len())
+            .finish()
+    }
+}
+
+/// A pairing e: G1 × G2 → GT together with its groups, so that protocols such as the
+/// SNARK can be written once and run over any of them.
+///
+/// Engines are values rather than bare types, since some need runtime data, such as the
+/// curve of [`ToyPairing`].
+pub trait PairingEngine {
+    /// The scalar field, whose elements act on G1 and G2.
+    type Fr: Field;
+    /// The first source group.
+    type G1: Clone + Debug + PartialEq;
+    /// The second source group.
+    type G2: Clone + Debug + PartialEq;
+    /// The target group.
+    type Gt: Clone + Debug + PartialEq;
+
+    /// Returns the standard generator of G1.
+    fn g1_generator(&self) -> Result<Self::G1, ZKError>;
+
+    /// Returns the standard generator of G2.
+    fn g2_generator(&self) -> Result<Self::G2, ZKError>;
+
+    /// Multiplies a point of G1 by a scalar.
+    fn g1_mul(&self, point: &Self::G1, scalar: &Self::Fr) -> Result<Self::G1, ZKError>;
+
+    /// Multiplies a point of G2 by a scalar.
+    fn g2_mul(&self, point: &Self::G2, scalar: &Self::Fr) -> Result<Self::G2, ZKError>;
+
+    /// Negates a point of G1, so that e(-P, Q) is the inverse of e(P, Q).
+    fn g1_neg(&self, point: &Self::G1) -> Result<Self::G1, ZKError>;
+
+    /// Computes e(P, Q).
+    fn pairing(&self, p: &Self::G1, q: &Self::G2) -> Result<Self::Gt, ZKError>;
+
+    /// Computes the product ∏ e(P_i, Q_i).
+    fn multi_pairing(&self, pairs: &[(Self::G1, Self::G2)]) -> Result<Self::Gt, ZKError>;
+
+    /// Checks that ∏ e(P_i, Q_i) is the identity of GT, the value of the empty product,
+    /// with one multi-pairing over all the pairs.
+    fn check(&self, pairs: &[(Self::G1, Self::G2)]) -> Result<bool, ZKError> {
+        Ok(self.multi_pairing(pairs)? == self.multi_pairing(&[])?)
+    }
+
+    /// Checks that ∏ e(P_i, Q_i) = ∏ e(R_j, S_j) by negating the R_j and testing the
+    /// combined product with `check`, so both sides share one final exponentiation.
+    fn check_equal(
+        &self,
+        lhs: &[(Self::G1, Self::G2)],
+        rhs: &[(Self::G1, Self::G2)],
+    ) -> Result<bool, ZKError> {
+        let mut pairs = lhs.to_vec();
+        for (r, s) in rhs {
+            pairs.push((self.g1_neg(r)?, s.clone()));
+        }
+        self.check(&pairs)
+    }
+}
+
+/// The dummy pairing of [`Pairing::create`] on a single curve, standing in for both
+/// source groups. Its group operations are dummies too, and none of it is secure.
+///
+/// Scalars multiply the coordinates of a point, so e(aP, bQ) = ab · e(P, Q): the dummy
+/// pairing is bilinear into the additive group of the field, and GT products are sums
+/// of the pairing values.
+#[derive(Debug, Clone)]
+pub struct ToyPairing {
+    pub curve: EllipticCurve,
+}
+
+impl ToyPairing {
+    /// Creates the engine for the given curve, which must describe its subgroup.
+    pub fn new(curve: EllipticCurve) -> Result<Self, ZKError> {
+        curve.subgroup()?;
+        Ok(ToyPairing { curve })
+    }
+
+    // "Multiplies" a point by a scalar by scaling its coordinates.
+    fn scale(
+        &self,
+        point: &EllipticCurvePoint,
+        scalar: &FieldElement,
+    ) -> Result<EllipticCurvePoint, ZKError> {
+        let modulus = self.curve.a.modulus;
+        let scalar = FieldElement::new(scalar.value % scalar.modulus % modulus, modulus)?;
+        Ok(match point {
+            EllipticCurvePoint::Point { x, y } => EllipticCurvePoint::Point {
+                x: x.mul(&scalar)?,
+                y: y.mul(&scalar)?,
+            },
+            EllipticCurvePoint::Infinity => EllipticCurvePoint::Infinity,
+        })
+    }
+}
+
+impl PairingEngine for ToyPairing {
+    type Fr = FieldElement;
+    type G1 = EllipticCurvePoint;
+    type G2 = EllipticCurvePoint;
+    type Gt = Pairing;
+
+    fn g1_generator(&self) -> Result<EllipticCurvePoint, ZKError> {
+        Ok(self.curve.subgroup()?.generator.clone())
+    }
+
+    fn g2_generator(&self) -> Result<EllipticCurvePoint, ZKError> {
+        self.g1_generator()
+    }
+
+    fn g1_mul(
+        &self,
+        point: &EllipticCurvePoint,
+        scalar: &FieldElement,
+    ) -> Result<EllipticCurvePoint, ZKError> {
+        self.scale(point, scalar)
+    }
+
+    fn g2_mul(
+        &self,
+        point: &EllipticCurvePoint,
+        scalar: &FieldElement,
+    ) -> Result<EllipticCurvePoint, ZKError> {
+        self.scale(point, scalar)
+    }
+
+    // Negation scales by -1, which negates the additive pairing value.
+    fn g1_neg(&self, point: &EllipticCurvePoint) -> Result<EllipticCurvePoint, ZKError> {
+        let modulus = self.curve.a.modulus;
+        self.scale(point, &FieldElement::new(modulus - 1, modulus)?)
+    }
+
+    fn pairing(&self, p: &EllipticCurvePoint, q: &EllipticCurvePoint) -> Result<Pairing, ZKError> {
+        Pairing::create(&self.curve, p, q)
+    }
+
+    fn multi_pairing(
+        &self,
+        pairs: &[(EllipticCurvePoint, EllipticCurvePoint)],
+    ) -> Result<Pairing, ZKError> {
+        let mut value = FieldElement::zero(self.curve.a.modulus)?;
+        for (p, q) in pairs {
+            value = value.add(&self.pairing(p, q)?.value)?;
+        }
+        Ok(Pairing { value })
+    }
+}
+
+/// The reduced Tate pairing of [`Pairing::compute`] on the pairing-friendly curve `C`,
+/// with scalars in `C::ScalarField`.
+pub struct TatePairing<C: Curve> {
+    _curve: PhantomData<fn() -> C>,
+}
+
+impl<C: Curve> TatePairing<C> {
+    /// Creates the engine.
+    pub fn new() -> Self {
+        TatePairing {
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: Curve> Default for TatePairing<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Curve> PairingEngine for TatePairing<C> {
+    type Fr = C::ScalarField;
+    type G1 = EllipticCurvePoint;
+    type G2 = EllipticCurvePoint<Fp2>;
+    type Gt = Gt;
+
+    fn g1_generator(&self) -> Result<EllipticCurvePoint, ZKError> {
+        C::g1_generator()
+    }
+
+    fn g2_generator(&self) -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+        C::g2_generator()
+    }
+
+    fn g1_mul(
+        &self,
+        point: &EllipticCurvePoint,
+        scalar: &C::ScalarField,
+    ) -> Result<EllipticCurvePoint, ZKError> {
+        C::g1_mul(point, scalar)
+    }
+
+    fn g2_mul(
+        &self,
+        point: &EllipticCurvePoint<Fp2>,
+        scalar: &C::ScalarField,
+    ) -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+        C::g2_mul(point, scalar)
+    }
+
+    fn g1_neg(&self, point: &EllipticCurvePoint) -> Result<EllipticCurvePoint, ZKError> {
+        Ok(point.neg())
+    }
+
+    fn pairing(&self, p: &EllipticCurvePoint, q: &EllipticCurvePoint<Fp2>) -> Result<Gt, ZKError> {
+        Pairing::compute::<C>(p, q)
+    }
+
+    fn multi_pairing(
+        &self,
+        pairs: &[(EllipticCurvePoint, EllipticCurvePoint<Fp2>)],
+    ) -> Result<Gt, ZKError> {
+        Pairing::multi_pairing::<C>(pairs)
+    }
+
+    fn check(
+        &self,
+        pairs: &[(EllipticCurvePoint, EllipticCurvePoint<Fp2>)],
+    ) -> Result<bool, ZKError> {
+        Pairing::check::<C>(pairs)
+    }
+}
+
+// Maps a point of the twist E'(Fp2) to E(Fp12): (x, y) ↦ (x·w^2, y·w^3) for a D-type
+// twist and (x·w^4 / ξ, y·w^3 / ξ) for an M-type twist, using w^2 = v and w^6 = ξ.
+fn untwist<C: Curve>(q: &EllipticCurvePoint<Fp2>) -> Result<(Fp12, Fp12), ZKError> {
+    let EllipticCurvePoint::Point { x, y } = q else {
+        return Err(ZKError::CurveError(
+            "The point at infinity has no affine image.".into(),
+        ));
+    };
+    let xi = C::fp6_non_residue()?;
+    let zero = Fp2::zero(&xi.non_residue);
+    let fp6 =
+        |c0: &Fp2, c1: &Fp2, c2: &Fp2| Fp6::new(c0.clone(), c1.clone(), c2.clone(), xi.clone());
+    let fp6_zero = Fp6::zero(&xi);
+    match C::TWIST_TYPE {
+        TwistType::D => Ok((
+            Fp12::new(fp6(&zero, x, &zero)?, fp6_zero.clone())?,
+            Fp12::new(fp6_zero, fp6(&zero, y, &zero)?)?,
+        )),
+        TwistType::M => {
+            let xi_inv = xi.inv()?;
+            Ok((
+                Fp12::new(fp6(&zero, &zero, &x.mul(&xi_inv)?)?, fp6_zero.clone())?,
+                Fp12::new(fp6_zero, fp6(&zero, &y.mul(&xi_inv)?, &zero)?)?,
+            ))
+        }
+    }
+}
+
+// Embeds an element of Fp into Fp12.
+fn embed(a: &FieldElement, xi: &Fp2) -> Result<Fp12, ZKError> {
+    let c0 = Fp6::from_fp2(Fp2::from_base(a.clone(), &xi.non_residue)?, xi)?;
+    Fp12::new(c0, Fp6::zero(xi))
+}
+
+// Evaluates f_(r,ψ(Q))(P) from the prepared lines of Q, squaring f at every step and
+// multiplying in each line y_P - λx_P - μ. Vertical lines are skipped.
+fn miller_loop<C: Curve>(
+    q: &PreparedG2<C>,
+    x_p: &Fp12,
+    y_p: &Fp12,
+    xi: &Fp2,
+) -> Result<Fp12, ZKError> {
+    let order = C::SCALAR_MODULUS;
+    let mut lines = q.lines.iter();
+    let mut next_line = |f: Fp12| -> Result<Fp12, ZKError> {
+        match lines.next() {
+            Some(Some((slope, intercept))) => f.mul(&y_p.sub(&slope.mul(x_p)?)?.sub(intercept)?),
+            Some(None) => Ok(f),
+            None => Err(ZKError::CurveError(
+                "Prepared point has too few lines.".into(),
+            )),
+        }
+    };
+
+    let mut f = Fp12::one(xi);
+    for i in (0..u64::BITS - 1 - order.leading_zeros()).rev() {
+        f = next_line(f.square()?)?;
+        if (order >> i) & 1 == 1 {
+            f = next_line(f)?;
+        }
+    }
+    Ok(f)
+}
+
+// Returns (λ, μ) for the line y = λx + μ through ψ(T) and ψ(Q) (the tangent when T = Q)
+// in E(Fp12), where `a` is the embedded coefficient of E. Returns `None` for vertical
+// lines.
+fn line<C: Curve>(
+    t: &EllipticCurvePoint<Fp2>,
+    q: &EllipticCurvePoint<Fp2>,
+    a: &Fp12,
+) -> Result<Option<(Fp12, Fp12)>, ZKError> {
+    let (EllipticCurvePoint::Point { .. }, EllipticCurvePoint::Point { .. }) = (t, q) else {
+        return Ok(None);
+    };
+    let (x_t, y_t) = untwist::<C>(t)?;
+    let (x_q, y_q) = untwist::<C>(q)?;
+
+    let slope = if x_t != x_q {
+        y_q.sub(&y_t)?.mul(&x_q.sub(&x_t)?.inv()?)?
+    } else if y_t == y_q && !y_t.is_zero() {
+        let x_squared = x_t.square()?;
+        x_squared
+            .add(&x_squared)?
+            .add(&x_squared)?
+            .add(a)?
+            .mul(&y_t.add(&y_t)?.inv()?)?
+    } else {
+        return Ok(None);
+    };
+    let intercept = y_t.sub(&slope.mul(&x_t)?)?;
+    Ok(Some((slope, intercept)))
+}
+
+// Raises f to (p^12 - 1) / r as f^((p^6 - 1)(p^2 + 1)) followed by the hard part
+// (p^4 - p^2 + 1) / r, which must fit in 64 bits.
+fn final_exponentiation<C: Curve>(f: &Fp12) -> Result<Fp12, ZKError> {
+    let p = C::BASE_MODULUS as u128;
+    let r = C::SCALAR_MODULUS as u128;
+    let cyclotomic = p
+        .checked_pow(4)
+        .map(|p4| p4 - p * p + 1)
+        .filter(|value| value % r == 0)
+        .ok_or_else(|| {
+            ZKError::CurveError(format!(
+                "r = {} does not divide p^4 - p^2 + 1 for p = {}.",
+                r, p
+            ))
+        })?;
+    let hard = u64::try_from(cyclotomic / r).map_err(|_| {
+        ZKError::CurveError("The final exponentiation needs exponents beyond 64 bits.".into())
+    })?;
+
+    // f^(p^6 - 1) = conj(f) / f lies in the cyclotomic subgroup.
+    let easy = f.conjugate().mul(&f.inv()?)?;
+    let easy = easy.frobenius_map(2)?.mul(&easy)?;
+    easy.cyclotomic_exp(hard)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::curve::EllipticCurve;
+    use crate::curves::{Toy97, ToyBls12, ToyBn};
     use crate::field::FieldElement;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_pairing() {
@@ -57,6 +764,7 @@ mod tests {
         let curve = EllipticCurve {
             a: FieldElement::new(2, modulus).unwrap(),
             b: FieldElement::new(3, modulus).unwrap(),
+            subgroup: None,
         };
         let point_a = EllipticCurvePoint::Point {
             x: FieldElement::new(3, modulus).unwrap(),
@@ -71,4 +779,372 @@ mod tests {
         // For p and q, x = 3, so expected result is 3 * 2 = 6 mod 97.
         assert_eq!(pairing.value, FieldElement::new(6, modulus).unwrap());
     }
+
+    fn check_pairing<C: Curve>() {
+        let p = C::g1_generator().unwrap();
+        let q = C::g2_generator().unwrap();
+        let g1 = C::g1_curve().unwrap();
+        let g2 = C::g2_curve().unwrap();
+        let r = C::SCALAR_MODULUS;
+
+        let e = Pairing::compute::<C>(&p, &q).unwrap();
+        assert!(!e.is_one(), "{} pairing is degenerate", C::NAME);
+        assert!(e.exp(r).unwrap().is_one());
+        for k in [0, 1, 5, r - 1, 1 << 33] {
+            assert_eq!(e.exp_compressed(k).unwrap(), e.exp(k).unwrap());
+        }
+
+        // e(aP, bQ) = e(P, Q)^(ab)
+        for (a, b) in [(2, 3), (5, 1), (1, 7), (r - 1, 2)] {
+            let lhs = Pairing::compute::<C>(
+                &g1.mul_scalar(&p, a).unwrap(),
+                &g2.mul_scalar(&q, b).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(lhs, e.exp(a * b % r).unwrap());
+        }
+
+        let infinity = Pairing::compute::<C>(&EllipticCurvePoint::Infinity, &q).unwrap();
+        assert!(infinity.is_one());
+        assert_eq!(e.mul(&e.inv()).unwrap(), infinity);
+    }
+
+    fn check_multi_pairing<C: Curve>() {
+        let p = C::g1_generator().unwrap();
+        let q = C::g2_generator().unwrap();
+        let g1 = C::g1_curve().unwrap();
+        let g2 = C::g2_curve().unwrap();
+        let e = Pairing::compute::<C>(&p, &q).unwrap();
+
+        // e(2P, Q) · e(P, 3Q) · e(∞, Q) = e(P, Q)^5
+        let pairs = [
+            (g1.mul_scalar(&p, 2).unwrap(), q.clone()),
+            (p.clone(), g2.mul_scalar(&q, 3).unwrap()),
+            (EllipticCurvePoint::Infinity, q.clone()),
+        ];
+        let product = Pairing::multi_pairing::<C>(&pairs).unwrap();
+        assert_eq!(product, e.exp(5).unwrap());
+
+        // e(P, Q) · e(-P, Q) = 1
+        let pairs = [(p.clone(), q.clone()), (p.neg(), q.clone())];
+        assert!(Pairing::multi_pairing::<C>(&pairs).unwrap().is_one());
+        assert!(Pairing::multi_pairing::<C>(&[]).unwrap().is_one());
+    }
+
+    #[test]
+    fn test_multi_pairing() {
+        check_multi_pairing::<ToyBn>();
+        check_multi_pairing::<ToyBls12>();
+
+        // Points off the curve are rejected anywhere in the list.
+        let p = ToyBn::g1_generator().unwrap();
+        let q = ToyBn::g2_generator().unwrap();
+        let off_curve = EllipticCurvePoint::Point {
+            x: FieldElement::new(1, 103).unwrap(),
+            y: FieldElement::new(1, 103).unwrap(),
+        };
+        let pairs = [(p, q.clone()), (off_curve, q)];
+        assert!(Pairing::multi_pairing::<ToyBn>(&pairs).is_err());
+    }
+
+    fn check_pairing_check<C: Curve>() {
+        let p = C::g1_generator().unwrap();
+        let q = C::g2_generator().unwrap();
+        let g1 = C::g1_curve().unwrap();
+        let g2 = C::g2_curve().unwrap();
+        let (p2, p3, p6) = (
+            g1.mul_scalar(&p, 2).unwrap(),
+            g1.mul_scalar(&p, 3).unwrap(),
+            g1.mul_scalar(&p, 6).unwrap(),
+        );
+        let (q2, q3) = (g2.mul_scalar(&q, 2).unwrap(), g2.mul_scalar(&q, 3).unwrap());
+
+        // e(2P, 3Q) · e(-6P, Q) = 1
+        assert!(Pairing::check::<C>(&[(p2.clone(), q3.clone()), (p6.neg(), q.clone())]).unwrap());
+        assert!(
+            !Pairing::check::<C>(&[(p2.clone(), q3.clone()), (p6.clone(), q.clone())]).unwrap()
+        );
+
+        // e(2P, 3Q) = e(3P, 2Q) = e(P, Q) · e(5P, Q)
+        assert!(
+            Pairing::check_equal::<C>(&[(p2.clone(), q3.clone())], &[(p3, q2.clone())]).unwrap()
+        );
+        let rhs = [
+            (p.clone(), q.clone()),
+            (g1.mul_scalar(&p, 5).unwrap(), q.clone()),
+        ];
+        assert!(Pairing::check_equal::<C>(&[(p2.clone(), q3)], &rhs).unwrap());
+        assert!(!Pairing::check_equal::<C>(&[(p2, q2)], &rhs).unwrap());
+    }
+
+    #[test]
+    fn test_pairing_check() {
+        check_pairing_check::<ToyBn>();
+        check_pairing_check::<ToyBls12>();
+    }
+
+    fn check_prepared<C: Curve>() {
+        let p = C::g1_generator().unwrap();
+        let q = C::g2_generator().unwrap();
+        let g1 = C::g1_curve().unwrap();
+        let prepared = PreparedG2::<C>::new(&q).unwrap();
+        assert_eq!(prepared.point(), &q);
+
+        // A prepared point gives the same pairings as the plain one, for any P.
+        for a in [1, 2, 5] {
+            let pa = g1.mul_scalar(&p, a).unwrap();
+            assert_eq!(
+                Pairing::multi_pairing_prepared::<C>(&[(pa.clone(), &prepared)]).unwrap(),
+                Pairing::compute::<C>(&pa, &q).unwrap()
+            );
+        }
+        let pairs = [(p.clone(), &prepared), (p.neg(), &prepared.clone())];
+        assert!(Pairing::multi_pairing_prepared::<C>(&pairs)
+            .unwrap()
+            .is_one());
+
+        let infinity = PreparedG2::<C>::new(&EllipticCurvePoint::Infinity).unwrap();
+        assert!(Pairing::multi_pairing_prepared::<C>(&[(p, &infinity)])
+            .unwrap()
+            .is_one());
+    }
+
+    #[test]
+    fn test_prepared_g2() {
+        check_prepared::<ToyBn>();
+        check_prepared::<ToyBls12>();
+
+        let q = ToyBn::g2_generator().unwrap();
+        let EllipticCurvePoint::Point { x, y } = q else {
+            unreachable!()
+        };
+        let off_twist = EllipticCurvePoint::Point { x: y, y: x };
+        assert!(PreparedG2::<ToyBn>::new(&off_twist).is_err());
+    }
+
+    fn check_batch<C: Curve>() {
+        let p = C::g1_generator().unwrap();
+        let q = C::g2_generator().unwrap();
+        let g1 = C::g1_curve().unwrap();
+        let g2 = C::g2_curve().unwrap();
+        let mut rng = StdRng::seed_from_u64(1100);
+
+        // e(aP, bQ) · e(-abP, Q) = 1 for every (a, b).
+        let equation = |a: u64, b: u64, c: u64| {
+            vec![
+                (g1.mul_scalar(&p, a).unwrap(), g2.mul_scalar(&q, b).unwrap()),
+                (g1.mul_scalar(&p, c).unwrap().neg(), q.clone()),
+            ]
+        };
+        let mut equations = vec![equation(2, 3, 6), equation(4, 5, 20), equation(1, 1, 1)];
+        assert!(Pairing::batch_check::<C, _>(&equations, &mut rng).unwrap());
+        assert!(Pairing::batch_check::<C, _>(&[], &mut rng).unwrap());
+
+        // One bad equation anywhere fails the whole batch.
+        for i in 0..equations.len() {
+            let saved = equations[i].clone();
+            equations[i] = equation(2, 3, 7);
+            assert!(!Pairing::batch_check::<C, _>(&equations, &mut rng).unwrap());
+            equations[i] = saved;
+        }
+    }
+
+    #[test]
+    fn test_batch_check() {
+        check_batch::<ToyBn>();
+        check_batch::<ToyBls12>();
+    }
+
+    #[test]
+    fn test_check_bilinearity() {
+        let mut rng = StdRng::seed_from_u64(1101);
+        let p = ToyBn::g1_generator().unwrap();
+        let q = ToyBn::g2_generator().unwrap();
+        assert!(Pairing::check_bilinearity::<ToyBn, _>(&p, &q, 8, &mut rng).unwrap());
+
+        let p = ToyBls12::g1_generator().unwrap();
+        let q = ToyBls12::g2_generator().unwrap();
+        assert!(Pairing::check_bilinearity::<ToyBls12, _>(&p, &q, 8, &mut rng).unwrap());
+
+        // The wrong twist type breaks bilinearity: ψ no longer lands on E, so the Miller
+        // loop walks points of some other curve.
+        struct WrongTwist;
+        impl Curve for WrongTwist {
+            const NAME: &'static str = "wrong-twist";
+            const BASE_MODULUS: u64 = ToyBn::BASE_MODULUS;
+            const SCALAR_MODULUS: u64 = ToyBn::SCALAR_MODULUS;
+            const G1_COFACTOR: u64 = ToyBn::G1_COFACTOR;
+            const G2_COFACTOR: u64 = ToyBn::G2_COFACTOR;
+            const TWIST_TYPE: TwistType = TwistType::M;
+            type BaseField = <ToyBn as Curve>::BaseField;
+            type ScalarField = <ToyBn as Curve>::ScalarField;
+
+            fn fp2_non_residue() -> Result<FieldElement, ZKError> {
+                ToyBn::fp2_non_residue()
+            }
+            fn fp6_non_residue() -> Result<Fp2, ZKError> {
+                ToyBn::fp6_non_residue()
+            }
+            fn g1_curve() -> Result<EllipticCurve, ZKError> {
+                ToyBn::g1_curve()
+            }
+            fn g2_curve() -> Result<EllipticCurve<Fp2>, ZKError> {
+                ToyBn::g2_curve()
+            }
+            fn g1_generator() -> Result<EllipticCurvePoint, ZKError> {
+                ToyBn::g1_generator()
+            }
+            fn g2_generator() -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+                ToyBn::g2_generator()
+            }
+        }
+        let p = WrongTwist::g1_generator().unwrap();
+        let q = WrongTwist::g2_generator().unwrap();
+        assert!(!Pairing::check_bilinearity::<WrongTwist, _>(&p, &q, 8, &mut rng).unwrap());
+    }
+
+    #[test]
+    fn test_gt_is_valid() {
+        let e = Pairing::compute::<ToyBn>(
+            &ToyBn::g1_generator().unwrap(),
+            &ToyBn::g2_generator().unwrap(),
+        )
+        .unwrap();
+        assert!(e.is_valid().unwrap());
+        assert!(e.exp(5).unwrap().is_valid().unwrap());
+
+        let xi = ToyBn::fp6_non_residue().unwrap();
+        let one = Fp12::one(&xi);
+        assert!(Gt { value: one.clone() }.is_valid().unwrap());
+        assert!(!Gt {
+            value: Fp12::zero(&xi)
+        }
+        .is_valid()
+        .unwrap());
+
+        // Elements of Fp other than 1 lie outside, but the easy part of the final
+        // exponentiation maps anything into the cyclotomic subgroup.
+        let x = e.value.add(&one).unwrap();
+        assert!(!Gt {
+            value: one.add(&one).unwrap()
+        }
+        .is_valid()
+        .unwrap());
+        assert!(!Gt { value: x.clone() }.is_valid().unwrap());
+        let easy = x.conjugate().mul(&x.inv().unwrap()).unwrap();
+        let easy = easy.frobenius_map(2).unwrap().mul(&easy).unwrap();
+        assert!(Gt { value: easy }.is_valid().unwrap());
+    }
+
+    #[test]
+    fn test_gt_compression() {
+        let xi = ToyBn::fp6_non_residue().unwrap();
+        let e = Pairing::compute::<ToyBn>(
+            &ToyBn::g1_generator().unwrap(),
+            &ToyBn::g2_generator().unwrap(),
+        )
+        .unwrap();
+
+        for k in [1, 2, 5, 96] {
+            let x = e.exp(k).unwrap();
+            let bytes = x.to_compressed().unwrap();
+            assert_eq!(bytes.len(), 1 + 48);
+            assert_eq!(Gt::from_compressed(&bytes, &xi).unwrap(), x);
+        }
+        let one = Gt::one(&xi);
+        assert_eq!(one.to_compressed().unwrap(), vec![COMPRESSED_ONE]);
+        assert_eq!(Gt::from_compressed(&[COMPRESSED_ONE], &xi).unwrap(), one);
+        let minus_one = Gt {
+            value: Fp12::one(&xi).neg(),
+        };
+        assert!(minus_one.to_compressed().is_err());
+
+        // Truncated payloads, unknown tags and elements outside the subgroup are rejected.
+        let bytes = e.to_compressed().unwrap();
+        assert!(Gt::from_compressed(&bytes[..40], &xi).is_err());
+        assert!(Gt::from_compressed(&[7], &xi).is_err());
+        assert!(Gt::from_compressed(&[], &xi).is_err());
+        assert!(Gt::from_compressed(&[COMPRESSED_ONE, 0], &xi).is_err());
+        let mut payload = vec![COMPRESSED_GENERIC];
+        payload.extend(Fp6::one(&xi).to_bytes());
+        assert!(Gt::from_compressed(&payload, &xi).is_err());
+        let outside = Gt {
+            value: e.value.add(&Fp12::one(&xi)).unwrap(),
+        };
+        assert!(outside.to_compressed().is_err());
+    }
+
+    #[test]
+    fn test_pairing_bytes() {
+        let pairing = Pairing {
+            value: FieldElement::new(42, 97).unwrap(),
+        };
+        let bytes = pairing.to_bytes();
+        assert_eq!(Pairing::from_bytes(&bytes, 97).unwrap(), pairing);
+        assert!(Pairing::from_bytes(&bytes, 13).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_pairing_serde() {
+        let pairing = Pairing {
+            value: FieldElement::new(42, 97).unwrap(),
+        };
+        let json = serde_json::to_string(&pairing).unwrap();
+        assert_eq!(serde_json::from_str::<Pairing>(&json).unwrap(), pairing);
+    }
+
+    #[test]
+    fn test_refuses_unusable_embedding_degree() {
+        // toy97 has embedding degree 106, so its pairing would not land in Fp12.
+        struct Toy97Pairing;
+        impl Curve for Toy97Pairing {
+            const NAME: &'static str = "toy97";
+            const BASE_MODULUS: u64 = 97;
+            const SCALAR_MODULUS: u64 = 107;
+            const G1_COFACTOR: u64 = 1;
+            const G2_COFACTOR: u64 = 1;
+            const TWIST_TYPE: TwistType = TwistType::D;
+            type BaseField = crate::fp::Fp<97>;
+            type ScalarField = crate::fp::Fp<107>;
+
+            fn fp2_non_residue() -> Result<FieldElement, ZKError> {
+                FieldElement::from_i64(-1, 97)
+            }
+            fn fp6_non_residue() -> Result<Fp2, ZKError> {
+                Fp2::new(
+                    FieldElement::new(2, 97)?,
+                    FieldElement::new(1, 97)?,
+                    Self::fp2_non_residue()?,
+                )
+            }
+            fn g1_curve() -> Result<EllipticCurve, ZKError> {
+                Toy97::curve()
+            }
+            fn g2_curve() -> Result<EllipticCurve<Fp2>, ZKError> {
+                Err(ZKError::CurveError("toy97 has no twist".into()))
+            }
+            fn g1_generator() -> Result<EllipticCurvePoint, ZKError> {
+                Ok(Toy97::curve()?.subgroup()?.generator.clone())
+            }
+            fn g2_generator() -> Result<EllipticCurvePoint<Fp2>, ZKError> {
+                Err(ZKError::CurveError("toy97 has no twist".into()))
+            }
+        }
+
+        let p = Toy97Pairing::g1_generator().unwrap();
+        let error = Pairing::compute::<Toy97Pairing>(&p, &EllipticCurvePoint::Infinity)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("embedding degree 106"), "{}", error);
+        assert!(Pairing::multi_pairing_prepared::<Toy97Pairing>(&[]).is_err());
+        assert!(ToyBn::check_embedding_degree().is_ok());
+        assert!(ToyBls12::check_embedding_degree().is_ok());
+    }
+
+    #[test]
+    fn test_tate_pairing() {
+        check_pairing::<ToyBn>();
+        check_pairing::<ToyBls12>();
+    }
 }
@@ -0,0 +1,40 @@
+// Work-splitting helpers that run on the rayon thread pool when the `parallel`
+// feature is enabled and sequentially otherwise, so callers need no feature gates.
+
+/// Runs both closures, potentially in parallel, and returns their results.
+pub(crate) fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        rayon::join(a, b)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (a(), b())
+    }
+}
+
+/// Maps `f` over `items`, potentially in parallel, preserving their order and
+/// returning the first error encountered.
+pub(crate) fn try_map<T, U, E, M>(items: &[T], f: M) -> Result<Vec<U>, E>
+where
+    T: Sync,
+    U: Send,
+    E: Send,
+    M: Fn(&T) -> Result<U, E> + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{
+    builder::{CircuitBuilder, Hint, Var},
+    edwards::{EdwardsPoint, EdwardsPointVar, TwistedEdwardsCurve},
+    errors::ZKError,
+    field::FieldElement,
+    hash::hash_to_field,
+};
+
+/// Number of message bits consumed per lookup.
+pub const WINDOW_BITS: usize = 3;
+
+/// Pedersen hash and commitment over a twisted Edwards curve.
+///
+/// The message bits are split into windows of `WINDOW_BITS` bits, and window j with value
+/// m_j contributes (m_j + 1) * G_j, read from a table of precomputed multiples, so that
+/// H(m) = sum_j (m_j + 1) * G_j. Offsetting every digit by one keeps zero windows from
+/// vanishing. A final window shorter than `WINDOW_BITS` is padded with zeros, so each use
+/// should fix the message length, as a circuit does.
+///
+/// The generators are derived by hashing, so nobody knows discrete logarithms between
+/// them; finding a collision means finding such a relation.
+#[derive(Debug, Clone)]
+pub struct PedersenHash {
+    curve: TwistedEdwardsCurve,
+    // tables[j][k] = (k + 1) * G_j.
+    tables: Vec<Vec<EdwardsPoint>>,
+    blinding: EdwardsPoint,
+}
+
+impl PedersenHash {
+    /// Derives generators for messages of up to `max_bits` bits, plus a blinding
+    /// generator for commitments. `cofactor` is cleared from every generator, so they lie
+    /// in the prime-order subgroup; `dst` separates independent generator sets.
+    pub fn new(
+        curve: TwistedEdwardsCurve,
+        cofactor: u64,
+        max_bits: usize,
+        dst: &[u8],
+    ) -> Result<Self, ZKError> {
+        let windows = max_bits.div_ceil(WINDOW_BITS);
+        let mut tables = Vec::with_capacity(windows);
+        for index in 0..windows {
+            let generator = derive_generator(&curve, cofactor, dst, index as u64)?;
+            let mut table = vec![generator.clone()];
+            for _ in 1..1 << WINDOW_BITS {
+                table.push(curve.add(&table[table.len() - 1], &generator)?);
+            }
+            tables.push(table);
+        }
+        let blinding = derive_generator(&curve, cofactor, dst, u64::MAX)?;
+        Ok(PedersenHash {
+            curve,
+            tables,
+            blinding,
+        })
+    }
+
+    /// Returns the longest message, in bits, the generators cover.
+    pub fn max_bits(&self) -> usize {
+        self.tables.len() * WINDOW_BITS
+    }
+
+    /// Returns the generator G_j of every window.
+    pub fn generators(&self) -> Vec<&EdwardsPoint> {
+        self.tables.iter().map(|table| &table[0]).collect()
+    }
+
+    /// Returns the generator that randomizes commitments.
+    pub fn blinding_generator(&self) -> &EdwardsPoint {
+        &self.blinding
+    }
+
+    /// Hashes a message given as bits, least significant bit of each window first.
+    pub fn hash(&self, bits: &[bool]) -> Result<EdwardsPoint, ZKError> {
+        if bits.len() > self.max_bits() {
+            return Err(ZKError::CurveError(format!(
+                "Message of {} bits exceeds the {} bits covered by the generators.",
+                bits.len(),
+                self.max_bits()
+            )));
+        }
+
+        let mut result = self.curve.identity();
+        for (window, table) in bits.chunks(WINDOW_BITS).zip(&self.tables) {
+            let digit = window
+                .iter()
+                .rev()
+                .fold(0, |acc, &bit| (acc << 1) | bit as usize);
+            result = self.curve.add(&result, &table[digit])?;
+        }
+        Ok(result)
+    }
+
+    /// Commits to a message as H(m) + randomness * B, where B is the blinding generator.
+    /// The commitment hides the message when the randomness is uniform.
+    pub fn commit(&self, bits: &[bool], randomness: u64) -> Result<EdwardsPoint, ZKError> {
+        let blinding = self.curve.mul_scalar(&self.blinding, randomness)?;
+        self.curve.add(&self.hash(bits)?, &blinding)
+    }
+
+    /// Computes the hash inside a circuit over the base field of the curve. The bits
+    /// must already be constrained to be boolean.
+    ///
+    /// Each window selects its precomputed multiple with a lookup costing 3 constraints,
+    /// and the selected points are added with the complete addition law.
+    pub fn hash_gadget(
+        &self,
+        builder: &mut CircuitBuilder,
+        bits: &[Var],
+    ) -> Result<EdwardsPointVar, ZKError> {
+        if builder.params != self.curve.a.modulus {
+            return Err(ZKError::CircuitError(
+                "The curve must be defined over the field of the circuit.".into(),
+            ));
+        }
+        if bits.len() > self.max_bits() {
+            return Err(ZKError::CurveError(format!(
+                "Message of {} bits exceeds the {} bits covered by the generators.",
+                bits.len(),
+                self.max_bits()
+            )));
+        }
+        let points = bits
+            .chunks(WINDOW_BITS)
+            .zip(&self.tables)
+            .map(|(window, table)| lookup(builder, window, table))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.sum_gadget(builder, points)
+    }
+
+    /// Commits to a message inside a circuit as H(m) + r * B, for the bits of the
+    /// randomness r, least significant first. All bits must already be constrained to be
+    /// boolean. A statement can then open a published commitment by constraining the
+    /// result to equal it.
+    pub fn commit_gadget(
+        &self,
+        builder: &mut CircuitBuilder,
+        bits: &[Var],
+        randomness: &[Var],
+    ) -> Result<EdwardsPointVar, ZKError> {
+        let hash = self.hash_gadget(builder, bits)?;
+
+        // r * B = sum_j r_j * 8^j * B over the windows r_j of r, with fixed tables.
+        let mut points = vec![hash];
+        let mut base = self.blinding.clone();
+        for window in randomness.chunks(WINDOW_BITS) {
+            let mut table = vec![self.curve.identity()];
+            for _ in 1..1 << WINDOW_BITS {
+                table.push(self.curve.add(&table[table.len() - 1], &base)?);
+            }
+            points.push(lookup(builder, window, &table)?);
+            base = self.curve.add(&table[table.len() - 1], &base)?;
+        }
+        self.sum_gadget(builder, points)
+    }
+
+    // Adds up points inside a circuit; the empty sum is the identity.
+    fn sum_gadget(
+        &self,
+        builder: &mut CircuitBuilder,
+        points: Vec<EdwardsPointVar>,
+    ) -> Result<EdwardsPointVar, ZKError> {
+        let mut points = points.into_iter();
+        let Some(mut result) = points.next() else {
+            let identity = self.curve.identity();
+            return Ok(EdwardsPointVar {
+                x: builder.constant(identity.x),
+                y: builder.constant(identity.y),
+            });
+        };
+        for point in points {
+            result = self.curve.add_gadget(builder, &result, &point)?;
+        }
+        Ok(result)
+    }
+}
+
+// Selects table[b0 + 2 b1 + 4 b2] for the bits of a window, missing bits being zero.
+//
+// With f the bilinear interpolation of the first four entries in (b0, b1), and g that of
+// the last four, the result is f + b2 (g - f): one product b0 b1, plus one constraint
+// per coordinate.
+fn lookup(
+    builder: &mut CircuitBuilder,
+    window: &[Var],
+    table: &[EdwardsPoint],
+) -> Result<EdwardsPointVar, ZKError> {
+    let mut bits = window.to_vec();
+    if bits.len() < WINDOW_BITS {
+        let zero = builder.constant(FieldElement::zero(builder.params)?);
+        bits.resize(WINDOW_BITS, zero);
+    }
+    let (b0, b1, b2) = (bits[0], bits[1], bits[2]);
+    let b01 = builder.mul(b0, b1);
+
+    let select = |builder: &mut CircuitBuilder,
+                  coordinate: fn(&EdwardsPoint) -> &FieldElement|
+     -> Result<Var, ZKError> {
+        let entry = |k: usize| coordinate(&table[k]);
+        let interpolate = |offset: usize| -> Result<[FieldElement; 4], ZKError> {
+            let (p0, p1, p2, p3) = (
+                entry(offset),
+                entry(offset + 1),
+                entry(offset + 2),
+                entry(offset + 3),
+            );
+            Ok([
+                p0.clone(),
+                p1.sub(p0)?,
+                p2.sub(p0)?,
+                p3.sub(p2)?.sub(p1)?.add(p0)?,
+            ])
+        };
+        let f = interpolate(0)?;
+        let g = interpolate(4)?;
+        let g_minus_f = [
+            g[0].sub(&f[0])?,
+            g[1].sub(&f[1])?,
+            g[2].sub(&f[2])?,
+            g[3].sub(&f[3])?,
+        ];
+        let vars = [builder.one(), b0, b1, b01];
+        let low = builder.lc(&vars.into_iter().zip(f).collect::<Vec<_>>());
+        let step = builder.lc(&vars.into_iter().zip(g_minus_f).collect::<Vec<_>>());
+
+        // b2 · (g - f) = result - f.
+        let result = builder.allocate(Hint::Quadratic(
+            builder.single(b2),
+            step.clone(),
+            low.clone(),
+        ));
+        let difference = builder.single(result) - low;
+        builder.emit(builder.single(b2), step, difference);
+        Ok(result)
+    };
+
+    Ok(EdwardsPointVar {
+        x: select(builder, |point| &point.x)?,
+        y: select(builder, |point| &point.y)?,
+    })
+}
+
+// Derives the generator with the given index by try-and-increment on the y-coordinate.
+fn derive_generator(
+    curve: &TwistedEdwardsCurve,
+    cofactor: u64,
+    dst: &[u8],
+    index: u64,
+) -> Result<EdwardsPoint, ZKError> {
+    let modulus = curve.a.modulus;
+    for counter in 0..=u8::MAX {
+        let input = [&index.to_le_bytes()[..], &[counter]].concat();
+        let elements = hash_to_field(&input, dst, 2, modulus)?;
+        let points = curve.points_with_y(&elements[0])?;
+        if points.is_empty() {
+            continue;
+        }
+        let point = &points[(elements[1].value % points.len() as u64) as usize];
+        let generator = curve.mul_scalar(point, cofactor)?;
+        if generator != curve.identity() {
+            return Ok(generator);
+        }
+    }
+    Err(ZKError::CurveError(
+        "Could not derive a Pedersen generator.".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::Curve,
+        curves::{ToyBn, ToyBnJubjub},
+    };
+
+    fn get_test_hash() -> PedersenHash {
+        PedersenHash::new(
+            ToyBnJubjub::curve().unwrap(),
+            ToyBnJubjub::COFACTOR,
+            8,
+            b"ZKSFS-PEDERSEN-TEST",
+        )
+        .unwrap()
+    }
+
+    fn bits(value: u64, length: usize) -> Vec<bool> {
+        (0..length).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    // Allocates boolean private inputs.
+    fn bit_vars(builder: &mut CircuitBuilder, length: usize) -> Vec<Var> {
+        (0..length)
+            .map(|_| {
+                let bit = builder.private_witness();
+                builder.enforce_boolean(bit);
+                bit
+            })
+            .collect()
+    }
+
+    fn bit_values(value: u64, length: usize) -> Vec<FieldElement> {
+        bits(value, length)
+            .into_iter()
+            .map(|bit| FieldElement::new(bit as u64, ToyBn::SCALAR_MODULUS).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_hash_matches_definition() {
+        let pedersen = get_test_hash();
+        let curve = ToyBnJubjub::curve().unwrap();
+        assert_eq!(pedersen.max_bits(), 9);
+        for generator in pedersen.generators() {
+            assert!(curve.is_on_curve(generator).unwrap());
+            assert_eq!(
+                curve.mul_scalar(generator, ToyBnJubjub::ORDER).unwrap(),
+                curve.identity()
+            );
+        }
+
+        for value in [0, 1, 5, 77, 255] {
+            let message = bits(value, 8);
+            let mut expected = curve.identity();
+            for (j, generator) in pedersen.generators().into_iter().enumerate() {
+                let digit = (value >> (WINDOW_BITS * j)) & 0b111;
+                expected = curve
+                    .add(&expected, &curve.mul_scalar(generator, digit + 1).unwrap())
+                    .unwrap();
+            }
+            assert_eq!(pedersen.hash(&message).unwrap(), expected);
+        }
+
+        assert!(pedersen.hash(&bits(0, 10)).is_err());
+    }
+
+    #[test]
+    fn test_commit() {
+        let pedersen = get_test_hash();
+        let curve = ToyBnJubjub::curve().unwrap();
+        let message = bits(0b1011_0110, 8);
+        assert_eq!(
+            pedersen.commit(&message, 0).unwrap(),
+            pedersen.hash(&message).unwrap()
+        );
+
+        // Commitments with the same message differ by a multiple of the blinding generator.
+        let c1 = pedersen.commit(&message, 3).unwrap();
+        let c2 = pedersen.commit(&message, 10).unwrap();
+        let shift = curve.mul_scalar(pedersen.blinding_generator(), 7).unwrap();
+        assert_eq!(curve.add(&c1, &shift).unwrap(), c2);
+
+        // Generators are deterministic and separated by the domain tag.
+        let again = get_test_hash();
+        assert_eq!(again.generators(), pedersen.generators());
+        let other = PedersenHash::new(curve, ToyBnJubjub::COFACTOR, 8, b"ZKSFS-OTHER").unwrap();
+        assert_ne!(other.generators(), pedersen.generators());
+    }
+
+    #[test]
+    fn test_hash_gadget() {
+        let pedersen = get_test_hash();
+        // 7 bits leave a short final window, and the empty message hashes to the
+        // identity.
+        for length in [0, 7, 8] {
+            let mut builder = CircuitBuilder::new(&ToyBn::SCALAR_MODULUS).unwrap();
+            let message = bit_vars(&mut builder, length);
+            let hash = pedersen.hash_gadget(&mut builder, &message).unwrap();
+            for value in [0, 1, 77, 127] {
+                let witness = builder
+                    .generate_witness(&[], &bit_values(value, length))
+                    .unwrap();
+                assert_eq!(
+                    hash.value(&witness).unwrap(),
+                    pedersen.hash(&bits(value, length)).unwrap()
+                );
+            }
+        }
+
+        let mut builder = CircuitBuilder::new(&ToyBn::SCALAR_MODULUS).unwrap();
+        let message = bit_vars(&mut builder, 10);
+        assert!(pedersen.hash_gadget(&mut builder, &message).is_err());
+        let mut builder = CircuitBuilder::new(&101).unwrap();
+        let message = bit_vars(&mut builder, 3);
+        assert!(pedersen.hash_gadget(&mut builder, &message).is_err());
+    }
+
+    #[test]
+    fn test_open_commitment_in_circuit() {
+        // Proves knowledge of an opening (m, r) of a public commitment C.
+        let pedersen = get_test_hash();
+        let mut builder = CircuitBuilder::new(&ToyBn::SCALAR_MODULUS).unwrap();
+        let commitment = EdwardsPointVar {
+            x: builder.public_input().unwrap(),
+            y: builder.public_input().unwrap(),
+        };
+        let message = bit_vars(&mut builder, 8);
+        // The blinding generator has order 29 < 2^5.
+        let randomness = bit_vars(&mut builder, 5);
+        let opened = pedersen
+            .commit_gadget(&mut builder, &message, &randomness)
+            .unwrap();
+        builder.enforce_equal(opened.x, commitment.x);
+        builder.enforce_equal(opened.y, commitment.y);
+
+        let private = [bit_values(0b1011_0110, 8), bit_values(19, 5)].concat();
+        let c = pedersen.commit(&bits(0b1011_0110, 8), 19).unwrap();
+        let witness = builder.generate_witness(&[c.x.clone(), c.y.clone()], &private);
+        assert!(witness.is_ok());
+
+        // The same opening does not match a different commitment.
+        let other = pedersen.commit(&bits(0b1011_0110, 8), 20).unwrap();
+        assert!(builder
+            .generate_witness(&[other.x, other.y], &private)
+            .is_err());
+    }
+}
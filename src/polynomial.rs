@@ -1,14 +1,61 @@
-use crate::{errors::ZKError, field::FieldElement};
+use rand::RngCore;
+
+use crate::{
+    errors::ZKError,
+    field::{is_prime, DisplayFormat, Field, FieldElement},
+    parallel,
+};
+
+// Number of points handed to each call of the backend's batch evaluation routine.
+const EVALUATION_CHUNK_SIZE: usize = 1024;
+
+// Minimum divisor degree and quotient length for which division switches from long
+// division to Newton inversion of the reversed divisor.
+const FAST_DIVISION_THRESHOLD: usize = 64;
+
+// Largest modulus for which root finding simply evaluates at every field element.
+const EXHAUSTIVE_ROOT_SEARCH_LIMIT: u64 = 1 << 12;
 
 /// Represents a polynomial with coefficients in a finite field.
+///
+/// Coefficients are stored lowest degree first. Constructors and arithmetic trim
+/// trailing zero coefficients, keeping a single zero for the zero polynomial, and
+/// equality ignores any trailing zeros left by direct edits to `coefficients`.
 #[derive(Clone, Debug)]
-pub struct Polynomial {
-    pub coefficients: Vec<FieldElement>,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "RawPolynomial<F>",
+        bound(
+            serialize = "F: serde::Serialize",
+            deserialize = "F: serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct Polynomial<F: Field = FieldElement> {
+    pub coefficients: Vec<F>,
 }
 
-impl Polynomial {
-    /// Creates a new polynomial.
-    pub fn new(coefficients: Vec<FieldElement>) -> Result<Self, ZKError> {
+// Unvalidated form of a polynomial, checked by `Polynomial::new` when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawPolynomial<F> {
+    coefficients: Vec<F>,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Field> TryFrom<RawPolynomial<F>> for Polynomial<F> {
+    type Error = ZKError;
+
+    fn try_from(raw: RawPolynomial<F>) -> Result<Self, Self::Error> {
+        Polynomial::new(raw.coefficients)
+    }
+}
+
+impl<F: Field> Polynomial<F> {
+    /// Creates a new polynomial, trimming trailing zero coefficients.
+    pub fn new(mut coefficients: Vec<F>) -> Result<Self, ZKError> {
         if coefficients.is_empty() {
             return Err(ZKError::CircuitError(
                 "Polynomial must have at least one coefficient".to_string(),
@@ -16,177 +63,964 @@ impl Polynomial {
         }
 
         // Verify that all coefficients have the same modulus.
-        let modulus = coefficients[0].modulus;
+        let params = coefficients[0].params();
         for coeff in &coefficients {
-            if coeff.modulus != modulus {
+            if coeff.params() != params {
                 return Err(ZKError::PolynomialError(
                     "All coefficients must have the same modulus".to_string(),
                 ));
             }
         }
 
+        while coefficients.len() > 1 && coefficients[coefficients.len() - 1].is_zero() {
+            coefficients.pop();
+        }
+
         Ok(Self { coefficients })
     }
 
-    /// Returns the degree of the polynomial.
+    /// Serializes the polynomial compactly: the number of coefficients as a little-endian
+    /// u64, followed by each coefficient's `Field::to_bytes` encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.coefficients.len() as u64).to_le_bytes().to_vec();
+        for coeff in &self.coefficients {
+            bytes.extend(coeff.to_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a polynomial over the field described by `params` from the output
+    /// of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8], params: &F::Params) -> Result<Self, ZKError> {
+        let width = F::zero(params).to_bytes().len();
+        let (count, body) = bytes
+            .split_first_chunk::<8>()
+            .ok_or_else(|| ZKError::PolynomialError("Missing coefficient count".to_string()))?;
+        let count = u64::from_le_bytes(*count);
+        if width == 0 || body.len() as u64 != count.saturating_mul(width as u64) {
+            return Err(ZKError::PolynomialError(format!(
+                "Expected {} coefficients of {} bytes, got {} bytes",
+                count,
+                width,
+                body.len()
+            )));
+        }
+
+        let coefficients = body
+            .chunks(width)
+            .map(|chunk| F::from_bytes(chunk, params))
+            .collect::<Result<Vec<F>, ZKError>>()?;
+        Polynomial::new(coefficients)
+    }
+
+    /// Tests equality by comparing evaluations at `rounds` random points instead of
+    /// all coefficients. Equal polynomials always pass; by the Schwartz–Zippel lemma,
+    /// distinct ones of degree at most d agree at a random point with probability at
+    /// most d / |F|, so a false positive has probability at most (d / |F|)^rounds.
+    pub fn eq_probabilistic<R: RngCore + ?Sized>(
+        &self,
+        other: &Polynomial<F>,
+        rng: &mut R,
+        rounds: usize,
+    ) -> Result<bool, ZKError> {
+        let params = self.coefficients[0].params();
+        if other.coefficients[0].params() != params {
+            return Ok(false);
+        }
+
+        for _ in 0..rounds {
+            let point = F::random(rng, &params)?;
+            if self.evaluate(&point)? != other.evaluate(&point)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns true if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|c| c.is_zero())
+    }
+
+    /// Returns the degree of the polynomial, taking the zero polynomial to have degree 0.
     pub fn degree(&self) -> usize {
         let mut deg = self.coefficients.len() - 1;
-        while deg > 0 && self.coefficients[deg].value == 0 {
+        while deg > 0 && self.coefficients[deg].is_zero() {
             deg -= 1;
         }
         deg
     }
 
+    // The coefficients without trailing zeros, always keeping the constant term.
+    fn trimmed_coefficients(&self) -> &[F] {
+        &self.coefficients[..=self.degree()]
+    }
+
+    /// Returns the monic polynomial Π (X - r) over the given roots, which must be
+    /// non-empty. Factors are multiplied pairwise up a balanced product tree, so the
+    /// large multiplications at the top are balanced and take the Karatsuba path.
+    pub fn from_roots(roots: &[F]) -> Result<Self, ZKError> {
+        let Some(first) = roots.first() else {
+            return Err(ZKError::PolynomialError("No roots given".to_string()));
+        };
+        let one = F::one(&first.params());
+
+        let mut layer = roots
+            .iter()
+            .map(|root| Polynomial::new(vec![root.neg(), one.clone()]))
+            .collect::<Result<Vec<_>, ZKError>>()?;
+        while layer.len() > 1 {
+            let pairs: Vec<&[Polynomial<F>]> = layer.chunks(2).collect();
+            layer = parallel::try_map(&pairs, |pair| match pair {
+                [lhs, rhs] => lhs.mul(rhs),
+                _ => Ok(pair[0].clone()),
+            })?;
+        }
+        Ok(layer.remove(0))
+    }
+
+    /// Returns the unique polynomial of lowest degree passing through the given
+    /// (x, y) points, whose x-coordinates must be distinct.
+    pub fn interpolate(points: &[(F, F)]) -> Result<Self, ZKError> {
+        let (xs, ys): (Vec<F>, Vec<F>) = points.iter().cloned().unzip();
+        InterpolationDomain::new(&xs)?.interpolate(&ys)
+    }
+
     /// Evaluates the polynomial at the given field element.
-    pub fn evaluate(&self, fe: &FieldElement) -> Result<FieldElement, ZKError> {
-        if self.coefficients[0].modulus != fe.modulus {
+    pub fn evaluate(&self, fe: &F) -> Result<F, ZKError> {
+        if self.coefficients[0].params() != fe.params() {
             return Err(ZKError::PolynomialError(
                 "Moduli must be the same for evaluation".to_string(),
             ));
         }
 
-        let mut result = FieldElement::new(0, fe.modulus)?;
-
         // Evaluate from highest degree coefficient downwards (Horner's method).
-        for coeff in self.coefficients.iter().rev() {
-            result = result.mul(fe)?;
-            result = result.add(coeff)?;
+        F::evaluate_polynomial(&self.coefficients, fe)
+    }
+
+    /// Evaluates the polynomial at each of the given field elements.
+    pub fn evaluate_batch(&self, points: &[F]) -> Result<Vec<F>, ZKError> {
+        let params = self.coefficients[0].params();
+        if points.iter().any(|point| point.params() != params) {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for evaluation".to_string(),
+            ));
         }
 
-        Ok(result)
+        // Each chunk of points is evaluated by the backend's batch routine, on its own
+        // thread when the `parallel` feature is enabled.
+        let chunks: Vec<&[F]> = points.chunks(EVALUATION_CHUNK_SIZE).collect();
+        let evaluations = parallel::try_map(&chunks, |chunk| {
+            F::evaluate_polynomial_batch(&self.coefficients, chunk)
+        })?;
+        Ok(evaluations.into_iter().flatten().collect())
     }
 
     /// Adds two polynomials.
-    pub fn add(&self, other: &Polynomial) -> Result<Polynomial, ZKError> {
-        if self.coefficients[0].modulus != other.coefficients[0].modulus {
+    pub fn add(&self, other: &Polynomial<F>) -> Result<Polynomial<F>, ZKError> {
+        if self.coefficients[0].params() != other.coefficients[0].params() {
             return Err(ZKError::PolynomialError(
                 "Moduli must be the same for addition".to_string(),
             ));
         }
 
-        let max_len = self.coefficients.len().max(other.coefficients.len());
-        let modulus = self.coefficients[0].modulus;
-        let mut sum = Vec::new();
-
-        for i in 0..max_len {
-            let a = self
-                .coefficients
-                .get(i)
-                .cloned()
-                .unwrap_or(FieldElement::new(0, modulus)?);
-            let b = other
-                .coefficients
-                .get(i)
-                .cloned()
-                .unwrap_or(FieldElement::new(0, modulus)?);
-            sum.push(a.add(&b)?);
-        }
+        let (longer, shorter) = if self.coefficients.len() >= other.coefficients.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut sum = longer.coefficients.clone();
+        F::add_assign_slices(
+            &mut sum[..shorter.coefficients.len()],
+            &shorter.coefficients,
+        )?;
 
         Polynomial::new(sum)
     }
 
     /// Subtracts two polynomials.
-    pub fn sub(&self, other: &Polynomial) -> Result<Polynomial, ZKError> {
-        if self.coefficients[0].modulus != other.coefficients[0].modulus {
+    pub fn sub(&self, other: &Polynomial<F>) -> Result<Polynomial<F>, ZKError> {
+        if self.coefficients[0].params() != other.coefficients[0].params() {
             return Err(ZKError::PolynomialError(
                 "Moduli must be the same for subtraction".to_string(),
             ));
         }
 
         let max_len = self.coefficients.len().max(other.coefficients.len());
-        let modulus = self.coefficients[0].modulus;
-        let mut diff = Vec::new();
-
-        for i in 0..max_len {
-            let a = self
-                .coefficients
-                .get(i)
-                .cloned()
-                .unwrap_or(FieldElement::new(0, modulus)?);
-            let b = other
-                .coefficients
-                .get(i)
-                .cloned()
-                .unwrap_or(FieldElement::new(0, modulus)?);
-            diff.push(a.sub(&b)?);
-        }
+        let mut diff = self.coefficients.clone();
+        diff.resize(max_len, F::zero(&self.coefficients[0].params()));
+        F::sub_assign_slices(&mut diff[..other.coefficients.len()], &other.coefficients)?;
 
         Polynomial::new(diff)
     }
 
+    /// Adds `other` to this polynomial in place.
+    pub fn add_assign(&mut self, other: &Polynomial<F>) -> Result<(), ZKError> {
+        if self.coefficients[0].params() != other.coefficients[0].params() {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for addition".to_string(),
+            ));
+        }
+
+        self.pad_to(other.coefficients.len());
+        F::add_assign_slices(
+            &mut self.coefficients[..other.coefficients.len()],
+            &other.coefficients,
+        )?;
+        self.trim();
+        Ok(())
+    }
+
+    /// Subtracts `other` from this polynomial in place.
+    pub fn sub_assign(&mut self, other: &Polynomial<F>) -> Result<(), ZKError> {
+        if self.coefficients[0].params() != other.coefficients[0].params() {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for subtraction".to_string(),
+            ));
+        }
+
+        self.pad_to(other.coefficients.len());
+        F::sub_assign_slices(
+            &mut self.coefficients[..other.coefficients.len()],
+            &other.coefficients,
+        )?;
+        self.trim();
+        Ok(())
+    }
+
+    /// Multiplies this polynomial by a scalar in place.
+    pub fn scale_in_place(&mut self, scalar: &F) -> Result<(), ZKError> {
+        F::scale_slice(&mut self.coefficients, scalar)?;
+        self.trim();
+        Ok(())
+    }
+
+    // Appends zero coefficients until there are at least `len` of them.
+    fn pad_to(&mut self, len: usize) {
+        if self.coefficients.len() < len {
+            let zero = F::zero(&self.coefficients[0].params());
+            self.coefficients.resize(len, zero);
+        }
+    }
+
+    // Drops trailing zero coefficients, keeping the constant term.
+    fn trim(&mut self) {
+        self.coefficients.truncate(self.degree() + 1);
+    }
+
     /// Multiplies two polynomials.
-    pub fn mul(&self, other: &Polynomial) -> Result<Polynomial, ZKError> {
-        if self.coefficients[0].modulus != other.coefficients[0].modulus {
+    pub fn mul(&self, other: &Polynomial<F>) -> Result<Polynomial<F>, ZKError> {
+        if self.coefficients[0].params() != other.coefficients[0].params() {
             return Err(ZKError::PolynomialError(
                 "Moduli must be the same for multiplication".to_string(),
             ));
         }
 
-        let n = self.coefficients.len();
-        let m = other.coefficients.len();
-        let modulus = self.coefficients[0].modulus;
-        let mut product = vec![FieldElement::new(0, modulus)?; n + m - 1];
+        let product = F::multiply_polynomials(&self.coefficients, &other.coefficients)?;
+        Polynomial::new(product)
+    }
 
-        for i in 0..n {
-            for j in 0..m {
-                let prod = self.coefficients[i].mul(&other.coefficients[j])?;
-                product[i + j] = product[i + j].add(&prod)?;
-            }
+    /// Multiplies two polynomials, writing the product into `out` and reusing its
+    /// allocation. `out` may be any polynomial; its previous coefficients are discarded.
+    pub fn mul_into(&self, other: &Polynomial<F>, out: &mut Polynomial<F>) -> Result<(), ZKError> {
+        if self.coefficients[0].params() != other.coefficients[0].params() {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for multiplication".to_string(),
+            ));
         }
-        Polynomial::new(product)
+
+        F::multiply_polynomials_into(
+            &self.coefficients,
+            &other.coefficients,
+            &mut out.coefficients,
+        )?;
+        out.trim();
+        Ok(())
     }
 
     /// Performs polynomial long division and returns the quotient and the remainder.
-    pub fn div(&self, other: &Polynomial) -> Result<(Polynomial, Polynomial), ZKError> {
-        let modulus = self.coefficients[0].modulus;
-        if modulus != other.coefficients[0].modulus {
+    pub fn div(&self, other: &Polynomial<F>) -> Result<(Polynomial<F>, Polynomial<F>), ZKError> {
+        let params = self.coefficients[0].params();
+        if params != other.coefficients[0].params() {
             return Err(ZKError::PolynomialError(
                 "Moduli must be the same for division".to_string(),
             ));
         }
 
-        let mut remainder = self.clone();
-        let quotient_size = self.degree().saturating_sub(other.degree()) + 1;
-        let mut quotient_coefficients = vec![FieldElement::new(0, modulus)?; quotient_size];
+        let divisor_degree = other.degree();
+        let lead_divisor_inv = other.coefficients[divisor_degree].inv()?;
+        let quotient_size = self.degree().saturating_sub(divisor_degree) + 1;
+        if divisor_degree.min(quotient_size) >= FAST_DIVISION_THRESHOLD {
+            return self.div_by_reversal(other);
+        }
 
-        while remainder.degree() >= other.degree()
-            && remainder.coefficients.len() > 0
-            && remainder.coefficients[remainder.degree()].value != 0
-        {
-            let deg_diff = remainder.degree() - other.degree();
-            let lead_dividend = remainder.coefficients[remainder.degree()].clone();
-            let lead_divisor = other.coefficients[other.degree()].clone();
-            let factor = lead_dividend.mul(&lead_divisor.inv()?)?;
-            // Create a polynomial factor_poly = factor * x^(deg_diff)
-            let mut factor_poly_coefficients = vec![FieldElement::new(0, modulus)?; deg_diff];
-            factor_poly_coefficients.push(factor.clone());
-            let factor_poly = Polynomial::new(factor_poly_coefficients)?;
+        let mut quotient_coefficients = vec![F::zero(&params); quotient_size];
+        let mut remainder_coefficients = self.coefficients.clone();
 
-            quotient_coefficients[deg_diff] = quotient_coefficients[deg_diff].add(&factor)?;
-            let subtrahend = factor_poly.mul(other)?;
-            remainder = remainder.sub(&subtrahend)?;
-        }
+        // The fields were validated above, so the elimination loop uses the checked
+        // variants and reports a single error if a stray coefficient slips through.
+        let mut eliminate = || -> Option<()> {
+            for i in (divisor_degree..remainder_coefficients.len()).rev() {
+                if remainder_coefficients[i].is_zero() {
+                    continue;
+                }
+                // Cancel the leading term with factor * x^(deg_diff) * divisor.
+                let deg_diff = i - divisor_degree;
+                let factor = remainder_coefficients[i].checked_mul(&lead_divisor_inv)?;
+                for (j, coeff) in other.coefficients[..=divisor_degree].iter().enumerate() {
+                    let product = factor.checked_mul(coeff)?;
+                    remainder_coefficients[deg_diff + j] =
+                        remainder_coefficients[deg_diff + j].checked_sub(&product)?;
+                }
+                quotient_coefficients[deg_diff] = factor;
+            }
+            Some(())
+        };
+        eliminate().ok_or_else(|| {
+            ZKError::PolynomialError("Moduli must be the same for division".to_string())
+        })?;
 
+        let remainder = Polynomial::new(remainder_coefficients)?;
         let quotient = Polynomial::new(quotient_coefficients)?;
         Ok((quotient, remainder))
     }
 
+    // Divides using rev(q) = rev(a) · rev(b)^(-1) mod x^(deg a - deg b + 1), which needs
+    // only multiplications, and then r = a - b·q. Requires deg a >= deg b.
+    fn div_by_reversal(
+        &self,
+        other: &Polynomial<F>,
+    ) -> Result<(Polynomial<F>, Polynomial<F>), ZKError> {
+        let quotient_size = self.degree() - other.degree() + 1;
+        let reverse = |p: &Polynomial<F>| -> Result<Polynomial<F>, ZKError> {
+            Polynomial::new(p.trimmed_coefficients().iter().rev().cloned().collect())
+        };
+
+        let divisor_inv = reverse(other)?.inverse_mod_xn(quotient_size)?;
+        let mut reversed_quotient = reverse(self)?.mul(&divisor_inv)?;
+        reversed_quotient.truncate_mod_xn(quotient_size);
+        reversed_quotient.pad_to(quotient_size);
+        reversed_quotient.coefficients.reverse();
+
+        let quotient = Polynomial::new(reversed_quotient.coefficients)?;
+        let remainder = self.sub(&other.mul(&quotient)?)?;
+        Ok((quotient, remainder))
+    }
+
+    /// Returns the power series inverse g of this polynomial modulo x^n, i.e. the unique
+    /// g of degree below n with f·g ≡ 1 (mod x^n). Uses Newton iteration,
+    /// g ← g·(2 - f·g), which doubles the number of correct coefficients each step.
+    /// The constant term must be invertible.
+    pub fn inverse_mod_xn(&self, n: usize) -> Result<Polynomial<F>, ZKError> {
+        if n == 0 {
+            return Err(ZKError::PolynomialError(
+                "Power series precision must be positive".to_string(),
+            ));
+        }
+
+        let params = self.coefficients[0].params();
+        let two = F::from_u64(2, &params);
+        let mut inverse = Polynomial::new(vec![self.coefficients[0].inv()?])?;
+        let mut precision = 1;
+        while precision < n {
+            precision = (2 * precision).min(n);
+
+            let mut truncated = self.clone();
+            truncated.truncate_mod_xn(precision);
+            let mut correction = truncated.mul(&inverse)?;
+            correction.truncate_mod_xn(precision);
+            F::scale_slice(&mut correction.coefficients, &F::one(&params).neg())?;
+            correction.coefficients[0] = correction.coefficients[0].add(&two)?;
+
+            inverse = inverse.mul(&correction)?;
+            inverse.truncate_mod_xn(precision);
+        }
+        Ok(inverse)
+    }
+
+    // Reduces the polynomial modulo x^n for n >= 1 by dropping coefficients of degree n and above.
+    fn truncate_mod_xn(&mut self, n: usize) {
+        self.coefficients.truncate(n);
+        self.trim();
+    }
+
     /// Scales the polynomial by a scalar field element.
-    pub fn scale(&self, scalar: &FieldElement) -> Result<Polynomial, ZKError> {
-        let scaled_coefficients = self
-            .coefficients
-            .iter()
-            .map(|c| c.mul(scalar).unwrap())
-            .collect();
+    pub fn scale(&self, scalar: &F) -> Result<Polynomial<F>, ZKError> {
+        let mut scaled_coefficients = self.coefficients.clone();
+        F::scale_slice(&mut scaled_coefficients, scalar)?;
         Polynomial::new(scaled_coefficients)
     }
+
+    /// Returns the polynomial p(a·x), i.e. the i-th coefficient multiplied by a^i.
+    /// Evaluating the result over a domain evaluates p over the domain's coset by a.
+    pub fn scale_variable(&self, a: &F) -> Result<Polynomial<F>, ZKError> {
+        let powers = a.powers(self.coefficients.len())?;
+        let mut coefficients = self.coefficients.clone();
+        F::mul_assign_slices(&mut coefficients, &powers)?;
+        Polynomial::new(coefficients)
+    }
+
+    /// Returns the polynomial p(x + b), computed with repeated synthetic division
+    /// (a Taylor shift) using O(n^2) field operations.
+    pub fn shift_variable(&self, b: &F) -> Result<Polynomial<F>, ZKError> {
+        if self.coefficients[0].params() != b.params() {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for shifting".to_string(),
+            ));
+        }
+
+        let mut coefficients = self.coefficients.clone();
+        let n = coefficients.len();
+        for i in 0..n {
+            for j in (i..n - 1).rev() {
+                coefficients[j] = coefficients[j].add(&b.mul(&coefficients[j + 1])?)?;
+            }
+        }
+        Polynomial::new(coefficients)
+    }
+}
+
+impl Polynomial<FieldElement> {
+    /// Returns the distinct roots of the polynomial in increasing order.
+    ///
+    /// Small fields are searched exhaustively. Otherwise the modulus must be prime: the
+    /// product of the linear factors is isolated as gcd(f, x^p - x) and then split by
+    /// equal-degree factorization (Cantor–Zassenhaus) with gcds against
+    /// (x + a)^((p - 1) / 2) - 1 for a = 1, 2, ...
+    pub fn roots(&self) -> Result<Vec<FieldElement>, ZKError> {
+        if self.is_zero() {
+            return Err(ZKError::PolynomialError(
+                "Every element is a root of the zero polynomial".to_string(),
+            ));
+        }
+
+        let modulus = self.coefficients[0].modulus;
+        if modulus <= EXHAUSTIVE_ROOT_SEARCH_LIMIT {
+            let points = (0..modulus)
+                .map(|x| FieldElement::new(x, modulus))
+                .collect::<Result<Vec<_>, ZKError>>()?;
+            let values = self.evaluate_batch(&points)?;
+            return Ok(points
+                .into_iter()
+                .zip(values)
+                .filter(|(_, value)| value.is_zero())
+                .map(|(point, _)| point)
+                .collect());
+        }
+        if !is_prime(modulus) {
+            return Err(ZKError::CompositeModulus(modulus));
+        }
+
+        let x = Polynomial::new(vec![
+            FieldElement::zero(modulus)?,
+            FieldElement::one(modulus)?,
+        ])?;
+        let f = self.monic()?;
+        let linear_part = f.gcd(&x.pow_mod(modulus, &f)?.sub(&x)?)?;
+
+        let mut roots = Vec::with_capacity(linear_part.degree());
+        linear_part.split_linear_factors(&x, &mut roots)?;
+        roots.sort();
+        Ok(roots)
+    }
+
+    // Collects the roots of a monic polynomial that is a product of distinct linear
+    // factors over a field of odd prime order.
+    fn split_linear_factors(
+        &self,
+        x: &Polynomial<FieldElement>,
+        roots: &mut Vec<FieldElement>,
+    ) -> Result<(), ZKError> {
+        match self.degree() {
+            0 => return Ok(()),
+            1 => {
+                roots.push(self.coefficients[0].neg());
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let modulus = self.coefficients[0].modulus;
+        let one = Polynomial::new(vec![FieldElement::one(modulus)?])?;
+        // (x + a)^((p - 1) / 2) is 1 at the roots r with r + a a square and -1 at the
+        // others, so the gcd separates the two classes whenever both are non-empty.
+        for a in 1..modulus {
+            let shift = Polynomial::new(vec![FieldElement::new(a, modulus)?])?;
+            let split = x.add(&shift)?.pow_mod((modulus - 1) / 2, self)?.sub(&one)?;
+            let factor = self.gcd(&split)?;
+            if factor.degree() > 0 && factor.degree() < self.degree() {
+                factor.split_linear_factors(x, roots)?;
+                let (cofactor, _) = self.div(&factor)?;
+                return cofactor.split_linear_factors(x, roots);
+            }
+        }
+        Err(ZKError::PolynomialError(
+            "Failed to split the linear factors".to_string(),
+        ))
+    }
+
+    // Returns self^exponent mod `modulus` by square-and-multiply.
+    fn pow_mod(
+        &self,
+        exponent: u64,
+        modulus: &Polynomial<FieldElement>,
+    ) -> Result<Polynomial<FieldElement>, ZKError> {
+        let one = FieldElement::one(self.coefficients[0].modulus)?;
+        let mut result = Polynomial::new(vec![one])?;
+        let (_, mut base) = self.div(modulus)?;
+        let mut exp = exponent;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base)?.div(modulus)?.1;
+            }
+            base = base.mul(&base)?.div(modulus)?.1;
+            exp >>= 1;
+        }
+        Ok(result)
+    }
+
+    // Returns the monic greatest common divisor of two polynomials, not both zero.
+    fn gcd(&self, other: &Polynomial<FieldElement>) -> Result<Polynomial<FieldElement>, ZKError> {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, remainder) = a.div(&b)?;
+            a = b;
+            b = remainder;
+        }
+        a.monic()
+    }
+
+    // Scales the polynomial so that its leading coefficient is 1.
+    fn monic(&self) -> Result<Polynomial<FieldElement>, ZKError> {
+        self.scale(&self.coefficients[self.degree()].inv()?)
+    }
+}
+
+impl<F: Field> PartialEq for Polynomial<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed_coefficients() == other.trimmed_coefficients()
+    }
+}
+
+impl<F: Field> Eq for Polynomial<F> {}
+
+/// Displays the polynomial lowest degree first with zero terms skipped and unit
+/// coefficients omitted, followed by the modulus, e.g. `3 + 5x + 2x^3 (mod 97)`.
+impl std::fmt::Display for Polynomial<FieldElement> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value_only = DisplayFormat {
+            hex: false,
+            with_modulus: false,
+        };
+
+        let mut first = true;
+        for (i, coeff) in self.trimmed_coefficients().iter().enumerate() {
+            if coeff.is_zero() {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+
+            if i == 0 || !coeff.is_one() {
+                write!(f, "{}", coeff.display(value_only))?;
+            }
+            match i {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", i)?,
+            }
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        write!(f, " (mod {})", self.coefficients[0].modulus)
+    }
+}
+
+/// A polynomial whose degree is checked against a maximum at construction and after
+/// every operation, as required by commitment schemes and the QAP quotient check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DegreeBoundedPolynomial<F: Field = FieldElement> {
+    polynomial: Polynomial<F>,
+    bound: usize,
+}
+
+impl<F: Field> DegreeBoundedPolynomial<F> {
+    /// Wraps `polynomial`, failing with `DegreeBoundExceeded` if its degree is above `bound`.
+    pub fn new(polynomial: Polynomial<F>, bound: usize) -> Result<Self, ZKError> {
+        let degree = polynomial.degree();
+        if degree > bound {
+            return Err(ZKError::DegreeBoundExceeded(degree, bound));
+        }
+        Ok(Self { polynomial, bound })
+    }
+
+    /// Creates a bounded polynomial from its coefficients, lowest degree first.
+    pub fn from_coefficients(coefficients: Vec<F>, bound: usize) -> Result<Self, ZKError> {
+        Self::new(Polynomial::new(coefficients)?, bound)
+    }
+
+    /// Returns the maximum allowed degree.
+    pub fn bound(&self) -> usize {
+        self.bound
+    }
+
+    /// Returns the underlying polynomial.
+    pub fn polynomial(&self) -> &Polynomial<F> {
+        &self.polynomial
+    }
+
+    /// Unwraps the underlying polynomial.
+    pub fn into_inner(self) -> Polynomial<F> {
+        self.polynomial
+    }
+
+    /// Returns the same polynomial under a different bound, checking it again.
+    pub fn with_bound(self, bound: usize) -> Result<Self, ZKError> {
+        Self::new(self.polynomial, bound)
+    }
+
+    /// Adds two bounded polynomials. The result keeps this polynomial's bound.
+    pub fn add(&self, other: &DegreeBoundedPolynomial<F>) -> Result<Self, ZKError> {
+        Self::new(self.polynomial.add(&other.polynomial)?, self.bound)
+    }
+
+    /// Subtracts two bounded polynomials. The result keeps this polynomial's bound.
+    pub fn sub(&self, other: &DegreeBoundedPolynomial<F>) -> Result<Self, ZKError> {
+        Self::new(self.polynomial.sub(&other.polynomial)?, self.bound)
+    }
+
+    /// Multiplies two bounded polynomials. The result keeps this polynomial's bound,
+    /// so the product's degree must also fit within it.
+    pub fn mul(&self, other: &DegreeBoundedPolynomial<F>) -> Result<Self, ZKError> {
+        Self::new(self.polynomial.mul(&other.polynomial)?, self.bound)
+    }
+
+    /// Scales the polynomial by a scalar field element.
+    pub fn scale(&self, scalar: &F) -> Result<Self, ZKError> {
+        Self::new(self.polynomial.scale(scalar)?, self.bound)
+    }
+
+    /// Evaluates the polynomial at the given field element.
+    pub fn evaluate(&self, fe: &F) -> Result<F, ZKError> {
+        self.polynomial.evaluate(fe)
+    }
+}
+
+/// Precomputed data for interpolating polynomials over a fixed set of x-coordinates.
+///
+/// Stores the vanishing polynomial L(X) = Π (X - x_j) and the barycentric weights
+/// w_j = 1 / Π_(k ≠ j) (x_j - x_k), so that each interpolation only needs
+/// p(X) = Σ_j y_j · w_j · L(X) / (X - x_j) and skips the points where y_j is zero.
+#[derive(Clone, Debug)]
+pub struct InterpolationDomain<F: Field = FieldElement> {
+    points: Vec<F>,
+    weights: Vec<F>,
+    vanishing_polynomial: Polynomial<F>,
+}
+
+impl<F: Field> InterpolationDomain<F> {
+    /// Precomputes the weights for the given distinct x-coordinates.
+    pub fn new(points: &[F]) -> Result<Self, ZKError> {
+        let Some(first) = points.first() else {
+            return Err(ZKError::PolynomialError("No points to interpolate".into()));
+        };
+        let params = first.params();
+
+        let vanishing_polynomial = Polynomial::from_roots(points)?;
+
+        let mut denominators = Vec::with_capacity(points.len());
+        for (j, x_j) in points.iter().enumerate() {
+            let mut denominator = F::one(&params);
+            for (k, x_k) in points.iter().enumerate() {
+                if j != k {
+                    denominator = denominator.mul(&x_j.sub(x_k)?)?;
+                }
+            }
+            denominators.push(denominator);
+        }
+        let weights = F::batch_inv(&denominators).map_err(|_| {
+            ZKError::PolynomialError("Interpolation points must be distinct".into())
+        })?;
+
+        Ok(InterpolationDomain {
+            points: points.to_vec(),
+            weights,
+            vanishing_polynomial,
+        })
+    }
+
+    /// Returns the x-coordinates of the domain.
+    pub fn points(&self) -> &[F] {
+        &self.points
+    }
+
+    /// Returns the polynomial Π (X - x_j) vanishing on the domain.
+    pub fn vanishing_polynomial(&self) -> &Polynomial<F> {
+        &self.vanishing_polynomial
+    }
+
+    /// Returns the value at `point` of the Lagrange basis polynomial of every point,
+    /// L_j(point) = w_j · L(point) / (point - x_j), so that the interpolant of `values`
+    /// takes the value Σ_j values[j] · L_j(point) there, without interpolating.
+    pub fn lagrange_coefficients(&self, point: &F) -> Result<Vec<F>, ZKError> {
+        let params = self.points[0].params();
+        if let Some(k) = self.points.iter().position(|x| x == point) {
+            let mut coefficients = vec![F::zero(&params); self.points.len()];
+            coefficients[k] = F::one(&params);
+            return Ok(coefficients);
+        }
+
+        let differences = self
+            .points
+            .iter()
+            .map(|x| point.sub(x))
+            .collect::<Result<Vec<F>, ZKError>>()?;
+        let vanishing = self.vanishing_polynomial.evaluate(point)?;
+        F::batch_inv(&differences)?
+            .iter()
+            .zip(&self.weights)
+            .map(|(inverse, w)| w.mul(&vanishing)?.mul(inverse))
+            .collect()
+    }
+
+    /// Returns the unique polynomial of degree below the domain size taking the value
+    /// `values[j]` at the j-th point.
+    pub fn interpolate(&self, values: &[F]) -> Result<Polynomial<F>, ZKError> {
+        if values.len() != self.points.len() {
+            return Err(ZKError::PolynomialError(format!(
+                "Expected {} values for interpolation, got {}",
+                self.points.len(),
+                values.len()
+            )));
+        }
+
+        let vanishing = &self.vanishing_polynomial.coefficients;
+        let n = self.points.len();
+        let mut result = vec![F::zero(&self.points[0].params()); n];
+        let mut quotient = result.clone();
+
+        for ((x_j, w_j), y_j) in self.points.iter().zip(&self.weights).zip(values) {
+            if y_j.is_zero() {
+                continue;
+            }
+
+            // Synthetic division: quotient = L(X) / (X - x_j), from the top coefficient down.
+            quotient[n - 1] = vanishing[n].clone();
+            for k in (1..n).rev() {
+                quotient[k - 1] = vanishing[k].add(&x_j.mul(&quotient[k])?)?;
+            }
+
+            let scale = y_j.mul(w_j)?;
+            for (r, q) in result.iter_mut().zip(&quotient) {
+                *r = r.add(&q.mul(&scale)?)?;
+            }
+        }
+
+        Polynomial::new(result)
+    }
+}
+
+/// A multiplicative subgroup {1, ω, ω^2, ..., ω^(n - 1)} of size n = 2^k, over which
+/// polynomials are converted between coefficients and evaluations with a radix-2 FFT
+/// in O(n log n) field operations.
+#[derive(Clone, Debug)]
+pub struct EvaluationDomain<F: Field = FieldElement> {
+    size: usize,
+    generator: F,
+    generator_inv: F,
+    size_inv: F,
+}
+
+impl<F: Field> EvaluationDomain<F> {
+    /// Creates the domain of the given power-of-two size, using the field's primitive
+    /// root of unity of that order.
+    pub fn new(size: usize, params: &F::Params) -> Result<Self, ZKError> {
+        if !size.is_power_of_two() {
+            return Err(ZKError::PolynomialError(format!(
+                "Domain size must be a power of two, got {}",
+                size
+            )));
+        }
+        let generator = F::root_of_unity(size as u64, params).ok_or_else(|| {
+            ZKError::PolynomialError(format!("Field has no root of unity of order {}", size))
+        })?;
+
+        Ok(EvaluationDomain {
+            size,
+            generator_inv: generator.inv()?,
+            generator,
+            size_inv: F::from_u64(size as u64, params).inv()?,
+        })
+    }
+
+    /// Returns the number of elements in the domain.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the generator ω of the domain.
+    pub fn generator(&self) -> &F {
+        &self.generator
+    }
+
+    /// Returns the elements 1, ω, ..., ω^(n - 1) of the domain.
+    pub fn elements(&self) -> Result<Vec<F>, ZKError> {
+        self.generator.powers(self.size)
+    }
+
+    /// Returns the polynomial X^n - 1 vanishing on the domain.
+    pub fn vanishing_polynomial(&self) -> Result<Polynomial<F>, ZKError> {
+        let params = self.generator.params();
+        let mut coefficients = vec![F::zero(&params); self.size + 1];
+        coefficients[0] = F::one(&params).neg();
+        coefficients[self.size] = F::one(&params);
+        Polynomial::new(coefficients)
+    }
+
+    /// Returns the value at `point` of the Lagrange basis polynomial of every element,
+    /// L_i(point) = ω^i · (point^n - 1) / (n · (point - ω^i)), in O(n) field operations.
+    pub fn lagrange_coefficients(&self, point: &F) -> Result<Vec<F>, ZKError> {
+        let params = self.generator.params();
+        let elements = self.elements()?;
+        let vanishing = point.exp(self.size as u64)?.sub(&F::one(&params))?;
+        if vanishing.is_zero() {
+            return Ok(elements
+                .iter()
+                .map(|x| F::from_u64((x == point) as u64, &params))
+                .collect());
+        }
+
+        let differences = elements
+            .iter()
+            .map(|x| point.sub(x))
+            .collect::<Result<Vec<F>, ZKError>>()?;
+        let scale = vanishing.mul(&self.size_inv)?;
+        F::batch_inv(&differences)?
+            .iter()
+            .zip(&elements)
+            .map(|(inverse, x)| x.mul(&scale)?.mul(inverse))
+            .collect()
+    }
+}
+
+// Replaces `values` by its discrete Fourier transform with respect to `root`, an
+// element of order values.len(), using the iterative Cooley–Tukey algorithm.
+fn fft_in_place<F: Field>(values: &mut [F], root: &F) -> Result<(), ZKError> {
+    let n = values.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i
+            .reverse_bits()
+            .checked_shr(usize::BITS - bits)
+            .unwrap_or(0);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let twiddles = root.exp((n / len) as u64)?.powers(len / 2)?;
+        for chunk in values.chunks_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+            for ((a, b), w) in low.iter_mut().zip(high.iter_mut()).zip(&twiddles) {
+                let t = b.mul(w)?;
+                *b = a.sub(&t)?;
+                *a = a.add(&t)?;
+            }
+        }
+        len *= 2;
+    }
+    Ok(())
+}
+
+impl<F: Field> Polynomial<F> {
+    /// Evaluates the polynomial at every element of the domain with one FFT, returning
+    /// p(ω^i) at index i. Coefficients beyond the domain size wrap around, since
+    /// ω^n = 1.
+    pub fn evaluate_over_domain(&self, domain: &EvaluationDomain<F>) -> Result<Vec<F>, ZKError> {
+        let params = domain.generator.params();
+        if self.coefficients[0].params() != params {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for evaluation".to_string(),
+            ));
+        }
+
+        let mut values = vec![F::zero(&params); domain.size];
+        for chunk in self.coefficients.chunks(domain.size) {
+            F::add_assign_slices(&mut values[..chunk.len()], chunk)?;
+        }
+        fft_in_place(&mut values, &domain.generator)?;
+        Ok(values)
+    }
+
+    /// Evaluates the polynomial at every element of the coset g·H of the domain, for a
+    /// shift g, returning p(g·ω^i) at index i. This is one FFT of the coefficients
+    /// scaled by the powers of g, so, as on the domain, any degree is accepted.
+    pub fn evaluate_over_coset(
+        &self,
+        domain: &EvaluationDomain<F>,
+        shift: &F,
+    ) -> Result<Vec<F>, ZKError> {
+        let mut scaled = self.coefficients.clone();
+        let powers = shift.powers(scaled.len())?;
+        F::mul_assign_slices(&mut scaled, &powers)?;
+        Polynomial::new(scaled)?.evaluate_over_domain(domain)
+    }
+
+    /// Returns the unique polynomial of degree below the domain size whose value at
+    /// g·ω^i is `evaluations[i]`, the inverse of `evaluate_over_coset`. The shift must
+    /// be non-zero.
+    pub fn from_coset_evaluations(
+        evaluations: &[F],
+        domain: &EvaluationDomain<F>,
+        shift: &F,
+    ) -> Result<Polynomial<F>, ZKError> {
+        let mut polynomial = Polynomial::from_evaluations(evaluations, domain)?;
+        let inverse_powers = shift.inv()?.powers(polynomial.coefficients.len())?;
+        F::mul_assign_slices(&mut polynomial.coefficients, &inverse_powers)?;
+        Ok(polynomial)
+    }
+
+    /// Returns the unique polynomial of degree below the domain size whose value at
+    /// ω^i is `evaluations[i]`, using one inverse FFT.
+    pub fn from_evaluations(
+        evaluations: &[F],
+        domain: &EvaluationDomain<F>,
+    ) -> Result<Polynomial<F>, ZKError> {
+        if evaluations.len() != domain.size {
+            return Err(ZKError::PolynomialError(format!(
+                "Expected {} evaluations, got {}",
+                domain.size,
+                evaluations.len()
+            )));
+        }
+        let params = domain.generator.params();
+        if evaluations.iter().any(|e| e.params() != params) {
+            return Err(ZKError::PolynomialError(
+                "Moduli must be the same for interpolation".to_string(),
+            ));
+        }
+
+        let mut coefficients = evaluations.to_vec();
+        fft_in_place(&mut coefficients, &domain.generator_inv)?;
+        F::scale_slice(&mut coefficients, &domain.size_inv)?;
+        Polynomial::new(coefficients)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::field::FieldElement;
 
-    use super::Polynomial;
+    use super::{
+        DegreeBoundedPolynomial, EvaluationDomain, InterpolationDomain, Polynomial,
+        EVALUATION_CHUNK_SIZE,
+    };
+    use crate::errors::ZKError;
 
     #[test]
     fn test_evaluate() {
@@ -250,15 +1084,15 @@ mod tests {
         let diff = polynomial1.sub(&polynomial2).unwrap();
         assert_eq!(
             diff.coefficients[0],
-            FieldElement::new(96, modulus).unwrap()
+            FieldElement::from_i64(-1, modulus).unwrap()
         );
         assert_eq!(
             diff.coefficients[1],
-            FieldElement::new(96, modulus).unwrap()
+            FieldElement::from_i64(-1, modulus).unwrap()
         );
         assert_eq!(
             diff.coefficients[2],
-            FieldElement::new(93, modulus).unwrap()
+            FieldElement::from_i64(-4, modulus).unwrap()
         );
     }
 
@@ -331,12 +1165,36 @@ mod tests {
             FieldElement::new(1, modulus).unwrap()
         );
 
-        for i in 0..2 {
-            assert_eq!(
-                remainder.coefficients[i],
-                FieldElement::new(0, modulus).unwrap()
-            );
-        }
+        assert!(remainder.is_zero());
+        assert_eq!(remainder.coefficients.len(), 1);
+    }
+
+    #[test]
+    fn test_mul_even_modulus() {
+        // Even moduli fall back to plain modular arithmetic.
+        let modulus = 16;
+        let polynomial1 = Polynomial::new(vec![
+            FieldElement::new(3, modulus).unwrap(),
+            FieldElement::new(5, modulus).unwrap(),
+        ])
+        .unwrap();
+        let polynomial2 = Polynomial::new(vec![
+            FieldElement::new(7, modulus).unwrap(),
+            FieldElement::new(9, modulus).unwrap(),
+        ])
+        .unwrap();
+
+        // (3 + 5x) * (7 + 9x) = 21 + 62x + 45x^2 = 5 + 14x + 13x^2 mod 16.
+        let product = polynomial1.mul(&polynomial2).unwrap();
+        let values: Vec<u64> = product.coefficients.iter().map(|c| c.value).collect();
+        assert_eq!(values, vec![5, 14, 13]);
+        assert_eq!(
+            product
+                .evaluate(&FieldElement::new(2, modulus).unwrap())
+                .unwrap()
+                .value,
+            (5 + 28 + 52) % 16
+        );
     }
 
     #[test]
@@ -367,4 +1225,606 @@ mod tests {
             FieldElement::new(6, modulus).unwrap()
         );
     }
+
+    #[test]
+    fn test_extension_field_coefficients() {
+        use crate::fp2::Fp2;
+
+        let modulus = 103;
+        let non_residue = FieldElement::new(modulus - 1, modulus).unwrap();
+        let fp2 = |c0, c1| {
+            Fp2::new(
+                FieldElement::new(c0, modulus).unwrap(),
+                FieldElement::new(c1, modulus).unwrap(),
+                non_residue.clone(),
+            )
+            .unwrap()
+        };
+
+        // (u + x) * (2 + x) = 2u + (2 + u)x + x^2 over Fp2 = Fp[u] / (u^2 + 1).
+        let p1 = Polynomial::new(vec![fp2(0, 1), fp2(1, 0)]).unwrap();
+        let p2 = Polynomial::new(vec![fp2(2, 0), fp2(1, 0)]).unwrap();
+        let product = p1.mul(&p2).unwrap();
+        assert_eq!(product.coefficients, vec![fp2(0, 2), fp2(2, 1), fp2(1, 0)]);
+
+        // At x = u: (2u)(2 + u) = 4u + 2u^2 = -2 + 4u.
+        let value = product.evaluate(&fp2(0, 1)).unwrap();
+        assert_eq!(value, fp2(modulus - 2, 4));
+
+        let (quotient, remainder) = product.div(&p2).unwrap();
+        assert_eq!(quotient.coefficients, p1.coefficients);
+        assert!(remainder.coefficients.iter().all(|c| c.is_zero()));
+    }
+
+    #[test]
+    fn test_div_by_zero_polynomial() {
+        let modulus = 97;
+        let p = Polynomial::new(vec![FieldElement::new(1, modulus).unwrap(); 3]).unwrap();
+        let zero = Polynomial::new(vec![FieldElement::new(0, modulus).unwrap(); 2]).unwrap();
+        assert!(p.div(&zero).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_batch() {
+        for modulus in [97, 1 << 40] {
+            let coefficients = [3, 0, 5, 1].map(|c| FieldElement::new(c, modulus).unwrap());
+            let polynomial = Polynomial::new(coefficients.to_vec()).unwrap();
+            // Enough points to span several evaluation chunks.
+            let points: Vec<FieldElement> = (0..2 * EVALUATION_CHUNK_SIZE as u64 + 10)
+                .map(|x| FieldElement::new(x % 97, modulus).unwrap())
+                .collect();
+
+            let expected: Vec<FieldElement> = points
+                .iter()
+                .map(|x| polynomial.evaluate(x).unwrap())
+                .collect();
+            assert_eq!(polynomial.evaluate_batch(&points).unwrap(), expected);
+            assert!(polynomial.evaluate_batch(&[]).unwrap().is_empty());
+        }
+
+        let polynomial = Polynomial::new(vec![FieldElement::new(1, 97).unwrap()]).unwrap();
+        let other = FieldElement::new(1, 101).unwrap();
+        assert!(polynomial.evaluate_batch(&[other]).is_err());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+
+        // 3 + 2x + x^2 through x = 1, 2, 5.
+        let expected = Polynomial::new(vec![fe(3), fe(2), fe(1)]).unwrap();
+        let points: Vec<(FieldElement, FieldElement)> = [1, 2, 5]
+            .iter()
+            .map(|&x| (fe(x), expected.evaluate(&fe(x)).unwrap()))
+            .collect();
+        let polynomial = Polynomial::interpolate(&points).unwrap();
+        assert_eq!(polynomial.coefficients, expected.coefficients);
+
+        let duplicate = [(fe(1), fe(2)), (fe(1), fe(3))];
+        assert!(Polynomial::interpolate(&duplicate).is_err());
+        assert!(Polynomial::<FieldElement>::interpolate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_interpolation_domain_reuse() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let xs: Vec<FieldElement> = (1..=6).map(fe).collect();
+        let domain = InterpolationDomain::new(&xs).unwrap();
+
+        for values in [[0, 0, 0, 0, 0, 0], [1, 0, 0, 0, 0, 0], [4, 96, 0, 17, 5, 1]] {
+            let ys = values.map(fe);
+            let polynomial = domain.interpolate(&ys).unwrap();
+            assert_eq!(polynomial.evaluate_batch(&xs).unwrap(), ys.to_vec());
+        }
+
+        for x in &xs {
+            assert!(domain.vanishing_polynomial().evaluate(x).unwrap().is_zero());
+        }
+        assert!(domain.interpolate(&[fe(1)]).is_err());
+    }
+
+    #[test]
+    fn test_lagrange_coefficients() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let ys = [4, 96, 0, 17, 5, 1, 33, 8].map(fe);
+        // Σ_j y_j · L_j(x) is the interpolant at x, on and off the domain.
+        let check = |coefficients: Vec<FieldElement>, expected: FieldElement| {
+            let mut sum = fe(0);
+            for (y, l) in ys.iter().zip(&coefficients) {
+                sum = sum.add(&y.mul(l).unwrap()).unwrap();
+            }
+            assert_eq!(sum, expected);
+        };
+
+        let xs: Vec<FieldElement> = (1..=8).map(fe).collect();
+        let domain = InterpolationDomain::new(&xs).unwrap();
+        let polynomial = domain.interpolate(&ys).unwrap();
+        for x in [fe(0), fe(3), fe(50)] {
+            check(
+                domain.lagrange_coefficients(&x).unwrap(),
+                polynomial.evaluate(&x).unwrap(),
+            );
+        }
+
+        let domain = EvaluationDomain::<FieldElement>::new(8, &modulus).unwrap();
+        let polynomial = Polynomial::from_evaluations(&ys, &domain).unwrap();
+        let omega = domain.generator().clone();
+        for x in [fe(0), omega.exp(5).unwrap(), fe(50)] {
+            check(
+                domain.lagrange_coefficients(&x).unwrap(),
+                polynomial.evaluate(&x).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalization() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+
+        let p = Polynomial::new(vec![fe(1), fe(2), fe(0), fe(0)]).unwrap();
+        assert_eq!(p.coefficients, vec![fe(1), fe(2)]);
+        assert_eq!(p.degree(), 1);
+
+        // Cancelling leading terms shrinks the result.
+        let q = Polynomial::new(vec![fe(5), fe(2)]).unwrap();
+        let diff = p.sub(&q).unwrap();
+        assert_eq!(diff.coefficients, vec![fe(93)]);
+
+        let zero = p.sub(&p).unwrap();
+        assert!(zero.is_zero());
+        assert_eq!(zero, Polynomial::new(vec![fe(0); 4]).unwrap());
+        assert!(!p.is_zero());
+    }
+
+    #[test]
+    fn test_eq_ignores_trailing_zeros() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+
+        let p = Polynomial::new(vec![fe(1), fe(2)]).unwrap();
+        let mut padded = p.clone();
+        padded.coefficients.extend([fe(0), fe(0)]);
+        assert_eq!(p, padded);
+        assert_eq!(padded.degree(), 1);
+        assert_ne!(p, Polynomial::new(vec![fe(1), fe(3)]).unwrap());
+    }
+
+    #[test]
+    fn test_scale_and_shift_variable() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        // 1 + 2x + 3x^2
+        let p = Polynomial::new(vec![fe(1), fe(2), fe(3)]).unwrap();
+
+        // p(2x) = 1 + 4x + 12x^2
+        let scaled = p.scale_variable(&fe(2)).unwrap();
+        assert_eq!(scaled, Polynomial::new(vec![fe(1), fe(4), fe(12)]).unwrap());
+
+        // p(x + 1) = 6 + 8x + 3x^2
+        let shifted = p.shift_variable(&fe(1)).unwrap();
+        assert_eq!(shifted, Polynomial::new(vec![fe(6), fe(8), fe(3)]).unwrap());
+
+        for x in 0..10 {
+            let x = fe(x);
+            let a = fe(5);
+            let b = fe(96);
+            assert_eq!(
+                p.scale_variable(&a).unwrap().evaluate(&x).unwrap(),
+                p.evaluate(&a.mul(&x).unwrap()).unwrap()
+            );
+            assert_eq!(
+                p.shift_variable(&b).unwrap().evaluate(&x).unwrap(),
+                p.evaluate(&x.add(&b).unwrap()).unwrap()
+            );
+        }
+
+        // Shifting back undoes the shift.
+        assert_eq!(shifted.shift_variable(&fe(96)).unwrap(), p);
+        assert!(p
+            .shift_variable(&FieldElement::new(1, 101).unwrap())
+            .is_err());
+        assert!(p
+            .scale_variable(&FieldElement::new(1, 101).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let p = Polynomial::new(vec![fe(1), fe(0), fe(96)]).unwrap();
+
+        let bytes = p.to_bytes();
+        assert_eq!(bytes.len(), 8 + 3 * 8);
+        assert_eq!(Polynomial::from_bytes(&bytes, &modulus).unwrap(), p);
+
+        assert!(
+            Polynomial::<FieldElement>::from_bytes(&bytes[..bytes.len() - 1], &modulus).is_err()
+        );
+        assert!(Polynomial::<FieldElement>::from_bytes(&bytes[..4], &modulus).is_err());
+        // 96 is not a canonical value modulo 89.
+        assert!(Polynomial::<FieldElement>::from_bytes(&bytes, &89).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let p = Polynomial::new(vec![fe(1), fe(2), fe(3)]).unwrap();
+
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(serde_json::from_str::<Polynomial>(&json).unwrap(), p);
+
+        // Deserialization rejects empty and mixed-modulus coefficient lists.
+        assert!(serde_json::from_str::<Polynomial>(r#"{"coefficients":[]}"#).is_err());
+        let mixed = r#"{"coefficients":[{"value":1,"modulus":97},{"value":1,"modulus":101}]}"#;
+        assert!(serde_json::from_str::<Polynomial>(mixed).is_err());
+    }
+
+    #[test]
+    fn test_in_place_arithmetic() {
+        let modulus = 97;
+        let poly = |coefficients: &[u64]| {
+            Polynomial::new(
+                coefficients
+                    .iter()
+                    .map(|&c| FieldElement::new(c, modulus).unwrap())
+                    .collect(),
+            )
+            .unwrap()
+        };
+        let p = poly(&[1, 2, 3]);
+        let q = poly(&[5, 6, 94, 7]);
+
+        let mut sum = p.clone();
+        sum.add_assign(&q).unwrap();
+        assert_eq!(sum, p.add(&q).unwrap());
+
+        let mut diff = q.clone();
+        diff.sub_assign(&p).unwrap();
+        assert_eq!(diff, q.sub(&p).unwrap());
+
+        // Cancelling the leading terms trims the result.
+        let mut cancelled = q.clone();
+        cancelled.sub_assign(&q).unwrap();
+        assert!(cancelled.is_zero());
+        assert_eq!(cancelled.coefficients.len(), 1);
+
+        let scalar = FieldElement::new(10, modulus).unwrap();
+        let mut scaled = p.clone();
+        scaled.scale_in_place(&scalar).unwrap();
+        assert_eq!(scaled, p.scale(&scalar).unwrap());
+
+        let mut product = poly(&[0; 10]);
+        p.mul_into(&q, &mut product).unwrap();
+        assert_eq!(product, p.mul(&q).unwrap());
+        p.mul_into(&poly(&[0]), &mut product).unwrap();
+        assert!(product.is_zero());
+
+        let other = Polynomial::new(vec![FieldElement::new(1, 101).unwrap()]).unwrap();
+        assert!(sum.add_assign(&other).is_err());
+        assert!(p.mul_into(&other, &mut product).is_err());
+    }
+
+    #[test]
+    fn test_inverse_mod_xn() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+
+        // 1 / (1 - x) = 1 + x + x^2 + ...
+        let p = Polynomial::new(vec![fe(1), fe(96)]).unwrap();
+        let inverse = p.inverse_mod_xn(5).unwrap();
+        assert_eq!(inverse.coefficients, vec![fe(1); 5]);
+
+        let mut rng = rand::rng();
+        for n in [1, 2, 3, 7, 16, 33] {
+            let f = Polynomial::new(
+                (0..20)
+                    .map(|i| {
+                        let c = FieldElement::random(&mut rng, modulus).unwrap();
+                        if i == 0 && c.is_zero() {
+                            fe(1)
+                        } else {
+                            c
+                        }
+                    })
+                    .collect(),
+            )
+            .unwrap();
+            let g = f.inverse_mod_xn(n).unwrap();
+            assert!(g.coefficients.len() <= n);
+            let mut product = f.mul(&g).unwrap();
+            product.coefficients.truncate(n);
+            assert_eq!(product, Polynomial::new(vec![fe(1)]).unwrap());
+        }
+
+        assert!(p.inverse_mod_xn(0).is_err());
+        let x = Polynomial::new(vec![fe(0), fe(1)]).unwrap();
+        assert!(x.inverse_mod_xn(3).is_err());
+    }
+
+    #[test]
+    fn test_div_large() {
+        // Large operands take the Newton inversion path; the quotient and remainder are
+        // unique given a = b·q + r with deg r < deg b.
+        let mut rng = rand::rng();
+        for modulus in [97, 1 << 40] {
+            let random_polynomial = |len: usize, rng: &mut rand::rngs::ThreadRng| {
+                let mut coefficients: Vec<FieldElement> = (0..len)
+                    .map(|_| FieldElement::random(rng, modulus).unwrap())
+                    .collect();
+                coefficients[len - 1] = FieldElement::new(1, modulus).unwrap();
+                Polynomial::new(coefficients).unwrap()
+            };
+            let a = random_polynomial(200, &mut rng);
+            let b = random_polynomial(80, &mut rng);
+
+            let (quotient, remainder) = a.div(&b).unwrap();
+            assert_eq!(quotient.degree(), 120);
+            assert!(remainder.degree() < b.degree());
+            assert_eq!(b.mul(&quotient).unwrap().add(&remainder).unwrap(), a);
+
+            // Exact division leaves no remainder.
+            let (quotient, remainder) = a.mul(&b).unwrap().div(&b).unwrap();
+            assert_eq!(quotient, a);
+            assert!(remainder.is_zero());
+        }
+    }
+
+    #[test]
+    fn test_roots() {
+        // Exhaustive search: (x - 3)(x - 5)^2(x^2 + 1) over F_97, where 22^2 = -1.
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let linear = |r: u64| Polynomial::new(vec![fe(r).neg(), fe(1)]).unwrap();
+        let quadratic = Polynomial::new(vec![fe(1), fe(0), fe(1)]).unwrap();
+        let p = linear(3)
+            .mul(&linear(5))
+            .unwrap()
+            .mul(&linear(5))
+            .unwrap()
+            .mul(&quadratic)
+            .unwrap();
+        assert_eq!(p.roots().unwrap(), vec![fe(3), fe(5), fe(22), fe(75)]);
+        assert!(Polynomial::new(vec![fe(0)]).unwrap().roots().is_err());
+        assert!(Polynomial::new(vec![fe(4)])
+            .unwrap()
+            .roots()
+            .unwrap()
+            .is_empty());
+
+        // Equal-degree factorization over 2^61 - 1, where x^2 + 1 is irreducible.
+        let modulus = (1u64 << 61) - 1;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let linear = |r: u64| Polynomial::new(vec![fe(r).neg(), fe(1)]).unwrap();
+        let mut rng = rand::rng();
+        let mut expected: Vec<FieldElement> = (0..6)
+            .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+            .collect();
+        let mut p = Polynomial::new(vec![fe(1), fe(0), fe(1)]).unwrap();
+        for root in &expected {
+            p = p.mul(&linear(root.value)).unwrap();
+        }
+        // A repeated root is reported once.
+        p = p.mul(&linear(expected[0].value)).unwrap();
+        expected.sort();
+        expected.dedup();
+        assert_eq!(p.roots().unwrap(), expected);
+
+        let composite = Polynomial::new(vec![FieldElement::new(1, 1 << 20).unwrap(); 2]).unwrap();
+        assert!(composite.roots().is_err());
+    }
+
+    #[test]
+    fn test_evaluation_domain() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let domain = EvaluationDomain::<FieldElement>::new(8, &modulus).unwrap();
+        let elements = domain.elements().unwrap();
+        assert_eq!(elements.len(), 8);
+        assert_eq!(domain.generator().exp(8).unwrap(), fe(1));
+        assert_ne!(domain.generator().exp(4).unwrap(), fe(1));
+
+        let vanishing = domain.vanishing_polynomial().unwrap();
+        assert!(elements
+            .iter()
+            .all(|x| vanishing.evaluate(x).unwrap().is_zero()));
+
+        // 96 = 2^5 · 3, and sizes that are not powers of two are rejected.
+        assert!(EvaluationDomain::<FieldElement>::new(32, &modulus).is_ok());
+        assert!(EvaluationDomain::<FieldElement>::new(64, &modulus).is_err());
+        assert!(EvaluationDomain::<FieldElement>::new(6, &modulus).is_err());
+        assert!(EvaluationDomain::<FieldElement>::new(2, &96).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_over_domain() {
+        let mut rng = rand::rng();
+        for (modulus, size) in [(97, 1), (97, 2), (97, 16), (18446744069414584321, 64)] {
+            let domain = EvaluationDomain::<FieldElement>::new(size, &modulus).unwrap();
+            let elements = domain.elements().unwrap();
+
+            // Degrees below, at and above the domain size; the last wraps around.
+            for len in [1, size, 2 * size + 1] {
+                let polynomial = Polynomial::new(
+                    (0..len)
+                        .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                        .collect(),
+                )
+                .unwrap();
+                let evaluations = polynomial.evaluate_over_domain(&domain).unwrap();
+                assert_eq!(evaluations, polynomial.evaluate_batch(&elements).unwrap());
+
+                if len <= size {
+                    let recovered = Polynomial::from_evaluations(&evaluations, &domain).unwrap();
+                    assert_eq!(recovered, polynomial);
+                }
+            }
+        }
+
+        let domain = EvaluationDomain::<FieldElement>::new(4, &97).unwrap();
+        let one = FieldElement::new(1, 97).unwrap();
+        assert!(Polynomial::from_evaluations(&[one.clone(), one.clone()], &domain).is_err());
+        let other = Polynomial::new(vec![FieldElement::new(1, 101).unwrap()]).unwrap();
+        assert!(other.evaluate_over_domain(&domain).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_over_coset() {
+        let mut rng = rand::rng();
+        for (modulus, size) in [(97, 1), (97, 8), (18446744069414584321, 32)] {
+            let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+            let domain = EvaluationDomain::<FieldElement>::new(size, &modulus).unwrap();
+            let shift = fe(5);
+            let coset: Vec<FieldElement> = domain
+                .elements()
+                .unwrap()
+                .iter()
+                .map(|x| x.mul(&shift).unwrap())
+                .collect();
+
+            for len in [1, size, 2 * size + 1] {
+                let polynomial = Polynomial::new(
+                    (0..len)
+                        .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                        .collect(),
+                )
+                .unwrap();
+                let evaluations = polynomial.evaluate_over_coset(&domain, &shift).unwrap();
+                assert_eq!(evaluations, polynomial.evaluate_batch(&coset).unwrap());
+
+                if len <= size {
+                    let recovered =
+                        Polynomial::from_coset_evaluations(&evaluations, &domain, &shift).unwrap();
+                    assert_eq!(recovered, polynomial);
+                }
+            }
+        }
+
+        let domain = EvaluationDomain::<FieldElement>::new(2, &97).unwrap();
+        let one = FieldElement::new(1, 97).unwrap();
+        let zero = FieldElement::new(0, 97).unwrap();
+        assert!(Polynomial::from_coset_evaluations(&[one.clone(), one], &domain, &zero).is_err());
+    }
+
+    #[test]
+    fn test_degree_bounded_polynomial() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let p = DegreeBoundedPolynomial::from_coefficients(vec![fe(1), fe(2), fe(3)], 3).unwrap();
+        let q = DegreeBoundedPolynomial::from_coefficients(vec![fe(4), fe(5)], 3).unwrap();
+        assert_eq!(p.bound(), 3);
+
+        // Trailing zeros do not count towards the degree.
+        assert!(DegreeBoundedPolynomial::from_coefficients(vec![fe(1), fe(0), fe(0)], 0).is_ok());
+        assert!(matches!(
+            DegreeBoundedPolynomial::from_coefficients(vec![fe(1), fe(2), fe(3)], 1),
+            Err(ZKError::DegreeBoundExceeded(2, 1))
+        ));
+
+        let sum = p.add(&q).unwrap();
+        assert_eq!(
+            sum.polynomial(),
+            &p.polynomial().add(q.polynomial()).unwrap()
+        );
+        assert_eq!(p.sub(&q).unwrap().bound(), 3);
+        assert_eq!(p.scale(&fe(2)).unwrap().evaluate(&fe(1)).unwrap(), fe(12));
+
+        // deg(p·q) = 3 fits, but deg(p·p) = 4 does not.
+        assert_eq!(p.mul(&q).unwrap().polynomial().degree(), 3);
+        assert!(matches!(p.mul(&p), Err(ZKError::DegreeBoundExceeded(4, 3))));
+        assert!(p.clone().with_bound(1).is_err());
+        assert_eq!(
+            p.clone().into_inner(),
+            Polynomial::new(vec![fe(1), fe(2), fe(3)]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let poly = |coefficients: &[u64]| {
+            Polynomial::new(
+                coefficients
+                    .iter()
+                    .map(|&c| FieldElement::new(c, 97).unwrap())
+                    .collect(),
+            )
+            .unwrap()
+        };
+        assert_eq!(poly(&[3, 5, 0, 2]).to_string(), "3 + 5x + 2x^3 (mod 97)");
+        assert_eq!(poly(&[0, 1, 1]).to_string(), "x + x^2 (mod 97)");
+        assert_eq!(poly(&[1]).to_string(), "1 (mod 97)");
+        assert_eq!(poly(&[0]).to_string(), "0 (mod 97)");
+        // Coefficients are shown reduced.
+        assert_eq!(poly(&[0, 0, 0, 100]).to_string(), "3x^3 (mod 97)");
+    }
+
+    #[test]
+    fn test_from_roots() {
+        let modulus = 97;
+        let fe = |v: u64| FieldElement::new(v, modulus).unwrap();
+        let expected = |roots: &[FieldElement]| {
+            let mut product = Polynomial::new(vec![fe(1)]).unwrap();
+            for root in roots {
+                product = product
+                    .mul(&Polynomial::new(vec![root.neg(), fe(1)]).unwrap())
+                    .unwrap();
+            }
+            product
+        };
+
+        // (x - 1)(x - 2)(x - 3) = x^3 - 6x^2 + 11x - 6.
+        let p = Polynomial::from_roots(&[fe(1), fe(2), fe(3)]).unwrap();
+        assert_eq!(p.coefficients, vec![fe(91), fe(11), fe(91), fe(1)]);
+
+        // Odd and even sizes, repeated roots, and enough roots to reach Karatsuba.
+        for n in [1, 2, 5, 8, 200] {
+            let roots: Vec<FieldElement> = (0..n).map(|i| fe(i % 13)).collect();
+            let p = Polynomial::from_roots(&roots).unwrap();
+            assert_eq!(p.degree(), n as usize);
+            assert_eq!(p, expected(&roots));
+        }
+
+        assert!(Polynomial::<FieldElement>::from_roots(&[]).is_err());
+        let mixed = [fe(1), FieldElement::new(1, 101).unwrap()];
+        assert!(Polynomial::from_roots(&mixed).is_err());
+    }
+
+    #[test]
+    fn test_eq_probabilistic() {
+        let mut rng = rand::rng();
+        let modulus = (1u64 << 61) - 1;
+        let p = Polynomial::new(
+            (0..300)
+                .map(|_| FieldElement::random(&mut rng, modulus).unwrap())
+                .collect(),
+        )
+        .unwrap();
+
+        // The same product computed two ways, and the same polynomial with padding.
+        let q = Polynomial::new(vec![FieldElement::new(3, modulus).unwrap(); 50]).unwrap();
+        let pq = p.mul(&q).unwrap();
+        let qp = q.mul(&p).unwrap();
+        assert!(pq.eq_probabilistic(&qp, &mut rng, 3).unwrap());
+        let mut padded = p.clone();
+        padded
+            .coefficients
+            .push(FieldElement::new(0, modulus).unwrap());
+        assert!(p.eq_probabilistic(&padded, &mut rng, 3).unwrap());
+
+        // Changing a single coefficient is caught.
+        let mut changed = pq.clone();
+        changed.coefficients[100] = changed.coefficients[100]
+            .add(&FieldElement::new(1, modulus).unwrap())
+            .unwrap();
+        assert!(!pq.eq_probabilistic(&changed, &mut rng, 3).unwrap());
+
+        let other = Polynomial::new(vec![FieldElement::new(1, 97).unwrap()]).unwrap();
+        assert!(!p.eq_probabilistic(&other, &mut rng, 1).unwrap());
+    }
 }
@@ -1,119 +1,486 @@
+use rand::RngCore;
+
 use crate::{
-    circuit::ConstraintSystem, errors::ZKError, field::FieldElement, polynomial::Polynomial,
+    circuit::{ConstraintSystem, LinearCombination, R1CSConstraint},
+    errors::ZKError,
+    field::{Field, FieldElement},
+    parallel,
+    polynomial::{DegreeBoundedPolynomial, EvaluationDomain, InterpolationDomain, Polynomial},
+    store::ConstraintStore,
 };
 
 /// Represents R1CS constraints in QAP form.
-pub struct QAP {
-    // Interpolated polynomials for a, b, and c constraints.
-    pub a_polynomials: Vec<Polynomial>,
-    pub b_polynomials: Vec<Polynomial>,
-    pub c_polynomials: Vec<Polynomial>,
+///
+/// The QAP is kept in evaluation form: the value of A_j, B_j and C_j at the point of
+/// constraint i is the coefficient of variable j in its a, b and c side, so the sparse
+/// constraints and the domain describe every polynomial. Dense polynomials, one per
+/// variable and side, take memory proportional to variables times constraints and are
+/// only interpolated on request, e.g. by `a_polynomial` or `a_polynomials`.
+///
+/// The QAP borrows the constraint store of its `ConstraintSystem` and reads it in a
+/// pass whenever it needs the constraints, so with a `FileConstraintStore` they are
+/// never all in memory. What it does hold, and what evaluation and the witness quotient
+/// allocate, is a few field elements per constraint or per variable.
+///
+/// As in the constraint system, the first `num_public` variables are the statement,
+/// whose polynomials a Groth16-style setup turns into the IC query, and the rest are
+/// the private witness.
+pub struct QAP<'a, F: Field = FieldElement, S: ConstraintStore<F> = Vec<R1CSConstraint<F>>> {
+    // The constraints. Repeated terms on a variable are not merged, so every pass adds
+    // them up.
+    constraints: &'a S,
+    num_variables: usize,
+    num_public: usize,
+    domain: ConstraintDomain<F>,
+    params: F::Params,
     // Target polynomial.
-    pub target_polynomial: Polynomial,
+    pub target_polynomial: Polynomial<F>,
+    // The point at which each constraint is enforced.
+    pub evaluation_points: Vec<F>,
+}
+
+/// The values A_j(τ), B_j(τ) and C_j(τ) of every variable's polynomials at a point τ,
+/// indexed by variable, and t(τ), as computed by `QAP::evaluate_at`. The first
+/// `num_public` variables are the public inputs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QAPEvaluations<F: Field = FieldElement> {
+    pub a: Vec<F>,
+    pub b: Vec<F>,
+    pub c: Vec<F>,
+    pub target: F,
+    pub num_public: usize,
+}
+
+impl<F: Field> QAPEvaluations<F> {
+    /// Returns β · A_j(τ) + α · B_j(τ) + C_j(τ) for every variable j, split into the
+    /// public inputs and the private witness. A Groth16-style setup divides the first
+    /// part by γ to form the IC query and the second by δ.
+    pub fn split_terms(&self, alpha: &F, beta: &F) -> Result<(Vec<F>, Vec<F>), ZKError> {
+        let mut terms = self
+            .a
+            .iter()
+            .zip(&self.b)
+            .zip(&self.c)
+            .map(|((a, b), c)| beta.mul(a)?.add(&alpha.mul(b)?)?.add(c))
+            .collect::<Result<Vec<F>, ZKError>>()?;
+        let witness = terms.split_off(self.num_public.min(terms.len()));
+        Ok((terms, witness))
+    }
+
+    /// Accumulates Σ_j x_j · (β · A_j(τ) + α · B_j(τ) + C_j(τ)) over the public inputs
+    /// x_j, the value a verifier combines from the IC query, constant one included.
+    pub fn accumulate_public_inputs(
+        &self,
+        public_inputs: &[F],
+        alpha: &F,
+        beta: &F,
+    ) -> Result<F, ZKError> {
+        if public_inputs.len() != self.num_public {
+            return Err(ZKError::CircuitError(format!(
+                "Expected {} public inputs, got {}.",
+                self.num_public,
+                public_inputs.len()
+            )));
+        }
+        let (terms, _) = self.split_terms(alpha, beta)?;
+        let mut sum = F::zero(&self.target.params());
+        for (input, term) in public_inputs.iter().zip(&terms) {
+            sum = sum.add(&input.mul(term)?)?;
+        }
+        Ok(sum)
+    }
+}
+
+// The side of a constraint a polynomial belongs to.
+#[derive(Clone, Copy)]
+enum Side {
+    A,
+    B,
+    C,
+}
+
+// The sides in the order a, b, c.
+const SIDES: [Side; 3] = [Side::A, Side::B, Side::C];
+
+impl Side {
+    fn of<F: Field>(self, constraint: &R1CSConstraint<F>) -> &LinearCombination<F> {
+        match self {
+            Side::A => &constraint.a,
+            Side::B => &constraint.b,
+            Side::C => &constraint.c,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Side::A => "a",
+            Side::B => "b",
+            Side::C => "c",
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
-struct Point {
-    x: FieldElement,
-    y: FieldElement,
+// The number of small integers tried as a coset shift when the field has no root of
+// unity of twice the domain size.
+const COSET_SHIFT_CANDIDATES: u64 = 64;
+
+// The points the constraints are interpolated over.
+enum ConstraintDomain<F: Field> {
+    // A power-of-two subgroup, padded with trivially satisfied rows and interpolated
+    // with one inverse FFT per polynomial.
+    Subgroup(EvaluationDomain<F>),
+    // The points 1, ..., n, for fields without a large enough subgroup.
+    Points(InterpolationDomain<F>),
 }
 
-impl QAP {
-    /// Creates a new QAP using the provided R1CS.
-    pub fn create(cs: &ConstraintSystem) -> Result<Self, ZKError> {
+impl<F: Field> ConstraintDomain<F> {
+    fn new(num_constraints: usize, params: &F::Params) -> Result<Self, ZKError> {
+        if let Ok(domain) = EvaluationDomain::new(num_constraints.next_power_of_two(), params) {
+            return Ok(ConstraintDomain::Subgroup(domain));
+        }
+        let points: Vec<F> = (0..num_constraints)
+            .map(|i| F::from_u64((i + 1) as u64, params))
+            .collect();
+        Ok(ConstraintDomain::Points(InterpolationDomain::new(&points)?))
+    }
+
+    fn points(&self, num_constraints: usize) -> Result<Vec<F>, ZKError> {
+        match self {
+            ConstraintDomain::Subgroup(domain) => {
+                let mut elements = domain.elements()?;
+                elements.truncate(num_constraints);
+                Ok(elements)
+            }
+            ConstraintDomain::Points(domain) => Ok(domain.points().to_vec()),
+        }
+    }
+
+    fn vanishing_polynomial(&self) -> Result<Polynomial<F>, ZKError> {
+        match self {
+            ConstraintDomain::Subgroup(domain) => domain.vanishing_polynomial(),
+            ConstraintDomain::Points(domain) => Ok(domain.vanishing_polynomial().clone()),
+        }
+    }
+
+    // Evaluates a polynomial at the point of each constraint, with one FFT on a
+    // subgroup.
+    fn evaluate(
+        &self,
+        polynomial: &Polynomial<F>,
+        num_constraints: usize,
+    ) -> Result<Vec<F>, ZKError> {
+        match self {
+            ConstraintDomain::Subgroup(domain) => {
+                let mut values = polynomial.evaluate_over_domain(domain)?;
+                values.truncate(num_constraints);
+                Ok(values)
+            }
+            ConstraintDomain::Points(domain) => polynomial.evaluate_batch(domain.points()),
+        }
+    }
+
+    // Returns a shift g whose coset g·H of the subgroup H misses H, i.e. g^n != 1, so
+    // that t(x) = x^n - 1 is non-zero on it: a root of unity of order 2n if the field
+    // has one, or else a small integer outside H. Fields whose whole multiplicative
+    // group is H, and the points domain, have none.
+    fn coset_shift(&self, params: &F::Params) -> Result<Option<F>, ZKError> {
+        let ConstraintDomain::Subgroup(domain) = self else {
+            return Ok(None);
+        };
+        let n = domain.size() as u64;
+        if let Some(root) = F::root_of_unity(2 * n, params) {
+            return Ok(Some(root));
+        }
+        for k in 2..COSET_SHIFT_CANDIDATES {
+            let shift = F::from_u64(k, params);
+            if !shift.is_zero() && !shift.exp(n)?.is_one() {
+                return Ok(Some(shift));
+            }
+        }
+        Ok(None)
+    }
+
+    // Returns L_i(point) for the basis polynomial of each constraint's point. Padding
+    // rows of a subgroup are zero, so their coefficients are dropped.
+    fn lagrange_coefficients(&self, point: &F, num_constraints: usize) -> Result<Vec<F>, ZKError> {
+        let mut coefficients = match self {
+            ConstraintDomain::Subgroup(domain) => domain.lagrange_coefficients(point)?,
+            ConstraintDomain::Points(domain) => domain.lagrange_coefficients(point)?,
+        };
+        coefficients.truncate(num_constraints);
+        Ok(coefficients)
+    }
+
+    // Interpolates one value per constraint.
+    fn interpolate(&self, values: &mut Vec<F>, zero: &F) -> Result<Polynomial<F>, ZKError> {
+        match self {
+            ConstraintDomain::Subgroup(domain) => {
+                values.resize(domain.size(), zero.clone());
+                Polynomial::from_evaluations(values, domain)
+            }
+            ConstraintDomain::Points(domain) => domain.interpolate(values),
+        }
+    }
+}
+
+impl<'a, F: Field, S: ConstraintStore<F>> QAP<'a, F, S> {
+    /// Creates a new QAP using the provided R1CS, borrowing its constraints.
+    pub fn create(cs: &'a ConstraintSystem<F, S>) -> Result<Self, ZKError> {
         let num_constraints = cs.constraints.len();
         if num_constraints == 0 {
             return Err(ZKError::PolynomialError("No constraints available.".into()));
         }
 
-        let num_variables = cs.num_variables;
-        let modulus = cs.constraints[0]
-            .a
-            .terms
-            .get(0)
-            .ok_or_else(|| ZKError::PolynomialError("Constraint has no terms.".into()))?
-            .coefficient
-            .modulus;
-
-        // Get evaluation points.
-        let evaluation_points: Vec<FieldElement> = (0..num_constraints)
-            .map(|i| FieldElement::new((i + 1) as u64, modulus))
-            .collect::<Result<_, _>>()?;
-
-        // Construct the target polynomial.
-        let mut target_polynomial = Polynomial::new(vec![FieldElement::new(1, modulus)?])?;
-        for point in &evaluation_points {
-            let factor = Polynomial::new(vec![
-                FieldElement::new((modulus - (point.value % modulus)) % modulus, modulus)?,
-                FieldElement::new(1, modulus)?,
-            ])?;
-            target_polynomial = target_polynomial.mul(&factor)?;
-        }
-
-        let mut a_polynomials = Vec::with_capacity(num_variables);
-        let mut b_polynomials = Vec::with_capacity(num_variables);
-        let mut c_polynomials = Vec::with_capacity(num_variables);
-
-        for i in 0..num_variables {
-            let mut a_points = Vec::with_capacity(num_constraints);
-            let mut b_points = Vec::with_capacity(num_constraints);
-            let mut c_points = Vec::with_capacity(num_constraints);
-
-            for (j, constraint) in cs.constraints.iter().enumerate() {
-                let r = evaluation_points[j].clone();
-                let a_coefficient = constraint
-                    .a
-                    .terms
-                    .iter()
-                    .find(|term| term.index == i)
-                    .map(|term| term.coefficient.clone())
-                    .unwrap_or(FieldElement::new(0, modulus)?);
-                let b_coefficient = constraint
-                    .b
-                    .terms
-                    .iter()
-                    .find(|term| term.index == i)
-                    .map(|term| term.coefficient.clone())
-                    .unwrap_or(FieldElement::new(0, modulus)?);
-                let c_coefficient = constraint
-                    .c
-                    .terms
-                    .iter()
-                    .find(|term| term.index == i)
-                    .map(|term| term.coefficient.clone())
-                    .unwrap_or(FieldElement::new(0, modulus)?);
-
-                a_points.push(Point {
-                    x: r.clone(),
-                    y: a_coefficient.clone(),
-                });
-                b_points.push(Point {
-                    x: r.clone(),
-                    y: b_coefficient.clone(),
-                });
-                c_points.push(Point {
-                    x: r.clone(),
-                    y: c_coefficient.clone(),
-                });
+        // Any term tells the field; the first constraint may have an empty side.
+        let params = cs.field_params()?;
+
+        // The domain is shared by all interpolations, and the target polynomial is its
+        // vanishing polynomial.
+        let domain = ConstraintDomain::new(num_constraints, &params)?;
+        let evaluation_points = domain.points(num_constraints)?;
+        let target_polynomial = domain.vanishing_polynomial()?;
+
+        Ok(QAP {
+            constraints: &cs.constraints,
+            num_variables: cs.num_variables,
+            num_public: cs.num_public,
+            domain,
+            params,
+            target_polynomial,
+            evaluation_points,
+        })
+    }
+
+    /// Returns the number of variables, i.e. of polynomials per side.
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    /// Returns the number of public inputs, the leading variables.
+    pub fn num_public(&self) -> usize {
+        self.num_public
+    }
+
+    /// Returns the number of private witness variables.
+    pub fn num_private(&self) -> usize {
+        self.num_variables - self.num_public
+    }
+
+    /// Returns the number of constraints.
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Splits a full witness vector into the public inputs (the statement) and the
+    /// private witness, like `ConstraintSystem::split_witness`.
+    pub fn split_witness<'w>(&self, witness: &'w [F]) -> Result<(&'w [F], &'w [F]), ZKError> {
+        if witness.len() != self.num_variables {
+            return Err(ZKError::CircuitError(format!(
+                "Expected a witness of {} variables, got {}.",
+                self.num_variables,
+                witness.len()
+            )));
+        }
+        Ok(witness.split_at(self.num_public))
+    }
+
+    /// Interpolates A_j, the polynomial of variable j on the a side.
+    pub fn a_polynomial(&self, j: usize) -> Result<Polynomial<F>, ZKError> {
+        self.polynomial(Side::A, j)
+    }
+
+    /// Interpolates B_j, the polynomial of variable j on the b side.
+    pub fn b_polynomial(&self, j: usize) -> Result<Polynomial<F>, ZKError> {
+        self.polynomial(Side::B, j)
+    }
+
+    /// Interpolates C_j, the polynomial of variable j on the c side.
+    pub fn c_polynomial(&self, j: usize) -> Result<Polynomial<F>, ZKError> {
+        self.polynomial(Side::C, j)
+    }
+
+    /// Interpolates A_j for every variable j.
+    pub fn a_polynomials(&self) -> Result<Vec<Polynomial<F>>, ZKError> {
+        self.polynomials(Side::A)
+    }
+
+    /// Interpolates B_j for every variable j.
+    pub fn b_polynomials(&self) -> Result<Vec<Polynomial<F>>, ZKError> {
+        self.polynomials(Side::B)
+    }
+
+    /// Interpolates C_j for every variable j.
+    pub fn c_polynomials(&self) -> Result<Vec<Polynomial<F>>, ZKError> {
+        self.polynomials(Side::C)
+    }
+
+    /// Evaluates every A_j, B_j and C_j, and t, at `tau` without interpolating:
+    /// A_j(τ) = Σ_i a_ij · L_i(τ) over the Lagrange basis of the constraint points, in
+    /// time linear in the number of terms plus the domain size. This is all a trusted
+    /// setup needs from the QAP.
+    pub fn evaluate_at(&self, tau: &F) -> Result<QAPEvaluations<F>, ZKError> {
+        let lagrange = self
+            .domain
+            .lagrange_coefficients(tau, self.num_constraints())?;
+        let zero = F::zero(&self.params);
+        let mut sides: [Vec<F>; 3] =
+            std::array::from_fn(|_| vec![zero.clone(); self.num_variables]);
+        // The store is read again here, so it is checked against what `create` saw.
+        let mut lagrange = lagrange.iter();
+        self.constraints.for_each(|constraint| {
+            let l = lagrange.next().ok_or_else(Self::too_many_constraints)?;
+            for (values, side) in sides.iter_mut().zip(SIDES) {
+                for term in &side.of(constraint).terms {
+                    let value = values
+                        .get_mut(term.index)
+                        .ok_or_else(|| self.variable_out_of_range(term.index))?;
+                    *value = value.add(&term.coefficient.mul(l)?)?;
+                }
             }
+            Ok(())
+        })?;
+        let [a, b, c] = sides;
+        Ok(QAPEvaluations {
+            a,
+            b,
+            c,
+            target: self.target_polynomial.evaluate(tau)?,
+            num_public: self.num_public,
+        })
+    }
 
-            let a_points_interpolated = Self::interpolate_points(&a_points)?;
-            let b_points_interpolated = Self::interpolate_points(&b_points)?;
-            let c_points_interpolated = Self::interpolate_points(&c_points)?;
+    /// Checks that the QAP encodes the constraints of `cs`, so that interpolation or
+    /// indexing bugs show up before keys are generated. For random weights ρ_j it
+    /// interpolates Σ_j ρ_j · A_j, and likewise for B and C, and checks its value at
+    /// the point of every constraint against the same combination of the coefficients
+    /// in `cs`; then it checks `evaluate_at` against these polynomials at a random τ.
+    /// It also checks the evaluation points and the target polynomial against the
+    /// domain. A QAP whose constraints differ from `cs` passes with probability at
+    /// most 1 / |F|.
+    pub fn verify_against<T: ConstraintStore<F>, R: RngCore + ?Sized>(
+        &self,
+        cs: &ConstraintSystem<F, T>,
+        rng: &mut R,
+    ) -> Result<(), ZKError> {
+        let num_constraints = self.num_constraints();
+        let counts = (self.num_variables, self.num_public, num_constraints);
+        let cs_counts = (cs.num_variables, cs.num_public, cs.constraints.len());
+        if counts != cs_counts {
+            return Err(ZKError::CircuitError(format!(
+                "QAP has {} variables ({} public) and {} constraints, but the constraint system has {} ({} public) and {}.",
+                counts.0, counts.1, counts.2, cs_counts.0, cs_counts.1, cs_counts.2
+            )));
+        }
+        if self.evaluation_points != self.domain.points(num_constraints)? {
+            return Err(ZKError::PolynomialError(
+                "Evaluation points do not match the domain.".into(),
+            ));
+        }
+        if self.target_polynomial != self.domain.vanishing_polynomial()? {
+            return Err(ZKError::PolynomialError(
+                "Target polynomial does not vanish exactly on the domain.".into(),
+            ));
+        }
+
+        let weights = (0..self.num_variables)
+            .map(|_| F::random(rng, &self.params))
+            .collect::<Result<Vec<F>, ZKError>>()?;
+        // The weighted coefficients of each constraint, read from `cs` in one pass.
+        let mut expected: [Vec<F>; 3] =
+            std::array::from_fn(|_| Vec::with_capacity(num_constraints));
+        cs.constraints.for_each(|constraint| {
+            for (values, side) in expected.iter_mut().zip(SIDES) {
+                values.push(side.of(constraint).evaluate(&weights)?);
+            }
+            Ok(())
+        })?;
 
-            a_polynomials.push(a_points_interpolated);
-            b_polynomials.push(b_points_interpolated);
-            c_polynomials.push(c_points_interpolated);
+        let tau = F::random(rng, &self.params)?;
+        let evaluations = self.evaluate_at(&tau)?;
+        let evaluated = [&evaluations.a, &evaluations.b, &evaluations.c];
+        let aggregated = self.aggregate_values(&weights)?;
+        let zero = F::zero(&self.params);
+        for (((side, expected), evaluated), mut aggregated) in SIDES
+            .into_iter()
+            .zip(&expected)
+            .zip(evaluated)
+            .zip(aggregated)
+        {
+            let polynomial = self.domain.interpolate(&mut aggregated, &zero)?;
+            let values = self.domain.evaluate(&polynomial, num_constraints)?;
+            if let Some(i) = values.iter().zip(expected).position(|(v, e)| v != e) {
+                return Err(ZKError::CircuitError(format!(
+                    "QAP polynomials on side {} do not match constraint {}.",
+                    side.name(),
+                    i
+                )));
+            }
+
+            let mut combined = F::zero(&self.params);
+            for (value, weight) in evaluated.iter().zip(&weights) {
+                combined = combined.add(&value.mul(weight)?)?;
+            }
+            if combined != polynomial.evaluate(&tau)? {
+                return Err(ZKError::CircuitError(format!(
+                    "QAP evaluations on side {} do not match its polynomials.",
+                    side.name()
+                )));
+            }
         }
+        Ok(())
+    }
 
-        Ok(QAP {
-            target_polynomial,
-            a_polynomials,
-            b_polynomials,
-            c_polynomials,
+    fn polynomial(&self, side: Side, j: usize) -> Result<Polynomial<F>, ZKError> {
+        if j >= self.num_variables {
+            return Err(self.variable_out_of_range(j));
+        }
+        let zero = F::zero(&self.params);
+        let mut values = Vec::with_capacity(self.num_constraints());
+        self.constraints.for_each(|constraint| {
+            let coefficient = side.of(constraint).coefficient(j)?;
+            values.push(coefficient.unwrap_or_else(|| zero.clone()));
+            Ok(())
+        })?;
+        self.domain.interpolate(&mut values, &zero)
+    }
+
+    fn polynomials(&self, side: Side) -> Result<Vec<Polynomial<F>>, ZKError> {
+        let zero = F::zero(&self.params);
+        // The transpose of the constraints: one column of values per variable.
+        let mut columns = vec![vec![zero.clone(); self.num_constraints()]; self.num_variables];
+        let mut i = 0;
+        self.constraints.for_each(|constraint| {
+            for term in &side.of(constraint).terms {
+                let value = columns
+                    .get_mut(term.index)
+                    .ok_or_else(|| self.variable_out_of_range(term.index))?
+                    .get_mut(i)
+                    .ok_or_else(Self::too_many_constraints)?;
+                *value = value.add(&term.coefficient)?;
+            }
+            i += 1;
+            Ok(())
+        })?;
+        // Each variable's interpolation is independent of the others.
+        let domain = &self.domain;
+        parallel::try_map(&columns, |values| {
+            domain.interpolate(&mut values.clone(), &zero)
         })
     }
 
+    fn variable_out_of_range(&self, j: usize) -> ZKError {
+        ZKError::PolynomialError(format!(
+            "Variable {} is out of range for {} variables.",
+            j, self.num_variables
+        ))
+    }
+
+    // A pass found more constraints than the QAP has coefficients for, as when the
+    // store behind it has changed since `create`.
+    fn too_many_constraints() -> ZKError {
+        ZKError::PolynomialError("More constraints than the QAP was created with.".into())
+    }
+
     /// Calculates the witness quotient polynomial h(x) such that:
     /// p(x) = h(x) * t(x),
     /// where:
@@ -123,98 +490,116 @@ impl QAP {
     ///   C(x) = Σ_j w_j * C_j(x),
     ///   t(x) = target polynomial.
     /// Returns an error if the remainder is not zero.
-    pub fn calculate_witness_quotient(
-        &self,
-        witness: &[FieldElement],
-    ) -> Result<Polynomial, ZKError> {
-        let a_polynomial = self.aggregate_polynomials(witness, |qap, j| &qap.a_polynomials[j])?;
-        let b_polynomial = self.aggregate_polynomials(witness, |qap, j| &qap.b_polynomials[j])?;
-        let c_polynomial = self.aggregate_polynomials(witness, |qap, j| &qap.c_polynomials[j])?;
-        let p_polynomial = a_polynomial.mul(&b_polynomial)?.sub(&c_polynomial)?;
-        let (quotient, remainder) = p_polynomial.div(&self.target_polynomial)?;
+    ///
+    /// On a subgroup H of size n, A, B and C are interpolated and evaluated on a coset
+    /// g·H with FFTs, where t(g·ω^i) = g^n - 1 is a non-zero constant, so h is
+    /// (A · B - C) / (g^n - 1) pointwise and one inverse FFT on the coset gives its
+    /// coefficients, in O(n log n) time. Otherwise h is found by dense multiplication
+    /// and long division.
+    pub fn calculate_witness_quotient(&self, witness: &[F]) -> Result<Polynomial<F>, ZKError> {
+        if witness.len() != self.num_variables {
+            return Err(ZKError::CircuitError(format!(
+                "Expected a witness of {} variables, got {}.",
+                self.num_variables,
+                witness.len()
+            )));
+        }
+        // A(x) takes the value of side a of constraint i at its point, so each of A, B
+        // and C is one interpolation of these values.
+        let values = self.aggregate_values(witness)?;
 
-        // Ensure remainder is zero.
-        for coeff in remainder.coefficients {
-            if coeff.value != 0 {
+        // t vanishes exactly on the constraint points, and padding rows are 0 * 0 = 0,
+        // so p(x) is divisible by t(x) if and only if every constraint holds.
+        for ((a, b), c) in values[0].iter().zip(&values[1]).zip(&values[2]) {
+            if a.mul(b)? != *c {
                 return Err(ZKError::PolynomialError(
                     "p(x) is not divisible by t(x)".into(),
                 ));
             }
         }
 
-        Ok(quotient)
-    }
-
-    // Interpolate points using Lagrange interpolation.
-    fn interpolate_points(points: &[Point]) -> Result<Polynomial, ZKError> {
-        if points.is_empty() {
-            return Err(ZKError::PolynomialError("No points to interpolate".into()));
-        }
+        let quotient = match (&self.domain, self.domain.coset_shift(&self.params)?) {
+            (ConstraintDomain::Subgroup(domain), Some(shift)) => {
+                self.coset_quotient(&values, domain, &shift)?
+            }
+            _ => self.dense_quotient(&values)?,
+        };
 
-        let modulus = points[0].x.modulus;
-        // Start with a zero polynomial.
-        let mut result = Polynomial::new(vec![FieldElement::new(0, modulus)?])?;
+        // A, B and C have degree below deg t, so deg h <= deg t - 2.
+        let bound = self.target_polynomial.degree().saturating_sub(2);
+        Ok(DegreeBoundedPolynomial::new(quotient, bound)?.into_inner())
+    }
 
-        for (i, point_outer) in points.iter().enumerate() {
-            let mut numerator = Polynomial::new(vec![FieldElement::new(1, modulus)?])?;
-            let mut denominator = FieldElement::new(1, modulus)?;
+    // Divides A · B - C by t in evaluation form on the coset g·H.
+    fn coset_quotient(
+        &self,
+        values: &[Vec<F>],
+        domain: &EvaluationDomain<F>,
+        shift: &F,
+    ) -> Result<Polynomial<F>, ZKError> {
+        let zero = F::zero(&self.params);
+        let cosets = parallel::try_map(values, |values| {
+            let mut values = values.clone();
+            values.resize(domain.size(), zero.clone());
+            Polynomial::from_evaluations(&values, domain)?.evaluate_over_coset(domain, shift)
+        })?;
 
-            for (j, point_inner) in points.iter().enumerate() {
-                if i == j {
-                    continue;
-                }
+        let t_inverse = shift
+            .exp(domain.size() as u64)?
+            .sub(&F::one(&self.params))?
+            .inv()?;
+        let mut h_values = cosets[0].clone();
+        F::mul_assign_slices(&mut h_values, &cosets[1])?;
+        F::sub_assign_slices(&mut h_values, &cosets[2])?;
+        F::scale_slice(&mut h_values, &t_inverse)?;
+        Polynomial::from_coset_evaluations(&h_values, domain, shift)
+    }
 
-                let numerator_factor = Polynomial::new(vec![
-                    FieldElement::new(
-                        (modulus - (point_inner.x.value % modulus)) % modulus,
-                        modulus,
-                    )?,
-                    FieldElement::new(1, modulus)?,
-                ])?;
-                numerator = numerator.mul(&numerator_factor)?;
-                denominator = denominator.mul(&point_outer.x.sub(&point_inner.x)?)?;
-            }
+    // Divides A · B - C by t with dense polynomials.
+    fn dense_quotient(&self, values: &[Vec<F>]) -> Result<Polynomial<F>, ZKError> {
+        let zero = F::zero(&self.params);
+        let domain = &self.domain;
+        let polynomials = parallel::try_map(values, |values| {
+            domain.interpolate(&mut values.clone(), &zero)
+        })?;
+        let p_polynomial = polynomials[0].mul(&polynomials[1])?.sub(&polynomials[2])?;
+        let (quotient, remainder) = p_polynomial.div(&self.target_polynomial)?;
 
-            let denominator_inverse = denominator.inv()?;
-            let final_polynomial = numerator.mul(&Polynomial::new(vec![point_outer
-                .y
-                .mul(&denominator_inverse)?])?)?;
-            result = result.add(&final_polynomial)?;
+        // Ensure remainder is zero.
+        if !remainder.is_zero() {
+            return Err(ZKError::PolynomialError(
+                "p(x) is not divisible by t(x)".into(),
+            ));
         }
-
-        Ok(result)
+        Ok(quotient)
     }
 
-    /// Aggregates the polynomials for a given side (A, B, or C) using the witness.
-    /// The closure `selector` picks the appropriate polynomial for variable j.
-    fn aggregate_polynomials<F>(
-        &self,
-        witness: &[FieldElement],
-        selector: F,
-    ) -> Result<Polynomial, ZKError>
-    where
-        F: Fn(&QAP, usize) -> &Polynomial,
-    {
-        let modulus = witness[0].modulus;
-        let mut sum = Polynomial::new(vec![FieldElement::new(0, modulus)?])?;
-        for j in 0..witness.len() {
-            let poly_j = selector(self, j);
-            let scaled = poly_j.scale(&witness[j])?;
-            sum = sum.add(&scaled)?;
-        }
-        Ok(sum)
+    // Returns the value of Σ_j w_j · P_j at each constraint point, for the
+    // polynomials P_j of each side, in one pass over the constraints.
+    fn aggregate_values(&self, witness: &[F]) -> Result<[Vec<F>; 3], ZKError> {
+        let mut values: [Vec<F>; 3] =
+            std::array::from_fn(|_| Vec::with_capacity(self.num_constraints()));
+        self.constraints.for_each(|constraint| {
+            for (values, side) in values.iter_mut().zip(SIDES) {
+                values.push(side.of(constraint).evaluate(witness)?);
+            }
+            Ok(())
+        })?;
+        Ok(values)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        circuit::{ConstraintSystem, LinearCombination, R1CSConstraint, Term},
+        circuit::{ConstraintSystem, LinearCombination, R1CSConstraint, Term, Variable},
         field::FieldElement,
+        goldilocks::Goldilocks,
         polynomial::Polynomial,
+        r1cs,
     };
 
-    use super::QAP;
+    use super::{ConstraintDomain, QAP};
 
     #[test]
     fn test_qap() {
@@ -343,22 +728,19 @@ mod tests {
         // Helper function to check interpolation.
         let check_interpolation = |poly: &Polynomial, expected: &[u64]| {
             for (i, &exp_coeff) in expected.iter().enumerate() {
-                let r = FieldElement::new((i + 1) as u64, modulus).unwrap();
-                let eval = poly.evaluate(&r).unwrap();
+                let r = &qap.evaluation_points[i];
+                let eval = poly.evaluate(r).unwrap();
                 assert_eq!(
-                    eval.value,
-                    exp_coeff,
+                    eval.value, exp_coeff,
                     "Expected coefficient {} at r = {} but got {}",
-                    exp_coeff,
-                    i + 1,
-                    eval.value
+                    exp_coeff, r.value, eval.value
                 );
             }
         };
 
         // Check poly_a for each variable.
         // There are 6 variables (indices 0 to 5).
-        let expected_a = vec![
+        let expected_a = [
             vec![0, 0, 0, 5], // v0
             vec![1, 1, 1, 0], // v1
             vec![0, 0, 0, 0], // v2
@@ -366,12 +748,12 @@ mod tests {
             vec![0, 0, 0, 1], // v4
             vec![0, 0, 0, 0], // v5
         ];
-        for j in 0..6 {
-            check_interpolation(&qap.a_polynomials[j], &expected_a[j]);
+        for (poly, expected) in qap.a_polynomials().unwrap().iter().zip(&expected_a) {
+            check_interpolation(poly, expected);
         }
 
         // Check poly_b for each variable.
-        let expected_b = vec![
+        let expected_b = [
             vec![0, 0, 1, 1], // v0
             vec![1, 0, 0, 0], // v1
             vec![0, 1, 0, 0], // v2
@@ -379,12 +761,12 @@ mod tests {
             vec![0, 0, 0, 0], // v4
             vec![0, 0, 0, 0], // v5
         ];
-        for j in 0..6 {
-            check_interpolation(&qap.b_polynomials[j], &expected_b[j]);
+        for (poly, expected) in qap.b_polynomials().unwrap().iter().zip(&expected_b) {
+            check_interpolation(poly, expected);
         }
 
         // Check poly_c for each variable.
-        let expected_c = vec![
+        let expected_c = [
             vec![0, 0, 0, 0], // v0
             vec![0, 0, 0, 0], // v1
             vec![1, 0, 0, 0], // v2
@@ -392,8 +774,8 @@ mod tests {
             vec![0, 0, 1, 0], // v4
             vec![0, 0, 0, 1], // v5
         ];
-        for j in 0..6 {
-            check_interpolation(&qap.c_polynomials[j], &expected_c[j]);
+        for (poly, expected) in qap.c_polynomials().unwrap().iter().zip(&expected_c) {
+            check_interpolation(poly, expected);
         }
 
         // Check witness.
@@ -414,39 +796,26 @@ mod tests {
 
         // As a sanity check, we verify that for several x-values, we have:
         // A(x) * B(x) - C(x) = h(x) * t(x)
+        let (a_polynomials, b_polynomials, c_polynomials) = (
+            qap.a_polynomials().unwrap(),
+            qap.b_polynomials().unwrap(),
+            qap.c_polynomials().unwrap(),
+        );
         for x_val in 1..=5 {
             let x = FieldElement::new(x_val, modulus).unwrap();
             // Aggregate A(x), B(x), and C(x)
             let mut a_eval = FieldElement::new(0, modulus).unwrap();
             let mut b_eval = FieldElement::new(0, modulus).unwrap();
             let mut c_eval = FieldElement::new(0, modulus).unwrap();
-            for j in 0..witness.len() {
+            for (j, w) in witness.iter().enumerate() {
                 a_eval = a_eval
-                    .add(
-                        &qap.a_polynomials[j]
-                            .scale(&witness[j])
-                            .unwrap()
-                            .evaluate(&x)
-                            .unwrap(),
-                    )
+                    .add(&a_polynomials[j].scale(w).unwrap().evaluate(&x).unwrap())
                     .unwrap();
                 b_eval = b_eval
-                    .add(
-                        &qap.b_polynomials[j]
-                            .scale(&witness[j])
-                            .unwrap()
-                            .evaluate(&x)
-                            .unwrap(),
-                    )
+                    .add(&b_polynomials[j].scale(w).unwrap().evaluate(&x).unwrap())
                     .unwrap();
                 c_eval = c_eval
-                    .add(
-                        &qap.c_polynomials[j]
-                            .scale(&witness[j])
-                            .unwrap()
-                            .evaluate(&x)
-                            .unwrap(),
-                    )
+                    .add(&c_polynomials[j].scale(w).unwrap().evaluate(&x).unwrap())
                     .unwrap();
             }
             let p_val = a_eval.mul(&b_eval).unwrap().sub(&c_eval).unwrap();
@@ -460,4 +829,371 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_qap_domains() {
+        // Three copies of v0 * v1 = v2 with witness 2 * 3 = 6.
+        let build = |modulus: u64| {
+            let mut cs = ConstraintSystem::new();
+            let variables: Vec<usize> = (0..3).map(|_| cs.allocate_variable()).collect();
+            let lc = |index: usize| {
+                let mut lc = LinearCombination::new();
+                lc.add_term(Term {
+                    index,
+                    coefficient: FieldElement::new(1, modulus).unwrap(),
+                });
+                lc
+            };
+            for _ in 0..3 {
+                cs.add_constraint(R1CSConstraint::new(
+                    lc(variables[0]),
+                    lc(variables[1]),
+                    lc(variables[2]),
+                ));
+            }
+            let witness: Vec<FieldElement> = [2, 3, 6]
+                .iter()
+                .map(|&v| FieldElement::new(v, modulus).unwrap())
+                .collect();
+            (cs, witness)
+        };
+
+        // 4 divides 96, so the constraints sit on the subgroup of order 4 and the
+        // target polynomial is X^4 - 1.
+        let (cs, witness) = build(97);
+        let qap = QAP::create(&cs).unwrap();
+        assert_eq!(qap.evaluation_points.len(), 3);
+        assert_eq!(qap.target_polynomial.degree(), 4);
+        for x in &qap.evaluation_points {
+            assert_eq!(x.exp(4).unwrap().value, 1);
+            assert_eq!(qap.a_polynomial(0).unwrap().evaluate(x).unwrap().value, 1);
+        }
+        assert!(qap.calculate_witness_quotient(&witness).is_ok());
+
+        // 102 = 2 · 51 has no subgroup of order 4, so the points 1, 2, 3 are used.
+        let (cs, witness) = build(103);
+        let qap = QAP::create(&cs).unwrap();
+        let points: Vec<u64> = qap.evaluation_points.iter().map(|x| x.value).collect();
+        assert_eq!(points, vec![1, 2, 3]);
+        assert_eq!(qap.target_polynomial.degree(), 3);
+        assert!(qap.calculate_witness_quotient(&witness).is_ok());
+    }
+
+    #[test]
+    fn test_qap_many_constraints() {
+        // 20 000 copies of v1 * v1 = v2 over Goldilocks, whose 2^32-element subgroup
+        // takes the constraints on a domain of 2^15 points with one inverse FFT each.
+        type G = Goldilocks;
+        let mut cs = ConstraintSystem::<G>::new();
+        cs.allocate_public_input().unwrap();
+        let x = Variable::new(cs.allocate_private_witness(), &());
+        let y = Variable::new(cs.allocate_private_witness(), &());
+        for _ in 0..20_000 {
+            r1cs!(cs; x * x == y);
+        }
+        let qap = QAP::create(&cs).unwrap();
+        assert_eq!(qap.target_polynomial.degree(), 1 << 15);
+        assert_eq!(qap.evaluation_points.len(), 20_000);
+        let point = &qap.evaluation_points[12_345];
+        assert_eq!(
+            qap.a_polynomial(1).unwrap().evaluate(point).unwrap(),
+            G::new(1)
+        );
+        assert_eq!(
+            qap.c_polynomial(2).unwrap().evaluate(point).unwrap(),
+            G::new(1)
+        );
+
+        // The quotient takes FFTs on a coset rather than long division.
+        let witness = [G::new(1), G::new(3), G::new(9)];
+        let h = qap.calculate_witness_quotient(&witness).unwrap();
+        assert!(h.degree() <= (1 << 15) - 2);
+        assert!(qap
+            .calculate_witness_quotient(&[G::new(1), G::new(3), G::new(10)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_witness_quotient_on_coset() {
+        // n copies of v1 * v1 = v2 with witness 3 * 3 = 9, plus v1 * v2 = v3.
+        let build = |modulus: u64, n: usize| {
+            let mut cs = ConstraintSystem::<FieldElement>::new();
+            let [_, x, y, z] = [(); 4].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+            for _ in 0..n {
+                r1cs!(cs; x * x == y);
+            }
+            r1cs!(cs; x * y == z);
+            let witness: Vec<FieldElement> = [1, 3, 9, 27]
+                .iter()
+                .map(|&v| FieldElement::new(v, modulus).unwrap())
+                .collect();
+            (cs, witness)
+        };
+
+        // On the subgroup of order 8 mod 97, the coset and dense quotients agree.
+        let (cs, witness) = build(97, 6);
+        let qap = QAP::create(&cs).unwrap();
+        let ConstraintDomain::Subgroup(domain) = &qap.domain else {
+            panic!("Expected a subgroup domain");
+        };
+        let shift = qap.domain.coset_shift(&qap.params).unwrap().unwrap();
+        let values = qap.aggregate_values(&witness).unwrap();
+        let coset = qap.coset_quotient(&values, domain, &shift).unwrap();
+        assert_eq!(coset, qap.dense_quotient(&values).unwrap());
+        assert_eq!(qap.calculate_witness_quotient(&witness).unwrap(), coset);
+
+        // Mod 17 the subgroup of order 16 is the whole multiplicative group, so there
+        // is no coset and the dense quotient is used.
+        let (cs, witness) = build(17, 12);
+        let qap = QAP::create(&cs).unwrap();
+        assert_eq!(qap.target_polynomial.degree(), 16);
+        assert!(qap.domain.coset_shift(&qap.params).unwrap().is_none());
+        let h = qap.calculate_witness_quotient(&witness).unwrap();
+        let values = qap.aggregate_values(&witness).unwrap();
+        assert_eq!(h, qap.dense_quotient(&values).unwrap());
+        let mut wrong = witness.clone();
+        wrong[3] = FieldElement::new(28, 17).unwrap();
+        assert!(qap.calculate_witness_quotient(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_qap_polynomials_on_demand() {
+        // v1 * (v2 + v1) = v3 and v3 * v0 = v2 + v2.
+        let modulus = 97;
+        let mut cs = ConstraintSystem::<FieldElement>::new();
+        let [v0, v1, v2, v3] = [(); 4].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+        r1cs!(cs;
+            v1 * (v2 + v1) == v3;
+            v3 * v0 == (v2 + v2);
+        );
+        let qap = QAP::create(&cs).unwrap();
+        assert_eq!((qap.num_variables(), qap.num_constraints()), (4, 2));
+
+        // One polynomial at a time matches all of them at once.
+        let sides = [
+            (qap.a_polynomials().unwrap(), 0),
+            (qap.b_polynomials().unwrap(), 1),
+            (qap.c_polynomials().unwrap(), 2),
+        ];
+        for (polynomials, side) in &sides {
+            assert_eq!(polynomials.len(), 4);
+            for (j, polynomial) in polynomials.iter().enumerate() {
+                let single = match side {
+                    0 => qap.a_polynomial(j),
+                    1 => qap.b_polynomial(j),
+                    _ => qap.c_polynomial(j),
+                };
+                assert_eq!(&single.unwrap(), polynomial);
+            }
+        }
+        // C_2 is 2 at the second constraint.
+        let point = &qap.evaluation_points[1];
+        assert_eq!(
+            qap.c_polynomial(2).unwrap().evaluate(point).unwrap().value,
+            2
+        );
+        assert!(qap.a_polynomial(4).is_err());
+
+        // 3 * (4 + 3) = 21, and 21 * v0 = 4 + 4 for v0 = 8 / 21.
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let v0 = fe(8).mul(&fe(21).inv().unwrap()).unwrap();
+        let witness = vec![v0, fe(3), fe(4), fe(21)];
+        assert!(cs.evaluate(&witness).unwrap());
+        assert!(qap.calculate_witness_quotient(&witness).is_ok());
+        assert!(qap.calculate_witness_quotient(&witness[..3]).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_at() {
+        // x^3 + x + 5 = out on the subgroup of order 4 mod 97, and on the points 1..4
+        // mod 103, which has no such subgroup.
+        for modulus in [97, 103] {
+            let fe = |v| FieldElement::new(v, modulus).unwrap();
+            let mut cs = ConstraintSystem::<FieldElement>::new();
+            let [one, out, x, x2, x3] =
+                [(); 5].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+            r1cs!(cs;
+                x * x == x2;
+                x2 * x == x3;
+                (x3 + x + one * fe(5)) * one == out;
+                (x + x) * (x2 + one) == (out * fe(2));
+            );
+            let qap = QAP::create(&cs).unwrap();
+            let polynomials = [
+                qap.a_polynomials().unwrap(),
+                qap.b_polynomials().unwrap(),
+                qap.c_polynomials().unwrap(),
+            ];
+
+            // Off the domain and on one of its points.
+            for tau in [fe(42), qap.evaluation_points[2].clone()] {
+                let evaluations = qap.evaluate_at(&tau).unwrap();
+                let sides = [&evaluations.a, &evaluations.b, &evaluations.c];
+                for (values, polynomials) in sides.iter().zip(&polynomials) {
+                    let expected: Vec<FieldElement> = polynomials
+                        .iter()
+                        .map(|p| p.evaluate(&tau).unwrap())
+                        .collect();
+                    assert_eq!(**values, expected);
+                }
+                assert_eq!(
+                    evaluations.target,
+                    qap.target_polynomial.evaluate(&tau).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_against() {
+        // The circuit of `test_evaluate_at`, on both kinds of domain.
+        let mut rng = rand::rng();
+        for modulus in [97, 103] {
+            let fe = |v| FieldElement::new(v, modulus).unwrap();
+            let build = |c: u64| {
+                let mut cs = ConstraintSystem::<FieldElement>::new();
+                let [one, out, x, x2, x3] =
+                    [(); 5].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+                r1cs!(cs;
+                    x * x == x2;
+                    x2 * x == x3;
+                    (x3 + x + one * fe(c)) * one == out;
+                    (x + x) * (x2 + one) == (out * fe(2));
+                );
+                cs
+            };
+            let cs = build(5);
+            let mut qap = QAP::create(&cs).unwrap();
+            for _ in 0..10 {
+                qap.verify_against(&cs, &mut rng).unwrap();
+            }
+
+            // One coefficient differs: A_0 is 6 rather than 5 at the third constraint.
+            let rejected = (0..10)
+                .filter(|_| qap.verify_against(&build(6), &mut rng).is_err())
+                .count();
+            assert!(rejected > 0);
+
+            // A constraint more, and points out of order.
+            let mut longer = build(5);
+            let one = Variable::new(0, &modulus);
+            r1cs!(longer; one * one == one);
+            assert!(qap.verify_against(&longer, &mut rng).is_err());
+            qap.evaluation_points.swap(0, 1);
+            assert!(qap.verify_against(&cs, &mut rng).is_err());
+        }
+    }
+
+    #[test]
+    fn test_public_inputs() {
+        // x^3 + x + 5 = out with one and out public, for x = 3.
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::<FieldElement>::new();
+        let [one, out] =
+            [(); 2].map(|_| Variable::new(cs.allocate_public_input().unwrap(), &modulus));
+        let [x, x2, x3] = [(); 3].map(|_| Variable::new(cs.allocate_private_witness(), &modulus));
+        r1cs!(cs;
+            x * x == x2;
+            x2 * x == x3;
+            (x3 + x + one * fe(5)) * one == out;
+        );
+        let qap = QAP::create(&cs).unwrap();
+        assert_eq!((qap.num_public(), qap.num_private()), (2, 3));
+
+        let witness: Vec<FieldElement> = [1, 35, 3, 9, 27].map(fe).to_vec();
+        let (public_inputs, private) = qap.split_witness(&witness).unwrap();
+        assert_eq!((public_inputs.len(), private.len()), (2, 3));
+        assert!(qap.split_witness(&witness[..4]).is_err());
+
+        // The statement and witness parts add up to β · A(τ) + α · B(τ) + C(τ).
+        let (alpha, beta, tau) = (fe(11), fe(13), fe(42));
+        let evaluations = qap.evaluate_at(&tau).unwrap();
+        let (ic, terms) = evaluations.split_terms(&alpha, &beta).unwrap();
+        assert_eq!((ic.len(), terms.len()), (2, 3));
+        let dot = |values: &[FieldElement], weights: &[FieldElement]| {
+            values
+                .iter()
+                .zip(weights)
+                .fold(fe(0), |sum, (v, w)| sum.add(&v.mul(w).unwrap()).unwrap())
+        };
+        let statement = evaluations
+            .accumulate_public_inputs(public_inputs, &alpha, &beta)
+            .unwrap();
+        let expected = beta
+            .mul(&dot(&evaluations.a, &witness))
+            .unwrap()
+            .add(&alpha.mul(&dot(&evaluations.b, &witness)).unwrap())
+            .unwrap()
+            .add(&dot(&evaluations.c, &witness))
+            .unwrap();
+        assert_eq!(statement.add(&dot(&terms, private)).unwrap(), expected);
+        assert!(evaluations
+            .accumulate_public_inputs(&witness[..1], &alpha, &beta)
+            .is_err());
+    }
+
+    #[test]
+    fn test_qap_duplicate_terms() {
+        // (v0 + v0) * v1 = v2 with witness 2 * 3 = 12, written with v0 split in two terms.
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::new();
+        let variables: Vec<usize> = (0..3).map(|_| cs.allocate_variable()).collect();
+        let lc = |terms: &[(usize, u64)]| {
+            let mut lc = LinearCombination::new();
+            for &(index, v) in terms {
+                lc.add_term(Term {
+                    index,
+                    coefficient: fe(v),
+                });
+            }
+            lc
+        };
+        cs.add_constraint(R1CSConstraint::new(
+            lc(&[(variables[0], 1), (variables[0], 1)]),
+            lc(&[(variables[1], 1)]),
+            lc(&[(variables[2], 1)]),
+        ));
+
+        let qap = QAP::create(&cs).unwrap();
+        let x = &qap.evaluation_points[0];
+        assert_eq!(qap.a_polynomial(0).unwrap().evaluate(x).unwrap(), fe(2));
+
+        let witness = vec![fe(2), fe(3), fe(12)];
+        assert!(cs.evaluate(&witness).unwrap());
+        assert!(qap.calculate_witness_quotient(&witness).is_ok());
+        // Reading only the first term would accept 2 * 3 = 6 instead.
+        assert!(qap
+            .calculate_witness_quotient(&[fe(2), fe(3), fe(6)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_qap_empty_sides() {
+        // 0 * 0 = v1 - 5 v0 pins v1 to 5, as in circom's linear constraints, then
+        // v1 * v1 = v2.
+        let modulus = 97;
+        let fe = |v| FieldElement::new(v, modulus).unwrap();
+        let mut cs = ConstraintSystem::new();
+        let [one, x, y] = [(); 3].map(|_| Variable::new(cs.allocate_variable(), &modulus));
+        cs.enforce(
+            LinearCombination::new(),
+            LinearCombination::new(),
+            x - one * fe(5),
+        );
+        cs.enforce(x, x, y);
+
+        let qap = QAP::create(&cs).unwrap();
+        assert!(qap
+            .calculate_witness_quotient(&[fe(1), fe(5), fe(25)])
+            .is_ok());
+        assert!(qap
+            .calculate_witness_quotient(&[fe(1), fe(6), fe(36)])
+            .is_err());
+
+        // Coefficients from two fields are rejected up front.
+        cs.enforce(x, Variable::new(y.index, &101), y);
+        assert!(QAP::create(&cs).is_err());
+    }
 }
@@ -1,116 +1,171 @@
 use crate::{
-    curve::{EllipticCurve, EllipticCurvePoint},
+    circuit::{Circuit, ConstraintSystem},
     errors::ZKError,
-    field::FieldElement,
-    pairing::Pairing,
+    field::Field,
+    pairing::PairingEngine,
     qap::QAP,
+    store::ConstraintStore,
 };
 
 /// Represents the CRS (Common Reference String) for the SNARK.
-pub struct CRS {
-    pub g1: EllipticCurvePoint,
-    pub g2: EllipticCurvePoint,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "E::G1: serde::Serialize, E::G2: serde::Serialize",
+        deserialize = "E::G1: serde::Deserialize<'de>, E::G2: serde::Deserialize<'de>"
+    ))
+)]
+pub struct CRS<E: PairingEngine> {
+    pub g1: E::G1,
+    pub g2: E::G2,
+    /// The `ConstraintSystem::digest` of the circuit the CRS was set up for, if any.
+    pub circuit_digest: Option<[u8; 32]>,
 }
 
 /// Represents a SNARK proof.
-pub struct Proof {
-    pub a: EllipticCurvePoint,
-    pub b: EllipticCurvePoint,
-    pub c: EllipticCurvePoint,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "E::G1: serde::Serialize, E::G2: serde::Serialize",
+        deserialize = "E::G1: serde::Deserialize<'de>, E::G2: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Proof<E: PairingEngine> {
+    pub a: E::G1,
+    pub b: E::G2,
+    pub c: E::G1,
+    /// The `ConstraintSystem::digest` of the circuit that was proven, if known.
+    ///
+    /// This is a plain label set by the prover and not bound to the group elements, so a
+    /// dishonest prover can copy it from the CRS. It only catches honest mix-ups of
+    /// proofs and circuits, and is not a security check.
+    pub circuit_digest: Option<[u8; 32]>,
 }
 
+/// A dummy SNARK, generic over the pairing it runs on.
 pub struct SNARK {}
 
 impl SNARK {
-    /// Generates a dummy CRS.
-    pub fn trusted_setup(curve: &EllipticCurve) -> Result<CRS, ZKError> {
-        let modulus = curve.a.modulus;
-
-        // We are choosing values here such that our dummy `verify_proof`
-        // method is satisfied for modulo 97.
-        let g1_x = FieldElement::new(47, modulus)?;
-        let g1_y = FieldElement::new(1, modulus)?;
-        let g2_x = FieldElement::new(2, modulus)?;
-        let g2_y = FieldElement::new(1, modulus)?;
+    /// Generates a dummy CRS from the generators of the engine's source groups.
+    pub fn trusted_setup<E: PairingEngine>(engine: &E) -> Result<CRS<E>, ZKError> {
+        Ok(CRS {
+            g1: engine.g1_generator()?,
+            g2: engine.g2_generator()?,
+            circuit_digest: None,
+        })
+    }
 
-        let g1 = EllipticCurvePoint::Point { x: g1_x, y: g1_y };
-        let g2 = EllipticCurvePoint::Point { x: g2_x, y: g2_y };
+    /// Runs the setup for `circuit`, returning the CRS and the constraint system of the
+    /// circuit, which `QAP::create` turns into the QAP the prover needs. Only the shape
+    /// of the circuit is used, so it may lack an assignment. The CRS records the digest
+    /// of the circuit.
+    pub fn circuit_setup<E: PairingEngine, C: Circuit<E::Fr> + ?Sized>(
+        circuit: &C,
+        engine: &E,
+    ) -> Result<(CRS<E>, ConstraintSystem<E::Fr>), ZKError> {
+        let cs = ConstraintSystem::from_circuit(circuit)?;
+        let crs = CRS {
+            circuit_digest: Some(cs.digest()),
+            ..Self::trusted_setup(engine)?
+        };
+        Ok((crs, cs))
+    }
 
-        Ok(CRS { g1, g2 })
+    /// Proves `circuit` against the QAP of the constraint system from `circuit_setup`,
+    /// using the assignment the circuit carries. The proof records the digest of the
+    /// circuit.
+    pub fn prove_circuit<
+        E: PairingEngine,
+        C: Circuit<E::Fr> + ?Sized,
+        S: ConstraintStore<E::Fr>,
+    >(
+        circuit: &C,
+        qap: &QAP<E::Fr, S>,
+        crs: &CRS<E>,
+        engine: &E,
+    ) -> Result<Proof<E>, ZKError> {
+        let witness = circuit.witness()?;
+        if witness.len() != qap.num_variables() {
+            return Err(ZKError::CircuitError(format!(
+                "Expected a witness of {} variables, got {}.",
+                qap.num_variables(),
+                witness.len()
+            )));
+        }
+        Ok(Proof {
+            circuit_digest: Some(ConstraintSystem::from_circuit(circuit)?.digest()),
+            ..Self::create_proof(qap, &witness, crs, engine)?
+        })
     }
 
     /// Given a QAP (from the circuit) and a witness vector,
-    /// compute the witness quotient polynomial h(x) and then "commit" to it via dummy group operations.
+    /// compute the witness quotient polynomial h(x) and then "commit" to it in both groups.
     /// The resulting proof consists of three group elements.
-    pub fn create_proof(qap: &QAP, witness: &[FieldElement], crs: &CRS) -> Result<Proof, ZKError> {
+    pub fn create_proof<E: PairingEngine, S: ConstraintStore<E::Fr>>(
+        qap: &QAP<E::Fr, S>,
+        witness: &[E::Fr],
+        crs: &CRS<E>,
+        engine: &E,
+    ) -> Result<Proof<E>, ZKError> {
         // Compute the witness quotient polynomial h(x).
         let h_polynomial = qap.calculate_witness_quotient(witness)?;
-        // For a dummy commitment, we take the constant term of h(x) (h(0)) and "multiply" the CRS group elements.
-        let h0 = h_polynomial
-            .coefficients
-            .get(0)
-            .ok_or_else(|| ZKError::PolynomialError("Witness quotient polynomial is empty".into()))?
-            .clone();
-
-        // Simulate scalar multiplication of group elements by h0.
-        let proof_a = match &crs.g1 {
-            EllipticCurvePoint::Point { x, y } => EllipticCurvePoint::Point {
-                x: x.mul(&h0)?,
-                y: y.mul(&h0)?,
-            },
-            EllipticCurvePoint::Infinity => EllipticCurvePoint::Infinity,
-        };
-        let proof_b = match &crs.g2 {
-            EllipticCurvePoint::Point { x, y } => EllipticCurvePoint::Point {
-                x: x.mul(&h0)?,
-                y: y.mul(&h0)?,
-            },
-            EllipticCurvePoint::Infinity => EllipticCurvePoint::Infinity,
-        };
-        // For proof_c, we combine g1 and g2 using a dummy addition (this is purely illustrative).
-        let proof_c = match (&crs.g1, &crs.g2) {
-            (
-                EllipticCurvePoint::Point { x: x1, y: y1 },
-                EllipticCurvePoint::Point { x: x2, y: y2 },
-            ) => {
-                // We simulate group addition by adding the coordinates.
-                // In practice, group addition is nontrivial.
-                EllipticCurvePoint::Point {
-                    x: x1.add(x2)?,
-                    y: y1.add(y2)?,
-                }
-            }
-            _ => EllipticCurvePoint::Infinity,
-        };
+        // For a dummy commitment, we take the constant term of h(x) (h(0)) and multiply the CRS group elements.
+        let h0 = h_polynomial.coefficients.first().ok_or_else(|| {
+            ZKError::PolynomialError("Witness quotient polynomial is empty".into())
+        })?;
+
+        // For proof_c, we take g1 itself and fold it into proof_a (this is purely
+        // illustrative), so that A = (h0 + 1) · g1 = h0 · g1 + C.
+        let one = E::Fr::one(&h0.params());
 
         Ok(Proof {
-            a: proof_a,
-            b: proof_b,
-            c: proof_c,
+            a: engine.g1_mul(&crs.g1, &h0.add(&one)?)?,
+            b: engine.g2_mul(&crs.g2, h0)?,
+            c: engine.g1_mul(&crs.g1, &one)?,
+            circuit_digest: None,
         })
     }
 
-    /// Given a proof, the CRS, and the elliptic curve,
-    /// perform a dummy pairing check to verify the proof.
-    pub fn verify_proof(proof: &Proof, crs: &CRS, curve: &EllipticCurve) -> Result<bool, ZKError> {
-        let pairing_a = Pairing::create(curve, &proof.a, &crs.g2)?;
-        let pairing_b = Pairing::create(curve, &proof.b, &crs.g1)?;
-        let pairing_c = Pairing::create(curve, &proof.c, &crs.g2)?;
-        let combined_value = pairing_b.value.mul(&pairing_c.value)?;
-        let combined = Pairing {
-            value: combined_value,
-        };
-        Ok(pairing_a == combined)
+    /// Given a proof, the CRS, and the engine, perform a dummy pairing check to verify
+    /// the proof: e(A, g2) = e(g1, B) · e(C, g2), i.e. A commits to the scalar of B plus
+    /// that of C. Both sides go into one `PairingEngine::check_equal`, so the three
+    /// pairings share a single final exponentiation.
+    /// If the CRS was set up for a circuit, the proof must carry the same circuit
+    /// digest, see `prove_circuit`. The pairing check does not cover the digest, so this
+    /// rejects proofs mixed up by mistake, not forged ones.
+    pub fn verify_proof<E: PairingEngine>(
+        proof: &Proof<E>,
+        crs: &CRS<E>,
+        engine: &E,
+    ) -> Result<bool, ZKError> {
+        if crs.circuit_digest.is_some() && proof.circuit_digest != crs.circuit_digest {
+            return Ok(false);
+        }
+        engine.check_equal(
+            &[(proof.a.clone(), crs.g2.clone())],
+            &[
+                (crs.g1.clone(), proof.b.clone()),
+                (proof.c.clone(), crs.g2.clone()),
+            ],
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        circuit::{ConstraintSystem, LinearCombination, R1CSConstraint, Term},
+        circuit::{Circuit, ConstraintSystem, LinearCombination, R1CSConstraint, Term, Variable},
         curve::EllipticCurve,
+        curves::ToyBn,
+        errors::ZKError,
         field::FieldElement,
+        fp::Fp,
+        pairing::{PairingEngine, TatePairing, ToyPairing},
+        r1cs,
         snark::SNARK,
+        store::ConstraintStore,
     };
 
     use super::QAP;
@@ -118,13 +173,10 @@ mod tests {
     #[test]
     fn test_snark() {
         let modulus = 97;
-        let curve = EllipticCurve {
-            a: FieldElement::new(2, modulus).unwrap(),
-            b: FieldElement::new(3, modulus).unwrap(),
-        };
+        let engine = ToyPairing::new(EllipticCurve::from_name("toy97").unwrap()).unwrap();
 
         // Run trusted setup to generate the CRS.
-        let crs = SNARK::trusted_setup(&curve).unwrap();
+        let crs = SNARK::trusted_setup(&engine).unwrap();
 
         // Equation: x^3 + x + 5 = 35.
         let mut cs = ConstraintSystem::new();
@@ -242,9 +294,130 @@ mod tests {
         ];
 
         // Prover: Generate a SNARK proof.
-        let proof = SNARK::create_proof(&qap, &witness, &crs).unwrap();
+        let proof = SNARK::create_proof(&qap, &witness, &crs, &engine).unwrap();
         // Verifier: Check the proof.
-        let valid = SNARK::verify_proof(&proof, &crs, &curve).unwrap();
+        let valid = SNARK::verify_proof(&proof, &crs, &engine).unwrap();
         assert!(valid, "The proof is invalid.");
+
+        // A forged B, committing to a different scalar than A, fails the check.
+        let two = FieldElement::new(2, modulus).unwrap();
+        let forged = super::Proof::<ToyPairing> {
+            b: engine.g2_mul(&proof.b, &two).unwrap(),
+            ..proof
+        };
+        assert!(!SNARK::verify_proof(&forged, &crs, &engine).unwrap());
+    }
+
+    // Proves knowledge of x with x^3 + x + 5 = out, for a public out.
+    struct CubicCircuit {
+        out: FieldElement,
+        x: Option<FieldElement>,
+    }
+
+    impl Circuit for CubicCircuit {
+        fn synthesize<S: ConstraintStore>(
+            &self,
+            cs: &mut ConstraintSystem<FieldElement, S>,
+        ) -> Result<(), ZKError> {
+            let params = self.out.modulus;
+            let fe = |v| FieldElement::new(v, params);
+            let one = Variable::new(cs.allocate_public_input()?, &params);
+            let out = Variable::new(cs.allocate_public_input()?, &params);
+            let x = Variable::new(cs.allocate_private_witness(), &params);
+            let x2 = Variable::new(cs.allocate_private_witness(), &params);
+            let x3 = Variable::new(cs.allocate_private_witness(), &params);
+            let five = fe(5)?;
+            r1cs!(cs;
+                x * x == x2;
+                x2 * x == x3;
+                (x3 + x + one * five) * one == out;
+            );
+            Ok(())
+        }
+
+        fn witness(&self) -> Result<Vec<FieldElement>, ZKError> {
+            let x = self
+                .x
+                .clone()
+                .ok_or_else(|| ZKError::CircuitError("No assignment for x.".into()))?;
+            let x2 = x.mul(&x)?;
+            let x3 = x2.mul(&x)?;
+            let one = FieldElement::new(1, x.modulus)?;
+            Ok(vec![one, self.out.clone(), x, x2, x3])
+        }
+    }
+
+    #[test]
+    fn test_circuit_trait() {
+        let modulus = 97;
+        let engine = ToyPairing::new(EllipticCurve::from_name("toy97").unwrap()).unwrap();
+        let out = FieldElement::new(35, modulus).unwrap();
+
+        // Setup sees only the shape of the circuit.
+        let shape = CubicCircuit {
+            out: out.clone(),
+            x: None,
+        };
+        let (crs, setup_cs) = SNARK::circuit_setup(&shape, &engine).unwrap();
+        let qap = QAP::create(&setup_cs).unwrap();
+        assert!(SNARK::prove_circuit(&shape, &qap, &crs, &engine).is_err());
+
+        let circuit = CubicCircuit {
+            out,
+            x: Some(FieldElement::new(3, modulus).unwrap()),
+        };
+        let cs = ConstraintSystem::from_circuit(&circuit).unwrap();
+        assert_eq!((cs.num_public, cs.num_private()), (2, 3));
+        assert!(cs.evaluate(&circuit.witness().unwrap()).unwrap());
+        let proof = SNARK::prove_circuit(&circuit, &qap, &crs, &engine).unwrap();
+        assert!(SNARK::verify_proof(&proof, &crs, &engine).unwrap());
+
+        // x = 4 gives 64 + 4 + 5 = 73, not 35.
+        let wrong = CubicCircuit {
+            out: circuit.out.clone(),
+            x: Some(FieldElement::new(4, modulus).unwrap()),
+        };
+        assert!(SNARK::prove_circuit(&wrong, &qap, &crs, &engine).is_err());
+
+        // Proofs must be made for the circuit the CRS was set up for.
+        assert_eq!(crs.circuit_digest, Some(cs.digest()));
+        assert_eq!(proof.circuit_digest, crs.circuit_digest);
+        let other_circuit = super::Proof::<ToyPairing> {
+            a: proof.a.clone(),
+            b: proof.b.clone(),
+            c: proof.c.clone(),
+            circuit_digest: Some([0; 32]),
+        };
+        assert!(!SNARK::verify_proof(&other_circuit, &crs, &engine).unwrap());
+        let witness = circuit.witness().unwrap();
+        let unbound = SNARK::create_proof(&qap, &witness, &crs, &engine).unwrap();
+        assert!(!SNARK::verify_proof(&unbound, &crs, &engine).unwrap());
+    }
+
+    #[test]
+    fn test_snark_over_tate_pairing() {
+        // x * x = y, proved for x = 5 with scalars in the scalar field of ToyBn.
+        type Fr = Fp<97>;
+        let engine = TatePairing::<ToyBn>::new();
+        let crs = SNARK::trusted_setup(&engine).unwrap();
+
+        let mut cs = ConstraintSystem::<Fr>::new();
+        let x = Variable::new(cs.allocate_variable(), &());
+        let y = Variable::new(cs.allocate_variable(), &());
+        r1cs!(cs; x * x == y);
+
+        let qap = QAP::create(&cs).unwrap();
+        let witness = vec![Fr::new(5), Fr::new(25)];
+        let proof = SNARK::create_proof(&qap, &witness, &crs, &engine).unwrap();
+        assert!(SNARK::verify_proof(&proof, &crs, &engine).unwrap());
+
+        // A mismatched B fails the check.
+        let forged = super::Proof::<TatePairing<ToyBn>> {
+            a: proof.a.clone(),
+            b: crs.g2.clone(),
+            c: proof.c.clone(),
+            circuit_digest: None,
+        };
+        assert!(!SNARK::verify_proof(&forged, &crs, &engine).unwrap());
     }
 }
@@ -0,0 +1,434 @@
+#[cfg(unix)]
+use std::{
+    fs::{File, OpenOptions},
+    os::fd::AsRawFd,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    circuit::R1CSConstraint,
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+/// Storage for the constraints of a `ConstraintSystem`.
+///
+/// Constraints are only appended and then read back in order, in passes, so they need
+/// not all be held in memory: a `QAP` borrows a store and reads it this way. A `Vec`
+/// keeps them in memory, and `FileConstraintStore` keeps them in a memory-mapped file.
+pub trait ConstraintStore<F: Field = FieldElement> {
+    /// Appends a constraint.
+    fn push(&mut self, constraint: R1CSConstraint<F>);
+
+    /// Returns the number of constraints.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no constraints.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Calls `visit` on each constraint in order, in one pass over the store, stopping
+    /// at the first error.
+    fn for_each<V>(&self, visit: V) -> Result<(), ZKError>
+    where
+        V: FnMut(&R1CSConstraint<F>) -> Result<(), ZKError>;
+}
+
+impl<F: Field> ConstraintStore<F> for Vec<R1CSConstraint<F>> {
+    fn push(&mut self, constraint: R1CSConstraint<F>) {
+        Vec::push(self, constraint);
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn for_each<V>(&self, visit: V) -> Result<(), ZKError>
+    where
+        V: FnMut(&R1CSConstraint<F>) -> Result<(), ZKError>,
+    {
+        self.iter().try_for_each(visit)
+    }
+}
+
+/// Keeps constraints in a memory-mapped file, each as its `R1CSConstraint::to_bytes`
+/// encoding prefixed with its length as a little-endian u64. Only the pages being
+/// written or read need be resident, and passes decode one constraint at a time.
+///
+/// The file grows by doubling, and is remapped when it does. A failed write is reported
+/// by the next pass over the store, since `push` cannot fail. When the store is dropped,
+/// the file is truncated to the constraints written and left in place.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct FileConstraintStore<F: Field = FieldElement> {
+    path: PathBuf,
+    params: F::Params,
+    file: File,
+    mapping: Mapping,
+    // Bytes of the mapping holding constraints.
+    written: usize,
+    len: usize,
+    // The first write error, if any.
+    error: Option<String>,
+}
+
+// Size of the file when the first constraint is written.
+#[cfg(unix)]
+const INITIAL_CAPACITY: usize = 1 << 16;
+
+#[cfg(unix)]
+impl<F: Field> FileConstraintStore<F> {
+    /// Creates an empty store in a new file at `path`, replacing an existing file. The
+    /// constraints are over the field described by `params`.
+    pub fn create(path: impl AsRef<Path>, params: &F::Params) -> Result<Self, ZKError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|e| ZKError::IoError(e.to_string()))?;
+        Ok(FileConstraintStore {
+            path,
+            params: params.clone(),
+            file,
+            mapping: Mapping::empty(),
+            written: 0,
+            len: 0,
+            error: None,
+        })
+    }
+
+    /// Returns the path of the file holding the constraints.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Grows the file and its mapping to hold at least `capacity` bytes.
+    fn reserve(&mut self, capacity: usize) -> std::io::Result<()> {
+        if capacity <= self.mapping.len {
+            return Ok(());
+        }
+        let capacity = capacity.max(2 * self.mapping.len).max(INITIAL_CAPACITY);
+        self.file.set_len(capacity as u64)?;
+        self.mapping = Mapping::new(&self.file, capacity)?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+impl<F: Field> ConstraintStore<F> for FileConstraintStore<F> {
+    fn push(&mut self, constraint: R1CSConstraint<F>) {
+        if self.error.is_some() {
+            return;
+        }
+        let bytes = constraint.to_bytes();
+        let end = self.written + 8 + bytes.len();
+        if let Err(e) = self.reserve(end) {
+            self.error = Some(e.to_string());
+            return;
+        }
+        let record = &mut self.mapping.as_mut_slice()[self.written..end];
+        record[..8].copy_from_slice(&(bytes.len() as u64).to_le_bytes());
+        record[8..].copy_from_slice(&bytes);
+        self.written = end;
+        self.len += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn for_each<V>(&self, mut visit: V) -> Result<(), ZKError>
+    where
+        V: FnMut(&R1CSConstraint<F>) -> Result<(), ZKError>,
+    {
+        if let Some(error) = &self.error {
+            return Err(ZKError::IoError(error.clone()));
+        }
+        let truncated = || ZKError::InvalidFormat("Constraint file is truncated.".into());
+
+        let mut rest = &self.mapping.as_slice()[..self.written];
+        for _ in 0..self.len {
+            let (length, tail) = rest.split_first_chunk::<8>().ok_or_else(truncated)?;
+            let length = usize::try_from(u64::from_le_bytes(*length))
+                .map_err(|_| ZKError::InvalidFormat("Constraint is too long.".into()))?;
+            if length > tail.len() {
+                return Err(truncated());
+            }
+            let (bytes, tail) = tail.split_at(length);
+            visit(&R1CSConstraint::from_bytes(bytes, &self.params)?)?;
+            rest = tail;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+impl<F: Field> Drop for FileConstraintStore<F> {
+    fn drop(&mut self) {
+        // Unmap first, then drop the unused capacity from the end of the file.
+        self.mapping = Mapping::empty();
+        let _ = self.file.set_len(self.written as u64);
+    }
+}
+
+// A shared, writable mapping of the start of a file, unmapped on drop.
+#[cfg(unix)]
+#[derive(Debug)]
+struct Mapping {
+    ptr: *mut u8,
+    len: usize,
+}
+
+// The mapping is owned by one store, and only written through `&mut` access to it.
+#[cfg(unix)]
+unsafe impl Send for Mapping {}
+#[cfg(unix)]
+unsafe impl Sync for Mapping {}
+
+#[cfg(unix)]
+impl Mapping {
+    fn empty() -> Self {
+        Mapping {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    // Maps the first `len` bytes of `file`, which must be at least that long.
+    fn new(file: &File, len: usize) -> std::io::Result<Self> {
+        // SAFETY: a fresh mapping chosen by the kernel does not alias any Rust memory.
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Mapping {
+            ptr: ptr.cast(),
+            len,
+        })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        // SAFETY: `ptr` points to `len` mapped bytes that live as long as `self`.
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        if self.len == 0 {
+            return &mut [];
+        }
+        // SAFETY: as above, and `&mut self` makes this the only view of the mapping.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            // SAFETY: `ptr` and `len` describe a mapping made by `Mapping::new`.
+            unsafe {
+                libc::munmap(self.ptr.cast(), self.len);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        fs::{self, OpenOptions},
+        os::unix::fs::FileExt,
+        path::PathBuf,
+    };
+
+    use crate::{
+        circuit::{ConstraintSystem, R1CSConstraint, Variable},
+        errors::ZKError,
+        field::FieldElement,
+        qap::QAP,
+        r1cs,
+    };
+
+    use super::{ConstraintStore, FileConstraintStore, INITIAL_CAPACITY};
+
+    // A path in the temporary directory, unique to the test and process, whose file is
+    // deleted on drop, so it is cleaned up even when the test fails.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(test: &str) -> Self {
+            TempFile(std::env::temp_dir().join(format!(
+                "zksfs-store-{}-{}.bin",
+                test,
+                std::process::id()
+            )))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    // Lays out x * y = z and (x + y) * 2 = w, with x public.
+    fn build<S: ConstraintStore>(cs: &mut ConstraintSystem<FieldElement, S>, modulus: u64) {
+        let one = Variable::new(cs.allocate_public_input().unwrap(), &modulus);
+        let x = Variable::new(cs.allocate_public_input().unwrap(), &modulus);
+        let y = Variable::new(cs.allocate_private_witness(), &modulus);
+        let z = Variable::new(cs.allocate_private_witness(), &modulus);
+        let w = Variable::new(cs.allocate_private_witness(), &modulus);
+        let two = FieldElement::new(2, modulus).unwrap();
+        r1cs!(cs;
+            x * y == z;
+            (x + y) * (one * two) == w;
+        );
+    }
+
+    #[test]
+    fn test_file_store() {
+        let modulus = 97;
+        let file = TempFile::new("file-store");
+        let store = FileConstraintStore::create(&file.0, &modulus).unwrap();
+        assert!(store.is_empty());
+        let mut on_disk = ConstraintSystem::with_store(store);
+        build(&mut on_disk, modulus);
+        let mut in_memory = ConstraintSystem::new();
+        build(&mut in_memory, modulus);
+
+        // The constraints read back are the ones added.
+        assert_eq!(on_disk.constraints.len(), 2);
+        let mut read = Vec::new();
+        on_disk
+            .constraints
+            .for_each(|constraint| {
+                read.push(constraint.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(read, in_memory.constraints);
+        assert!(on_disk.validate().is_ok());
+
+        // Both stores give the same QAP, which a satisfying witness divides.
+        let file_qap = QAP::create(&on_disk).unwrap();
+        let memory_qap = QAP::create(&in_memory).unwrap();
+        assert_eq!(
+            file_qap.a_polynomials().unwrap(),
+            memory_qap.a_polynomials().unwrap()
+        );
+        assert_eq!(
+            file_qap.b_polynomials().unwrap(),
+            memory_qap.b_polynomials().unwrap()
+        );
+        assert_eq!(
+            file_qap.c_polynomials().unwrap(),
+            memory_qap.c_polynomials().unwrap()
+        );
+        assert_eq!(file_qap.target_polynomial, memory_qap.target_polynomial);
+        let witness: Vec<FieldElement> = [1, 3, 4, 12, 14]
+            .iter()
+            .map(|&v| FieldElement::new(v, modulus).unwrap())
+            .collect();
+        assert!(file_qap.calculate_witness_quotient(&witness).is_ok());
+
+        // Constraints added after a pass are appended.
+        let x = Variable::new(1, &modulus);
+        on_disk.enforce(x, x, x);
+        let mut count = 0;
+        on_disk
+            .constraints
+            .for_each(|_| {
+                count += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count, 3);
+
+        // A term on an unallocated variable is caught while reading the file.
+        let y = Variable::new(9, &modulus);
+        on_disk.enforce(y, y, y);
+        assert!(on_disk.validate().is_err());
+        assert!(QAP::create(&on_disk).is_err());
+        assert_eq!(on_disk.constraints.path(), file.0);
+    }
+
+    #[test]
+    fn test_file_store_changed_under_qap() {
+        let modulus = 97;
+        let file = TempFile::new("changed");
+        let store = FileConstraintStore::create(&file.0, &modulus).unwrap();
+        let mut cs = ConstraintSystem::with_store(store);
+        build(&mut cs, modulus);
+        let qap = QAP::create(&cs).unwrap();
+
+        // Overwrite x * y = z, in place, with a constraint of the same size on a variable
+        // the QAP does not have.
+        let v = Variable::<FieldElement>::new(9, &modulus);
+        let constraint = R1CSConstraint::new(v.into(), v.into(), v.into());
+        OpenOptions::new()
+            .write(true)
+            .open(&file.0)
+            .unwrap()
+            .write_all_at(&constraint.to_bytes(), 8)
+            .unwrap();
+
+        let tau = FieldElement::new(5, modulus).unwrap();
+        assert!(matches!(
+            qap.evaluate_at(&tau),
+            Err(ZKError::PolynomialError(_))
+        ));
+        assert!(matches!(
+            qap.a_polynomials(),
+            Err(ZKError::PolynomialError(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_store_grows() {
+        let modulus = 97;
+        let file = TempFile::new("grows");
+        let mut store = FileConstraintStore::<FieldElement>::create(&file.0, &modulus).unwrap();
+        let x = Variable::new(1, &modulus);
+        let constraint = R1CSConstraint::new(x.into(), x.into(), x.into());
+        let record = 8 + constraint.to_bytes().len();
+
+        // Enough constraints to remap the file several times.
+        let count = 4 * INITIAL_CAPACITY / record;
+        for _ in 0..count {
+            store.push(constraint.clone());
+        }
+        assert_eq!(store.len(), count);
+        let mut read = 0;
+        store
+            .for_each(|c| {
+                assert_eq!(c, &constraint);
+                read += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(read, count);
+
+        // Dropping the store trims the file to the constraints written.
+        drop(store);
+        assert_eq!(
+            fs::metadata(&file.0).unwrap().len(),
+            (count * record) as u64
+        );
+    }
+}
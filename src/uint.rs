@@ -0,0 +1,418 @@
+use crate::{
+    builder::{CircuitBuilder, Hint, Var},
+    circuit::LinearCombination,
+    errors::ZKError,
+    field::Field,
+};
+
+/// An unsigned integer of `BITS` bits inside a circuit, stored as boolean variables,
+/// least significant bit first.
+///
+/// Arithmetic wraps modulo 2^BITS like the machine integers it models, and reports the
+/// carry or borrow. Sums are recomposed in chunks of columns small enough not to wrap
+/// around the modulus, so the gadgets work over any field, even one smaller than 2^BITS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UInt<const BITS: usize> {
+    bits: Vec<Var>,
+}
+
+/// A 32-bit unsigned integer.
+pub type UInt32 = UInt<32>;
+
+/// A 64-bit unsigned integer.
+pub type UInt64 = UInt<64>;
+
+impl<const BITS: usize> UInt<BITS> {
+    // Evaluated once per width, so widths that do not fit in a u64 fail to compile.
+    const SUPPORTED: () = assert!(BITS > 0 && BITS <= 64, "Width must be 1 to 64 bits.");
+
+    // The bits must already be constrained to be boolean.
+    pub(crate) fn from_bits(bits: Vec<Var>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::SUPPORTED;
+        UInt { bits }
+    }
+
+    /// Allocates an integer whose bits are private inputs, least significant first, and
+    /// constrains each to be boolean.
+    pub fn alloc<F: Field>(builder: &mut CircuitBuilder<F>) -> Self {
+        let bits = (0..BITS)
+            .map(|_| {
+                let bit = builder.private_witness();
+                builder.enforce_boolean(bit);
+                bit
+            })
+            .collect();
+        Self::from_bits(bits)
+    }
+
+    /// Decomposes `var` into an integer, proving that it lies in [0, 2^BITS). Fails if
+    /// the field cannot hold `BITS`-bit values.
+    pub fn from_var<F: Field>(builder: &mut CircuitBuilder<F>, var: Var) -> Result<Self, ZKError> {
+        Ok(Self::from_bits(builder.to_bits(var, BITS)?))
+    }
+
+    /// Returns the constant `value`, truncated to `BITS` bits.
+    pub fn constant<F: Field>(builder: &mut CircuitBuilder<F>, value: u64) -> Self {
+        let zero = builder.constant(F::zero(&builder.params));
+        let bits = (0..BITS)
+            .map(|i| {
+                if (value >> i) & 1 == 1 {
+                    builder.one()
+                } else {
+                    zero
+                }
+            })
+            .collect();
+        Self::from_bits(bits)
+    }
+
+    /// Returns the bits, least significant first.
+    pub fn bits(&self) -> &[Var] {
+        &self.bits
+    }
+
+    /// Packs the bits into a single variable. Fails if the field cannot hold `BITS`-bit
+    /// values.
+    pub fn to_var<F: Field>(&self, builder: &mut CircuitBuilder<F>) -> Result<Var, ZKError> {
+        if BITS > builder.capacity() {
+            return Err(ZKError::CircuitError(format!(
+                "Cannot pack {} bits; the field holds only {}.",
+                BITS,
+                builder.capacity()
+            )));
+        }
+        let packed = weighted_sum(builder, &self.bits)?;
+        let var = builder.allocate(Hint::Linear(packed.clone()));
+        builder.emit(packed, builder.single(builder.one()), builder.single(var));
+        Ok(var)
+    }
+
+    /// Reads the value of the integer from a witness.
+    pub fn value<F: Field>(&self, witness: &[F]) -> Result<u64, ZKError> {
+        self.bits.iter().enumerate().try_fold(0, |value, (i, bit)| {
+            let bit = witness.get(bit.index()).ok_or_else(|| {
+                ZKError::CircuitError(format!("Witness has no variable {}.", bit.index()))
+            })?;
+            Ok(value | (bit.is_one() as u64) << i)
+        })
+    }
+
+    /// Returns (self + other) mod 2^BITS and the carry out of the top bit.
+    pub fn overflowing_add<F: Field>(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        other: &Self,
+    ) -> Result<(Self, Var), ZKError> {
+        let columns = (0..BITS)
+            .map(|i| vec![builder.single(self.bits[i]), builder.single(other.bits[i])])
+            .collect();
+        let (bits, carry) = sum_columns(builder, columns)?;
+        Ok((Self::from_bits(bits), carry[0]))
+    }
+
+    /// Returns (self − other) mod 2^BITS and the borrow, which is 1 if other > self.
+    pub fn overflowing_sub<F: Field>(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        other: &Self,
+    ) -> Result<(Self, Var), ZKError> {
+        // self − other = self + !other + 1 − 2^BITS, so the carry is the complement of
+        // the borrow.
+        let one = F::one(&builder.params);
+        let mut columns: Vec<Vec<LinearCombination<F>>> = (0..BITS)
+            .map(|i| {
+                let complement =
+                    builder.lc(&[(builder.one(), one.clone()), (other.bits[i], one.neg())]);
+                vec![builder.single(self.bits[i]), complement]
+            })
+            .collect();
+        columns[0].push(builder.single(builder.one()));
+        let (bits, carry) = sum_columns(builder, columns)?;
+        let borrow = builder.not(carry[0]);
+        Ok((Self::from_bits(bits), borrow))
+    }
+
+    /// Returns (self + operands[0] + operands[1] + ...) mod 2^BITS. Adding many words
+    /// at once shares the carry propagation, so it is cheaper than repeated additions.
+    pub fn wrapping_sum<F: Field>(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        operands: &[&Self],
+    ) -> Result<Self, ZKError> {
+        let columns = (0..BITS)
+            .map(|i| {
+                std::iter::once(self)
+                    .chain(operands.iter().copied())
+                    .map(|operand| builder.single(operand.bits[i]))
+                    .collect()
+            })
+            .collect();
+        Ok(Self::from_bits(sum_columns(builder, columns)?.0))
+    }
+
+    /// Returns self XOR other, with one constraint per bit: 2a · b = a + b − c.
+    pub fn xor<F: Field>(&self, builder: &mut CircuitBuilder<F>, other: &Self) -> Self {
+        let one = F::one(&builder.params);
+        let two = F::from_u64(2, &builder.params);
+        let bits = self
+            .bits
+            .iter()
+            .zip(&other.bits)
+            .map(|(&a, &b)| {
+                let c = builder.allocate(Hint::Xor(a, b));
+                let sum = builder.lc(&[(a, one.clone()), (b, one.clone()), (c, one.neg())]);
+                builder.emit(builder.lc(&[(a, two.clone())]), builder.single(b), sum);
+                c
+            })
+            .collect();
+        Self::from_bits(bits)
+    }
+
+    /// Returns (self · other) mod 2^BITS.
+    ///
+    /// Every product of bits below the top is a column entry, BITS (BITS + 1) / 2 in all;
+    /// the column sums then propagate carries.
+    pub fn wrapping_mul<F: Field>(
+        &self,
+        builder: &mut CircuitBuilder<F>,
+        other: &Self,
+    ) -> Result<Self, ZKError> {
+        let mut columns = vec![Vec::new(); BITS];
+        for (j, &b) in other.bits.iter().enumerate() {
+            for (i, &a) in self.bits.iter().take(BITS - j).enumerate() {
+                let product = builder.mul(a, b);
+                columns[i + j].push(builder.single(product));
+            }
+        }
+        Ok(Self::from_bits(sum_columns(builder, columns)?.0))
+    }
+
+    /// Rotates the bits left by `n`, towards the most significant bit. Costs nothing.
+    pub fn rotate_left(&self, n: usize) -> Self {
+        let mut bits = self.bits.clone();
+        bits.rotate_right(n % BITS);
+        Self::from_bits(bits)
+    }
+
+    /// Rotates the bits right by `n`, towards the least significant bit. Costs nothing.
+    pub fn rotate_right(&self, n: usize) -> Self {
+        let mut bits = self.bits.clone();
+        bits.rotate_left(n % BITS);
+        Self::from_bits(bits)
+    }
+
+    /// Returns self << n, dropping the bits shifted out.
+    pub fn shl<F: Field>(&self, builder: &mut CircuitBuilder<F>, n: usize) -> Self {
+        let zero = builder.constant(F::zero(&builder.params));
+        let n = n.min(BITS);
+        let bits = std::iter::repeat_n(zero, n)
+            .chain(self.bits[..BITS - n].iter().copied())
+            .collect();
+        Self::from_bits(bits)
+    }
+
+    /// Returns self >> n, dropping the bits shifted out.
+    pub fn shr<F: Field>(&self, builder: &mut CircuitBuilder<F>, n: usize) -> Self {
+        let zero = builder.constant(F::zero(&builder.params));
+        let n = n.min(BITS);
+        let bits = self.bits[n..]
+            .iter()
+            .copied()
+            .chain(std::iter::repeat_n(zero, n))
+            .collect();
+        Self::from_bits(bits)
+    }
+}
+
+// Returns sum 2^i · bits[i].
+fn weighted_sum<F: Field>(
+    builder: &CircuitBuilder<F>,
+    bits: &[Var],
+) -> Result<LinearCombination<F>, ZKError> {
+    let mut terms = Vec::with_capacity(bits.len());
+    let mut weight = F::one(&builder.params);
+    for &bit in bits {
+        terms.push((bit, weight.clone()));
+        weight = weight.add(&weight)?;
+    }
+    Ok(builder.lc(&terms))
+}
+
+// Adds up columns of boolean linear combinations, where column k carries weight 2^k, and
+// returns one result bit per column together with the bits of the final carry.
+//
+// Consecutive columns are grouped into chunks whose largest possible sum, including the
+// carry from the previous chunk, still fits in the capacity of the field. Each chunk sum
+// is decomposed into bits: the low bits are the results for its columns, and the rest
+// form the carry into the next chunk. A column too tall to fit on its own is first
+// compressed: some of its entries are replaced by the bits of their sum, spread over
+// this and the following columns.
+fn sum_columns<F: Field>(
+    builder: &mut CircuitBuilder<F>,
+    mut columns: Vec<Vec<LinearCombination<F>>>,
+) -> Result<(Vec<Var>, Vec<Var>), ZKError> {
+    let capacity = builder.capacity();
+    let limit = 1u128
+        .checked_shl(capacity as u32)
+        .map_or(u128::MAX, |power| power - 1);
+    let bit_length = |bound: u128| bound.checked_ilog2().map_or(0, |log| log + 1) as usize;
+    let one = F::one(&builder.params);
+    let num_columns = columns.len();
+
+    let mut result = Vec::with_capacity(num_columns);
+    let mut carry: Vec<Var> = Vec::new();
+    let mut carry_bound: u128 = 0;
+    let mut start = 0;
+    while start < columns.len() {
+        while columns[start].len() as u128 + carry_bound > limit {
+            // The carry is below 2^(capacity - 1), so at least two entries are taken.
+            if limit < 3 {
+                return Err(ZKError::CircuitError(
+                    "The field is too small to add up a single column.".into(),
+                ));
+            }
+            let height = columns[start].len();
+            let take = height.min(limit as usize);
+            let entries = columns[start].split_off(height - take);
+            let sum = entries
+                .into_iter()
+                .fold(LinearCombination::new(), |sum, entry| sum + entry);
+            let partial = builder.allocate(Hint::Linear(sum.clone()));
+            builder.emit(sum, builder.single(builder.one()), builder.single(partial));
+            let bits = builder.to_bits(partial, bit_length(take as u128))?;
+            if columns.len() < start + bits.len() {
+                columns.resize(start + bits.len(), Vec::new());
+            }
+            for (j, bit) in bits.into_iter().enumerate() {
+                columns[start + j].push(builder.single(bit));
+            }
+        }
+
+        // Grow the chunk while its largest sum fits.
+        let mut end = start;
+        let mut bound = carry_bound;
+        while end < columns.len() && end - start < 64 {
+            let extended = bound + ((columns[end].len() as u128) << (end - start));
+            if extended > limit {
+                break;
+            }
+            bound = extended;
+            end += 1;
+        }
+
+        let mut sum = weighted_sum(builder, &carry)?;
+        let mut weight = one.clone();
+        for column in &columns[start..end] {
+            for entry in column {
+                sum = sum + entry.clone() * weight.clone();
+            }
+            weight = weight.add(&weight)?;
+        }
+        let chunk = builder.allocate(Hint::Linear(sum.clone()));
+        builder.emit(sum, builder.single(builder.one()), builder.single(chunk));
+
+        let mut bits = builder.to_bits(chunk, bit_length(bound).max(end - start))?;
+        carry = bits.split_off(end - start);
+        carry_bound = bound >> (end - start);
+        result.extend(bits);
+        start = end;
+    }
+
+    // Columns added by compression hold overflow, which belongs to the carry.
+    let overflow = result.split_off(num_columns.min(result.len()));
+    Ok((result, [overflow, carry].concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field::FieldElement, goldilocks::Goldilocks};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn bits<F: Field>(value: u64, width: usize, params: &F::Params) -> Vec<F> {
+        (0..width)
+            .map(|i| F::from_u64((value >> i) & 1, params))
+            .collect()
+    }
+
+    #[test]
+    fn test_u32_arithmetic() {
+        let mut builder = CircuitBuilder::<Goldilocks>::new(&()).unwrap();
+        let x = builder.private_witness();
+        let y = builder.private_witness();
+        let a = UInt32::from_var(&mut builder, x).unwrap();
+        let b = UInt32::from_var(&mut builder, y).unwrap();
+        let (sum, carry) = a.overflowing_add(&mut builder, &b).unwrap();
+        let (difference, borrow) = a.overflowing_sub(&mut builder, &b).unwrap();
+        let product = a.wrapping_mul(&mut builder, &b).unwrap();
+        let xor = a.xor(&mut builder, &b);
+        let total = a.wrapping_sum(&mut builder, &[&b, &b, &a]).unwrap();
+        let rotated = a.rotate_left(7).rotate_right(3);
+        let shifted = (a.shl(&mut builder, 5), a.shr(&mut builder, 9));
+        let packed = sum.to_var(&mut builder).unwrap();
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let cases = [(0, 0), (u32::MAX, 1), (1, u32::MAX), (u32::MAX, u32::MAX)];
+        let random = (0..8).map(|_| (rng.random::<u32>(), rng.random::<u32>()));
+        for (p, q) in cases.into_iter().chain(random) {
+            let inputs = [Goldilocks::new(p as u64), Goldilocks::new(q as u64)];
+            let witness = builder.generate_witness(&[], &inputs).unwrap();
+            let (s, c) = p.overflowing_add(q);
+            let (d, w) = p.overflowing_sub(q);
+            assert_eq!(sum.value(&witness).unwrap(), s as u64);
+            assert_eq!(witness[carry.index()], Goldilocks::new(c as u64));
+            assert_eq!(difference.value(&witness).unwrap(), d as u64);
+            assert_eq!(witness[borrow.index()], Goldilocks::new(w as u64));
+            assert_eq!(product.value(&witness).unwrap(), p.wrapping_mul(q) as u64);
+            assert_eq!(xor.value(&witness).unwrap(), (p ^ q) as u64);
+            let expected = p.wrapping_add(q).wrapping_mul(2);
+            assert_eq!(total.value(&witness).unwrap(), expected as u64);
+            assert_eq!(rotated.value(&witness).unwrap(), p.rotate_left(4) as u64);
+            assert_eq!(shifted.0.value(&witness).unwrap(), (p << 5) as u64);
+            assert_eq!(shifted.1.value(&witness).unwrap(), (p >> 9) as u64);
+            assert_eq!(witness[packed.index()], Goldilocks::new(s as u64));
+        }
+
+        // Values outside the range of a u32 are rejected.
+        let inputs = [Goldilocks::new(1 << 32), Goldilocks::new(0)];
+        assert!(builder.generate_witness(&[], &inputs).is_err());
+    }
+
+    #[test]
+    fn test_u64_in_a_small_field() {
+        // 64-bit words over F_97: every column sum is split into chunks of a few bits.
+        let mut builder = CircuitBuilder::<FieldElement>::new(&97).unwrap();
+        let a = UInt64::alloc(&mut builder);
+        let b = UInt64::alloc(&mut builder);
+        let constant = UInt64::constant(&mut builder, 0xdead_beef_0123_4567);
+        let (sum, carry) = a.overflowing_add(&mut builder, &constant).unwrap();
+        let (difference, borrow) = a.overflowing_sub(&mut builder, &b).unwrap();
+        let product = a.wrapping_mul(&mut builder, &b).unwrap();
+        assert!(a.to_var(&mut builder).is_err());
+        let one = builder.one();
+        assert!(UInt64::from_var(&mut builder, one).is_err());
+
+        let mut rng = StdRng::seed_from_u64(12);
+        for (p, q) in [
+            (u64::MAX, u64::MAX),
+            (rng.random(), rng.random()),
+            (3, 1 << 40),
+        ] {
+            let inputs = [bits(p, 64, &97), bits(q, 64, &97)].concat();
+            let witness = builder.generate_witness(&[], &inputs).unwrap();
+            let (s, c) = p.overflowing_add(0xdead_beef_0123_4567);
+            let (d, w) = p.overflowing_sub(q);
+            assert_eq!(sum.value(&witness).unwrap(), s);
+            assert_eq!(witness[carry.index()].value, c as u64);
+            assert_eq!(difference.value(&witness).unwrap(), d);
+            assert_eq!(witness[borrow.index()].value, w as u64);
+            assert_eq!(product.value(&witness).unwrap(), p.wrapping_mul(q));
+        }
+
+        // Bits must be boolean.
+        let mut inputs = [bits(5, 64, &97), bits(0, 64, &97)].concat();
+        inputs[0] = FieldElement::new(2, 97).unwrap();
+        assert!(builder.generate_witness(&[], &inputs).is_err());
+    }
+}
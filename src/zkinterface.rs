@@ -0,0 +1,666 @@
+use crate::{
+    circuit::{ConstraintSystem, LinearCombination, R1CSConstraint, Term},
+    errors::ZKError,
+    field::{Field, FieldElement},
+};
+
+// Every zkInterface message is a size-prefixed flatbuffer with this identifier.
+const FILE_IDENTIFIER: &[u8; 4] = b"zkif";
+// Members of the `Message` union.
+const CIRCUIT_HEADER: u8 = 1;
+const CONSTRAINT_SYSTEM: u8 = 2;
+const WITNESS: u8 = 3;
+
+/// A circuit read from zkInterface messages.
+///
+/// zkInterface reserves variable 0 for the constant one. The constraint system numbers
+/// it 0, followed by the instance variables of the circuit header as its public inputs,
+/// and then the remaining variables in increasing order of their zkInterface ids.
+#[derive(Clone, Debug)]
+pub struct ZkInterfaceCircuit {
+    pub cs: ConstraintSystem,
+    pub modulus: u64,
+    /// The full witness vector, present if the messages included a witness.
+    pub witness: Option<Vec<FieldElement>>,
+}
+
+/// Writes a constraint system as zkInterface messages: a circuit header, the
+/// constraints and, if `witness` is given, the witness. Like in zkInterface, variable 0
+/// must be the constant one, so the system needs at least one public input.
+pub fn write_zkinterface(
+    cs: &ConstraintSystem,
+    modulus: u64,
+    witness: Option<&[FieldElement]>,
+) -> Result<Vec<u8>, ZKError> {
+    FieldElement::validate_params(&modulus)?;
+    if cs.num_public == 0 {
+        return Err(ZKError::CircuitError(
+            "Variable 0 must be the public constant one.".into(),
+        ));
+    }
+    if let Some(witness) = witness {
+        cs.split_witness(witness)?;
+    }
+    let encode = |elements: &[FieldElement]| -> Result<Vec<u8>, ZKError> {
+        let mut bytes = Vec::with_capacity(8 * elements.len());
+        for element in elements {
+            if element.modulus != modulus {
+                return Err(ZKError::InvalidFieldElement(format!(
+                    "Expected an element modulo {}, got one modulo {}.",
+                    modulus, element.modulus
+                )));
+            }
+            bytes.extend(element.to_bytes());
+        }
+        Ok(bytes)
+    };
+    let variables = |ids: Vec<u64>, values: Option<Vec<u8>>| {
+        Object::Table(vec![
+            Some(Slot::Ref(Object::U64s(ids))),
+            values.map(|values| Slot::Ref(Object::Bytes(values))),
+        ])
+    };
+    let range = |start: usize, end: usize| (start as u64..end as u64).collect();
+    let values = |start: usize, end: usize| witness.map(|w| encode(&w[start..end])).transpose();
+    let lc = |lc: &LinearCombination| -> Result<Object, ZKError> {
+        let ids = lc.terms.iter().map(|term| term.index as u64).collect();
+        let coefficients: Vec<_> = lc.terms.iter().map(|t| t.coefficient.clone()).collect();
+        Ok(variables(ids, Some(encode(&coefficients)?)))
+    };
+
+    let header = Object::Table(vec![
+        Some(Slot::Ref(variables(
+            range(1, cs.num_public),
+            values(1, cs.num_public)?,
+        ))),
+        Some(Slot::U64(cs.num_variables as u64)),
+        Some(Slot::Ref(Object::Bytes(
+            (modulus - 1).to_le_bytes().to_vec(),
+        ))),
+    ]);
+    let mut bytes = write_message(CIRCUIT_HEADER, header);
+
+    let constraints = cs
+        .constraints
+        .iter()
+        .map(|constraint| {
+            Ok(Object::Table(vec![
+                Some(Slot::Ref(lc(&constraint.a)?)),
+                Some(Slot::Ref(lc(&constraint.b)?)),
+                Some(Slot::Ref(lc(&constraint.c)?)),
+            ]))
+        })
+        .collect::<Result<_, ZKError>>()?;
+    let constraints = Object::Table(vec![Some(Slot::Ref(Object::Tables(constraints)))]);
+    bytes.extend(write_message(CONSTRAINT_SYSTEM, constraints));
+
+    if witness.is_some() {
+        let assigned = variables(
+            range(cs.num_public, cs.num_variables),
+            values(cs.num_public, cs.num_variables)?,
+        );
+        let witness = Object::Table(vec![Some(Slot::Ref(assigned))]);
+        bytes.extend(write_message(WITNESS, witness));
+    }
+    Ok(bytes)
+}
+
+/// Reads a circuit from a sequence of zkInterface messages. There must be exactly one
+/// circuit header, whose field must have a prime below 2^64; constraints may be split
+/// over several messages. Other messages, such as commands, are skipped.
+pub fn read_zkinterface(bytes: &[u8]) -> Result<ZkInterfaceCircuit, ZKError> {
+    let mut header = None;
+    let mut constraints = Vec::new();
+    let mut assigned = None;
+
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let size = Buffer(rest).u32(0)? as usize;
+        let length = size
+            .checked_add(4)
+            .filter(|&length| length <= rest.len())
+            .ok_or_else(|| ZKError::InvalidFormat("Truncated message.".into()))?;
+        let (message, next) = rest.split_at(length);
+        rest = next;
+
+        let buffer = Buffer(message);
+        if buffer.slice(8, 4)? != FILE_IDENTIFIER {
+            return Err(ZKError::InvalidFormat(
+                "Message is not a zkInterface message.".into(),
+            ));
+        }
+        let root = buffer.reference(4)?;
+        let Some(message_type) = buffer.field(root, 0)? else {
+            continue;
+        };
+        let Some(table) = buffer.table_field(root, 1)? else {
+            continue;
+        };
+        match buffer.u8(message_type)? {
+            CIRCUIT_HEADER => {
+                if header.is_some() {
+                    return Err(ZKError::InvalidFormat("Repeated circuit header.".into()));
+                }
+                let instance = match buffer.table_field(table, 0)? {
+                    Some(variables) => buffer.variables(variables)?,
+                    None => RawVariables::default(),
+                };
+                let free_variable_id = match buffer.field(table, 1)? {
+                    Some(position) => buffer.u64(position)?,
+                    None => 0,
+                };
+                let field_maximum = match buffer.vector_field(table, 2, 1)? {
+                    Some((start, len)) => buffer.slice(start, len)?.to_vec(),
+                    None => Vec::new(),
+                };
+                header = Some((instance, free_variable_id, field_maximum));
+            }
+            CONSTRAINT_SYSTEM => {
+                if let Some((start, len)) = buffer.vector_field(table, 0, 4)? {
+                    for i in 0..len {
+                        let constraint = buffer.reference(start + 4 * i)?;
+                        let mut sides = [0, 1, 2].map(|_| RawVariables::default());
+                        for (slot, side) in sides.iter_mut().enumerate() {
+                            if let Some(variables) = buffer.table_field(constraint, slot)? {
+                                *side = buffer.variables(variables)?;
+                            }
+                        }
+                        constraints.push(sides);
+                    }
+                }
+            }
+            WITNESS => {
+                if assigned.is_some() {
+                    return Err(ZKError::InvalidFormat("Repeated witness.".into()));
+                }
+                assigned = Some(match buffer.table_field(table, 0)? {
+                    Some(variables) => buffer.variables(variables)?,
+                    None => RawVariables::default(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let (instance, free_variable_id, field_maximum) =
+        header.ok_or_else(|| ZKError::InvalidFormat("Missing circuit header.".into()))?;
+    let modulus = read_u64(&field_maximum)?
+        .checked_add(1)
+        .ok_or_else(|| ZKError::InvalidFormat("Field prime does not fit in 64 bits.".into()))?;
+    FieldElement::validate_params(&modulus)?;
+
+    // Number the constant one, then the instance variables, then everything else.
+    let num_variables = usize::try_from(free_variable_id)
+        .map_err(|_| ZKError::InvalidFormat("Too many variables.".into()))?;
+    if num_variables == 0 {
+        return Err(ZKError::InvalidFormat(
+            "Circuit has no constant one variable.".into(),
+        ));
+    }
+    // The header is untrusted and sizes the tables below. A circuit's variables appear in
+    // its messages, so one with more variables than the input has bytes is malformed.
+    if num_variables > bytes.len() {
+        return Err(ZKError::InvalidFormat(format!(
+            "Free variable id {} is too large for a message of {} bytes.",
+            free_variable_id,
+            bytes.len()
+        )));
+    }
+    let mut indices = vec![None; num_variables];
+    indices[0] = Some(0);
+    let mut cs = ConstraintSystem::new();
+    cs.allocate_public_input()?;
+    for &id in &instance.ids {
+        let slot = indices
+            .get_mut(id as usize)
+            .filter(|slot| slot.is_none())
+            .ok_or_else(|| ZKError::InvalidFormat(format!("Invalid instance variable {}.", id)))?;
+        *slot = Some(cs.allocate_public_input()?);
+    }
+    for slot in indices.iter_mut().filter(|slot| slot.is_none()) {
+        *slot = Some(cs.allocate_private_witness());
+    }
+    let index = |id: u64| {
+        indices.get(id as usize).copied().flatten().ok_or_else(|| {
+            ZKError::InvalidFormat(format!(
+                "Variable {} is not below the free variable id {}.",
+                id, free_variable_id
+            ))
+        })
+    };
+
+    for [a, b, c] in &constraints {
+        let lc = |variables: &RawVariables| -> Result<LinearCombination, ZKError> {
+            let mut lc = LinearCombination::new();
+            for (&id, coefficient) in variables.ids.iter().zip(variables.elements(modulus)?) {
+                lc.add_term(Term {
+                    index: index(id)?,
+                    coefficient,
+                });
+            }
+            Ok(lc)
+        };
+        cs.add_constraint(R1CSConstraint::new(lc(a)?, lc(b)?, lc(c)?));
+    }
+
+    let witness = match assigned {
+        None => None,
+        Some(assigned) => {
+            let mut witness = vec![None; num_variables];
+            witness[0] = Some(FieldElement::new(1, modulus)?);
+            for variables in [&instance, &assigned] {
+                for (&id, value) in variables.ids.iter().zip(variables.elements(modulus)?) {
+                    witness[index(id)?] = Some(value);
+                }
+            }
+            let witness = witness
+                .into_iter()
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    ZKError::InvalidFormat("Witness does not assign every variable.".into())
+                })?;
+            Some(witness)
+        }
+    };
+
+    Ok(ZkInterfaceCircuit {
+        cs,
+        modulus,
+        witness,
+    })
+}
+
+// Reads a little-endian integer of any width that must fit in 64 bits.
+fn read_u64(bytes: &[u8]) -> Result<u64, ZKError> {
+    if bytes.iter().skip(8).any(|&byte| byte != 0) {
+        return Err(ZKError::FieldElementOutOfRange(
+            "Value does not fit in 64 bits.".into(),
+        ));
+    }
+    let mut low = [0; 8];
+    let n = bytes.len().min(8);
+    low[..n].copy_from_slice(&bytes[..n]);
+    Ok(u64::from_le_bytes(low))
+}
+
+// A `Variables` table: variable ids with optional values of equal byte width.
+#[derive(Default)]
+struct RawVariables {
+    ids: Vec<u64>,
+    values: Vec<u8>,
+}
+
+impl RawVariables {
+    // Returns the values as field elements. Fails if there are no values.
+    fn elements(&self, modulus: u64) -> Result<Vec<FieldElement>, ZKError> {
+        if self.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.values.is_empty() || !self.values.len().is_multiple_of(self.ids.len()) {
+            return Err(ZKError::InvalidFormat(format!(
+                "{} bytes of values do not match {} variables.",
+                self.values.len(),
+                self.ids.len()
+            )));
+        }
+        let width = self.values.len() / self.ids.len();
+        self.values
+            .chunks(width)
+            .map(|chunk| FieldElement::from_bytes(&read_u64(chunk)?.to_le_bytes(), &modulus))
+            .collect()
+    }
+}
+
+// A flatbuffer object to be written: a table with optional fields, or a vector.
+enum Object {
+    Table(Vec<Option<Slot>>),
+    Bytes(Vec<u8>),
+    U64s(Vec<u64>),
+    Tables(Vec<Object>),
+}
+
+// A field of a table: a scalar, or a reference to another object.
+enum Slot {
+    U8(u8),
+    U64(u64),
+    Ref(Object),
+}
+
+impl Slot {
+    fn size(&self) -> usize {
+        match self {
+            Slot::U8(_) => 1,
+            Slot::U64(_) => 8,
+            Slot::Ref(_) => 4,
+        }
+    }
+}
+
+// Writes `message` as a size-prefixed zkInterface flatbuffer.
+fn write_message(message_type: u8, message: Object) -> Vec<u8> {
+    let root = Object::Table(vec![Some(Slot::U8(message_type)), Some(Slot::Ref(message))]);
+    // Size prefix, root offset and file identifier.
+    let mut builder = Builder(vec![0; 12]);
+    builder.0[8..].copy_from_slice(FILE_IDENTIFIER);
+    let root = builder.object(&root);
+    builder.patch(4, root);
+    builder.pad(8, 0);
+    let size = (builder.0.len() - 4) as u32;
+    builder.0[..4].copy_from_slice(&size.to_le_bytes());
+    builder.0
+}
+
+// Lays out flatbuffer objects front to back, so every reference points forward. Each
+// table is preceded by its vtable, and data is aligned to its size.
+struct Builder(Vec<u8>);
+
+impl Builder {
+    // Pads until `offset` bytes past the end are aligned to `align`.
+    fn pad(&mut self, align: usize, offset: usize) {
+        while !(self.0.len() + offset).is_multiple_of(align) {
+            self.0.push(0);
+        }
+    }
+
+    // Stores the offset from `at` to `target` at `at`.
+    fn patch(&mut self, at: usize, target: usize) {
+        let offset = (target - at) as u32;
+        self.0[at..at + 4].copy_from_slice(&offset.to_le_bytes());
+    }
+
+    // Writes an object and returns its position.
+    fn object(&mut self, object: &Object) -> usize {
+        match object {
+            Object::Table(fields) => self.table(fields),
+            Object::Bytes(bytes) => {
+                self.pad(4, 0);
+                let position = self.0.len();
+                self.0.extend((bytes.len() as u32).to_le_bytes());
+                self.0.extend(bytes);
+                position
+            }
+            Object::U64s(values) => {
+                self.pad(8, 4);
+                let position = self.0.len();
+                self.0.extend((values.len() as u32).to_le_bytes());
+                for value in values {
+                    self.0.extend(value.to_le_bytes());
+                }
+                position
+            }
+            Object::Tables(tables) => {
+                self.pad(4, 0);
+                let position = self.0.len();
+                self.0.extend((tables.len() as u32).to_le_bytes());
+                self.0.resize(position + 4 + 4 * tables.len(), 0);
+                for (i, table) in tables.iter().enumerate() {
+                    let target = self.object(table);
+                    self.patch(position + 4 + 4 * i, target);
+                }
+                position
+            }
+        }
+    }
+
+    fn table(&mut self, fields: &[Option<Slot>]) -> usize {
+        // After the vtable offset come the 8-byte fields, then references, then bytes.
+        let mut offsets = vec![0u16; fields.len()];
+        let mut size = 4;
+        for width in [8, 4, 1] {
+            for (i, field) in fields.iter().enumerate() {
+                if let Some(field) = field.as_ref().filter(|field| field.size() == width) {
+                    offsets[i] = size as u16;
+                    size += field.size();
+                }
+            }
+        }
+
+        self.pad(2, 0);
+        let vtable = self.0.len();
+        self.0.extend((4 + 2 * fields.len() as u16).to_le_bytes());
+        self.0.extend((size as u16).to_le_bytes());
+        for offset in &offsets {
+            self.0.extend(offset.to_le_bytes());
+        }
+        // Aligns the 8-byte fields.
+        self.pad(8, 4);
+        let table = self.0.len();
+        self.0.extend(((table - vtable) as i32).to_le_bytes());
+        self.0.resize(table + size, 0);
+
+        let mut references = Vec::new();
+        for (field, &offset) in fields.iter().zip(&offsets) {
+            let at = table + offset as usize;
+            match field {
+                Some(Slot::U8(value)) => self.0[at] = *value,
+                Some(Slot::U64(value)) => self.0[at..at + 8].copy_from_slice(&value.to_le_bytes()),
+                Some(Slot::Ref(object)) => references.push((at, object)),
+                None => {}
+            }
+        }
+        for (at, object) in references {
+            let target = self.object(object);
+            self.patch(at, target);
+        }
+        table
+    }
+}
+
+// A flatbuffer being read, with every access bounds-checked.
+struct Buffer<'a>(&'a [u8]);
+
+impl<'a> Buffer<'a> {
+    fn slice(&self, position: usize, len: usize) -> Result<&'a [u8], ZKError> {
+        position
+            .checked_add(len)
+            .and_then(|end| self.0.get(position..end))
+            .ok_or_else(|| ZKError::InvalidFormat("Offset is out of bounds.".into()))
+    }
+
+    fn u8(&self, position: usize) -> Result<u8, ZKError> {
+        Ok(self.slice(position, 1)?[0])
+    }
+
+    fn u16(&self, position: usize) -> Result<u16, ZKError> {
+        Ok(u16::from_le_bytes(
+            self.slice(position, 2)?.try_into().unwrap(),
+        ))
+    }
+
+    fn u32(&self, position: usize) -> Result<u32, ZKError> {
+        Ok(u32::from_le_bytes(
+            self.slice(position, 4)?.try_into().unwrap(),
+        ))
+    }
+
+    fn u64(&self, position: usize) -> Result<u64, ZKError> {
+        Ok(u64::from_le_bytes(
+            self.slice(position, 8)?.try_into().unwrap(),
+        ))
+    }
+
+    // Follows the reference stored at `position`.
+    fn reference(&self, position: usize) -> Result<usize, ZKError> {
+        position
+            .checked_add(self.u32(position)? as usize)
+            .ok_or_else(|| ZKError::InvalidFormat("Offset is out of bounds.".into()))
+    }
+
+    // Returns the position of field `slot` of the table at `table`, if it is present.
+    fn field(&self, table: usize, slot: usize) -> Result<Option<usize>, ZKError> {
+        let offset = i32::from_le_bytes(self.slice(table, 4)?.try_into().unwrap());
+        let vtable = (table as i64 - offset as i64)
+            .try_into()
+            .map_err(|_| ZKError::InvalidFormat("Offset is out of bounds.".into()))?;
+        if 4 + 2 * slot >= self.u16(vtable)? as usize {
+            return Ok(None);
+        }
+        match self.u16(vtable + 4 + 2 * slot)? {
+            0 => Ok(None),
+            offset => Ok(Some(table + offset as usize)),
+        }
+    }
+
+    fn table_field(&self, table: usize, slot: usize) -> Result<Option<usize>, ZKError> {
+        self.field(table, slot)?
+            .map(|position| self.reference(position))
+            .transpose()
+    }
+
+    // Returns the start and length of a vector field with elements of `width` bytes.
+    fn vector_field(
+        &self,
+        table: usize,
+        slot: usize,
+        width: usize,
+    ) -> Result<Option<(usize, usize)>, ZKError> {
+        let Some(vector) = self.table_field(table, slot)? else {
+            return Ok(None);
+        };
+        let len = self.u32(vector)? as usize;
+        len.checked_mul(width)
+            .and_then(|bytes| self.slice(vector + 4, bytes).ok())
+            .ok_or_else(|| ZKError::InvalidFormat("Vector is out of bounds.".into()))?;
+        Ok(Some((vector + 4, len)))
+    }
+
+    fn variables(&self, table: usize) -> Result<RawVariables, ZKError> {
+        let ids = match self.vector_field(table, 0, 8)? {
+            Some((start, len)) => (0..len)
+                .map(|i| self.u64(start + 8 * i))
+                .collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+        let values = match self.vector_field(table, 1, 1)? {
+            Some((start, len)) => self.slice(start, len)?.to_vec(),
+            None => Vec::new(),
+        };
+        Ok(RawVariables { ids, values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{builder::CircuitBuilder, qap::QAP};
+
+    const MODULUS: u64 = 97;
+
+    fn fe(values: &[u64]) -> Vec<FieldElement> {
+        values
+            .iter()
+            .map(|&v| FieldElement::new(v, MODULUS).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // x^3 + x + 5 = out, with out public.
+        let mut builder = CircuitBuilder::new(&MODULUS).unwrap();
+        let out = builder.public_input().unwrap();
+        let x = builder.private_witness();
+        let x2 = builder.mul(x, x);
+        let x3 = builder.mul(x2, x);
+        let sum = builder.add(x3, x);
+        let five = builder.constant(fe(&[5])[0].clone());
+        let result = builder.add(sum, five);
+        builder.enforce_equal(result, out);
+        let witness = builder.generate_witness(&fe(&[35]), &fe(&[3])).unwrap();
+        let cs = builder.build();
+
+        let bytes = write_zkinterface(&cs, MODULUS, Some(&witness)).unwrap();
+        // Three 8-byte aligned messages.
+        assert_eq!(bytes.len() % 8, 0);
+        let circuit = read_zkinterface(&bytes).unwrap();
+        assert_eq!(circuit.modulus, MODULUS);
+        assert_eq!(circuit.witness.as_ref(), Some(&witness));
+        let read = &circuit.cs;
+        assert_eq!(
+            (read.num_public, read.num_variables, read.constraints.len()),
+            (cs.num_public, cs.num_variables, cs.constraints.len())
+        );
+        assert!(read.evaluate(&witness).unwrap());
+        let qap = QAP::create(read).unwrap();
+        assert!(qap.calculate_witness_quotient(&witness).is_ok());
+
+        // Without a witness, only the shape is written.
+        let shape = write_zkinterface(&cs, MODULUS, None).unwrap();
+        let circuit = read_zkinterface(&shape).unwrap();
+        assert!(circuit.witness.is_none());
+        assert_eq!(circuit.cs.constraints.len(), cs.constraints.len());
+
+        // Wrong witnesses and moduli are rejected.
+        assert!(write_zkinterface(&cs, MODULUS, Some(&witness[1..])).is_err());
+        assert!(write_zkinterface(&cs, 101, None).is_err());
+        assert!(write_zkinterface(&cs, 91, None).is_err());
+        assert!(write_zkinterface(&ConstraintSystem::new(), MODULUS, None).is_err());
+    }
+
+    #[test]
+    fn test_read_renumbers_variables() {
+        // A front-end that puts its instance variable last: v1 * v2 = v3 with v3 public.
+        let variables = |ids: Vec<u64>, values: &[u64]| {
+            let values = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            Object::Table(vec![
+                Some(Slot::Ref(Object::U64s(ids))),
+                Some(Slot::Ref(Object::Bytes(values))),
+            ])
+        };
+        let header = Object::Table(vec![
+            Some(Slot::Ref(variables(vec![3], &[12]))),
+            Some(Slot::U64(4)),
+            Some(Slot::Ref(Object::Bytes(vec![96]))),
+        ]);
+        let constraint = Object::Table(vec![
+            Some(Slot::Ref(variables(vec![1], &[1]))),
+            Some(Slot::Ref(variables(vec![2], &[1]))),
+            Some(Slot::Ref(variables(vec![3], &[1]))),
+        ]);
+        let constraints = Object::Table(vec![Some(Slot::Ref(Object::Tables(vec![constraint])))]);
+        let witness = Object::Table(vec![Some(Slot::Ref(variables(vec![2, 1], &[4, 3])))]);
+
+        let mut bytes = write_message(CONSTRAINT_SYSTEM, constraints);
+        bytes.extend(write_message(CIRCUIT_HEADER, header));
+        let shape = bytes.clone();
+        bytes.extend(write_message(WITNESS, witness));
+
+        let circuit = read_zkinterface(&bytes).unwrap();
+        assert_eq!((circuit.cs.num_public, circuit.cs.num_variables), (2, 4));
+        // The instance variable 3 becomes index 1, and 1 and 2 follow it.
+        assert_eq!(circuit.witness.as_ref().unwrap(), &fe(&[1, 12, 3, 4]));
+        let c = &circuit.cs.constraints[0].c;
+        assert_eq!(c.terms[0].index, 1);
+        assert!(circuit
+            .cs
+            .evaluate(circuit.witness.as_ref().unwrap())
+            .unwrap());
+
+        // A witness that leaves a variable unassigned, and malformed input.
+        let partial = Object::Table(vec![Some(Slot::Ref(variables(vec![2], &[4])))]);
+        let mut incomplete = shape.clone();
+        incomplete.extend(write_message(WITNESS, partial));
+        assert!(read_zkinterface(&incomplete).is_err());
+        assert!(read_zkinterface(&shape[..shape.len() - 1]).is_err());
+        let mut wrong_identifier = shape.clone();
+        wrong_identifier[8] = b'x';
+        assert!(read_zkinterface(&wrong_identifier).is_err());
+        assert!(
+            read_zkinterface(&write_message(CONSTRAINT_SYSTEM, Object::Table(vec![]))).is_err()
+        );
+
+        // A header claiming far more variables than the input could hold is rejected
+        // before anything is allocated for them.
+        for free_variable_id in [1 << 60, u64::MAX] {
+            let header = Object::Table(vec![
+                Some(Slot::Ref(variables(vec![3], &[12]))),
+                Some(Slot::U64(free_variable_id)),
+                Some(Slot::Ref(Object::Bytes(vec![96]))),
+            ]);
+            let huge = write_message(CIRCUIT_HEADER, header);
+            assert!(matches!(
+                read_zkinterface(&huge),
+                Err(ZKError::InvalidFormat(_))
+            ));
+        }
+    }
+}